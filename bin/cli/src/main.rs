@@ -0,0 +1,986 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anchor_client::Cluster;
+use anchor_lang::AccountDeserialize;
+use anchor_lang::__private::bytemuck::cast_ref;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use openbook_v2::state::{
+    BookSide, DynBookSide, DynEventHeap, EventHeap, EventType, FillEvent, Market,
+    OpenOrdersIndexer, OracleConfigParams, OutEvent, PlaceOrderType, SelfTradeBehavior, Side,
+    MAX_NUM_EVENTS, MAX_ORDERTREE_NODES,
+};
+use openbook_v2_client::market_maker::{FairValueSource, OracleFairValue};
+use openbook_v2_client::{
+    instructions, keypair_from_cli, pubkey_from_cli, AlignedAccountData, Client, OpenBookClient,
+    PriorityFeeStrategy, TransactionBuilder, TransactionBuilderConfig,
+};
+use serde_json::{json, Value};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    system_instruction,
+};
+
+mod show;
+
+#[derive(Parser)]
+#[clap(
+    name = "openbook-cli",
+    about = "Market and account operations on openbook-v2"
+)]
+struct Cli {
+    #[clap(flatten)]
+    rpc: Rpc,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct Rpc {
+    /// Cluster name or rpc url
+    #[clap(short, long, env = "RPC_URL", default_value = "mainnet")]
+    url: String,
+
+    /// Keypair file or bytes, the owner and fee payer of all transactions
+    #[clap(
+        short,
+        long,
+        env = "KEYPAIR",
+        default_value = "~/.config/solana/id.json"
+    )]
+    keypair: String,
+
+    /// Compute unit price in micro lamports
+    #[clap(long)]
+    priority_fee: Option<u64>,
+}
+
+#[derive(Args)]
+struct MarketArg {
+    #[clap(short, long)]
+    market: String,
+}
+
+#[derive(Args)]
+struct AccountArg {
+    /// Open orders account of the keypair
+    #[clap(short, long)]
+    account: String,
+}
+
+#[derive(Args)]
+struct TokenAccounts {
+    /// Base token account, the keypair's associated token account by default
+    #[clap(long)]
+    user_base_account: Option<String>,
+
+    /// Quote token account, the keypair's associated token account by default
+    #[clap(long)]
+    user_quote_account: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SideArg {
+    Bid,
+    Ask,
+}
+
+impl From<SideArg> for Side {
+    fn from(side: SideArg) -> Self {
+        match side {
+            SideArg::Bid => Side::Bid,
+            SideArg::Ask => Side::Ask,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OrderTypeArg {
+    Limit,
+    ImmediateOrCancel,
+    PostOnly,
+    Market,
+    PostOnlySlide,
+    FillOrKill,
+}
+
+impl From<OrderTypeArg> for PlaceOrderType {
+    fn from(order_type: OrderTypeArg) -> Self {
+        match order_type {
+            OrderTypeArg::Limit => PlaceOrderType::Limit,
+            OrderTypeArg::ImmediateOrCancel => PlaceOrderType::ImmediateOrCancel,
+            OrderTypeArg::PostOnly => PlaceOrderType::PostOnly,
+            OrderTypeArg::Market => PlaceOrderType::Market,
+            OrderTypeArg::PostOnlySlide => PlaceOrderType::PostOnlySlide,
+            OrderTypeArg::FillOrKill => PlaceOrderType::FillOrKill,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SelfTradeArg {
+    DecrementTake,
+    CancelProvide,
+    AbortTransaction,
+}
+
+impl From<SelfTradeArg> for SelfTradeBehavior {
+    fn from(self_trade: SelfTradeArg) -> Self {
+        match self_trade {
+            SelfTradeArg::DecrementTake => SelfTradeBehavior::DecrementTake,
+            SelfTradeArg::CancelProvide => SelfTradeBehavior::CancelProvide,
+            SelfTradeArg::AbortTransaction => SelfTradeBehavior::AbortTransaction,
+        }
+    }
+}
+
+#[derive(Args)]
+struct OrderArgs {
+    #[clap(flatten)]
+    market: MarketArg,
+    #[clap(flatten)]
+    account: AccountArg,
+    #[clap(flatten)]
+    token_accounts: TokenAccounts,
+
+    #[clap(long, value_enum)]
+    side: SideArg,
+
+    #[clap(long)]
+    max_base_lots: i64,
+
+    /// No limit by default
+    #[clap(long, default_value_t = i64::MAX)]
+    max_quote_lots_including_fees: i64,
+
+    #[clap(long, default_value_t = 0)]
+    client_order_id: u64,
+
+    #[clap(long, value_enum, default_value = "limit")]
+    order_type: OrderTypeArg,
+
+    #[clap(long, value_enum, default_value = "decrement-take")]
+    self_trade_behavior: SelfTradeArg,
+
+    /// Unix timestamp, 0 for no expiry
+    #[clap(long, default_value_t = 0)]
+    expiry_timestamp: u64,
+
+    /// Maximum number of orders to match against
+    #[clap(long, default_value_t = 10)]
+    limit: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates the market together with its book sides and event heap
+    CreateMarket {
+        #[clap(long)]
+        name: String,
+        #[clap(long)]
+        base_mint: String,
+        #[clap(long)]
+        quote_mint: String,
+        #[clap(long)]
+        base_lot_size: i64,
+        #[clap(long)]
+        quote_lot_size: i64,
+        /// In 1e-6 units
+        #[clap(long, default_value_t = 0)]
+        maker_fee: i64,
+        /// In 1e-6 units
+        #[clap(long, default_value_t = 0)]
+        taker_fee: i64,
+        #[clap(long)]
+        oracle_a: Option<String>,
+        #[clap(long)]
+        oracle_b: Option<String>,
+        #[clap(long, default_value_t = 0.1)]
+        oracle_conf_filter: f32,
+        #[clap(long)]
+        oracle_max_staleness_slots: Option<u32>,
+        /// Unix timestamp, 0 for a market that never expires
+        #[clap(long, default_value_t = 0)]
+        time_expiry: i64,
+        #[clap(long, default_value_t = 0)]
+        auction_duration_secs: u32,
+        #[clap(long, default_value_t = 0)]
+        auction_interval_secs: u32,
+        #[clap(long, default_value_t = 0)]
+        min_base_lots_per_order: i64,
+        #[clap(long, default_value_t = 0)]
+        min_quote_notional: u64,
+        /// 0 for no limit
+        #[clap(long, default_value_t = 0)]
+        max_base_lots_per_order: i64,
+        /// Number of nodes of each book side
+        #[clap(long, default_value_t = MAX_ORDERTREE_NODES)]
+        book_capacity: usize,
+        #[clap(long, default_value_t = MAX_NUM_EVENTS as usize)]
+        event_heap_capacity: usize,
+        /// The keypair by default
+        #[clap(long)]
+        collect_fee_admin: Option<String>,
+        #[clap(long)]
+        open_orders_admin: Option<String>,
+        #[clap(long)]
+        consume_events_admin: Option<String>,
+        #[clap(long)]
+        close_market_admin: Option<String>,
+        #[clap(long)]
+        market_status_admin: Option<String>,
+    },
+    /// Creates an open orders account for the keypair, and its indexer if needed
+    CreateAccount {
+        #[clap(flatten)]
+        market: MarketArg,
+        #[clap(long, default_value = "")]
+        name: String,
+        #[clap(long)]
+        delegate: Option<String>,
+    },
+    Deposit {
+        #[clap(flatten)]
+        market: MarketArg,
+        #[clap(flatten)]
+        account: AccountArg,
+        #[clap(flatten)]
+        token_accounts: TokenAccounts,
+        /// Native amount
+        #[clap(long, default_value_t = 0)]
+        base_amount: u64,
+        /// Native amount
+        #[clap(long, default_value_t = 0)]
+        quote_amount: u64,
+    },
+    Place {
+        #[clap(flatten)]
+        order: OrderArgs,
+        #[clap(long)]
+        price_lots: i64,
+    },
+    PlacePegged {
+        #[clap(flatten)]
+        order: OrderArgs,
+        /// Offset from the oracle price
+        #[clap(long, allow_hyphen_values = true)]
+        price_offset_lots: i64,
+        /// The order is not matched beyond this price, -1 for no limit
+        #[clap(long, allow_hyphen_values = true, default_value_t = -1)]
+        peg_limit: i64,
+    },
+    Cancel {
+        #[clap(flatten)]
+        market: MarketArg,
+        #[clap(flatten)]
+        account: AccountArg,
+        #[clap(long)]
+        order_id: u128,
+    },
+    CancelAll {
+        #[clap(flatten)]
+        market: MarketArg,
+        #[clap(flatten)]
+        account: AccountArg,
+        /// Both sides by default
+        #[clap(long, value_enum)]
+        side: Option<SideArg>,
+        #[clap(long, default_value_t = 255)]
+        limit: u8,
+    },
+    Settle {
+        #[clap(flatten)]
+        market: MarketArg,
+        #[clap(flatten)]
+        account: AccountArg,
+        #[clap(flatten)]
+        token_accounts: TokenAccounts,
+        #[clap(long)]
+        referrer: Option<String>,
+    },
+    /// Consumes events, passing the open orders accounts of their makers
+    ConsumeEvents {
+        #[clap(flatten)]
+        market: MarketArg,
+        #[clap(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Sends the collected fees to the receiver, or to the destinations of the fee split
+    SweepFees {
+        #[clap(flatten)]
+        market: MarketArg,
+        /// The keypair's quote associated token account by default, unused with a fee split
+        #[clap(long)]
+        token_receiver_account: Option<String>,
+    },
+    ShowMarket {
+        #[clap(flatten)]
+        market: MarketArg,
+    },
+    ShowBook {
+        #[clap(flatten)]
+        market: MarketArg,
+        /// Number of price levels per side
+        #[clap(long, default_value_t = 20)]
+        depth: usize,
+    },
+    ShowAccount {
+        #[clap(flatten)]
+        account: AccountArg,
+    },
+    /// Lists the open orders accounts of the keypair
+    ListAccounts,
+}
+
+fn rpc_client(rpc: &Rpc) -> anyhow::Result<(Client, Arc<Keypair>)> {
+    let owner = Arc::new(keypair_from_cli(&rpc.keypair));
+    let cluster = Cluster::from_str(&rpc.url)?;
+    let client = Client::new(
+        cluster,
+        CommitmentConfig::confirmed(),
+        owner.clone(),
+        Some(Duration::from_secs(60)),
+        TransactionBuilderConfig {
+            priority_fee: rpc.priority_fee.map(PriorityFeeStrategy::Fixed),
+            ..TransactionBuilderConfig::default()
+        },
+    );
+    Ok((client, owner))
+}
+
+/// Client for the commands that don't act on an open orders account of the keypair
+fn openbook_client(client: Client, owner: Arc<Keypair>) -> anyhow::Result<OpenBookClient> {
+    let rpc = client.rpc_async();
+    let account_fetcher = Arc::new(openbook_v2_client::CachedAccountFetcher::new(Arc::new(
+        openbook_v2_client::RpcAccountFetcher { rpc },
+    )));
+    OpenBookClient::new_detail(client, Pubkey::default(), owner, account_fetcher)
+}
+
+async fn openbook_client_for_account(
+    client: Client,
+    owner: Arc<Keypair>,
+    account: &AccountArg,
+) -> anyhow::Result<OpenBookClient> {
+    OpenBookClient::new_for_existing_account(client, pubkey_from_cli(&account.account), owner).await
+}
+
+async fn fetch_market(client: &Client, market: &MarketArg) -> anyhow::Result<(Pubkey, Market)> {
+    let address = pubkey_from_cli(&market.market);
+    Ok((
+        address,
+        client.rpc_anchor_account::<Market>(&address).await?,
+    ))
+}
+
+fn token_accounts(owner: &Pubkey, market: &Market, accounts: &TokenAccounts) -> (Pubkey, Pubkey) {
+    let account = |arg: &Option<String>, mint| match arg {
+        Some(arg) => pubkey_from_cli(arg),
+        None => spl_associated_token_account::get_associated_token_address(owner, mint),
+    };
+    (
+        account(&accounts.user_base_account, &market.base_mint),
+        account(&accounts.user_quote_account, &market.quote_mint),
+    )
+}
+
+fn signature(signature: Signature) -> Value {
+    json!({ "signature": signature.to_string() })
+}
+
+fn now_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+async fn account_data(client: &Client, address: &Pubkey) -> anyhow::Result<AlignedAccountData> {
+    let data = client.rpc_async().get_account_data(address).await?;
+    Ok(AlignedAccountData::new(&data))
+}
+
+/// The open orders accounts the first `limit` events of the heap need
+fn event_accounts(event_heap: &DynEventHeap, limit: usize) -> Vec<Pubkey> {
+    let mut accounts = HashSet::new();
+    for (event, _) in event_heap.iter().take(limit) {
+        match EventType::try_from(event.event_type) {
+            Ok(EventType::Fill) => {
+                let fill: &FillEvent = cast_ref(event);
+                accounts.insert(fill.maker);
+                if fill.is_auction() {
+                    accounts.insert(fill.taker);
+                }
+            }
+            Ok(EventType::Out) => {
+                let out: &OutEvent = cast_ref(event);
+                accounts.insert(out.owner);
+            }
+            Err(_) => {}
+        }
+    }
+    accounts.into_iter().collect()
+}
+
+async fn place(
+    client: Client,
+    owner: Arc<Keypair>,
+    order: OrderArgs,
+    price_lots: i64,
+    pegged: Option<(i64, i64)>,
+) -> anyhow::Result<Value> {
+    let (market_address, market) = fetch_market(&client, &order.market).await?;
+    let (user_base_account, user_quote_account) =
+        token_accounts(&owner.pubkey(), &market, &order.token_accounts);
+    let openbook = openbook_client_for_account(client, owner, &order.account).await?;
+    let side = Side::from(order.side);
+    let user_token_account = match side {
+        Side::Bid => user_quote_account,
+        Side::Ask => user_base_account,
+    };
+    let txsig = match pegged {
+        None => {
+            openbook
+                .place_order(
+                    market,
+                    market_address,
+                    side,
+                    price_lots,
+                    order.max_base_lots,
+                    order.max_quote_lots_including_fees,
+                    order.client_order_id,
+                    order.order_type.into(),
+                    order.expiry_timestamp,
+                    order.limit,
+                    user_token_account,
+                    order.self_trade_behavior.into(),
+                )
+                .await?
+        }
+        Some((price_offset_lots, peg_limit)) => {
+            openbook
+                .place_order_pegged(
+                    market,
+                    market_address,
+                    side,
+                    price_offset_lots,
+                    peg_limit,
+                    order.max_base_lots,
+                    order.max_quote_lots_including_fees,
+                    order.client_order_id,
+                    order.order_type.into(),
+                    order.expiry_timestamp,
+                    order.limit,
+                    user_token_account,
+                    order.self_trade_behavior.into(),
+                )
+                .await?
+        }
+    };
+    Ok(signature(txsig))
+}
+
+async fn run(cli: Cli) -> anyhow::Result<Value> {
+    let (client, owner) = rpc_client(&cli.rpc)?;
+    match cli.command {
+        Command::CreateMarket {
+            name,
+            base_mint,
+            quote_mint,
+            base_lot_size,
+            quote_lot_size,
+            maker_fee,
+            taker_fee,
+            oracle_a,
+            oracle_b,
+            oracle_conf_filter,
+            oracle_max_staleness_slots,
+            time_expiry,
+            auction_duration_secs,
+            auction_interval_secs,
+            min_base_lots_per_order,
+            min_quote_notional,
+            max_base_lots_per_order,
+            book_capacity,
+            event_heap_capacity,
+            collect_fee_admin,
+            open_orders_admin,
+            consume_events_admin,
+            close_market_admin,
+            market_status_admin,
+        } => {
+            let optional = |arg: Option<String>| arg.as_deref().map(pubkey_from_cli);
+            let market = Keypair::new();
+            let bids = Keypair::new();
+            let asks = Keypair::new();
+            let event_heap = Keypair::new();
+
+            // the program only takes over the book sides and event heap, their sizes
+            // decide the capacities
+            let rpc = client.rpc_async();
+            let mut instructions = vec![];
+            for (account, space) in [
+                (&bids, BookSide::space(book_capacity)),
+                (&asks, BookSide::space(book_capacity)),
+                (&event_heap, EventHeap::space(event_heap_capacity)),
+            ] {
+                instructions.push(system_instruction::create_account(
+                    &owner.pubkey(),
+                    &account.pubkey(),
+                    rpc.get_minimum_balance_for_rent_exemption(space).await?,
+                    space as u64,
+                    &openbook_v2::id(),
+                ));
+            }
+            let accounts_txsig = TransactionBuilder {
+                instructions,
+                address_lookup_tables: vec![],
+                payer: owner.pubkey(),
+                signers: vec![&*owner, &bids, &asks, &event_heap],
+                config: client.transaction_builder_config,
+            }
+            .send_and_confirm(&client)
+            .await?;
+
+            let openbook = openbook_client(client, owner.clone())?;
+            let txsig = openbook
+                .create_market(
+                    &market,
+                    bids.pubkey(),
+                    asks.pubkey(),
+                    event_heap.pubkey(),
+                    pubkey_from_cli(&base_mint),
+                    pubkey_from_cli(&quote_mint),
+                    optional(oracle_a),
+                    optional(oracle_b),
+                    optional(collect_fee_admin).unwrap_or(owner.pubkey()),
+                    optional(open_orders_admin),
+                    optional(consume_events_admin),
+                    optional(close_market_admin),
+                    optional(market_status_admin),
+                    name,
+                    OracleConfigParams {
+                        conf_filter: oracle_conf_filter,
+                        max_staleness_slots: oracle_max_staleness_slots,
+                    },
+                    base_lot_size,
+                    quote_lot_size,
+                    maker_fee,
+                    taker_fee,
+                    time_expiry,
+                    auction_duration_secs,
+                    auction_interval_secs,
+                    min_base_lots_per_order,
+                    min_quote_notional,
+                    max_base_lots_per_order,
+                )
+                .await?;
+            Ok(json!({
+                "market": market.pubkey().to_string(),
+                "bids": bids.pubkey().to_string(),
+                "asks": asks.pubkey().to_string(),
+                "event_heap": event_heap.pubkey().to_string(),
+                "accounts_signature": accounts_txsig.to_string(),
+                "signature": txsig.to_string(),
+            }))
+        }
+        Command::CreateAccount {
+            market,
+            name,
+            delegate,
+        } => {
+            let market = pubkey_from_cli(&market.market);
+            let indexer_address = instructions::open_orders_indexer_address(&owner.pubkey());
+            // only a missing indexer is created, rpc errors are passed on
+            let created_counter = match client
+                .rpc_async()
+                .get_account_with_commitment(&indexer_address, client.commitment)
+                .await?
+                .value
+            {
+                Some(account) => {
+                    OpenOrdersIndexer::try_deserialize(&mut &account.data[..])?.created_counter
+                }
+                None => {
+                    OpenBookClient::create_open_orders_indexer(&client, &owner, &owner).await?;
+                    0
+                }
+            };
+            let (account, txsig) = OpenBookClient::create_open_orders_account(
+                &client,
+                market,
+                &owner,
+                &owner,
+                delegate.as_deref().map(pubkey_from_cli),
+                created_counter + 1,
+                &name,
+            )
+            .await?;
+            Ok(json!({
+                "open_orders_account": account.to_string(),
+                "signature": txsig.to_string(),
+            }))
+        }
+        Command::Deposit {
+            market,
+            account,
+            token_accounts: accounts,
+            base_amount,
+            quote_amount,
+        } => {
+            let (market_address, market) = fetch_market(&client, &market).await?;
+            let (user_base_account, user_quote_account) =
+                token_accounts(&owner.pubkey(), &market, &accounts);
+            let openbook = openbook_client_for_account(client, owner, &account).await?;
+            let txsig = openbook
+                .deposit(
+                    market,
+                    market_address,
+                    base_amount,
+                    quote_amount,
+                    user_base_account,
+                    user_quote_account,
+                )
+                .await?;
+            Ok(signature(txsig))
+        }
+        Command::Place { order, price_lots } => place(client, owner, order, price_lots, None).await,
+        Command::PlacePegged {
+            order,
+            price_offset_lots,
+            peg_limit,
+        } => {
+            place(
+                client,
+                owner,
+                order,
+                0,
+                Some((price_offset_lots, peg_limit)),
+            )
+            .await
+        }
+        Command::Cancel {
+            market,
+            account,
+            order_id,
+        } => {
+            let (market_address, market) = fetch_market(&client, &market).await?;
+            let openbook = openbook_client_for_account(client, owner, &account).await?;
+            let txsig = openbook
+                .cancel_order(market, market_address, order_id)
+                .await?;
+            Ok(signature(txsig))
+        }
+        Command::CancelAll {
+            market,
+            account,
+            side,
+            limit,
+        } => {
+            let (market_address, market) = fetch_market(&client, &market).await?;
+            let openbook = openbook_client_for_account(client, owner, &account).await?;
+            let txsig = openbook
+                .cancel_all_orders(market, market_address, side.map(Side::from), limit)
+                .await?;
+            Ok(signature(txsig))
+        }
+        Command::Settle {
+            market,
+            account,
+            token_accounts: accounts,
+            referrer,
+        } => {
+            let (market_address, market) = fetch_market(&client, &market).await?;
+            let (user_base_account, user_quote_account) =
+                token_accounts(&owner.pubkey(), &market, &accounts);
+            let openbook = openbook_client_for_account(client, owner, &account).await?;
+            let txsig = openbook
+                .settle_funds(
+                    market,
+                    market_address,
+                    user_base_account,
+                    user_quote_account,
+                    referrer.as_deref().map(pubkey_from_cli),
+                )
+                .await?;
+            Ok(signature(txsig))
+        }
+        Command::ConsumeEvents { market, limit } => {
+            let (market_address, market) = fetch_market(&client, &market).await?;
+            let event_heap = account_data(&client, &market.event_heap).await?;
+            let event_heap = DynEventHeap::from_account_data(event_heap.as_slice())?;
+            if event_heap.is_empty() {
+                return Ok(json!({ "events": 0 }));
+            }
+            let accounts = event_accounts(event_heap, limit);
+            let events = event_heap.len().min(limit);
+            let openbook = openbook_client(client, owner)?;
+            let txsig = openbook
+                .consume_events(market, market_address, accounts, limit)
+                .await?;
+            Ok(json!({ "events": events, "signature": txsig.to_string() }))
+        }
+        Command::SweepFees {
+            market,
+            token_receiver_account,
+        } => {
+            let (market_address, market) = fetch_market(&client, &market).await?;
+            let token_receiver_account = if market.has_fee_split != 0 {
+                None
+            } else {
+                Some(match token_receiver_account {
+                    Some(arg) => pubkey_from_cli(&arg),
+                    None => spl_associated_token_account::get_associated_token_address(
+                        &owner.pubkey(),
+                        &market.quote_mint,
+                    ),
+                })
+            };
+            let openbook = openbook_client(client, owner)?;
+            let txsig = openbook
+                .sweep_fees(market, market_address, token_receiver_account)
+                .await?;
+            Ok(signature(txsig))
+        }
+        Command::ShowMarket { market } => {
+            let (market_address, market) = fetch_market(&client, &market).await?;
+            Ok(show::market(&market_address, &market))
+        }
+        Command::ShowBook { market, depth } => {
+            let (market_address, market) = fetch_market(&client, &market).await?;
+            let bids = account_data(&client, &market.bids).await?;
+            let asks = account_data(&client, &market.asks).await?;
+            // pegged orders are only shown when the oracle price is usable
+            let oracle_price_lots = if market.oracle_a.is_some() {
+                let oracle = OracleFairValue {
+                    market,
+                    account_fetcher: openbook_client(client, owner)?.account_fetcher,
+                };
+                oracle
+                    .fair_value()
+                    .await?
+                    .and_then(|price| market.native_price_to_lot(price).ok())
+            } else {
+                None
+            };
+            let now_ts = now_ts();
+            Ok(json!({
+                "market": market_address.to_string(),
+                "bids": show::book_side(
+                    &market,
+                    DynBookSide::from_account_data(bids.as_slice())?,
+                    now_ts,
+                    oracle_price_lots,
+                    depth,
+                ),
+                "asks": show::book_side(
+                    &market,
+                    DynBookSide::from_account_data(asks.as_slice())?,
+                    now_ts,
+                    oracle_price_lots,
+                    depth,
+                ),
+            }))
+        }
+        Command::ShowAccount { account } => {
+            let address = pubkey_from_cli(&account.account);
+            let openbook = openbook_client_for_account(client, owner, &account).await?;
+            Ok(show::open_orders_account(
+                &address,
+                &openbook.openorders_account().await?,
+            ))
+        }
+        Command::ListAccounts => {
+            let accounts = OpenBookClient::find_accounts(&client, &owner).await?;
+            Ok(accounts
+                .iter()
+                .map(|(address, account)| show::open_orders_account(address, account))
+                .collect())
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let output = run(Cli::parse()).await?;
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn parse(args: &[&str]) -> Command {
+        Cli::try_parse_from(std::iter::once("openbook-cli").chain(args.iter().copied()))
+            .unwrap()
+            .command
+    }
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_market_commands() {
+        let market = Pubkey::new_unique().to_string();
+        let mint = Pubkey::new_unique().to_string();
+
+        let command = parse(&[
+            "create-market",
+            "--name",
+            "SOL-USDC",
+            "--base-mint",
+            &mint,
+            "--quote-mint",
+            &mint,
+            "--base-lot-size",
+            "100",
+            "--quote-lot-size",
+            "10",
+            "--book-capacity",
+            "256",
+        ]);
+        assert!(matches!(
+            command,
+            Command::CreateMarket {
+                base_lot_size: 100,
+                quote_lot_size: 10,
+                book_capacity: 256,
+                event_heap_capacity,
+                collect_fee_admin: None,
+                ..
+            } if event_heap_capacity == MAX_NUM_EVENTS as usize
+        ));
+
+        assert!(matches!(
+            parse(&["consume-events", "--market", &market]),
+            Command::ConsumeEvents { limit: 10, .. }
+        ));
+        assert!(matches!(
+            parse(&["sweep-fees", "-m", &market]),
+            Command::SweepFees {
+                token_receiver_account: None,
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse(&["show-market", "--market", &market]),
+            Command::ShowMarket { .. }
+        ));
+        assert!(matches!(
+            parse(&["show-book", "--market", &market, "--depth", "5"]),
+            Command::ShowBook { depth: 5, .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_account_commands() {
+        let market = Pubkey::new_unique().to_string();
+        let account = Pubkey::new_unique().to_string();
+
+        assert!(matches!(
+            parse(&["create-account", "--market", &market]),
+            Command::CreateAccount { delegate: None, name, .. } if name.is_empty()
+        ));
+        assert!(matches!(
+            parse(&[
+                "deposit",
+                "-m",
+                &market,
+                "-a",
+                &account,
+                "--quote-amount",
+                "1000",
+            ]),
+            Command::Deposit {
+                base_amount: 0,
+                quote_amount: 1000,
+                ..
+            }
+        ));
+
+        let command = parse(&[
+            "place",
+            "-m",
+            &market,
+            "-a",
+            &account,
+            "--side",
+            "bid",
+            "--max-base-lots",
+            "5",
+            "--price-lots",
+            "1000",
+            "--order-type",
+            "post-only",
+        ]);
+        let Command::Place { order, price_lots } = command else {
+            panic!("not a place command");
+        };
+        assert_eq!(price_lots, 1000);
+        assert_eq!(order.max_base_lots, 5);
+        assert_eq!(order.max_quote_lots_including_fees, i64::MAX);
+        assert!(matches!(order.side, SideArg::Bid));
+        assert!(matches!(order.order_type, OrderTypeArg::PostOnly));
+        assert!(matches!(
+            order.self_trade_behavior,
+            SelfTradeArg::DecrementTake
+        ));
+
+        // negative offsets aren't taken for flags
+        assert!(matches!(
+            parse(&[
+                "place-pegged",
+                "-m",
+                &market,
+                "-a",
+                &account,
+                "--side",
+                "ask",
+                "--max-base-lots",
+                "1",
+                "--price-offset-lots",
+                "-20",
+            ]),
+            Command::PlacePegged {
+                price_offset_lots: -20,
+                peg_limit: -1,
+                ..
+            }
+        ));
+
+        assert!(matches!(
+            parse(&["cancel", "-m", &market, "-a", &account, "--order-id", "42"]),
+            Command::Cancel { order_id: 42, .. }
+        ));
+        assert!(matches!(
+            parse(&["cancel-all", "-m", &market, "-a", &account, "--side", "ask"]),
+            Command::CancelAll {
+                side: Some(SideArg::Ask),
+                limit: 255,
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse(&["settle", "-m", &market, "-a", &account]),
+            Command::Settle { referrer: None, .. }
+        ));
+        assert!(matches!(
+            parse(&["show-account", "-a", &account]),
+            Command::ShowAccount { .. }
+        ));
+        assert!(matches!(parse(&["list-accounts"]), Command::ListAccounts));
+
+        // the market is required
+        assert!(Cli::try_parse_from(["openbook-cli", "settle", "-a", &account]).is_err());
+    }
+}
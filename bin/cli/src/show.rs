@@ -0,0 +1,129 @@
+//! JSON views of the program accounts
+
+use openbook_v2::state::{DynBookSide, Market, OpenOrdersAccount, Side};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+fn optional(pubkey: openbook_v2::pubkey_option::NonZeroPubkeyOption) -> Value {
+    match Option::<Pubkey>::from(pubkey) {
+        Some(pubkey) => json!(pubkey.to_string()),
+        None => Value::Null,
+    }
+}
+
+/// Price of one whole base token in whole quote tokens
+fn ui_price(market: &Market, price_lots: i64) -> f64 {
+    market.lot_to_native_price(price_lots).to_num::<f64>()
+        * 10f64.powi(market.base_decimals as i32 - market.quote_decimals as i32)
+}
+
+pub fn market(address: &Pubkey, market: &Market) -> Value {
+    json!({
+        "address": address.to_string(),
+        "name": market.name(),
+        "base_mint": market.base_mint.to_string(),
+        "quote_mint": market.quote_mint.to_string(),
+        "base_decimals": market.base_decimals,
+        "quote_decimals": market.quote_decimals,
+        "base_lot_size": market.base_lot_size,
+        "quote_lot_size": market.quote_lot_size,
+        "maker_fee": market.maker_fee,
+        "taker_fee": market.taker_fee,
+        "min_base_lots_per_order": market.min_base_lots_per_order,
+        "min_quote_notional": market.min_quote_notional,
+        "max_base_lots_per_order": market.max_base_lots_per_order,
+        "time_expiry": market.time_expiry,
+        "status": market.status,
+        "market_authority": market.market_authority.to_string(),
+        "bids": market.bids.to_string(),
+        "asks": market.asks.to_string(),
+        "event_heap": market.event_heap.to_string(),
+        "market_base_vault": market.market_base_vault.to_string(),
+        "market_quote_vault": market.market_quote_vault.to_string(),
+        "oracle_a": optional(market.oracle_a),
+        "oracle_b": optional(market.oracle_b),
+        "collect_fee_admin": market.collect_fee_admin.to_string(),
+        "open_orders_admin": optional(market.open_orders_admin),
+        "consume_events_admin": optional(market.consume_events_admin),
+        "close_market_admin": optional(market.close_market_admin),
+        "market_status_admin": optional(market.market_status_admin),
+        "has_fee_split": market.has_fee_split != 0,
+        "base_deposit_total": market.base_deposit_total,
+        "quote_deposit_total": market.quote_deposit_total,
+        "fees_available": market.fees_available,
+        "fees_accrued": market.fees_accrued.to_string(),
+        "maker_volume": market.maker_volume.to_string(),
+        "seq_num": market.seq_num,
+    })
+}
+
+/// Price levels of one side, best first, with the base lots of the valid orders summed up
+pub fn book_side(
+    market: &Market,
+    book_side: &DynBookSide,
+    now_ts: u64,
+    oracle_price_lots: Option<i64>,
+    depth: usize,
+) -> Value {
+    let mut levels: Vec<(i64, i64, usize)> = vec![];
+    for order in book_side.iter_valid(now_ts, oracle_price_lots) {
+        match levels.last_mut() {
+            Some((price_lots, base_lots, count)) if *price_lots == order.price_lots => {
+                *base_lots += order.node.quantity;
+                *count += 1;
+            }
+            _ if levels.len() == depth => break,
+            _ => levels.push((order.price_lots, order.node.quantity, 1)),
+        }
+    }
+    levels
+        .into_iter()
+        .map(|(price_lots, base_lots, orders)| {
+            json!({
+                "price_lots": price_lots,
+                "price": ui_price(market, price_lots),
+                "base_lots": base_lots,
+                "orders": orders,
+            })
+        })
+        .collect()
+}
+
+pub fn open_orders_account(address: &Pubkey, account: &OpenOrdersAccount) -> Value {
+    let position = &account.position;
+    let orders: Vec<Value> = account
+        .all_orders_in_use()
+        .map(|order| {
+            json!({
+                "id": order.id.to_string(),
+                "client_id": order.client_id,
+                "side": match order.side_and_tree().side() {
+                    Side::Bid => "bid",
+                    Side::Ask => "ask",
+                },
+                "locked_price": order.locked_price,
+            })
+        })
+        .collect();
+    json!({
+        "address": address.to_string(),
+        "name": account.name(),
+        "owner": account.owner.to_string(),
+        "market": account.market.to_string(),
+        "delegate": optional(account.delegate),
+        "account_num": account.account_num,
+        "version": account.version,
+        "position": {
+            "bids_base_lots": position.bids_base_lots,
+            "asks_base_lots": position.asks_base_lots,
+            "bids_quote_lots": position.bids_quote_lots,
+            "base_free_native": position.base_free_native,
+            "quote_free_native": position.quote_free_native,
+            "locked_maker_fees": position.locked_maker_fees,
+            "referrer_rebates_available": position.referrer_rebates_available,
+            "maker_volume": position.maker_volume.to_string(),
+            "taker_volume": position.taker_volume.to_string(),
+        },
+        "open_orders": orders,
+    })
+}
@@ -0,0 +1,903 @@
+//! Backtests quoting strategies against historical market data, using the program's own
+//! matching code on an in-memory `Orderbook`, `EventHeap` and `OpenOrdersAccount`s.
+//!
+//! Historical resting orders are posted for synthetic makers, one open orders account each,
+//! so that the strategy's orders queue behind the ones that were there before them.
+//! Historical trades are replayed as immediate-or-cancel taker orders and fill whatever
+//! rests at the front of the simulated book, which may be the strategy's orders.
+
+use anchor_lang::__private::bytemuck::{cast_ref, Zeroable};
+use anchor_lang::prelude::{ProgramError, Pubkey};
+use openbook_v2::state::{
+    BookSide, EventHeap, EventType, FillEvent, Market, OpenOrdersAccount, Order, OrderParams,
+    OrderWithAmounts, Orderbook, OutEvent, PostOrderType, SelfTradeBehavior, Side,
+};
+
+use log::*;
+use std::{
+    cell::{RefCell, RefMut},
+    collections::{HashMap, VecDeque},
+};
+
+/// An order resting on the historical book
+#[derive(Clone, Debug)]
+pub struct L3Order {
+    /// The order id on the historical book, `LeafNode::key`
+    pub order_id: u128,
+    pub side: Side,
+    pub price_lots: i64,
+    pub base_lots: i64,
+}
+
+impl L3Order {
+    /// Orders placed earlier at the same price have a lower priority value
+    fn time_priority(&self) -> u64 {
+        let seq_num = self.order_id as u64;
+        match self.side {
+            Side::Bid => !seq_num,
+            Side::Ask => seq_num,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum MarketEvent {
+    /// The full historical book, the simulated book is updated to match it. Orders whose
+    /// price or quantity changed lose their place in the queue.
+    Snapshot(Vec<L3Order>),
+    /// An order was added to the historical book
+    Post(L3Order),
+    /// An order left the historical book, by cancellation or by being filled
+    Remove {
+        order_id: u128,
+    },
+    /// A historical trade, replayed as a taker order of `side` limited to `price_lots`
+    Trade {
+        side: Side,
+        price_lots: i64,
+        base_lots: i64,
+    },
+    OraclePrice {
+        price_lots: i64,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct TimedEvent {
+    pub timestamp: u64,
+    pub event: MarketEvent,
+}
+
+pub enum Action {
+    Place(Order),
+    Cancel { client_order_id: u64 },
+    CancelAll,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Balances {
+    pub base_native: i64,
+    pub quote_native: i64,
+}
+
+/// A fill of one of the strategy's orders
+#[derive(Clone, Debug)]
+pub struct StrategyFill {
+    pub timestamp: u64,
+    /// Side of the strategy's order
+    pub side: Side,
+    pub price_lots: i64,
+    pub base_lots: i64,
+    pub maker: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FillStats {
+    pub maker_fills: u64,
+    pub taker_fills: u64,
+    pub maker_base_lots: i64,
+    pub taker_base_lots: i64,
+    pub maker_quote_native: u64,
+    pub taker_quote_native: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BacktestReport {
+    pub initial_balances: Balances,
+    /// Balances once all orders of the strategy were cancelled and the funds settled
+    pub final_balances: Balances,
+    /// Change of the balances valued at the price of the last historical trade
+    pub pnl_quote_native: Option<i64>,
+    pub taker_fees: u64,
+    pub maker_fees: u64,
+    pub maker_rebates: u64,
+    pub fill_stats: FillStats,
+    pub fills: Vec<StrategyFill>,
+    /// Orders the program would have rejected, including the ones that need more funds
+    /// than the wallet and the position have. The state is left unchanged like for a
+    /// failed transaction
+    pub rejected_orders: u64,
+}
+
+pub struct StrategyContext<'a> {
+    pub now_ts: u64,
+    pub market: &'a Market,
+    pub bids: &'a BookSide,
+    pub asks: &'a BookSide,
+    pub oracle_price_lots: Option<i64>,
+    pub open_orders: &'a OpenOrdersAccount,
+    pub balances: Balances,
+    /// Fills of the strategy's orders since the previous call
+    pub new_fills: &'a [StrategyFill],
+}
+
+pub trait Strategy {
+    /// Called after every historical event, the actions reach the book once the latency
+    /// of the backtest has passed
+    fn on_event(&mut self, ctx: &StrategyContext, event: &MarketEvent) -> Vec<Action>;
+}
+
+pub struct BacktestConfig {
+    pub market: Market,
+    pub initial_balances: Balances,
+    /// Seconds between a strategy action and its arrival on the book, on-chain timestamps
+    /// have a resolution of one second as well
+    pub latency_secs: u64,
+}
+
+struct BookState {
+    market: Market,
+    market_pk: Pubkey,
+    bids: Box<RefCell<BookSide>>,
+    asks: Box<RefCell<BookSide>>,
+    event_heap: Box<EventHeap>,
+    oracle_price_lots: Option<i64>,
+}
+
+impl BookState {
+    fn orderbook(&self) -> Orderbook {
+        Orderbook {
+            bids: RefMut::map(self.bids.borrow_mut(), |bids| &mut **bids),
+            asks: RefMut::map(self.asks.borrow_mut(), |asks| &mut **asks),
+        }
+    }
+
+    /// Matches the order with the program's code, restoring the previous state when it
+    /// fails or when `check` rejects its amounts, as the transaction would have been
+    /// reverted. The book is only copied when that can happen after matching started.
+    fn new_order(
+        &mut self,
+        order: &Order,
+        mut open_orders_account: Option<&mut OpenOrdersAccount>,
+        owner: &Pubkey,
+        now_ts: u64,
+        check: Option<&dyn Fn(&OrderWithAmounts) -> anchor_lang::Result<()>>,
+    ) -> anchor_lang::Result<OrderWithAmounts> {
+        let saved_book = (check.is_some()
+            || self.may_fail_after_matching(order, open_orders_account.as_deref(), now_ts))
+        .then(|| {
+            (
+                self.bids.clone(),
+                self.asks.clone(),
+                self.event_heap.clone(),
+            )
+        });
+        let saved_market = self.market;
+        let saved_account = open_orders_account.as_deref().copied();
+
+        let result = Orderbook {
+            bids: RefMut::map(self.bids.borrow_mut(), |bids| &mut **bids),
+            asks: RefMut::map(self.asks.borrow_mut(), |asks| &mut **asks),
+        }
+        .new_order(
+            order,
+            &mut self.market,
+            &self.market_pk,
+            &mut self.event_heap,
+            self.oracle_price_lots,
+            open_orders_account.as_deref_mut(),
+            owner,
+            now_ts,
+            u8::MAX,
+            &[],
+        )
+        .and_then(|amounts| {
+            if let Some(check) = check {
+                check(&amounts)?;
+            }
+            Ok(amounts)
+        });
+
+        if result.is_err() {
+            self.market = saved_market;
+            if let Some((bids, asks, event_heap)) = saved_book {
+                self.bids = bids;
+                self.asks = asks;
+                self.event_heap = event_heap;
+            }
+            if let (Some(account), Some(saved_account)) = (open_orders_account, saved_account) {
+                *account = saved_account;
+            }
+        }
+        result
+    }
+
+    /// Whether `new_order` may fail once it started changing the book. The checks before
+    /// matching leave it untouched, and so do orders that only take.
+    fn may_fail_after_matching(
+        &self,
+        order: &Order,
+        open_orders_account: Option<&OpenOrdersAccount>,
+        now_ts: u64,
+    ) -> bool {
+        if order.is_fill_or_kill()
+            || order.self_trade_behavior == SelfTradeBehavior::AbortTransaction
+        {
+            return true;
+        }
+        if order.post_target().is_none() {
+            return false;
+        }
+        let Ok((price_lots, _)) = order.price(now_ts, self.oracle_price_lots, &self.orderbook())
+        else {
+            return false;
+        };
+        let bookside_full = match order.side {
+            Side::Bid => self.bids.borrow().is_full(),
+            Side::Ask => self.asks.borrow().is_full(),
+        };
+        bookside_full
+            || open_orders_account.map_or(true, |account| account.next_order_slot().is_err())
+            || order.max_base_lots.saturating_mul(price_lots) > self.market.max_quote_lots()
+            || self.market.is_above_max_order_size(order.max_base_lots)
+    }
+}
+
+/// Funds `place_order` transfers from the position and the wallet for an order: quote for
+/// bids, base for asks
+fn funds_to_lock(side: Side, amounts: &OrderWithAmounts) -> u64 {
+    match side {
+        Side::Bid => {
+            amounts.total_quote_taken_native
+                + amounts.posted_quote_native
+                + amounts.taker_fees
+                + amounts.maker_fees
+        }
+        Side::Ask => amounts.total_base_taken_native + amounts.posted_base_native,
+    }
+}
+
+/// Upper bound of [`funds_to_lock`] before the order is matched
+fn max_funds_to_lock(market: &Market, order: &Order) -> i128 {
+    match order.side {
+        Side::Bid => {
+            let quote_native =
+                order.max_quote_lots_including_fees as i128 * market.quote_lot_size as i128;
+            quote_native + market.maker_fees_ceil(quote_native).max(0)
+        }
+        Side::Ask => order.max_base_lots as i128 * market.base_lot_size as i128,
+    }
+}
+
+struct BackgroundOrder {
+    owner: Pubkey,
+    side: Side,
+    price_lots: i64,
+    base_lots: i64,
+    account: Box<OpenOrdersAccount>,
+}
+
+pub struct Backtest<S> {
+    pub strategy: S,
+    state: BookState,
+    latency_secs: u64,
+    owner: Pubkey,
+    account: Box<OpenOrdersAccount>,
+    balances: Balances,
+    background: HashMap<u128, BackgroundOrder>,
+    background_owners: HashMap<Pubkey, u128>,
+    historical_taker: Pubkey,
+    pending: VecDeque<(u64, Action)>,
+    last_trade_price_lots: Option<i64>,
+    reported_fills: usize,
+    report: BacktestReport,
+}
+
+impl<S: Strategy> Backtest<S> {
+    pub fn new(config: BacktestConfig, strategy: S) -> Self {
+        let mut event_heap = Box::new(EventHeap::zeroed());
+        event_heap.init();
+        let state = BookState {
+            market: config.market,
+            market_pk: Pubkey::new_unique(),
+            bids: Box::new(RefCell::new(BookSide::zeroed())),
+            asks: Box::new(RefCell::new(BookSide::zeroed())),
+            event_heap,
+            oracle_price_lots: None,
+        };
+        state.orderbook().init();
+
+        Self {
+            strategy,
+            state,
+            latency_secs: config.latency_secs,
+            owner: Pubkey::new_unique(),
+            account: OpenOrdersAccount::default_for_tests(),
+            balances: config.initial_balances,
+            background: HashMap::new(),
+            background_owners: HashMap::new(),
+            historical_taker: Pubkey::new_unique(),
+            pending: VecDeque::new(),
+            last_trade_price_lots: None,
+            reported_fills: 0,
+            report: BacktestReport {
+                initial_balances: config.initial_balances,
+                ..BacktestReport::default()
+            },
+        }
+    }
+
+    /// Replays the events, which must be ordered by timestamp, cancels the remaining orders
+    /// of the strategy at the end and reports the results
+    pub fn run(&mut self, events: impl IntoIterator<Item = TimedEvent>) -> BacktestReport {
+        let mut now_ts = 0;
+        for TimedEvent { timestamp, event } in events {
+            now_ts = timestamp;
+            self.apply_pending_actions(now_ts);
+            self.apply_market_event(&event, now_ts);
+            self.consume_events();
+            self.settle();
+
+            let actions = {
+                let bids = self.state.bids.borrow();
+                let asks = self.state.asks.borrow();
+                let ctx = StrategyContext {
+                    now_ts,
+                    market: &self.state.market,
+                    bids: &bids,
+                    asks: &asks,
+                    oracle_price_lots: self.state.oracle_price_lots,
+                    open_orders: &self.account,
+                    balances: self.balances,
+                    new_fills: &self.report.fills[self.reported_fills..],
+                };
+                self.strategy.on_event(&ctx, &event)
+            };
+            self.reported_fills = self.report.fills.len();
+            self.pending.extend(
+                actions
+                    .into_iter()
+                    .map(|action| (now_ts + self.latency_secs, action)),
+            );
+            self.apply_pending_actions(now_ts);
+        }
+
+        if !self.pending.is_empty() {
+            debug!(
+                "{} actions did not arrive before the end",
+                self.pending.len()
+            );
+            self.pending.clear();
+        }
+        self.apply_action(Action::CancelAll, now_ts);
+        self.consume_events();
+        self.settle();
+
+        let mut report = self.report.clone();
+        report.final_balances = self.balances;
+        report.pnl_quote_native = self.last_trade_price_lots.map(|price_lots| {
+            self.value(report.final_balances, price_lots)
+                - self.value(report.initial_balances, price_lots)
+        });
+        report
+    }
+
+    fn value(&self, balances: Balances, price_lots: i64) -> i64 {
+        let market = &self.state.market;
+        let base_value =
+            balances.base_native as i128 * price_lots as i128 * market.quote_lot_size as i128
+                / market.base_lot_size as i128;
+        (balances.quote_native as i128 + base_value)
+            .try_into()
+            .unwrap()
+    }
+
+    fn apply_pending_actions(&mut self, now_ts: u64) {
+        while self
+            .pending
+            .front()
+            .map_or(false, |(arrival_ts, _)| *arrival_ts <= now_ts)
+        {
+            let (_, action) = self.pending.pop_front().unwrap();
+            self.apply_action(action, now_ts);
+            self.consume_events();
+            self.settle();
+        }
+    }
+
+    fn apply_action(&mut self, action: Action, now_ts: u64) {
+        let market = self.state.market;
+        let result = match action {
+            Action::Place(order) => self.place_order(&order, now_ts),
+            Action::Cancel { client_order_id } => self
+                .state
+                .orderbook()
+                .cancel_all_orders(
+                    &mut self.account,
+                    market,
+                    u8::MAX,
+                    None,
+                    Some(client_order_id),
+                )
+                .map(|_| ()),
+            Action::CancelAll => self
+                .state
+                .orderbook()
+                .cancel_all_orders(&mut self.account, market, u8::MAX, None, None)
+                .map(|_| ()),
+        };
+        if let Err(err) = result {
+            debug!("strategy action failed: {:?}", err);
+            self.report.rejected_orders += 1;
+        }
+    }
+
+    /// Places an order of the strategy. Like the token transfer of place_order, it fails
+    /// when the funds to lock exceed the free funds of the position and the wallet.
+    fn place_order(&mut self, order: &Order, now_ts: u64) -> anchor_lang::Result<()> {
+        let position = &self.account.position;
+        let available = match order.side {
+            Side::Bid => position.quote_free_native as i128 + self.balances.quote_native as i128,
+            Side::Ask => position.base_free_native as i128 + self.balances.base_native as i128,
+        };
+        let check_funds = |amounts: &OrderWithAmounts| -> anchor_lang::Result<()> {
+            if funds_to_lock(order.side, amounts) as i128 > available {
+                return Err(ProgramError::InsufficientFunds.into());
+            }
+            Ok(())
+        };
+        let check: Option<&dyn Fn(&OrderWithAmounts) -> anchor_lang::Result<()>> =
+            if max_funds_to_lock(&self.state.market, order) > available {
+                Some(&check_funds)
+            } else {
+                None
+            };
+
+        let amounts =
+            self.state
+                .new_order(order, Some(&mut self.account), &self.owner, now_ts, check)?;
+        self.report.taker_fees += amounts.taker_fees;
+
+        // Lock the funds from the free position first and the wallet next, as place_order does
+        let to_lock = funds_to_lock(order.side, &amounts);
+        let position = &mut self.account.position;
+        let (free_native, wallet_native) = match order.side {
+            Side::Bid => (
+                &mut position.quote_free_native,
+                &mut self.balances.quote_native,
+            ),
+            Side::Ask => (
+                &mut position.base_free_native,
+                &mut self.balances.base_native,
+            ),
+        };
+        let from_position = to_lock.min(*free_native);
+        *free_native -= from_position;
+        *wallet_native -= (to_lock - from_position) as i64;
+        Ok(())
+    }
+
+    fn apply_market_event(&mut self, event: &MarketEvent, now_ts: u64) {
+        match event {
+            MarketEvent::Snapshot(orders) => {
+                let wanted: HashMap<u128, &L3Order> =
+                    orders.iter().map(|order| (order.order_id, order)).collect();
+                let changed: Vec<u128> = self
+                    .background
+                    .iter()
+                    .filter(|(order_id, current)| {
+                        wanted.get(order_id).map_or(true, |order| {
+                            order.side != current.side
+                                || order.price_lots != current.price_lots
+                                || order.base_lots != current.base_lots
+                        })
+                    })
+                    .map(|(order_id, _)| *order_id)
+                    .collect();
+                for order_id in changed {
+                    self.remove_background_order(order_id);
+                }
+
+                let mut added: Vec<&L3Order> = orders
+                    .iter()
+                    .filter(|order| !self.background.contains_key(&order.order_id))
+                    .collect();
+                added.sort_by_key(|order| (order.price_lots, order.time_priority()));
+                for order in added {
+                    self.post_background_order(order, now_ts);
+                }
+            }
+            MarketEvent::Post(order) => {
+                self.remove_background_order(order.order_id);
+                self.post_background_order(order, now_ts);
+            }
+            MarketEvent::Remove { order_id } => self.remove_background_order(*order_id),
+            MarketEvent::Trade {
+                side,
+                price_lots,
+                base_lots,
+            } => {
+                self.last_trade_price_lots = Some(*price_lots);
+                let order = Order {
+                    side: *side,
+                    max_base_lots: *base_lots,
+                    max_quote_lots_including_fees: self.state.market.max_quote_lots(),
+                    client_order_id: 0,
+                    time_in_force: 0,
+                    self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                    params: OrderParams::ImmediateOrCancel {
+                        price_lots: *price_lots,
+                    },
+                };
+                let taker = self.historical_taker;
+                if let Err(err) = self.state.new_order(&order, None, &taker, now_ts, None) {
+                    debug!("historical trade could not be replayed: {:?}", err);
+                }
+            }
+            MarketEvent::OraclePrice { price_lots } => {
+                self.state.oracle_price_lots = Some(*price_lots)
+            }
+        }
+    }
+
+    fn post_background_order(&mut self, order: &L3Order, now_ts: u64) {
+        let owner = Pubkey::new_unique();
+        let mut account = OpenOrdersAccount::default_for_tests();
+        let new_order = Order {
+            side: order.side,
+            max_base_lots: order.base_lots,
+            max_quote_lots_including_fees: self.state.market.max_quote_lots(),
+            client_order_id: 0,
+            time_in_force: 0,
+            self_trade_behavior: SelfTradeBehavior::DecrementTake,
+            params: OrderParams::Fixed {
+                price_lots: order.price_lots,
+                order_type: PostOrderType::Limit,
+            },
+        };
+        match self
+            .state
+            .new_order(&new_order, Some(&mut account), &owner, now_ts, None)
+        {
+            Ok(_) if !account.has_no_orders() => {
+                self.background_owners.insert(owner, order.order_id);
+                self.background.insert(
+                    order.order_id,
+                    BackgroundOrder {
+                        owner,
+                        side: order.side,
+                        price_lots: order.price_lots,
+                        base_lots: order.base_lots,
+                        account,
+                    },
+                );
+            }
+            // fully matched against the strategy's orders
+            Ok(_) => {}
+            Err(err) => debug!("historical order could not be posted: {:?}", err),
+        }
+    }
+
+    fn remove_background_order(&mut self, order_id: u128) {
+        let Some(mut background) = self.background.remove(&order_id) else {
+            return;
+        };
+        self.background_owners.remove(&background.owner);
+        let market = self.state.market;
+        if let Err(err) = self.state.orderbook().cancel_all_orders(
+            &mut background.account,
+            market,
+            u8::MAX,
+            None,
+            None,
+        ) {
+            debug!("historical order could not be cancelled: {:?}", err);
+        }
+    }
+
+    fn background_account(&mut self, owner: &Pubkey) -> Option<&mut OpenOrdersAccount> {
+        let order_id = self.background_owners.get(owner)?;
+        Some(&mut self.background.get_mut(order_id)?.account)
+    }
+
+    /// Processes the event heap right away, like a crank running after every transaction
+    fn consume_events(&mut self) {
+        while let Some(event) = self.state.event_heap.front().copied() {
+            match EventType::try_from(event.event_type) {
+                Ok(EventType::Fill) => self.execute_fill(cast_ref(&event)),
+                Ok(EventType::Out) => {
+                    let out: &OutEvent = cast_ref(&event);
+                    let market = self.state.market;
+                    if out.owner == self.owner {
+                        self.account
+                            .cancel_order(out.owner_slot as usize, out.quantity, market);
+                    } else if let Some(account) = self.background_account(&out.owner) {
+                        account.cancel_order(out.owner_slot as usize, out.quantity, market);
+                        self.drop_background_account_if_empty(out.owner);
+                    }
+                }
+                Err(_) => warn!("unknown event type {}", event.event_type),
+            }
+            self.state.event_heap.pop_front().unwrap();
+        }
+    }
+
+    fn execute_fill(&mut self, fill: &FillEvent) {
+        let market = &mut self.state.market;
+        let quote_native = (fill.quantity * fill.price * market.quote_lot_size) as u64;
+
+        if fill.maker == self.owner {
+            if fill.maker != fill.taker {
+                self.report.maker_fees += market.maker_fees_floor(quote_native);
+                if !fill.is_auction() {
+                    self.report.maker_rebates += market.maker_rebate_floor(quote_native);
+                }
+            }
+            self.account.execute_maker(market, fill);
+            self.record_fill(fill, true);
+        } else if let Some(order_id) = self.background_owners.get(&fill.maker) {
+            let background = self.background.get_mut(order_id).unwrap();
+            background.account.execute_maker(market, fill);
+            self.drop_background_account_if_empty(fill.maker);
+        }
+
+        if fill.taker == self.owner {
+            self.record_fill(fill, false);
+        }
+    }
+
+    fn record_fill(&mut self, fill: &FillEvent, maker: bool) {
+        let quote_native = (fill.quantity * fill.price * self.state.market.quote_lot_size) as u64;
+        let stats = &mut self.report.fill_stats;
+        if maker {
+            stats.maker_fills += 1;
+            stats.maker_base_lots += fill.quantity;
+            stats.maker_quote_native += quote_native;
+        } else {
+            stats.taker_fills += 1;
+            stats.taker_base_lots += fill.quantity;
+            stats.taker_quote_native += quote_native;
+        }
+        self.report.fills.push(StrategyFill {
+            timestamp: fill.timestamp,
+            side: if maker {
+                fill.taker_side().invert_side()
+            } else {
+                fill.taker_side()
+            },
+            price_lots: fill.price,
+            base_lots: fill.quantity,
+            maker,
+        });
+    }
+
+    fn drop_background_account_if_empty(&mut self, owner: Pubkey) {
+        let Some(order_id) = self.background_owners.get(&owner).copied() else {
+            return;
+        };
+        if self.background[&order_id].account.has_no_orders() {
+            self.background.remove(&order_id);
+            self.background_owners.remove(&owner);
+        }
+    }
+
+    /// Withdraws the free funds of the strategy, as settle_funds does. Referrer rebates
+    /// are not paid to the strategy and are dropped.
+    fn settle(&mut self) {
+        let position = &mut self.account.position;
+        self.balances.base_native += position.base_free_native as i64;
+        self.balances.quote_native += position.quote_free_native as i64;
+        position.base_free_native = 0;
+        position.quote_free_native = 0;
+        position.referrer_rebates_available = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Quoter {
+        placed: bool,
+    }
+
+    impl Strategy for Quoter {
+        fn on_event(&mut self, _ctx: &StrategyContext, _event: &MarketEvent) -> Vec<Action> {
+            if self.placed {
+                return vec![];
+            }
+            self.placed = true;
+            [(Side::Bid, 9_900, 1), (Side::Ask, 10_100, 2)]
+                .into_iter()
+                .map(|(side, price_lots, client_order_id)| {
+                    Action::Place(Order {
+                        side,
+                        max_base_lots: 5,
+                        max_quote_lots_including_fees: 1_000_000,
+                        client_order_id,
+                        time_in_force: 0,
+                        self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                        params: OrderParams::Fixed {
+                            price_lots,
+                            order_type: PostOrderType::PostOnly,
+                        },
+                    })
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_backtest_queue_position_and_fees() {
+        let mut market = Market::zeroed();
+        market.base_lot_size = 1;
+        market.quote_lot_size = 1;
+        market.maker_fee = 200;
+        market.taker_fee = 400;
+
+        let initial_balances = Balances {
+            base_native: 1_000,
+            quote_native: 1_000_000,
+        };
+        let mut backtest = Backtest::new(
+            BacktestConfig {
+                market,
+                initial_balances,
+                latency_secs: 0,
+            },
+            Quoter { placed: false },
+        );
+
+        let event = |timestamp, event| TimedEvent { timestamp, event };
+        let report = backtest.run(vec![
+            event(
+                100,
+                MarketEvent::Snapshot(vec![
+                    L3Order {
+                        order_id: 1,
+                        side: Side::Bid,
+                        price_lots: 9_900,
+                        base_lots: 4,
+                    },
+                    L3Order {
+                        order_id: 2,
+                        side: Side::Ask,
+                        price_lots: 10_200,
+                        base_lots: 10,
+                    },
+                ]),
+            ),
+            // fills the historical bid that was first in the queue, then the strategy's bid
+            event(
+                101,
+                MarketEvent::Trade {
+                    side: Side::Ask,
+                    price_lots: 9_900,
+                    base_lots: 6,
+                },
+            ),
+            // the strategy's ask is better than the historical one
+            event(
+                102,
+                MarketEvent::Trade {
+                    side: Side::Bid,
+                    price_lots: 10_100,
+                    base_lots: 3,
+                },
+            ),
+        ]);
+
+        assert_eq!(report.rejected_orders, 0);
+        assert_eq!(report.fill_stats.maker_fills, 2);
+        assert_eq!(report.fill_stats.taker_fills, 0);
+        assert_eq!(report.fill_stats.maker_base_lots, 5);
+        assert_eq!(report.fills[0].side, Side::Bid);
+        assert_eq!(report.fills[0].base_lots, 2);
+        assert_eq!(report.fills[1].side, Side::Ask);
+        assert_eq!(report.fills[1].base_lots, 3);
+
+        // floor(2 * 9_900 * 0.0002) + floor(3 * 10_100 * 0.0002)
+        assert_eq!(report.maker_fees, 3 + 6);
+        assert_eq!(report.taker_fees, 0);
+
+        // bought 2 and sold 3, the remaining orders were cancelled at the end
+        assert_eq!(
+            report.final_balances,
+            Balances {
+                base_native: 999,
+                quote_native: 1_000_000 - 2 * 9_900 - 3 + 3 * 10_100 - 6 - 1,
+            }
+        );
+        // one lamport of maker fees stays locked as roundoff
+        assert_eq!(report.pnl_quote_native, Some(-19_803 + 30_294 - 10_100 - 1));
+        assert!(backtest.strategy.placed);
+    }
+
+    /// Places the orders once, on the first event
+    struct PlaceOnce(Vec<Order>);
+
+    impl Strategy for PlaceOnce {
+        fn on_event(&mut self, _ctx: &StrategyContext, _event: &MarketEvent) -> Vec<Action> {
+            self.0.drain(..).map(Action::Place).collect()
+        }
+    }
+
+    #[test]
+    fn test_backtest_rejects_orders_above_balances() {
+        let mut market = Market::zeroed();
+        market.base_lot_size = 1;
+        market.quote_lot_size = 1;
+
+        let initial_balances = Balances {
+            base_native: 0,
+            quote_native: 20_000,
+        };
+        let bid = |client_order_id, max_base_lots| Order {
+            side: Side::Bid,
+            max_base_lots,
+            max_quote_lots_including_fees: 1_000_000,
+            client_order_id,
+            time_in_force: 0,
+            self_trade_behavior: SelfTradeBehavior::DecrementTake,
+            params: OrderParams::Fixed {
+                price_lots: 9_900,
+                order_type: PostOrderType::Limit,
+            },
+        };
+        let ask = Order {
+            side: Side::Ask,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 1_000_000,
+            client_order_id: 3,
+            time_in_force: 0,
+            self_trade_behavior: SelfTradeBehavior::DecrementTake,
+            params: OrderParams::Fixed {
+                price_lots: 10_100,
+                order_type: PostOrderType::Limit,
+            },
+        };
+        let mut backtest = Backtest::new(
+            BacktestConfig {
+                market,
+                initial_balances,
+                latency_secs: 0,
+            },
+            PlaceOnce(vec![bid(1, 3), bid(2, 2), ask]),
+        );
+
+        let event = |timestamp, event| TimedEvent { timestamp, event };
+        let report = backtest.run(vec![
+            event(100, MarketEvent::OraclePrice { price_lots: 10_000 }),
+            event(
+                101,
+                MarketEvent::Trade {
+                    side: Side::Ask,
+                    price_lots: 9_900,
+                    base_lots: 5,
+                },
+            ),
+        ]);
+
+        // 3 lots need 29_700 quote and there is no base to sell, only the bid of 2 lots
+        // reached the book
+        assert_eq!(report.rejected_orders, 2);
+        assert_eq!(report.fill_stats.maker_base_lots, 2);
+        assert_eq!(
+            report.final_balances,
+            Balances {
+                base_native: 2,
+                quote_native: 20_000 - 2 * 9_900,
+            }
+        );
+    }
+}
@@ -3,12 +3,15 @@ use anyhow::Result;
 use fixed::types::I80F48;
 use itertools::Itertools;
 use openbook_v2::state::{
-    Market, Orderbook, Side, DROP_EXPIRED_ORDER_LIMIT, FILL_EVENT_REMAINING_LIMIT,
+    DynBookSide, Market, Orderbook, Side, DROP_EXPIRED_ORDER_LIMIT, FILL_EVENT_REMAINING_LIMIT,
 };
 use std::collections::HashSet;
 
 pub const MAXIMUM_TAKEN_ORDERS: u8 = 45;
 const MAXIMUM_REMAINING_ACCOUNTS: usize = 3;
+/// Open orders accounts of makers with market maker protection passed to a taker order,
+/// the orders of other such makers are skipped by the program
+pub const MAXIMUM_MMP_MAKER_ACCOUNTS: usize = 8;
 
 pub struct Amounts {
     pub total_base_taken_native: u64,
@@ -30,19 +33,63 @@ pub fn remaining_accounts_to_crank(
         None
     };
 
-    let mut remaining_accounts = HashSet::new();
+    // Makers with market maker protection must be passed to be matched against
     let opposing_bookside = book.bookside(side.invert_side());
-    for order in opposing_bookside.iter_valid(now_ts, oracle_price_lots) {
-        remaining_accounts.insert(order.node.owner);
-
-        if remaining_accounts.len() >= MAXIMUM_REMAINING_ACCOUNTS {
-            break;
+    let mut remaining_accounts = passed_mmp_makers(opposing_bookside, now_ts, oracle_price_lots);
+    let mut other_accounts = 0;
+    for order in opposing_bookside
+        .iter_valid(now_ts, oracle_price_lots)
+        .take(MAXIMUM_TAKEN_ORDERS.into())
+    {
+        if order.node.mmp_enabled == 0
+            && other_accounts < MAXIMUM_REMAINING_ACCOUNTS
+            && remaining_accounts.insert(order.node.owner)
+        {
+            other_accounts += 1;
         }
     }
 
     Ok(remaining_accounts.into_iter().collect_vec())
 }
 
+/// The first makers with market maker protection on `opposing_bookside`, up to
+/// MAXIMUM_MMP_MAKER_ACCOUNTS. These are passed to taker orders, which skip the orders
+/// of the others.
+fn passed_mmp_makers(
+    opposing_bookside: &DynBookSide,
+    now_ts: u64,
+    oracle_price_lots: Option<i64>,
+) -> HashSet<Pubkey> {
+    opposing_bookside
+        .iter_valid(now_ts, oracle_price_lots)
+        .filter(|order| order.node.mmp_enabled != 0)
+        .map(|order| order.node.owner)
+        .unique()
+        .take(MAXIMUM_MMP_MAKER_ACCOUNTS)
+        .collect()
+}
+
+/// The makers with market maker protection among the orders that an order of `side` up
+/// to `price_lots` can match, up to MAXIMUM_MMP_MAKER_ACCOUNTS. The order skips the
+/// orders of such makers whose open orders account isn't passed.
+pub fn crossed_mmp_makers(
+    opposing_bookside: &DynBookSide,
+    side: Side,
+    price_lots: i64,
+    now_ts: u64,
+    oracle_price_lots: Option<i64>,
+) -> Vec<Pubkey> {
+    opposing_bookside
+        .iter_valid(now_ts, oracle_price_lots)
+        .take_while(|order| side.is_price_within_limit(order.price_lots, price_lots))
+        .take(MAXIMUM_TAKEN_ORDERS.into())
+        .filter(|order| order.node.mmp_enabled != 0)
+        .map(|order| order.node.owner)
+        .unique()
+        .take(MAXIMUM_MMP_MAKER_ACCOUNTS)
+        .collect()
+}
+
 pub fn amounts_from_book(
     book: Orderbook,
     side: Side,
@@ -81,6 +128,9 @@ pub fn amounts_from_book(
     })
 }
 
+/// Models the matching of a taker order that passes the makers of
+/// remaining_accounts_to_crank: the orders of makers with market maker protection that
+/// aren't among them are skipped.
 #[allow(clippy::too_many_arguments)]
 pub fn iterate_book(
     book: Orderbook,
@@ -107,6 +157,7 @@ pub fn iterate_book(
     let mut remaining_quote_lots = order_max_quote_lots;
 
     let opposing_bookside = book.bookside(side.invert_side());
+    let mmp_makers = passed_mmp_makers(opposing_bookside, now_ts, oracle_price_lots);
     for best_opposing in opposing_bookside.iter_all_including_invalid(now_ts, oracle_price_lots) {
         if !best_opposing.is_valid() {
             // Remove the order from the book unless we've done that enough
@@ -121,6 +172,13 @@ pub fn iterate_book(
             break;
         }
 
+        if best_opposing.node.mmp_enabled != 0 {
+            if !mmp_makers.contains(&best_opposing.node.owner) {
+                continue;
+            }
+            accounts.push(best_opposing.node.owner);
+        }
+
         let best_opposing_price = best_opposing.price_lots;
         let max_match_by_quote = remaining_quote_lots / best_opposing_price;
         if max_match_by_quote == 0 {
@@ -161,3 +219,89 @@ pub fn iterate_book(
         not_enough_liquidity,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::__private::bytemuck::Zeroable;
+    use openbook_v2::state::{new_node_key, BookSide, BookSideOrderTree, LeafNode, OrderTreeType};
+    use std::cell::{RefCell, RefMut};
+
+    fn asks_with_makers(
+        makers: &[(Pubkey, bool)],
+    ) -> (Box<RefCell<BookSide>>, Box<RefCell<BookSide>>) {
+        let bids = Box::new(RefCell::new(BookSide::zeroed()));
+        bids.borrow_mut().nodes.order_tree_type = OrderTreeType::Bids.into();
+        let asks = Box::new(RefCell::new(BookSide::zeroed()));
+        asks.borrow_mut().nodes.order_tree_type = OrderTreeType::Asks.into();
+        for (i, (owner, mmp_enabled)) in makers.iter().enumerate() {
+            let price_lots = 100 + i as i64;
+            let mut leaf = LeafNode::new(
+                0,
+                new_node_key(Side::Ask, price_lots as u64, i as u64),
+                *owner,
+                1,
+                0,
+                0,
+                -1,
+                0,
+            );
+            leaf.mmp_enabled = (*mmp_enabled).into();
+            asks.borrow_mut()
+                .insert_leaf(BookSideOrderTree::Fixed, &leaf)
+                .unwrap();
+        }
+        (bids, asks)
+    }
+
+    fn orderbook<'a>(bids: &'a RefCell<BookSide>, asks: &'a RefCell<BookSide>) -> Orderbook<'a> {
+        Orderbook {
+            bids: RefMut::map(bids.borrow_mut(), |bids| &mut **bids),
+            asks: RefMut::map(asks.borrow_mut(), |asks| &mut **asks),
+        }
+    }
+
+    #[test]
+    fn test_unpassed_mmp_makers_are_skipped() {
+        let mut market = Market::zeroed();
+        market.base_lot_size = 1;
+        market.quote_lot_size = 1;
+
+        // more makers with market maker protection than can be passed, in front of a
+        // maker without it
+        let mmp_makers = (0..=MAXIMUM_MMP_MAKER_ACCOUNTS)
+            .map(|_| (Pubkey::new_unique(), true))
+            .collect_vec();
+        let other_maker = Pubkey::new_unique();
+        let makers = [mmp_makers.clone(), vec![(other_maker, false)]].concat();
+        let (bids, asks) = asks_with_makers(&makers);
+
+        let accounts =
+            remaining_accounts_to_crank(orderbook(&bids, &asks), Side::Bid, &market, None, 0)
+                .unwrap();
+        assert_eq!(accounts.len(), MAXIMUM_MMP_MAKER_ACCOUNTS + 1);
+        assert!(accounts.contains(&other_maker));
+        assert!(!accounts.contains(&mmp_makers[MAXIMUM_MMP_MAKER_ACCOUNTS].0));
+
+        // the order of the last protected maker is skipped, the next one is taken instead
+        let mut taken = Vec::new();
+        let (base_lots, quote_lots, _, not_enough_liquidity) = iterate_book(
+            orderbook(&bids, &asks),
+            Side::Bid,
+            MAXIMUM_MMP_MAKER_ACCOUNTS as i64 + 1,
+            i64::MAX,
+            &market,
+            None,
+            0,
+            &mut taken,
+        );
+        assert!(!not_enough_liquidity);
+        assert_eq!(base_lots, MAXIMUM_MMP_MAKER_ACCOUNTS as i64 + 1);
+        let skipped_price = 100 + MAXIMUM_MMP_MAKER_ACCOUNTS as i64;
+        assert_eq!(
+            quote_lots,
+            (100..skipped_price).sum::<i64>() + skipped_price + 1
+        );
+        assert!(!taken.contains(&mmp_makers[MAXIMUM_MMP_MAKER_ACCOUNTS].0));
+    }
+}
@@ -28,7 +28,9 @@ pub struct AccountAndSlot {
 /// Track slots and account writes
 ///
 /// - use account() to retrieve the current best data for an account.
-/// - update_from_snapshot() and update_from_websocket() update the state for new messages
+/// - update_account() and update_slot() update the state for new messages, see
+///   `Message::update_chain_data()` for the messages of `snapshot_source`, `websocket_source`
+///   and `replay_source`
 pub struct ChainData {
     /// only slots >= newest_rooted_slot are retained
     slots: HashMap<u64, SlotData>,
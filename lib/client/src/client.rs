@@ -1,32 +1,47 @@
+use std::collections::HashMap;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use anchor_client::Cluster;
 
-use anchor_lang::prelude::System;
-use anchor_lang::{AccountDeserialize, Id};
-use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::Token;
+use anchor_lang::AccountDeserialize;
 
 use itertools::Itertools;
 
+use openbook_v2::error::OpenBookError;
 use openbook_v2::state::OracleConfigParams;
 use openbook_v2::{
-    state::{Market, OpenOrdersAccount, PlaceOrderType, SelfTradeBehavior, Side},
+    state::{
+        AccessGate, DynBookSide, FeeSplit, Market, MarketAccessGate, OpenOrdersAccount,
+        PlaceOrderType, SelfTradeBehavior, Side,
+    },
     PlaceMultipleOrdersArgs, PlaceOrderArgs, PlaceOrderPeggedArgs,
 };
 
 use solana_client::nonblocking::rpc_client::RpcClient as RpcClientAsync;
-use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_client::rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig};
+use solana_sdk::account::ReadableAccount;
 use solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
+use solana_sdk::clock::Clock;
 use solana_sdk::commitment_config::CommitmentLevel;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
 use solana_sdk::hash::Hash;
 use solana_sdk::signer::keypair;
 use solana_sdk::transaction::TransactionError;
 
 use crate::account_fetcher::*;
+use crate::book::crossed_mmp_makers;
 use crate::gpa::{fetch_anchor_account, fetch_openbook_accounts};
+use crate::instructions;
+use crate::lookup_table::{
+    create_lookup_table, extend_lookup_table, fetch_active_lookup_table, fetch_lookup_table,
+    market_lookup_table_addresses,
+};
+use crate::market_maker::{FairValueSource, OracleFairValue};
+use crate::program_error::{decode_transaction_error, program_notices, ProgramNotice};
+use crate::sender::{SendReport, SenderConfig, TransactionSender};
+use crate::util::AlignedAccountData;
 
 use anyhow::Context;
 use solana_sdk::instruction::Instruction;
@@ -94,6 +109,9 @@ pub struct OpenBookClient {
     pub open_orders_account: Pubkey,
 
     pub http_client: reqwest::Client,
+
+    // lookup tables by market, attached to every transaction that touches the market
+    lookup_tables: RwLock<HashMap<Pubkey, AddressLookupTableAccount>>,
 }
 
 // TODO: add retry framework for sending tx and rpc calls
@@ -157,27 +175,8 @@ impl OpenBookClient {
         owner: &Keypair,
         payer: &Keypair, // pays the SOL for the new account
     ) -> anyhow::Result<(Pubkey, Signature)> {
-        let open_orders_indexer = Pubkey::find_program_address(
-            &[b"OpenOrdersIndexer".as_ref(), owner.pubkey().as_ref()],
-            &openbook_v2::id(),
-        )
-        .0;
-
-        let ix = Instruction {
-            program_id: openbook_v2::id(),
-            accounts: anchor_lang::ToAccountMetas::to_account_metas(
-                &openbook_v2::accounts::CreateOpenOrdersIndexer {
-                    owner: owner.pubkey(),
-                    open_orders_indexer,
-                    payer: payer.pubkey(),
-                    system_program: System::id(),
-                },
-                None,
-            ),
-            data: anchor_lang::InstructionData::data(
-                &openbook_v2::instruction::CreateOpenOrdersIndexer {},
-            ),
-        };
+        let open_orders_indexer = instructions::open_orders_indexer_address(&owner.pubkey());
+        let ix = instructions::create_open_orders_indexer(owner.pubkey(), payer.pubkey());
 
         let txsig = TransactionBuilder {
             instructions: vec![ix],
@@ -201,42 +200,17 @@ impl OpenBookClient {
         account_num: u32,
         name: &str,
     ) -> anyhow::Result<(Pubkey, Signature)> {
-        let open_orders_indexer = Pubkey::find_program_address(
-            &[b"OpenOrdersIndexer".as_ref(), owner.pubkey().as_ref()],
-            &openbook_v2::id(),
-        )
-        .0;
-
-        let account = Pubkey::find_program_address(
-            &[
-                b"OpenOrders".as_ref(),
-                owner.pubkey().as_ref(),
-                &account_num.to_le_bytes(),
-            ],
-            &openbook_v2::id(),
-        )
-        .0;
-
-        let ix = Instruction {
-            program_id: openbook_v2::id(),
-            accounts: anchor_lang::ToAccountMetas::to_account_metas(
-                &openbook_v2::accounts::CreateOpenOrdersAccount {
-                    owner: owner.pubkey(),
-                    open_orders_indexer,
-                    open_orders_account: account,
-                    payer: payer.pubkey(),
-                    delegate_account: delegate,
-                    market,
-                    system_program: System::id(),
-                },
-                None,
-            ),
-            data: anchor_lang::InstructionData::data(
-                &openbook_v2::instruction::CreateOpenOrdersAccount {
-                    name: name.to_string(),
-                },
-            ),
-        };
+        let account = instructions::open_orders_account_address(&owner.pubkey(), account_num);
+        let ix = instructions::create_open_orders_account(
+            market,
+            owner.pubkey(),
+            payer.pubkey(),
+            delegate,
+            account_num,
+            name.to_string(),
+            None,
+            None,
+        );
 
         let txsig = TransactionBuilder {
             instructions: vec![ix],
@@ -288,6 +262,7 @@ impl OpenBookClient {
             owner,
             open_orders_account: account,
             http_client: reqwest::Client::new(),
+            lookup_tables: RwLock::new(HashMap::new()),
         })
     }
 
@@ -300,11 +275,63 @@ impl OpenBookClient {
             .await
     }
 
+    /// Attaches an existing lookup table to the transactions on the market, once the
+    /// addresses it was last extended with can be looked up
+    pub async fn use_market_lookup_table(
+        &self,
+        market_address: Pubkey,
+        table_address: Pubkey,
+    ) -> anyhow::Result<()> {
+        let table = fetch_active_lookup_table(&self.client.rpc_async(), table_address).await?;
+        self.lookup_tables
+            .write()
+            .unwrap()
+            .insert(market_address, table);
+        Ok(())
+    }
+
+    /// Creates a lookup table owned by the owner when table_address is None, adds the
+    /// static accounts of the market it is missing and attaches it to the transactions on
+    /// the market. New addresses can only be looked up from the next slot on, so this
+    /// waits for the slot after the last extend before attaching the table.
+    pub async fn find_or_create_market_lookup_table(
+        &self,
+        market: &Market,
+        market_address: Pubkey,
+        table_address: Option<Pubkey>,
+    ) -> anyhow::Result<Pubkey> {
+        let table_address = match table_address {
+            Some(address) => address,
+            None => create_lookup_table(&self.client, &self.owner).await?.0,
+        };
+        let rpc = self.client.rpc_async();
+        let table = fetch_lookup_table(&rpc, table_address).await?;
+        let addresses = market_lookup_table_addresses(market_address, market);
+        extend_lookup_table(&self.client, &self.owner, &table, &addresses).await?;
+        self.use_market_lookup_table(market_address, table_address)
+            .await?;
+        Ok(table_address)
+    }
+
+    /// The cached lookup tables of the markets the instructions touch
+    pub fn lookup_tables_for(
+        &self,
+        instructions: &[Instruction],
+    ) -> Vec<AddressLookupTableAccount> {
+        let lookup_tables = self.lookup_tables.read().unwrap();
+        instructions
+            .iter()
+            .flat_map(|ix| ix.accounts.iter())
+            .filter_map(|meta| lookup_tables.get(&meta.pubkey))
+            .unique_by(|table| table.key)
+            .cloned()
+            .collect()
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn create_market(
         &self,
-        market: Pubkey,
-        market_authority: Pubkey,
+        market: &Keypair,
         bids: Pubkey,
         asks: Pubkey,
         event_heap: Pubkey,
@@ -316,7 +343,7 @@ impl OpenBookClient {
         open_orders_admin: Option<Pubkey>,
         consume_events_admin: Option<Pubkey>,
         close_market_admin: Option<Pubkey>,
-        event_authority: Pubkey,
+        market_status_admin: Option<Pubkey>,
         name: String,
         oracle_config: OracleConfigParams,
         base_lot_size: i64,
@@ -324,58 +351,53 @@ impl OpenBookClient {
         maker_fee: i64,
         taker_fee: i64,
         time_expiry: i64,
+        auction_duration_secs: u32,
+        auction_interval_secs: u32,
+        min_base_lots_per_order: i64,
+        min_quote_notional: u64,
+        max_base_lots_per_order: i64,
     ) -> anyhow::Result<Signature> {
-        let ix = Instruction {
-            program_id: openbook_v2::id(),
-            accounts: {
-                anchor_lang::ToAccountMetas::to_account_metas(
-                    &openbook_v2::accounts::CreateMarket {
-                        market,
-                        market_authority,
-                        bids,
-                        asks,
-                        event_heap,
-                        payer: self.owner(),
-                        market_base_vault:
-                            spl_associated_token_account::get_associated_token_address(
-                                &market_authority,
-                                &base_mint,
-                            ),
-                        market_quote_vault:
-                            spl_associated_token_account::get_associated_token_address(
-                                &market_authority,
-                                &quote_mint,
-                            ),
-                        base_mint,
-                        quote_mint,
-                        system_program: solana_sdk::system_program::id(),
-                        oracle_a,
-                        oracle_b,
-                        collect_fee_admin,
-                        open_orders_admin,
-                        consume_events_admin,
-                        close_market_admin,
-                        event_authority,
-                        program: openbook_v2::id(),
-                        token_program: Token::id(),
-                        associated_token_program: AssociatedToken::id(),
-                    },
-                    None,
-                )
-            },
-            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::CreateMarket {
-                name,
-                oracle_config,
-                base_lot_size,
-                quote_lot_size,
-                maker_fee,
-                taker_fee,
-                time_expiry,
-            }),
-        };
-        self.send_and_confirm_owner_tx(vec![ix]).await
+        let ix = instructions::create_market(
+            self.owner(),
+            market.pubkey(),
+            bids,
+            asks,
+            event_heap,
+            base_mint,
+            quote_mint,
+            oracle_a,
+            oracle_b,
+            collect_fee_admin,
+            open_orders_admin,
+            consume_events_admin,
+            close_market_admin,
+            market_status_admin,
+            name,
+            oracle_config,
+            base_lot_size,
+            quote_lot_size,
+            maker_fee,
+            taker_fee,
+            time_expiry,
+            auction_duration_secs,
+            auction_interval_secs,
+            min_base_lots_per_order,
+            min_quote_notional,
+            max_base_lots_per_order,
+        );
+        // the market account is created by the instruction and has to sign
+        TransactionBuilder {
+            address_lookup_tables: vec![],
+            instructions: vec![ix],
+            payer: self.client.fee_payer.pubkey(),
+            signers: vec![&*self.owner, &*self.client.fee_payer, market],
+            config: self.client.transaction_builder_config,
+        }
+        .send_and_confirm(&self.client)
+        .await
     }
 
+    /// user_token_account holds the quote token for bids and the base token for asks
     #[allow(clippy::too_many_arguments)]
     pub async fn place_order(
         &self,
@@ -390,44 +412,35 @@ impl OpenBookClient {
         expiry_timestamp: u64,
         limit: u8,
         user_token_account: Pubkey,
-        market_vault: Pubkey,
         self_trade_behavior: SelfTradeBehavior,
     ) -> anyhow::Result<Signature> {
-        let ix = Instruction {
-            program_id: openbook_v2::id(),
-            accounts: {
-                anchor_lang::ToAccountMetas::to_account_metas(
-                    &openbook_v2::accounts::PlaceOrder {
-                        open_orders_account: self.open_orders_account,
-                        open_orders_admin: None,
-                        signer: self.owner(),
-                        market: market_address,
-                        bids: market.bids,
-                        asks: market.asks,
-                        event_heap: market.event_heap,
-                        oracle_a: market.oracle_a.into(),
-                        oracle_b: market.oracle_b.into(),
-                        user_token_account,
-                        market_vault,
-                        token_program: Token::id(),
-                    },
-                    None,
-                )
+        let remaining_accounts = self
+            .order_remaining_accounts(
+                &market,
+                market_address,
+                self.open_orders_account,
+                crossing_price(side, price_lots, order_type),
+            )
+            .await?;
+        let ix = instructions::place_order(
+            &market,
+            market_address,
+            self.open_orders_account,
+            self.owner(),
+            user_token_account,
+            PlaceOrderArgs {
+                side,
+                price_lots,
+                max_base_lots,
+                max_quote_lots_including_fees,
+                client_order_id,
+                order_type,
+                expiry_timestamp,
+                self_trade_behavior,
+                limit,
             },
-            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::PlaceOrder {
-                args: PlaceOrderArgs {
-                    side,
-                    price_lots,
-                    max_base_lots,
-                    max_quote_lots_including_fees,
-                    client_order_id,
-                    order_type,
-                    expiry_timestamp,
-                    self_trade_behavior,
-                    limit,
-                },
-            }),
-        };
+            remaining_accounts,
+        );
         self.send_and_confirm_owner_tx(vec![ix]).await
     }
 
@@ -446,77 +459,61 @@ impl OpenBookClient {
         expiry_timestamp: u64,
         limit: u8,
         user_token_account: Pubkey,
-        market_vault: Pubkey,
         self_trade_behavior: SelfTradeBehavior,
     ) -> anyhow::Result<Signature> {
-        let ix = Instruction {
-            program_id: openbook_v2::id(),
-            accounts: {
-                anchor_lang::ToAccountMetas::to_account_metas(
-                    &openbook_v2::accounts::PlaceOrder {
-                        open_orders_account: self.open_orders_account,
-                        open_orders_admin: None,
-                        signer: self.owner(),
-                        market: market_address,
-                        bids: market.bids,
-                        asks: market.asks,
-                        event_heap: market.event_heap,
-                        oracle_a: market.oracle_a.into(),
-                        oracle_b: market.oracle_b.into(),
-                        user_token_account,
-                        market_vault,
-                        token_program: Token::id(),
-                    },
-                    None,
-                )
-            },
-            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::PlaceOrderPegged {
-                args: PlaceOrderPeggedArgs {
-                    side,
-                    price_offset_lots,
-                    peg_limit,
-                    max_base_lots,
-                    max_quote_lots_including_fees,
-                    client_order_id,
-                    order_type,
-                    expiry_timestamp,
-                    self_trade_behavior,
-                    limit,
-                },
-            }),
+        // the price of a pegged order is only known on chain, it matches up to its peg_limit
+        let price_limit = match (peg_limit, side) {
+            (-1, Side::Bid) => i64::MAX,
+            (-1, Side::Ask) => 1,
+            _ => peg_limit,
         };
+        let remaining_accounts = self
+            .order_remaining_accounts(
+                &market,
+                market_address,
+                self.open_orders_account,
+                crossing_price(side, price_limit, order_type),
+            )
+            .await?;
+        let ix = instructions::place_order_pegged(
+            &market,
+            market_address,
+            self.open_orders_account,
+            self.owner(),
+            user_token_account,
+            PlaceOrderPeggedArgs {
+                side,
+                price_offset_lots,
+                peg_limit,
+                max_base_lots,
+                max_quote_lots_including_fees,
+                client_order_id,
+                order_type,
+                expiry_timestamp,
+                self_trade_behavior,
+                limit,
+            },
+            remaining_accounts,
+        );
         self.send_and_confirm_owner_tx(vec![ix]).await
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub async fn cancel_order(
         &self,
         market: Market,
         market_address: Pubkey,
         order_id: u128,
     ) -> anyhow::Result<Signature> {
-        let ix = Instruction {
-            program_id: openbook_v2::id(),
-            accounts: {
-                anchor_lang::ToAccountMetas::to_account_metas(
-                    &openbook_v2::accounts::CancelOrder {
-                        open_orders_account: self.open_orders_account,
-                        signer: self.owner(),
-                        market: market_address,
-                        bids: market.bids,
-                        asks: market.asks,
-                    },
-                    None,
-                )
-            },
-            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::CancelOrder {
-                order_id,
-            }),
-        };
+        let ix = instructions::cancel_order(
+            &market,
+            market_address,
+            self.open_orders_account,
+            self.owner(),
+            order_id,
+        );
         self.send_and_confirm_owner_tx(vec![ix]).await
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub async fn cancel_all_orders(
         &self,
         market: Market,
@@ -524,25 +521,14 @@ impl OpenBookClient {
         side_option: Option<Side>,
         limit: u8,
     ) -> anyhow::Result<Signature> {
-        let ix = Instruction {
-            program_id: openbook_v2::id(),
-            accounts: {
-                anchor_lang::ToAccountMetas::to_account_metas(
-                    &openbook_v2::accounts::CancelOrder {
-                        open_orders_account: self.open_orders_account,
-                        signer: self.owner(),
-                        market: market_address,
-                        bids: market.bids,
-                        asks: market.asks,
-                    },
-                    None,
-                )
-            },
-            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::CancelAllOrders {
-                side_option,
-                limit,
-            }),
-        };
+        let ix = instructions::cancel_all_orders(
+            &market,
+            market_address,
+            self.open_orders_account,
+            self.owner(),
+            side_option,
+            limit,
+        );
         self.send_and_confirm_owner_tx(vec![ix]).await
     }
 
@@ -558,137 +544,215 @@ impl OpenBookClient {
         asks: Vec<PlaceMultipleOrdersArgs>,
         limit: u8,
     ) -> anyhow::Result<Signature> {
-        let ix = Instruction {
-            program_id: openbook_v2::id(),
-            accounts: {
-                anchor_lang::ToAccountMetas::to_account_metas(
-                    &openbook_v2::accounts::CancelAllAndPlaceOrders {
-                        open_orders_account: self.open_orders_account,
-                        signer: self.owner(),
-                        open_orders_admin: market.open_orders_admin.into(),
-                        user_quote_account: user_quote_account,
-                        user_base_account: user_base_account,
-                        market: market_address,
-                        bids: market.bids,
-                        asks: market.asks,
-                        event_heap: market.event_heap,
-                        market_quote_vault: market.market_quote_vault,
-                        market_base_vault: market.market_base_vault,
-                        oracle_a: market.oracle_a.into(),
-                        oracle_b: market.oracle_b.into(),
-                        token_program: Token::id(),
-                    },
-                    None,
+        self.cancel_all_and_place_orders_batch(vec![CancelAllAndPlaceOrdersBatch {
+            market,
+            market_address,
+            open_orders_account: self.open_orders_account,
+            user_base_account,
+            user_quote_account,
+            orders_type,
+            bids,
+            asks,
+            limit,
+        }])
+        .await
+    }
+
+    /// Replaces the orders on several markets in one transaction, which usually needs
+    /// the markets' lookup tables to fit. The open orders accounts have to be owned by
+    /// the client's owner.
+    pub async fn cancel_all_and_place_orders_batch(
+        &self,
+        batches: Vec<CancelAllAndPlaceOrdersBatch>,
+    ) -> anyhow::Result<Signature> {
+        let mut ixs = vec![];
+        for batch in batches {
+            let remaining_accounts = self
+                .order_remaining_accounts(
+                    &batch.market,
+                    batch.market_address,
+                    batch.open_orders_account,
+                    batch.crossing_prices(),
                 )
-            },
-            data: anchor_lang::InstructionData::data(
-                &openbook_v2::instruction::CancelAllAndPlaceOrders {
-                    orders_type,
-                    bids,
-                    asks,
-                    limit,
-                },
-            ),
-        };
-        self.send_and_confirm_owner_tx(vec![ix]).await
+                .await?;
+            ixs.push(batch.instruction(self.owner(), remaining_accounts));
+        }
+        self.send_and_confirm_owner_tx(ixs).await
+    }
+
+    /// The remaining accounts of the owner's orders on the market: the access gate and the
+    /// owner's access proof on gated markets, the referral of an open orders account bound
+    /// to a referrer, and the makers with market maker protection that orders up to the
+    /// crossing prices can match
+    pub async fn order_remaining_accounts(
+        &self,
+        market: &Market,
+        market_address: Pubkey,
+        open_orders_account: Pubkey,
+        crossing_prices: impl IntoIterator<Item = (Side, i64)>,
+    ) -> anyhow::Result<Vec<Pubkey>> {
+        let mut accounts = vec![];
+        if market.is_access_gated() {
+            let access_gate_address = instructions::access_gate_address(&market_address);
+            let access_gate: MarketAccessGate =
+                fetch_anchor_account(&self.client.rpc_async(), &access_gate_address).await?;
+            accounts.push(access_gate_address);
+            accounts.push(match access_gate.kind() {
+                AccessGate::CredentialToken => {
+                    anchor_spl::associated_token::get_associated_token_address(
+                        &self.owner(),
+                        &access_gate.credential_mint,
+                    )
+                }
+                _ => instructions::access_pass_address(&market_address, &self.owner()),
+            });
+        }
+
+        let account =
+            account_fetcher_fetch_openorders_account(&*self.account_fetcher, &open_orders_account)
+                .await?;
+        if account.has_referral() {
+            accounts.push(instructions::open_orders_referral_address(
+                &open_orders_account,
+            ));
+        }
+
+        let crossing_prices = crossing_prices.into_iter().collect_vec();
+        if !crossing_prices.is_empty() {
+            let clock_account = self
+                .account_fetcher
+                .fetch_raw_account(&solana_sdk::sysvar::clock::ID)
+                .await?;
+            let clock: Clock = bincode::deserialize(clock_account.data())?;
+            // without an oracle price pegged makers are left out, the order skips them
+            let oracle_price_lots = if market.oracle_a.is_some() {
+                OracleFairValue {
+                    market: *market,
+                    account_fetcher: self.account_fetcher.clone(),
+                }
+                .fair_value()
+                .await
+                .ok()
+                .flatten()
+                .and_then(|price| market.native_price_to_lot(price).ok())
+            } else {
+                None
+            };
+            for (side, price_lots) in crossing_prices {
+                let opposing_bookside = match side {
+                    Side::Bid => market.asks,
+                    Side::Ask => market.bids,
+                };
+                let data = AlignedAccountData::new(
+                    self.account_fetcher
+                        .fetch_raw_account(&opposing_bookside)
+                        .await?
+                        .data(),
+                );
+                accounts.extend(crossed_mmp_makers(
+                    DynBookSide::from_account_data(data.as_slice())?,
+                    side,
+                    price_lots,
+                    clock.unix_timestamp as u64,
+                    oracle_price_lots,
+                ));
+            }
+        }
+
+        Ok(accounts.into_iter().unique().collect())
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub async fn deposit(
         &self,
+        market: Market,
         market_address: Pubkey,
         base_amount: u64,
         quote_amount: u64,
         user_base_account: Pubkey,
         user_quote_account: Pubkey,
-        market_base_vault: Pubkey,
-        market_quote_vault: Pubkey,
     ) -> anyhow::Result<Signature> {
-        let ix = Instruction {
-            program_id: openbook_v2::id(),
-            accounts: {
-                anchor_lang::ToAccountMetas::to_account_metas(
-                    &openbook_v2::accounts::Deposit {
-                        open_orders_account: self.open_orders_account,
-                        owner: self.owner(),
-                        market: market_address,
-                        user_base_account,
-                        user_quote_account,
-                        market_base_vault,
-                        market_quote_vault,
-                        token_program: Token::id(),
-                    },
-                    None,
-                )
-            },
-            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::Deposit {
-                base_amount,
-                quote_amount,
-            }),
-        };
+        let ix = instructions::deposit(
+            &market,
+            market_address,
+            self.open_orders_account,
+            self.owner(),
+            user_base_account,
+            user_quote_account,
+            base_amount,
+            quote_amount,
+        );
         self.send_and_confirm_owner_tx(vec![ix]).await
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub async fn settle_funds(
         &self,
         market: Market,
         market_address: Pubkey,
         user_base_account: Pubkey,
         user_quote_account: Pubkey,
-        market_base_vault: Pubkey,
-        market_quote_vault: Pubkey,
         referrer_account: Option<Pubkey>,
     ) -> anyhow::Result<Signature> {
-        let ix = Instruction {
-            program_id: openbook_v2::id(),
-            accounts: {
-                anchor_lang::ToAccountMetas::to_account_metas(
-                    &openbook_v2::accounts::SettleFunds {
-                        owner: self.owner(),
-                        penalty_payer: self.owner(),
-                        open_orders_account: self.open_orders_account,
-                        market: market_address,
-                        market_authority: market.market_authority,
-                        user_base_account,
-                        user_quote_account,
-                        market_base_vault,
-                        market_quote_vault,
-                        referrer_account,
-                        system_program: System::id(),
-                        token_program: Token::id(),
-                    },
-                    None,
-                )
-            },
-            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::SettleFunds {}),
+        let ix = instructions::settle_funds(
+            &market,
+            market_address,
+            self.open_orders_account,
+            self.owner(),
+            self.owner(),
+            user_base_account,
+            user_quote_account,
+            referrer_account,
+        );
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
+    /// Sends the fees of the market to token_receiver_account, or to the destinations of
+    /// the fee split when the market has one. The owner must be the collect_fee_admin.
+    pub async fn sweep_fees(
+        &self,
+        market: Market,
+        market_address: Pubkey,
+        token_receiver_account: Option<Pubkey>,
+    ) -> anyhow::Result<Signature> {
+        let destinations = if market.has_fee_split() {
+            let address = instructions::fee_split_address(&market_address);
+            let fee_split: FeeSplit =
+                account_fetcher_fetch_anchor_account(&*self.account_fetcher, &address).await?;
+            fee_split
+                .destinations
+                .iter()
+                .map(|destination| destination.token_account)
+                .collect()
+        } else {
+            vec![]
         };
+
+        let ix = instructions::sweep_fees(
+            &market,
+            market_address,
+            token_receiver_account,
+            destinations,
+        );
         self.send_and_confirm_owner_tx(vec![ix]).await
     }
 
-    #[allow(clippy::too_many_arguments)]
+    /// The open orders accounts of the makers of the events are passed as remaining
+    /// accounts, events of makers that are missing are skipped. The owner receives the
+    /// crank reward.
     pub async fn consume_events(
         &self,
         market: Market,
         market_address: Pubkey,
+        open_orders_accounts: Vec<Pubkey>,
         limit: usize,
     ) -> anyhow::Result<Signature> {
-        let ix = Instruction {
-            program_id: openbook_v2::id(),
-            accounts: {
-                anchor_lang::ToAccountMetas::to_account_metas(
-                    &openbook_v2::accounts::ConsumeEvents {
-                        consume_events_admin: market.consume_events_admin.into(),
-                        market: market_address,
-                        event_heap: market.event_heap,
-                    },
-                    None,
-                )
-            },
-            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::ConsumeEvents {
-                limit,
-            }),
-        };
+        let ix = instructions::consume_events_with_reward(
+            &market,
+            market_address,
+            self.owner(),
+            open_orders_accounts,
+            limit,
+            None,
+        );
         self.send_and_confirm_owner_tx(vec![ix]).await
     }
 
@@ -697,8 +761,8 @@ impl OpenBookClient {
         instructions: Vec<Instruction>,
     ) -> anyhow::Result<Signature> {
         TransactionBuilder {
+            address_lookup_tables: self.lookup_tables_for(&instructions),
             instructions,
-            address_lookup_tables: vec![],
             payer: self.client.fee_payer.pubkey(),
             signers: vec![&*self.owner, &*self.client.fee_payer],
             config: self.client.transaction_builder_config,
@@ -712,8 +776,8 @@ impl OpenBookClient {
         instructions: Vec<Instruction>,
     ) -> anyhow::Result<Signature> {
         TransactionBuilder {
+            address_lookup_tables: self.lookup_tables_for(&instructions),
             instructions,
-            address_lookup_tables: vec![],
             payer: self.client.fee_payer.pubkey(),
             signers: vec![&*self.client.fee_payer],
             config: self.client.transaction_builder_config,
@@ -723,6 +787,63 @@ impl OpenBookClient {
     }
 }
 
+/// The orders of one market for cancel_all_and_place_orders_batch
+pub struct CancelAllAndPlaceOrdersBatch {
+    pub market: Market,
+    pub market_address: Pubkey,
+    /// The owner's open orders account on this market
+    pub open_orders_account: Pubkey,
+    pub user_base_account: Pubkey,
+    pub user_quote_account: Pubkey,
+    pub orders_type: PlaceOrderType,
+    pub bids: Vec<PlaceMultipleOrdersArgs>,
+    pub asks: Vec<PlaceMultipleOrdersArgs>,
+    pub limit: u8,
+}
+
+impl CancelAllAndPlaceOrdersBatch {
+    /// The most aggressive price of each side, unless the orders never match
+    fn crossing_prices(&self) -> Vec<(Side, i64)> {
+        let best_bid = self.bids.iter().map(|order| order.price_lots).max();
+        let best_ask = self.asks.iter().map(|order| order.price_lots).min();
+        [(Side::Bid, best_bid), (Side::Ask, best_ask)]
+            .into_iter()
+            .filter_map(|(side, price_lots)| crossing_price(side, price_lots?, self.orders_type))
+            .collect()
+    }
+
+    fn instruction(self, owner: Pubkey, remaining_accounts: Vec<Pubkey>) -> Instruction {
+        instructions::cancel_all_and_place_orders(
+            &self.market,
+            self.market_address,
+            self.open_orders_account,
+            owner,
+            self.user_base_account,
+            self.user_quote_account,
+            self.orders_type,
+            self.bids,
+            self.asks,
+            self.limit,
+            remaining_accounts,
+        )
+    }
+}
+
+/// The side and price up to which an order can match, None for orders that never match
+fn crossing_price(side: Side, price_lots: i64, order_type: PlaceOrderType) -> Option<(Side, i64)> {
+    match order_type {
+        PlaceOrderType::PostOnly | PlaceOrderType::PostOnlySlide => None,
+        PlaceOrderType::Market => Some((
+            side,
+            match side {
+                Side::Bid => i64::MAX,
+                Side::Ask => 1,
+            },
+        )),
+        _ => Some((side, price_lots)),
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum OpenBookClientError {
     #[error("Transaction simulation error. Error: {err:?}, Logs: {}",
@@ -732,14 +853,133 @@ pub enum OpenBookClientError {
         err: Option<TransactionError>,
         logs: Vec<String>,
     },
+    #[error("OpenBook error {error:?} in instruction {instruction_index}: {error}. Logs: {}",
+        .logs.iter().join("; ")
+    )]
+    Program {
+        instruction_index: u8,
+        error: OpenBookError,
+        notices: Vec<ProgramNotice>,
+        logs: Vec<String>,
+    },
 }
 
-#[derive(Copy, Clone, Debug)]
+impl OpenBookClientError {
+    /// Decodes the error of a failed transaction, falling back to
+    /// SendTransactionPreflightFailure when it did not fail in the openbook program
+    pub fn from_transaction_error(err: Option<TransactionError>, logs: Vec<String>) -> Self {
+        match err
+            .as_ref()
+            .and_then(|err| decode_transaction_error(err, &logs))
+        {
+            Some((instruction_index, error)) => Self::Program {
+                instruction_index,
+                error,
+                notices: program_notices(&logs),
+                logs,
+            },
+            None => Self::SendTransactionPreflightFailure { err, logs },
+        }
+    }
+
+    /// Lets callers branch on the program error, e.g. to retry after WouldSelfTrade
+    pub fn openbook_error(&self) -> Option<OpenBookError> {
+        match self {
+            Self::Program { error, .. } => Some(*error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
 pub struct TransactionBuilderConfig {
     // adds a SetComputeUnitPrice instruction in front
+    pub priority_fee: Option<PriorityFeeStrategy>,
+    // simulates the transaction and adds a SetComputeUnitLimit instruction in front, asking
+    // for the consumed compute units multiplied by this factor
+    pub compute_unit_limit_headroom: Option<f64>,
+    // a fixed compute unit price, used when priority_fee is None
+    #[deprecated(note = "use priority_fee: Some(PriorityFeeStrategy::Fixed(..)) instead")]
     pub prioritization_micro_lamports: Option<u64>,
 }
 
+impl TransactionBuilderConfig {
+    /// Takes the priority fee, the deprecated prioritization_micro_lamports being a fixed one
+    #[allow(deprecated)]
+    fn take_priority_fee(&mut self) -> Option<PriorityFeeStrategy> {
+        let fixed = self
+            .prioritization_micro_lamports
+            .take()
+            .map(PriorityFeeStrategy::Fixed);
+        self.priority_fee.take().or(fixed)
+    }
+}
+
+/// How the compute unit price of a transaction is chosen
+#[derive(Copy, Clone, Debug)]
+pub enum PriorityFeeStrategy {
+    /// A fixed price in micro lamports per compute unit
+    Fixed(u64),
+    /// The percentile (0-100) of the fees recently paid to write the accounts the
+    /// transaction writes to
+    Percentile(u8),
+    /// Like Percentile, but never more than max_micro_lamports
+    Capped {
+        percentile: u8,
+        max_micro_lamports: u64,
+    },
+}
+
+impl PriorityFeeStrategy {
+    pub async fn micro_lamports(
+        &self,
+        rpc: &RpcClientAsync,
+        writable_accounts: &[Pubkey],
+    ) -> anyhow::Result<u64> {
+        Ok(match *self {
+            Self::Fixed(price) => price,
+            Self::Percentile(percentile) => {
+                recent_fee_percentile(rpc, writable_accounts, percentile).await?
+            }
+            Self::Capped {
+                percentile,
+                max_micro_lamports,
+            } => recent_fee_percentile(rpc, writable_accounts, percentile)
+                .await?
+                .min(max_micro_lamports),
+        })
+    }
+}
+
+// getRecentPrioritizationFees accepts at most this many accounts
+const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+async fn recent_fee_percentile(
+    rpc: &RpcClientAsync,
+    writable_accounts: &[Pubkey],
+    percentile: u8,
+) -> anyhow::Result<u64> {
+    let accounts =
+        &writable_accounts[..writable_accounts.len().min(MAX_PRIORITIZATION_FEE_ACCOUNTS)];
+    let fees = rpc
+        .get_recent_prioritization_fees(accounts)
+        .await?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+    Ok(fee_percentile(fees, percentile))
+}
+
+fn fee_percentile(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let index = (fees.len() - 1) * usize::from(percentile.min(100)) / 100;
+    fees[index]
+}
+
 pub struct TransactionBuilder<'a> {
     pub instructions: Vec<Instruction>,
     pub address_lookup_tables: Vec<AddressLookupTableAccount>,
@@ -753,32 +993,80 @@ impl<'a> TransactionBuilder<'a> {
         self,
         rpc: &RpcClientAsync,
     ) -> anyhow::Result<solana_sdk::transaction::VersionedTransaction> {
-        let latest_blockhash = rpc.get_latest_blockhash().await?;
-        self.transaction_with_blockhash(latest_blockhash)
+        Ok(self.transaction_with_block_height(rpc).await?.0)
+    }
+
+    /// The transaction and the last block height its blockhash is valid for
+    pub async fn transaction_with_block_height(
+        mut self,
+        rpc: &RpcClientAsync,
+    ) -> anyhow::Result<(solana_sdk::transaction::VersionedTransaction, u64)> {
+        let (latest_blockhash, last_valid_block_height) = rpc
+            .get_latest_blockhash_with_commitment(rpc.commitment())
+            .await?;
+
+        let mut compute_budget = vec![];
+        if let Some(priority_fee) = self.config.take_priority_fee() {
+            let price = priority_fee
+                .micro_lamports(rpc, &self.writable_accounts())
+                .await?;
+            compute_budget.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        if let Some(headroom) = self.config.compute_unit_limit_headroom.take() {
+            let units = self.simulate_compute_units(rpc, latest_blockhash).await?;
+            let limit = (units as f64 * headroom)
+                .ceil()
+                .min(MAX_COMPUTE_UNIT_LIMIT as f64);
+            compute_budget.push(ComputeBudgetInstruction::set_compute_unit_limit(
+                limit as u32,
+            ));
+        }
+        self.instructions.splice(0..0, compute_budget);
+
+        let tx = self.transaction_with_blockhash(latest_blockhash)?;
+        Ok((tx, last_valid_block_height))
     }
 
+    /// Only a fixed priority fee can be applied without an rpc, use transaction() for the
+    /// others
     pub fn transaction_with_blockhash(
         mut self,
         blockhash: Hash,
     ) -> anyhow::Result<solana_sdk::transaction::VersionedTransaction> {
-        if let Some(prio_price) = self.config.prioritization_micro_lamports {
-            self.instructions.insert(
+        anyhow::ensure!(
+            self.config.compute_unit_limit_headroom.is_none(),
+            "the compute unit limit can only be estimated by transaction()"
+        );
+        match self.config.take_priority_fee() {
+            Some(PriorityFeeStrategy::Fixed(prio_price)) => self.instructions.insert(
                 0,
-                solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(
-                    prio_price,
-                ),
-            )
+                ComputeBudgetInstruction::set_compute_unit_price(prio_price),
+            ),
+            Some(strategy) => anyhow::bail!(
+                "priority fee {:?} can only be estimated by transaction()",
+                strategy
+            ),
+            None => {}
         }
+        self.compile(&self.instructions, blockhash)
+    }
+
+    fn compile(
+        &self,
+        instructions: &[Instruction],
+        blockhash: Hash,
+    ) -> anyhow::Result<solana_sdk::transaction::VersionedTransaction> {
         let v0_message = solana_sdk::message::v0::Message::try_compile(
             &self.payer,
-            &self.instructions,
+            instructions,
             &self.address_lookup_tables,
             blockhash,
         )?;
         let versioned_message = solana_sdk::message::VersionedMessage::V0(v0_message);
         let signers = self
             .signers
-            .into_iter()
+            .iter()
+            .copied()
             .unique_by(|s| s.pubkey())
             .collect::<Vec<_>>();
         let tx =
@@ -786,6 +1074,54 @@ impl<'a> TransactionBuilder<'a> {
         Ok(tx)
     }
 
+    /// The accounts whose recent fees decide the priority fee: the market, bids, asks,
+    /// event heap and whatever else the instructions write to, except the payer
+    pub fn writable_accounts(&self) -> Vec<Pubkey> {
+        self.instructions
+            .iter()
+            .flat_map(|ix| ix.accounts.iter())
+            .filter(|meta| meta.is_writable && meta.pubkey != self.payer)
+            .map(|meta| meta.pubkey)
+            .unique()
+            .collect()
+    }
+
+    async fn simulate_compute_units(
+        &self,
+        rpc: &RpcClientAsync,
+        blockhash: Hash,
+    ) -> anyhow::Result<u64> {
+        // simulate with the maximum limit, so the estimate isn't cut off by the default
+        let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+            MAX_COMPUTE_UNIT_LIMIT,
+        )];
+        instructions.extend(self.instructions.iter().cloned());
+        let tx = self.compile(&instructions, blockhash)?;
+        let simulation = rpc
+            .simulate_transaction_with_config(
+                &tx,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    commitment: Some(CommitmentConfig::processed()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(prettify_solana_client_error)?
+            .value;
+        if simulation.err.is_some() {
+            return Err(OpenBookClientError::from_transaction_error(
+                simulation.err,
+                simulation.logs.unwrap_or_default(),
+            )
+            .into());
+        }
+        simulation
+            .units_consumed
+            .context("simulation did not report the consumed compute units")
+    }
+
     // These two send() functions don't really belong into the transaction builder!
 
     pub async fn send(self, client: &Client) -> anyhow::Result<Signature> {
@@ -797,12 +1133,18 @@ impl<'a> TransactionBuilder<'a> {
     }
 
     pub async fn send_and_confirm(self, client: &Client) -> anyhow::Result<Signature> {
-        let rpc = client.rpc_async();
-        let tx = self.transaction(&rpc).await?;
-        // TODO: Wish we could use client.rpc_send_transaction_config here too!
-        rpc.send_and_confirm_transaction(&tx)
-            .await
-            .map_err(prettify_solana_client_error)
+        let sender = TransactionSender::new(
+            Arc::new(client.rpc_async()),
+            SenderConfig::from_client(client),
+        );
+        self.send_with(&sender).await?.into_result()
+    }
+
+    /// Sends through a shared sender, which rebroadcasts until the transaction is final
+    pub async fn send_with(self, sender: &TransactionSender) -> anyhow::Result<SendReport> {
+        let (tx, last_valid_block_height) =
+            self.transaction_with_block_height(sender.rpc()).await?;
+        sender.send(&tx, last_valid_block_height).await
     }
 }
 
@@ -829,10 +1171,10 @@ pub fn prettify_solana_client_error(
         ..
     }) = err.kind()
     {
-        return OpenBookClientError::SendTransactionPreflightFailure {
-            err: s.err.clone(),
-            logs: s.logs.clone().unwrap_or_default(),
-        }
+        return OpenBookClientError::from_transaction_error(
+            s.err.clone(),
+            s.logs.clone().unwrap_or_default(),
+        )
         .into();
     }
 
@@ -863,3 +1205,164 @@ pub fn pubkey_from_cli(pubkey: &str) -> Pubkey {
         Err(_) => keypair_from_cli(pubkey).pubkey(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    #[test]
+    fn test_cancel_all_and_place_orders_batch_accounts() {
+        use anchor_lang::__private::bytemuck::Zeroable;
+
+        let owner = Pubkey::new_unique();
+        let batch = |market_address, open_orders_account| CancelAllAndPlaceOrdersBatch {
+            market: Market::zeroed(),
+            market_address,
+            open_orders_account,
+            user_base_account: Pubkey::new_unique(),
+            user_quote_account: Pubkey::new_unique(),
+            orders_type: PlaceOrderType::PostOnly,
+            bids: vec![],
+            asks: vec![],
+            limit: 10,
+        };
+        let markets = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let open_orders_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // each instruction uses the open orders account of its market
+        let ixs: Vec<Instruction> = markets
+            .into_iter()
+            .zip(open_orders_accounts)
+            .map(|(market, open_orders_account)| {
+                batch(market, open_orders_account).instruction(owner, vec![])
+            })
+            .collect();
+        for (i, ix) in ixs.iter().enumerate() {
+            let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+            assert!(keys.contains(&markets[i]));
+            assert!(keys.contains(&open_orders_accounts[i]));
+            assert!(!keys.contains(&markets[1 - i]));
+            assert!(!keys.contains(&open_orders_accounts[1 - i]));
+        }
+    }
+
+    #[test]
+    fn test_crossing_prices() {
+        use anchor_lang::__private::bytemuck::Zeroable;
+
+        let order = |price_lots| PlaceMultipleOrdersArgs {
+            price_lots,
+            max_quote_lots_including_fees: 1000,
+            expiry_timestamp: 0,
+        };
+        let mut batch = CancelAllAndPlaceOrdersBatch {
+            market: Market::zeroed(),
+            market_address: Pubkey::new_unique(),
+            open_orders_account: Pubkey::new_unique(),
+            user_base_account: Pubkey::new_unique(),
+            user_quote_account: Pubkey::new_unique(),
+            orders_type: PlaceOrderType::Limit,
+            bids: vec![order(90), order(100)],
+            asks: vec![],
+            limit: 10,
+        };
+        assert_eq!(batch.crossing_prices(), vec![(Side::Bid, 100)]);
+
+        batch.asks = vec![order(120), order(110)];
+        assert_eq!(
+            batch.crossing_prices(),
+            vec![(Side::Bid, 100), (Side::Ask, 110)]
+        );
+
+        // post only orders never match, market orders match at any price
+        batch.orders_type = PlaceOrderType::PostOnly;
+        assert!(batch.crossing_prices().is_empty());
+        assert_eq!(
+            crossing_price(Side::Ask, 110, PlaceOrderType::Market),
+            Some((Side::Ask, 1))
+        );
+    }
+
+    #[test]
+    fn test_fee_percentile() {
+        assert_eq!(fee_percentile(vec![], 50), 0);
+        let fees = vec![50, 0, 10, 40, 20, 30];
+        assert_eq!(fee_percentile(fees.clone(), 0), 0);
+        assert_eq!(fee_percentile(fees.clone(), 50), 20);
+        assert_eq!(fee_percentile(fees.clone(), 100), 50);
+        assert_eq!(fee_percentile(fees, 255), 50);
+    }
+
+    #[test]
+    fn test_writable_accounts() {
+        let payer = Keypair::new();
+        let market = Pubkey::new_unique();
+        let bids = Pubkey::new_unique();
+        let oracle = Pubkey::new_unique();
+        let ix = |accounts| Instruction {
+            program_id: openbook_v2::id(),
+            accounts,
+            data: vec![],
+        };
+        let builder = TransactionBuilder {
+            instructions: vec![
+                ix(vec![
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new(market, false),
+                    AccountMeta::new_readonly(oracle, false),
+                ]),
+                ix(vec![
+                    AccountMeta::new(market, false),
+                    AccountMeta::new(bids, false),
+                ]),
+            ],
+            address_lookup_tables: vec![],
+            signers: vec![&payer],
+            payer: payer.pubkey(),
+            config: TransactionBuilderConfig::default(),
+        };
+        assert_eq!(builder.writable_accounts(), vec![market, bids]);
+    }
+
+    #[test]
+    fn test_fixed_priority_fee_without_rpc() {
+        let payer = Keypair::new();
+        let builder = |priority_fee| TransactionBuilder {
+            instructions: vec![],
+            address_lookup_tables: vec![],
+            signers: vec![&payer],
+            payer: payer.pubkey(),
+            config: TransactionBuilderConfig {
+                priority_fee,
+                ..TransactionBuilderConfig::default()
+            },
+        };
+        let tx = builder(Some(PriorityFeeStrategy::Fixed(7)))
+            .transaction_with_blockhash(Hash::default())
+            .unwrap();
+        assert_eq!(tx.message.instructions().len(), 1);
+        assert!(builder(Some(PriorityFeeStrategy::Percentile(50)))
+            .transaction_with_blockhash(Hash::default())
+            .is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_prioritization_micro_lamports() {
+        let payer = Keypair::new();
+        let tx = TransactionBuilder {
+            instructions: vec![],
+            address_lookup_tables: vec![],
+            signers: vec![&payer],
+            payer: payer.pubkey(),
+            config: TransactionBuilderConfig {
+                prioritization_micro_lamports: Some(7),
+                ..TransactionBuilderConfig::default()
+            },
+        }
+        .transaction_with_blockhash(Hash::default())
+        .unwrap();
+        assert_eq!(tx.message.instructions().len(), 1);
+    }
+}
@@ -37,4 +37,35 @@ impl MarketContext {
     pub fn max_base_lots(&self, base_size: u64) -> u64 {
         base_size / (self.market.base_lot_size as u64)
     }
+
+    pub fn min_base_lots_per_order(&self) -> i64 {
+        self.market.min_base_lots_per_order
+    }
+    pub fn min_quote_notional(&self) -> u64 {
+        self.market.min_quote_notional
+    }
+    // No limit = None
+    pub fn max_base_lots_per_order(&self) -> Option<i64> {
+        (self.market.max_base_lots_per_order != 0).then_some(self.market.max_base_lots_per_order)
+    }
+
+    // Validates the part of an order that would rest on the book. The program doesn't post
+    // remainders below the minimum size and rejects the ones above the maximum.
+    pub fn check_order_size(&self, price_lots: i64, base_lots: i64) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !self.market.is_below_min_order_size(base_lots, price_lots),
+            "order of {} base lots at price {} is below the minimum size of {} base lots and {} quote native",
+            base_lots,
+            price_lots,
+            self.market.min_base_lots_per_order,
+            self.market.min_quote_notional,
+        );
+        anyhow::ensure!(
+            !self.market.is_above_max_order_size(base_lots),
+            "order of {} base lots is above the maximum size of {} base lots",
+            base_lots,
+            self.market.max_base_lots_per_order,
+        );
+        Ok(())
+    }
 }
@@ -1,4 +1,3 @@
-use anchor_lang::__private::bytemuck::Zeroable;
 use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
 use anyhow::Result;
@@ -7,13 +6,13 @@ use openbook_v2::{
     accounts::PlaceTakeOrder,
     accounts_zerocopy,
     pubkey_option::NonZeroPubkeyOption,
-    state::{BookSide, EventHeap, Market, Orderbook, Side},
+    state::{DynBookSide, DynEventHeap, Market, Orderbook, Side},
 };
 
 use crate::{
     book::{amounts_from_book, Amounts},
     remaining_accounts_to_crank,
-    util::ZeroCopyDeserialize,
+    util::{AlignedAccountData, ZeroCopyDeserialize},
 };
 use jupiter_amm_interface::{
     AccountMap, Amm, KeyedAccount, Quote, QuoteParams, Side as JupiterSide, Swap,
@@ -21,14 +20,31 @@ use jupiter_amm_interface::{
 };
 /// An abstraction in order to share reserve mints and necessary data
 use solana_sdk::{pubkey::Pubkey, sysvar::clock};
-use std::cell::RefCell;
+use std::cell::{RefCell, RefMut};
+
+/// Orderbook over copies of the bids and asks account data
+fn orderbook<'a>(
+    bids: &'a RefCell<AlignedAccountData>,
+    asks: &'a RefCell<AlignedAccountData>,
+) -> Result<Orderbook<'a>> {
+    let side = |data: &'a RefCell<AlignedAccountData>| -> Result<RefMut<'a, DynBookSide>> {
+        DynBookSide::from_account_data(data.borrow().as_slice())?;
+        Ok(RefMut::map(data.borrow_mut(), |data| {
+            DynBookSide::from_account_data_mut(data.as_mut_slice()).unwrap()
+        }))
+    };
+    Ok(Orderbook {
+        bids: side(bids)?,
+        asks: side(asks)?,
+    })
+}
 
 #[derive(Clone)]
 pub struct OpenBookMarket {
     market: Market,
-    event_heap: EventHeap,
-    bids: BookSide,
-    asks: BookSide,
+    event_heap: AlignedAccountData,
+    bids: AlignedAccountData,
+    asks: AlignedAccountData,
     timestamp: u64,
     key: Pubkey,
     label: String,
@@ -83,9 +99,9 @@ impl Amm for OpenBookMarket {
             label: market.name().to_string(),
             related_accounts,
             reserve_mints: [market.base_mint, market.quote_mint],
-            event_heap: EventHeap::zeroed(),
-            bids: BookSide::zeroed(),
-            asks: BookSide::zeroed(),
+            event_heap: AlignedAccountData::default(),
+            bids: AlignedAccountData::default(),
+            asks: AlignedAccountData::default(),
             oracle_price: None,
             timestamp: 0,
             is_permissioned,
@@ -98,14 +114,16 @@ impl Amm for OpenBookMarket {
         }
 
         let bids_data = account_map.get(&self.market.bids).unwrap();
-        self.bids = BookSide::try_deserialize_from_slice(&mut bids_data.data.as_slice()).unwrap();
+        self.bids = AlignedAccountData::new(&bids_data.data);
+        DynBookSide::from_account_data(self.bids.as_slice())?;
 
         let asks_data = account_map.get(&self.market.asks).unwrap();
-        self.asks = BookSide::try_deserialize_from_slice(&mut asks_data.data.as_slice()).unwrap();
+        self.asks = AlignedAccountData::new(&asks_data.data);
+        DynBookSide::from_account_data(self.asks.as_slice())?;
 
         let event_heap_data = account_map.get(&self.market.event_heap).unwrap();
-        self.event_heap =
-            EventHeap::try_deserialize_from_slice(&mut event_heap_data.data.as_slice()).unwrap();
+        self.event_heap = AlignedAccountData::new(&event_heap_data.data);
+        DynEventHeap::from_account_data(self.event_heap.as_slice())?;
 
         let clock_data = account_map.get(&clock::ID).unwrap();
         let clock: Clock = bincode::deserialize(clock_data.data.as_slice())?;
@@ -157,12 +175,9 @@ impl Amm for OpenBookMarket {
             ),
         };
 
-        let bids_ref = RefCell::new(self.bids);
-        let asks_ref = RefCell::new(self.asks);
-        let book = Orderbook {
-            bids: bids_ref.borrow_mut(),
-            asks: asks_ref.borrow_mut(),
-        };
+        let bids_ref = RefCell::new(self.bids.clone());
+        let asks_ref = RefCell::new(self.asks.clone());
+        let book = orderbook(&bids_ref, &asks_ref)?;
 
         let order_amounts: Amounts = amounts_from_book(
             book,
@@ -245,12 +260,9 @@ impl Amm for OpenBookMarket {
 
             let mut account_metas = accounts.to_account_metas(None);
 
-            let bids_ref = RefCell::new(self.bids);
-            let asks_ref = RefCell::new(self.asks);
-            let book = Orderbook {
-                bids: bids_ref.borrow_mut(),
-                asks: asks_ref.borrow_mut(),
-            };
+            let bids_ref = RefCell::new(self.bids.clone());
+            let asks_ref = RefCell::new(self.asks.clone());
+            let book = orderbook(&bids_ref, &asks_ref)?;
 
             let remaining_accounts = remaining_accounts_to_crank(
                 book,
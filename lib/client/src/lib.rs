@@ -3,10 +3,12 @@ pub use book::*;
 pub use client::*;
 pub use context::*;
 pub use jup::*;
+pub use openbook_v2_instructions as instructions;
 pub use util::*;
 
 mod account_fetcher;
 pub mod account_update_stream;
+pub mod backtest;
 mod book;
 pub mod chain_data;
 mod chain_data_fetcher;
@@ -14,5 +16,11 @@ mod client;
 mod context;
 mod gpa;
 mod jup;
+pub mod lookup_table;
+pub mod market_maker;
+pub mod program_error;
+pub mod replay_source;
+pub mod sender;
 pub mod snapshot_source;
 mod util;
+pub mod websocket_source;
@@ -0,0 +1,173 @@
+//! Address lookup tables holding the static accounts of a market, so that transactions
+//! touching several markets or many makers still fit into one transaction.
+
+use anchor_lang::Id;
+use anchor_spl::token::Token;
+use itertools::Itertools;
+use openbook_v2::state::Market;
+use solana_client::nonblocking::rpc_client::RpcClient as RpcClientAsync;
+use solana_sdk::{
+    address_lookup_table::{instruction as alt_instruction, state::AddressLookupTable},
+    address_lookup_table_account::AddressLookupTableAccount,
+    clock::Slot,
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+};
+
+use std::time::Duration;
+
+use crate::{Client, TransactionBuilder};
+
+// a transaction extending a table has room for about 30 addresses
+const EXTEND_CHUNK_SIZE: usize = 20;
+
+// about a slot
+const ACTIVATION_POLL_INTERVAL: Duration = Duration::from_millis(400);
+
+/// The accounts every order flow on the market uses
+pub fn market_lookup_table_addresses(market_address: Pubkey, market: &Market) -> Vec<Pubkey> {
+    let mut addresses = vec![
+        market_address,
+        market.bids,
+        market.asks,
+        market.event_heap,
+        market.market_base_vault,
+        market.market_quote_vault,
+        market.market_authority,
+    ];
+    addresses.extend(Option::<Pubkey>::from(market.oracle_a));
+    addresses.extend(Option::<Pubkey>::from(market.oracle_b));
+    addresses.push(Token::id());
+    addresses
+}
+
+pub async fn fetch_lookup_table(
+    rpc: &RpcClientAsync,
+    address: Pubkey,
+) -> anyhow::Result<AddressLookupTableAccount> {
+    Ok(fetch_lookup_table_with_last_extended_slot(rpc, address)
+        .await?
+        .0)
+}
+
+async fn fetch_lookup_table_with_last_extended_slot(
+    rpc: &RpcClientAsync,
+    address: Pubkey,
+) -> anyhow::Result<(AddressLookupTableAccount, Slot)> {
+    let account = rpc.get_account(&address).await?;
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|e| anyhow::anyhow!("{} is not a lookup table: {}", address, e))?;
+    Ok((
+        AddressLookupTableAccount {
+            key: address,
+            addresses: table.addresses.to_vec(),
+        },
+        table.meta.last_extended_slot,
+    ))
+}
+
+/// Fetches the table once the slot it was last extended in has passed, addresses added
+/// in that slot can't be looked up before
+pub async fn fetch_active_lookup_table(
+    rpc: &RpcClientAsync,
+    address: Pubkey,
+) -> anyhow::Result<AddressLookupTableAccount> {
+    loop {
+        let (table, last_extended_slot) =
+            fetch_lookup_table_with_last_extended_slot(rpc, address).await?;
+        if rpc.get_slot().await? > last_extended_slot {
+            return Ok(table);
+        }
+        tokio::time::sleep(ACTIVATION_POLL_INTERVAL).await;
+    }
+}
+
+pub async fn create_lookup_table(
+    client: &Client,
+    authority: &Keypair,
+) -> anyhow::Result<(Pubkey, Signature)> {
+    let rpc = client.rpc_async();
+    let recent_slot = rpc
+        .get_slot_with_commitment(CommitmentConfig::finalized())
+        .await?;
+    let (ix, address) = alt_instruction::create_lookup_table(
+        authority.pubkey(),
+        client.fee_payer.pubkey(),
+        recent_slot,
+    );
+    let txsig = TransactionBuilder {
+        instructions: vec![ix],
+        address_lookup_tables: vec![],
+        payer: client.fee_payer.pubkey(),
+        signers: vec![authority, &*client.fee_payer],
+        config: client.transaction_builder_config,
+    }
+    .send_and_confirm(client)
+    .await?;
+    Ok((address, txsig))
+}
+
+/// Adds the addresses the table doesn't hold yet, returns the signatures of the extend
+/// transactions
+pub async fn extend_lookup_table(
+    client: &Client,
+    authority: &Keypair,
+    table: &AddressLookupTableAccount,
+    addresses: &[Pubkey],
+) -> anyhow::Result<Vec<Signature>> {
+    let missing = addresses
+        .iter()
+        .filter(|address| !table.addresses.contains(address))
+        .unique()
+        .copied()
+        .collect::<Vec<_>>();
+
+    let mut txsigs = vec![];
+    for chunk in missing.chunks(EXTEND_CHUNK_SIZE) {
+        let ix = alt_instruction::extend_lookup_table(
+            table.key,
+            authority.pubkey(),
+            Some(client.fee_payer.pubkey()),
+            chunk.to_vec(),
+        );
+        txsigs.push(
+            TransactionBuilder {
+                instructions: vec![ix],
+                address_lookup_tables: vec![],
+                payer: client.fee_payer.pubkey(),
+                signers: vec![authority, &*client.fee_payer],
+                config: client.transaction_builder_config,
+            }
+            .send_and_confirm(client)
+            .await?,
+        );
+    }
+    Ok(txsigs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::__private::bytemuck::Zeroable;
+    use openbook_v2::pubkey_option::NonZeroPubkeyOption;
+
+    #[test]
+    fn test_market_lookup_table_addresses() {
+        let mut market = Market::zeroed();
+        market.bids = Pubkey::new_unique();
+        market.oracle_b = NonZeroPubkeyOption::from(Some(Pubkey::new_unique()));
+        let market_address = Pubkey::new_unique();
+
+        let addresses = market_lookup_table_addresses(market_address, &market);
+        assert_eq!(addresses.len(), 9);
+        assert_eq!(addresses[0], market_address);
+        assert_eq!(addresses[1], market.bids);
+        assert_eq!(
+            addresses[7],
+            Option::<Pubkey>::from(market.oracle_b).unwrap()
+        );
+        assert_eq!(addresses[8], Token::id());
+    }
+}
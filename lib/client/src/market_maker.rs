@@ -0,0 +1,436 @@
+//! A quoting loop for market makers: keeps a ladder of post-only orders around a fair
+//! value on both sides of the book, skewed by the base inventory held in the open orders
+//! account and the base token account.
+//!
+//! Every tick the fair value is fetched. Without a trustworthy fair value, like a stale
+//! oracle, all orders are cancelled until it recovers. Otherwise the ladder is replaced
+//! with `cancel_all_and_place_orders` whenever the fair value moved enough or the inventory
+//! changed, and free funds are settled back to the token accounts.
+
+use anchor_lang::prelude::Clock;
+use anchor_spl::token::spl_token;
+use fixed::types::I80F48;
+use openbook_v2::{
+    accounts_zerocopy::KeyedAccountSharedData,
+    state::{Market, PlaceOrderType, Position},
+    PlaceMultipleOrdersArgs,
+};
+use solana_sdk::{account::ReadableAccount, program_pack::Pack, pubkey::Pubkey, sysvar::clock};
+
+use log::*;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    account_fetcher::{account_fetcher_fetch_openorders_account, AccountFetcher},
+    context::MarketContext,
+    OpenBookClient,
+};
+
+#[async_trait::async_trait]
+pub trait FairValueSource: Send + Sync {
+    /// Price in native quote per native base, None while it can't be trusted
+    async fn fair_value(&self) -> anyhow::Result<Option<I80F48>>;
+}
+
+/// Fair value from the market's oracles, through `Market::oracle_price`. Stale oracles and
+/// oracles with a too wide confidence interval give no fair value.
+pub struct OracleFairValue {
+    pub market: Market,
+    pub account_fetcher: Arc<dyn AccountFetcher>,
+}
+
+#[async_trait::async_trait]
+impl FairValueSource for OracleFairValue {
+    async fn fair_value(&self) -> anyhow::Result<Option<I80F48>> {
+        let mut oracles = vec![];
+        for oracle in [self.market.oracle_a, self.market.oracle_b]
+            .into_iter()
+            .filter_map(Option::<Pubkey>::from)
+        {
+            let account = self.account_fetcher.fetch_raw_account(&oracle).await?;
+            oracles.push(KeyedAccountSharedData::new(oracle, account));
+        }
+        anyhow::ensure!(!oracles.is_empty(), "the market has no oracle");
+
+        let clock_account = self.account_fetcher.fetch_raw_account(&clock::ID).await?;
+        let clock: Clock = bincode::deserialize(clock_account.data())?;
+
+        Ok(self
+            .market
+            .oracle_price(oracles.first(), oracles.get(1), clock.slot)?)
+    }
+}
+
+/// Fair value pushed by an external feed, which is considered stale after `max_age`
+#[derive(Clone)]
+pub struct ExternalFairValue {
+    latest: Arc<Mutex<Option<(I80F48, Instant)>>>,
+    max_age: Duration,
+}
+
+impl ExternalFairValue {
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            latest: Arc::new(Mutex::new(None)),
+            max_age,
+        }
+    }
+
+    pub fn update(&self, price: I80F48) {
+        *self.latest.lock().unwrap() = Some((price, Instant::now()));
+    }
+}
+
+#[async_trait::async_trait]
+impl FairValueSource for ExternalFairValue {
+    async fn fair_value(&self) -> anyhow::Result<Option<I80F48>> {
+        let latest = *self.latest.lock().unwrap();
+        Ok(latest
+            .filter(|(_, updated)| updated.elapsed() <= self.max_age)
+            .map(|(price, _)| price))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LadderLevel {
+    /// Distance of the bid and the ask from the (skewed) fair value
+    pub spread_bps: f64,
+    pub base_lots: i64,
+}
+
+#[derive(Clone, Debug)]
+pub struct MarketMakerConfig {
+    pub levels: Vec<LadderLevel>,
+    /// Base inventory to hold in the open orders account and `user_base_account`, in
+    /// native units
+    pub target_base_native: i64,
+    /// Quotes move down by this much for every base lot of inventory above the target,
+    /// and up when below it
+    pub skew_bps_per_base_lot: f64,
+    pub max_skew_bps: f64,
+    /// Replace the orders when the fair value moved more than this since the last quotes
+    pub requote_threshold_bps: f64,
+    /// Orders expire after this time, so they don't outlive the market maker. 0 to
+    /// keep them until cancelled
+    pub order_expiry_secs: u64,
+    pub tick_interval: Duration,
+    pub settle_interval: Duration,
+    pub user_base_account: Pubkey,
+    pub user_quote_account: Pubkey,
+}
+
+/// Base tokens owned in the open orders account, free or locked in asks, and in the
+/// wallet. Settling moves funds between the two without changing the inventory.
+pub fn inventory_base_native(market: &Market, position: &Position, wallet_base_native: u64) -> i64 {
+    position.base_free_native as i64
+        + position.asks_base_lots * market.base_lot_size
+        + wallet_base_native as i64
+}
+
+/// The bids and asks of the ladder, best first. Levels whose bid price would not be
+/// positive are left out.
+pub fn ladder_orders(
+    market: &Market,
+    config: &MarketMakerConfig,
+    fair_value: I80F48,
+    inventory_base_native: i64,
+    expiry_timestamp: u64,
+) -> (Vec<PlaceMultipleOrdersArgs>, Vec<PlaceMultipleOrdersArgs>) {
+    let excess_base_lots =
+        (inventory_base_native - config.target_base_native) as f64 / market.base_lot_size as f64;
+    let skew_bps = (-excess_base_lots * config.skew_bps_per_base_lot)
+        .clamp(-config.max_skew_bps, config.max_skew_bps);
+    let center_price_lots = fair_value.to_num::<f64>() * market.base_lot_size as f64
+        / market.quote_lot_size as f64
+        * (1.0 + skew_bps / 10_000.0);
+
+    // Posted orders reserve maker fees out of max_quote_lots_including_fees, on both sides
+    let order = |price_lots: i64, base_lots: i64| {
+        let quote_lots = price_lots * base_lots;
+        let fees = market.maker_fees_ceil(quote_lots);
+        PlaceMultipleOrdersArgs {
+            price_lots,
+            max_quote_lots_including_fees: quote_lots + fees + i64::from(fees > 0),
+            expiry_timestamp,
+        }
+    };
+
+    let mut bids = vec![];
+    let mut asks = vec![];
+    for level in config.levels.iter() {
+        let spread = level.spread_bps / 10_000.0;
+        let bid_price_lots = (center_price_lots * (1.0 - spread)).floor() as i64;
+        let ask_price_lots = (center_price_lots * (1.0 + spread)).ceil() as i64;
+        if bid_price_lots < 1 {
+            continue;
+        }
+        bids.push(order(bid_price_lots, level.base_lots));
+        asks.push(order(ask_price_lots, level.base_lots));
+    }
+    (bids, asks)
+}
+
+pub struct MarketMaker {
+    pub client: OpenBookClient,
+    pub market: MarketContext,
+    pub fair_value_source: Box<dyn FairValueSource>,
+    /// Should return fresh data, the cache of `OpenBookClient::account_fetcher` would hide
+    /// the fills
+    pub account_fetcher: Arc<dyn AccountFetcher>,
+    pub config: MarketMakerConfig,
+    /// Fair value and inventory of the current quotes, None while not quoting
+    quoted: Option<(I80F48, i64)>,
+    last_settle: Option<Instant>,
+}
+
+impl MarketMaker {
+    pub fn new(
+        client: OpenBookClient,
+        market: MarketContext,
+        fair_value_source: Box<dyn FairValueSource>,
+        account_fetcher: Arc<dyn AccountFetcher>,
+        config: MarketMakerConfig,
+    ) -> Self {
+        Self {
+            client,
+            market,
+            fair_value_source,
+            account_fetcher,
+            config,
+            quoted: None,
+            last_settle: None,
+        }
+    }
+
+    pub fn is_quoting(&self) -> bool {
+        self.quoted.is_some()
+    }
+
+    /// Runs ticks until an error occurs, cancelling all orders before returning it
+    pub async fn run(&mut self) -> anyhow::Result<()> {
+        let mut interval = tokio::time::interval(self.config.tick_interval);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.tick().await {
+                if let Err(cancel_err) = self.cancel_all().await {
+                    error!("cancelling the orders failed: {:?}", cancel_err);
+                }
+                return Err(err);
+            }
+        }
+    }
+
+    pub async fn tick(&mut self) -> anyhow::Result<()> {
+        let fair_value = match self.fair_value_source.fair_value().await {
+            Ok(Some(fair_value)) if fair_value > I80F48::ZERO => fair_value,
+            result => {
+                if self.is_quoting() {
+                    warn!("no fair value ({:?}), cancelling all orders", result);
+                    self.cancel_all().await?;
+                }
+                return Ok(());
+            }
+        };
+
+        let open_orders = account_fetcher_fetch_openorders_account(
+            &*self.account_fetcher,
+            &self.client.open_orders_account,
+        )
+        .await?;
+        let wallet = self
+            .account_fetcher
+            .fetch_raw_account(&self.config.user_base_account)
+            .await?;
+        let wallet_base_native = spl_token::state::Account::unpack(wallet.data())?.amount;
+        let inventory = inventory_base_native(
+            &self.market.market,
+            &open_orders.position,
+            wallet_base_native,
+        );
+
+        let requote = match self.quoted {
+            None => true,
+            Some((quoted_fair_value, quoted_inventory)) => {
+                let moved_bps = ((fair_value - quoted_fair_value) / quoted_fair_value)
+                    .abs()
+                    .to_num::<f64>()
+                    * 10_000.0;
+                moved_bps >= self.config.requote_threshold_bps || quoted_inventory != inventory
+            }
+        };
+        if requote {
+            self.requote(fair_value, inventory).await?;
+        }
+
+        let position = &open_orders.position;
+        let has_free_funds = position.base_free_native > 0 || position.quote_free_native > 0;
+        let settle_due = self
+            .last_settle
+            .map_or(true, |last| last.elapsed() >= self.config.settle_interval);
+        if has_free_funds && settle_due {
+            self.settle().await?;
+        }
+        Ok(())
+    }
+
+    async fn requote(&mut self, fair_value: I80F48, inventory: i64) -> anyhow::Result<()> {
+        let expiry_timestamp = if self.config.order_expiry_secs == 0 {
+            0
+        } else {
+            SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + self.config.order_expiry_secs
+        };
+        let (bids, asks) = ladder_orders(
+            &self.market.market,
+            &self.config,
+            fair_value,
+            inventory,
+            expiry_timestamp,
+        );
+        let signature = self
+            .client
+            .cancel_all_and_place_orders(
+                self.market.market,
+                self.market.address,
+                self.config.user_base_account,
+                self.config.user_quote_account,
+                PlaceOrderType::PostOnly,
+                bids,
+                asks,
+                u8::MAX,
+            )
+            .await?;
+        debug!(
+            "requoted around {} with inventory {}: {}",
+            fair_value, inventory, signature
+        );
+        self.quoted = Some((fair_value, inventory));
+        Ok(())
+    }
+
+    pub async fn cancel_all(&mut self) -> anyhow::Result<()> {
+        self.client
+            .cancel_all_orders(self.market.market, self.market.address, None, u8::MAX)
+            .await?;
+        self.quoted = None;
+        Ok(())
+    }
+
+    async fn settle(&mut self) -> anyhow::Result<()> {
+        self.client
+            .settle_funds(
+                self.market.market,
+                self.market.address,
+                self.config.user_base_account,
+                self.config.user_quote_account,
+                None,
+            )
+            .await?;
+        self.last_settle = Some(Instant::now());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::__private::bytemuck::Zeroable;
+
+    fn config(levels: Vec<LadderLevel>) -> MarketMakerConfig {
+        MarketMakerConfig {
+            levels,
+            target_base_native: 0,
+            skew_bps_per_base_lot: 10.0,
+            max_skew_bps: 50.0,
+            requote_threshold_bps: 5.0,
+            order_expiry_secs: 0,
+            tick_interval: Duration::from_secs(1),
+            settle_interval: Duration::from_secs(60),
+            user_base_account: Pubkey::new_unique(),
+            user_quote_account: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_ladder_orders() {
+        let mut market = Market::zeroed();
+        market.base_lot_size = 100;
+        market.quote_lot_size = 10;
+        market.maker_fee = 1_000;
+        let config = config(vec![
+            LadderLevel {
+                spread_bps: 10.0,
+                base_lots: 5,
+            },
+            LadderLevel {
+                spread_bps: 50.0,
+                base_lots: 10,
+            },
+        ]);
+
+        // 1_000 quote lots per base lot
+        let fair_value = I80F48::from_num(100);
+        let (bids, asks) = ladder_orders(&market, &config, fair_value, 0, 7);
+        assert_eq!(
+            bids.iter().map(|o| o.price_lots).collect::<Vec<_>>(),
+            vec![999, 995]
+        );
+        assert_eq!(
+            asks.iter().map(|o| o.price_lots).collect::<Vec<_>>(),
+            vec![1_001, 1_005]
+        );
+        assert!(bids
+            .iter()
+            .chain(asks.iter())
+            .all(|o| o.expiry_timestamp == 7));
+
+        // the remainder posted after reserving maker fees is the level size
+        for (order, base_lots) in bids.iter().chain(asks.iter()).zip([5, 10, 5, 10]) {
+            let quote_lots = order.max_quote_lots_including_fees;
+            let posted = (quote_lots - market.maker_fees_ceil(quote_lots)) / order.price_lots;
+            assert_eq!(posted, base_lots);
+        }
+
+        // long 3 lots: quotes move down by 30 bps
+        let (bids, asks) = ladder_orders(&market, &config, fair_value, 300, 0);
+        assert_eq!(bids[0].price_lots, 996);
+        assert_eq!(asks[0].price_lots, 998);
+
+        // the skew is capped
+        let (bids, _) = ladder_orders(&market, &config, fair_value, -10_000, 0);
+        assert_eq!(bids[0].price_lots, 1_003);
+    }
+
+    #[test]
+    fn test_inventory_includes_wallet() {
+        let mut market = Market::zeroed();
+        market.base_lot_size = 100;
+        let mut position = Position {
+            base_free_native: 250,
+            asks_base_lots: 2,
+            ..Position::default()
+        };
+        assert_eq!(inventory_base_native(&market, &position, 1_000), 1_450);
+
+        // settling the free funds doesn't change the inventory
+        position.base_free_native = 0;
+        assert_eq!(inventory_base_native(&market, &position, 1_250), 1_450);
+    }
+
+    #[tokio::test]
+    async fn test_external_fair_value_staleness() {
+        let source = ExternalFairValue::new(Duration::from_millis(50));
+        assert_eq!(source.fair_value().await.unwrap(), None);
+
+        source.update(I80F48::from_num(2));
+        assert_eq!(
+            source.fair_value().await.unwrap(),
+            Some(I80F48::from_num(2))
+        );
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(source.fair_value().await.unwrap(), None);
+    }
+}
@@ -0,0 +1,275 @@
+//! Turn the raw outcome of a transaction back into something a bot can branch on:
+//! custom error codes become `OpenBookError` variants and the `msg!` lines the program
+//! logged become `ProgramNotice`s.
+
+use openbook_v2::error::OpenBookError;
+use solana_sdk::{
+    instruction::InstructionError, message::VersionedMessage, pubkey::Pubkey,
+    transaction::TransactionError,
+};
+
+use std::str::FromStr;
+
+/// The variant for a custom program error code, None for codes outside of OpenBookError
+pub fn openbook_error_from_code(code: u32) -> Option<OpenBookError> {
+    code.checked_sub(anchor_lang::error::ERROR_CODE_OFFSET)
+        .and_then(|index| OpenBookError::try_from(index).ok())
+}
+
+/// Things the program reports in its logs without failing the transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProgramNotice {
+    /// A post only order would have crossed the book and was dropped
+    PostOnlyNotPlaced,
+    /// Matching stopped at the limit, the rest of the order was not filled
+    MatchingLimitReached,
+    /// Cancelling stopped at the limit, some orders are still on the book
+    CancelLimitReached,
+    /// The order was already expired and was not placed
+    OrderAlreadyExpired,
+    /// The pegged order was not posted because its price was beyond its peg_limit
+    PegLimitNotPosted,
+    /// The remainder of the order was below the minimum order size and was not posted
+    BelowMinimumOrderSize,
+    /// The remainder of the order was not posted because the market's quoting is disabled
+    QuotingDisabled,
+    /// The order skipped crossing orders of makers with market maker protection whose
+    /// accounts were not passed, so its remainder was not posted
+    MmpMakersSkipped,
+    /// A cancel targeted an order that was no longer on the book
+    OrderNotFound {
+        order_id: u128,
+    },
+    OracleStale,
+    OracleConfidence,
+    /// consume_events skipped an event because the open orders account was not passed
+    ConsumeEventsAccountMissing {
+        account: Pubkey,
+    },
+}
+
+impl ProgramNotice {
+    pub fn from_log_message(message: &str) -> Option<Self> {
+        Some(
+            if message.starts_with("Order could not be placed due to PostOnly") {
+                Self::PostOnlyNotPlaced
+            } else if message.starts_with("Order matching limit reached") {
+                Self::MatchingLimitReached
+            } else if message.starts_with("Cancel orders limit reached") {
+                Self::CancelLimitReached
+            } else if message.starts_with("Order is already expired") {
+                Self::OrderAlreadyExpired
+            } else if message.starts_with("Posting on book disallowed due to peg_limit") {
+                Self::PegLimitNotPosted
+            } else if message
+                .starts_with("Posting on book disallowed due to the minimum order size")
+            {
+                Self::BelowMinimumOrderSize
+            } else if message.starts_with("Posting on book disallowed while quoting is disabled") {
+                Self::QuotingDisabled
+            } else if message.starts_with(
+                "Posting on book disallowed due to skipped orders of makers with market maker protection",
+            ) {
+                Self::MmpMakersSkipped
+            } else if message.starts_with("Oracle is stale") {
+                Self::OracleStale
+            } else if message.starts_with("Oracle confidence not good enough")
+                || message.starts_with("Combined confidence too high")
+            {
+                Self::OracleConfidence
+            } else if let Some(rest) = message.strip_prefix("order ") {
+                let (order_id, _) = rest.split_once(" was not found on orderbook")?;
+                Self::OrderNotFound {
+                    order_id: order_id.parse().ok()?,
+                }
+            } else if let Some(rest) = message.strip_prefix("Unable to find ") {
+                let account = rest.strip_suffix(", skipping")?.rsplit(' ').next()?;
+                Self::ConsumeEventsAccountMissing {
+                    account: Pubkey::from_str(account).ok()?,
+                }
+            } else {
+                return None;
+            },
+        )
+    }
+}
+
+/// The `msg!` lines logged by the openbook program itself, skipping those of other
+/// programs in the same transaction
+pub fn openbook_log_messages(logs: &[String]) -> Vec<&str> {
+    let openbook = openbook_v2::id().to_string();
+    let mut invoked = Vec::new();
+    let mut messages = Vec::new();
+    for line in logs {
+        if let Some(message) = line.strip_prefix("Program log: ") {
+            if invoked.last() == Some(&openbook.as_str()) {
+                messages.push(message);
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            if let Some((program, _)) = rest.split_once(" invoke [") {
+                invoked.push(program);
+            } else if rest.ends_with(" success") || rest.contains(" failed: ") {
+                invoked.pop();
+            }
+        }
+    }
+    messages
+}
+
+pub fn program_notices(logs: &[String]) -> Vec<ProgramNotice> {
+    openbook_log_messages(logs)
+        .into_iter()
+        .filter_map(ProgramNotice::from_log_message)
+        .collect()
+}
+
+/// The program that failed the transaction, according to the logs
+fn failed_program(logs: &[String]) -> Option<Pubkey> {
+    logs.iter().rev().find_map(|line| {
+        let (program, _) = line.strip_prefix("Program ")?.split_once(" failed: ")?;
+        Pubkey::from_str(program).ok()
+    })
+}
+
+/// The instruction index and custom error code of a failed instruction
+fn custom_error(err: &TransactionError) -> Option<(u8, u32)> {
+    match err {
+        TransactionError::InstructionError(instruction_index, InstructionError::Custom(code)) => {
+            Some((*instruction_index, *code))
+        }
+        _ => None,
+    }
+}
+
+/// The instruction index and OpenBookError of a transaction that failed in the openbook
+/// program, according to the logs. Other programs use the same custom codes, so nothing
+/// is decoded without logs.
+pub fn decode_transaction_error(
+    err: &TransactionError,
+    logs: &[String],
+) -> Option<(u8, OpenBookError)> {
+    let (instruction_index, code) = custom_error(err)?;
+    if failed_program(logs) != Some(openbook_v2::id()) {
+        return None;
+    }
+    Some((instruction_index, openbook_error_from_code(code)?))
+}
+
+/// The instruction index and OpenBookError of a failed transaction without logs, when
+/// its failed instruction is one of the openbook program
+pub fn decode_message_error(
+    err: &TransactionError,
+    message: &VersionedMessage,
+) -> Option<(u8, OpenBookError)> {
+    let (instruction_index, code) = custom_error(err)?;
+    let instruction = message.instructions().get(instruction_index as usize)?;
+    let program_id = message
+        .static_account_keys()
+        .get(instruction.program_id_index as usize)?;
+    if *program_id != openbook_v2::id() {
+        return None;
+    }
+    Some((instruction_index, openbook_error_from_code(code)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{instruction::Instruction, message::Message};
+
+    fn logs(code: u32) -> Vec<String> {
+        let openbook = openbook_v2::id();
+        let token = anchor_spl::token::ID;
+        vec![
+            format!("Program {openbook} invoke [1]"),
+            "Program log: Instruction: PlaceOrder".to_string(),
+            format!("Program {token} invoke [2]"),
+            "Program log: Instruction: Transfer".to_string(),
+            format!("Program {token} success"),
+            "Program log: Order matching limit reached".to_string(),
+            "Program log: Posting on book disallowed while quoting is disabled".to_string(),
+            "Program log: Posting on book disallowed due to skipped orders of makers with market maker protection, their accounts are missing".to_string(),
+            "Program log: order 1234 was not found on orderbook, expired or filled already"
+                .to_string(),
+            format!("Program {openbook} consumed 20000 of 200000 compute units"),
+            format!("Program {openbook} failed: custom program error: {code:#x}"),
+        ]
+    }
+
+    #[test]
+    fn test_decode_transaction_error() {
+        let code: u32 = OpenBookError::WouldSelfTrade.into();
+        let err = TransactionError::InstructionError(1, InstructionError::Custom(code));
+        assert!(matches!(
+            decode_transaction_error(&err, &logs(code)),
+            Some((1, OpenBookError::WouldSelfTrade))
+        ));
+        assert!(decode_transaction_error(&err, &[]).is_none());
+
+        let code: u32 = OpenBookError::OpenOrdersFull.into();
+        assert!(matches!(
+            openbook_error_from_code(code),
+            Some(OpenBookError::OpenOrdersFull)
+        ));
+        assert!(openbook_error_from_code(1).is_none());
+        assert!(openbook_error_from_code(anchor_lang::error::ERROR_CODE_OFFSET + 10_000).is_none());
+
+        // the same code failing another program is not an OpenBookError
+        let mut other_logs = logs(code);
+        other_logs.push(format!(
+            "Program {} failed: custom program error: {code:#x}",
+            Pubkey::new_unique()
+        ));
+        let err = TransactionError::InstructionError(0, InstructionError::Custom(code));
+        assert!(decode_transaction_error(&err, &other_logs).is_none());
+    }
+
+    #[test]
+    fn test_decode_message_error() {
+        let payer = Pubkey::new_unique();
+        let message = VersionedMessage::Legacy(Message::new(
+            &[
+                Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]),
+                Instruction::new_with_bytes(openbook_v2::id(), &[], vec![]),
+            ],
+            Some(&payer),
+        ));
+
+        let code: u32 = OpenBookError::WouldSelfTrade.into();
+        let err = TransactionError::InstructionError(1, InstructionError::Custom(code));
+        assert!(matches!(
+            decode_message_error(&err, &message),
+            Some((1, OpenBookError::WouldSelfTrade))
+        ));
+
+        // the same code failing the instruction of another program is not an OpenBookError
+        let err = TransactionError::InstructionError(0, InstructionError::Custom(code));
+        assert!(decode_message_error(&err, &message).is_none());
+        let err = TransactionError::InstructionError(2, InstructionError::Custom(code));
+        assert!(decode_message_error(&err, &message).is_none());
+    }
+
+    #[test]
+    fn test_program_notices() {
+        assert_eq!(
+            program_notices(&logs(6000)),
+            vec![
+                ProgramNotice::MatchingLimitReached,
+                ProgramNotice::QuotingDisabled,
+                ProgramNotice::MmpMakersSkipped,
+                ProgramNotice::OrderNotFound { order_id: 1234 },
+            ]
+        );
+        // the token program's log lines are not attributed to openbook
+        assert_eq!(
+            openbook_log_messages(&logs(6000)),
+            vec![
+                "Instruction: PlaceOrder",
+                "Order matching limit reached",
+                "Posting on book disallowed while quoting is disabled",
+                "Posting on book disallowed due to skipped orders of makers with market maker protection, their accounts are missing",
+                "order 1234 was not found on orderbook, expired or filled already",
+            ]
+        );
+    }
+}
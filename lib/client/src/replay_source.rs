@@ -0,0 +1,471 @@
+//! Record the `Message` stream that feeds `ChainData` to a file and play it back later,
+//! so that an incident seen in production can be reproduced offline.
+//!
+//! A recording starts with `FILE_MAGIC` and is followed by bincode encoded `Record`s,
+//! each remembering how long after the start of the recording it was received.
+
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_response::SlotUpdate;
+use solana_sdk::{
+    account::{Account, ReadableAccount},
+    pubkey::Pubkey,
+};
+
+use anyhow::Context;
+use log::*;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::account_update_stream::{AccountUpdate, Message};
+
+pub const FILE_MAGIC: &[u8; 8] = b"OBv2REC1";
+
+/// Buffered records are written to the file at least this often while recording, so a
+/// crash of the recorder loses at most this much of the recording
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize)]
+struct RecordedAccount {
+    pubkey: Pubkey,
+    slot: u64,
+    lamports: u64,
+    owner: Pubkey,
+    executable: bool,
+    rent_epoch: u64,
+    data: Vec<u8>,
+}
+
+impl From<&AccountUpdate> for RecordedAccount {
+    fn from(update: &AccountUpdate) -> Self {
+        Self {
+            pubkey: update.pubkey,
+            slot: update.slot,
+            lamports: update.account.lamports(),
+            owner: *update.account.owner(),
+            executable: update.account.executable(),
+            rent_epoch: update.account.rent_epoch(),
+            data: update.account.data().to_vec(),
+        }
+    }
+}
+
+impl From<RecordedAccount> for AccountUpdate {
+    fn from(recorded: RecordedAccount) -> Self {
+        let account = Account {
+            lamports: recorded.lamports,
+            data: recorded.data,
+            owner: recorded.owner,
+            executable: recorded.executable,
+            rent_epoch: recorded.rent_epoch,
+        };
+        Self {
+            pubkey: recorded.pubkey,
+            slot: recorded.slot,
+            account: account.into(),
+        }
+    }
+}
+
+/// Only the slot updates that `ChainData` makes use of are recorded
+#[derive(Serialize, Deserialize)]
+enum RecordedSlot {
+    CreatedBank {
+        slot: u64,
+        parent: u64,
+        timestamp: u64,
+    },
+    OptimisticConfirmation {
+        slot: u64,
+        timestamp: u64,
+    },
+    Root {
+        slot: u64,
+        timestamp: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+enum RecordedMessage {
+    Account(RecordedAccount),
+    Snapshot(Vec<RecordedAccount>),
+    Slot(RecordedSlot),
+}
+
+impl RecordedMessage {
+    fn from_message(message: &Message) -> Option<Self> {
+        Some(match message {
+            Message::Account(update) => RecordedMessage::Account(update.into()),
+            Message::Snapshot(updates) => {
+                RecordedMessage::Snapshot(updates.iter().map(Into::into).collect())
+            }
+            Message::Slot(slot_update) => RecordedMessage::Slot(match **slot_update {
+                SlotUpdate::CreatedBank {
+                    slot,
+                    parent,
+                    timestamp,
+                } => RecordedSlot::CreatedBank {
+                    slot,
+                    parent,
+                    timestamp,
+                },
+                SlotUpdate::OptimisticConfirmation { slot, timestamp } => {
+                    RecordedSlot::OptimisticConfirmation { slot, timestamp }
+                }
+                SlotUpdate::Root { slot, timestamp } => RecordedSlot::Root { slot, timestamp },
+                _ => return None,
+            }),
+        })
+    }
+
+    fn into_message(self) -> Message {
+        match self {
+            RecordedMessage::Account(recorded) => Message::Account(recorded.into()),
+            RecordedMessage::Snapshot(recorded) => {
+                Message::Snapshot(recorded.into_iter().map(Into::into).collect())
+            }
+            RecordedMessage::Slot(recorded) => Message::Slot(Arc::new(match recorded {
+                RecordedSlot::CreatedBank {
+                    slot,
+                    parent,
+                    timestamp,
+                } => SlotUpdate::CreatedBank {
+                    slot,
+                    parent,
+                    timestamp,
+                },
+                RecordedSlot::OptimisticConfirmation { slot, timestamp } => {
+                    SlotUpdate::OptimisticConfirmation { slot, timestamp }
+                }
+                RecordedSlot::Root { slot, timestamp } => SlotUpdate::Root { slot, timestamp },
+            })),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Record {
+    elapsed_micros: u64,
+    message: RecordedMessage,
+}
+
+pub struct Recorder {
+    writer: BufWriter<File>,
+    started: Instant,
+}
+
+impl Recorder {
+    pub fn create(path: &Path) -> anyhow::Result<Self> {
+        let file =
+            File::create(path).with_context(|| format!("creating recording {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(FILE_MAGIC)?;
+        Ok(Self {
+            writer,
+            started: Instant::now(),
+        })
+    }
+
+    pub fn record(&mut self, message: &Message) -> anyhow::Result<()> {
+        let Some(message) = RecordedMessage::from_message(message) else {
+            return Ok(());
+        };
+        let record = Record {
+            elapsed_micros: self.started.elapsed().as_micros() as u64,
+            message,
+        };
+        bincode::serialize_into(&mut self.writer, &record)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> anyhow::Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Reads the messages of a recording together with the time they were received at,
+/// relative to the start of the recording
+pub struct RecordingReader {
+    reader: BufReader<File>,
+}
+
+impl RecordingReader {
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("opening recording {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        anyhow::ensure!(
+            &magic == FILE_MAGIC,
+            "{} is not a recording",
+            path.display()
+        );
+        Ok(Self { reader })
+    }
+}
+
+impl Iterator for RecordingReader {
+    type Item = anyhow::Result<(Duration, Message)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match bincode::deserialize_from::<_, Record>(&mut self.reader) {
+            Ok(record) => Some(Ok((
+                Duration::from_micros(record.elapsed_micros),
+                record.message.into_message(),
+            ))),
+            // a record cut short by a crash of the recorder ends the recording as well
+            Err(err)
+                if matches!(*err, bincode::ErrorKind::Io(ref io_err)
+                    if io_err.kind() == std::io::ErrorKind::UnexpectedEof) =>
+            {
+                None
+            }
+            Err(err) => Some(Err(err.into())),
+        }
+    }
+}
+
+/// Forwards every message from `receiver` to `sender` and records it on the way, so a
+/// recorder can be put between any source and its consumer. Stops when either side
+/// is closed.
+///
+/// Records are flushed every `FLUSH_INTERVAL`. Failing to record is logged and ends the
+/// recording, but never the forwarding of the messages.
+pub fn start_recording(
+    path: PathBuf,
+    receiver: async_channel::Receiver<Message>,
+    sender: async_channel::Sender<Message>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut recorder = Recorder::create(&path)
+            .map_err(|err| error!("recording to {} failed: {:?}", path.display(), err))
+            .ok();
+        let mut flush_interval = tokio::time::interval(FLUSH_INTERVAL);
+        loop {
+            tokio::select! {
+                message = receiver.recv() => {
+                    let Ok(message) = message else {
+                        break;
+                    };
+                    if let Some(Err(err)) = recorder.as_mut().map(|r| r.record(&message)) {
+                        error!("recording to {} failed: {:?}", path.display(), err);
+                        recorder = None;
+                    }
+                    if sender.send(message).await.is_err() {
+                        break;
+                    }
+                },
+                _ = flush_interval.tick() => {
+                    if let Some(Err(err)) = recorder.as_mut().map(|r| r.flush()) {
+                        error!("recording to {} failed: {:?}", path.display(), err);
+                        recorder = None;
+                    }
+                }
+            }
+        }
+        if let Some(Err(err)) = recorder.as_mut().map(|r| r.flush()) {
+            error!("recording to {} failed: {:?}", path.display(), err);
+        }
+    })
+}
+
+pub enum Playback {
+    /// Messages are sent with the same delays between them as when they were recorded
+    RealTime,
+    /// Delays are divided by the factor, `f64::INFINITY` sends everything immediately
+    Accelerated(f64),
+    /// Every value received on the channel releases the next message
+    Step(async_channel::Receiver<()>),
+}
+
+pub struct Config {
+    pub path: PathBuf,
+    pub playback: Playback,
+}
+
+async fn replay(config: Config, sender: async_channel::Sender<Message>) -> anyhow::Result<()> {
+    let speed = match config.playback {
+        Playback::RealTime => 1.0,
+        Playback::Accelerated(speed) => speed,
+        Playback::Step(_) => f64::INFINITY,
+    };
+    anyhow::ensure!(speed > 0.0, "playback speed must be positive");
+
+    let started = tokio::time::Instant::now();
+    for record in RecordingReader::open(&config.path)? {
+        let (elapsed, message) = record?;
+        if let Playback::Step(steps) = &config.playback {
+            if steps.recv().await.is_err() {
+                break;
+            }
+        } else if speed.is_finite() {
+            tokio::time::sleep_until(started + elapsed.div_f64(speed)).await;
+        }
+        if sender.send(message).await.is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Plays a recording back into `sender`, in place of the sources it was recorded from
+pub fn start(
+    config: Config,
+    sender: async_channel::Sender<Message>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let path = config.path.clone();
+        match replay(config, sender).await {
+            Ok(()) => info!("replay of {} finished", path.display()),
+            Err(err) => warn!("replay error: {:?}", err),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_data::ChainData;
+
+    fn messages(market: Pubkey) -> Vec<Message> {
+        let update = |slot, data: &[u8]| AccountUpdate {
+            pubkey: market,
+            slot,
+            account: Account {
+                lamports: 100,
+                data: data.to_vec(),
+                owner: openbook_v2::id(),
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        };
+        vec![
+            Message::Snapshot(vec![update(1, &[1])]),
+            Message::Slot(Arc::new(SlotUpdate::CreatedBank {
+                slot: 2,
+                parent: 1,
+                timestamp: 0,
+            })),
+            Message::Account(update(2, &[2, 2])),
+            Message::Slot(Arc::new(SlotUpdate::Dead {
+                slot: 3,
+                timestamp: 0,
+                err: "dead".to_string(),
+            })),
+            Message::Slot(Arc::new(SlotUpdate::Root {
+                slot: 2,
+                timestamp: 0,
+            })),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let path = std::env::temp_dir().join(format!("replay-{}.bin", Pubkey::new_unique()));
+        let market = Pubkey::new_unique();
+
+        let (source_sender, source_receiver) = async_channel::unbounded();
+        let (sender, receiver) = async_channel::unbounded();
+        let recording = start_recording(path.clone(), source_receiver, sender);
+        for message in messages(market) {
+            source_sender.send(message).await.unwrap();
+        }
+        drop(source_sender);
+        recording.await.unwrap();
+        assert_eq!(receiver.len(), 5);
+
+        // the dead slot update is of no use to ChainData and is not recorded
+        assert_eq!(RecordingReader::open(&path).unwrap().count(), 4);
+
+        let (steps_sender, steps_receiver) = async_channel::unbounded();
+        let (sender, receiver) = async_channel::unbounded();
+        let replay = start(
+            Config {
+                path: path.clone(),
+                playback: Playback::Step(steps_receiver),
+            },
+            sender,
+        );
+
+        let mut chain = ChainData::new();
+        steps_sender.send(()).await.unwrap();
+        receiver.recv().await.unwrap().update_chain_data(&mut chain);
+        assert!(receiver.is_empty());
+        assert_eq!(chain.account_writes_count(), 1);
+
+        for _ in 0..3 {
+            steps_sender.send(()).await.unwrap();
+            receiver.recv().await.unwrap().update_chain_data(&mut chain);
+        }
+        replay.await.unwrap();
+        assert!(receiver.recv().await.is_err());
+
+        let account = chain.account_and_slot(&market).unwrap();
+        assert_eq!(account.slot, 2);
+        assert_eq!(account.account.data(), &[2, 2]);
+        assert_eq!(account.account.lamports(), 100);
+        assert_eq!(account.account.owner(), &openbook_v2::id());
+        // rooting slot 2 dropped the older write
+        assert_eq!(chain.account_writes_count(), 1);
+        assert_eq!(chain.slots_count(), 1);
+
+        // replaying as fast as possible gives the same messages
+        let (sender, receiver) = async_channel::unbounded();
+        start(
+            Config {
+                path: path.clone(),
+                playback: Playback::Accelerated(f64::INFINITY),
+            },
+            sender,
+        )
+        .await
+        .unwrap();
+        assert_eq!(receiver.len(), 4);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_recording_is_flushed_while_running() {
+        let path = std::env::temp_dir().join(format!("replay-{}.bin", Pubkey::new_unique()));
+
+        let (source_sender, source_receiver) = async_channel::unbounded();
+        let (sender, _receiver) = async_channel::unbounded();
+        let recording = start_recording(path.clone(), source_receiver, sender);
+        for message in messages(Pubkey::new_unique()) {
+            source_sender.send(message).await.unwrap();
+        }
+        tokio::time::sleep(FLUSH_INTERVAL * 2).await;
+        assert_eq!(RecordingReader::open(&path).unwrap().count(), 4);
+
+        drop(source_sender);
+        recording.await.unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_recording_errors_keep_forwarding() {
+        let path = std::env::temp_dir()
+            .join(Pubkey::new_unique().to_string())
+            .join("replay.bin");
+
+        let (source_sender, source_receiver) = async_channel::unbounded();
+        let (sender, receiver) = async_channel::unbounded();
+        let recording = start_recording(path.clone(), source_receiver, sender);
+        for message in messages(Pubkey::new_unique()) {
+            source_sender.send(message).await.unwrap();
+        }
+        drop(source_sender);
+        recording.await.unwrap();
+
+        assert_eq!(receiver.len(), 5);
+        assert!(!path.exists());
+    }
+}
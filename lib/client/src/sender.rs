@@ -0,0 +1,284 @@
+//! Sends transactions until they confirm or their blockhash expires, rebroadcasting them
+//! in between, since rpc nodes drop transactions under congestion.
+
+use openbook_v2::error::OpenBookError;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient as RpcClientAsync, rpc_config::RpcSendTransactionConfig,
+};
+use solana_sdk::{
+    clock::Slot,
+    commitment_config::CommitmentConfig,
+    signature::Signature,
+    transaction::{TransactionError, VersionedTransaction},
+};
+
+use log::*;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::watch;
+
+use crate::program_error::decode_message_error;
+use crate::{prettify_solana_client_error, Client, OpenBookClientError};
+
+#[derive(Clone, Debug)]
+pub struct SenderConfig {
+    /// The commitment a transaction needs to count as confirmed or failed
+    pub commitment: CommitmentConfig,
+    /// Used for the first broadcast, rebroadcasts skip the preflight
+    pub send_config: RpcSendTransactionConfig,
+    pub rebroadcast_interval: Duration,
+    pub status_interval: Duration,
+}
+
+impl SenderConfig {
+    pub fn from_client(client: &Client) -> Self {
+        Self {
+            commitment: client.commitment,
+            send_config: client.rpc_send_transaction_config,
+            rebroadcast_interval: Duration::from_secs(2),
+            status_interval: Duration::from_millis(500),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum TransactionStatus {
+    Confirmed {
+        slot: Slot,
+    },
+    Failed {
+        slot: Slot,
+        err: TransactionError,
+        /// The instruction index and error, when the openbook program failed it
+        openbook_error: Option<(u8, OpenBookError)>,
+    },
+    /// The blockhash expired before the transaction was seen with the commitment,
+    /// it can't land anymore
+    Expired,
+}
+
+#[derive(Clone, Debug)]
+pub struct SendReport {
+    pub signature: Signature,
+    pub status: TransactionStatus,
+    pub broadcasts: usize,
+}
+
+impl SendReport {
+    pub fn into_result(self) -> anyhow::Result<Signature> {
+        match self.status {
+            TransactionStatus::Confirmed { .. } => Ok(self.signature),
+            // a failure seen while confirming comes without logs
+            TransactionStatus::Failed {
+                openbook_error: Some((instruction_index, error)),
+                ..
+            } => Err(OpenBookClientError::Program {
+                instruction_index,
+                error,
+                notices: vec![],
+                logs: vec![],
+            }
+            .into()),
+            TransactionStatus::Failed { err, .. } => Err(prettify_solana_client_error(err.into())),
+            TransactionStatus::Expired => anyhow::bail!(
+                "transaction {} expired after {} broadcasts",
+                self.signature,
+                self.broadcasts
+            ),
+        }
+    }
+}
+
+type InFlight = watch::Receiver<Option<SendReport>>;
+
+/// Share one sender between the tasks of a bot: sending a transaction that is already in
+/// flight waits for the outcome of the first send instead of broadcasting it again
+pub struct TransactionSender {
+    rpc: Arc<RpcClientAsync>,
+    config: SenderConfig,
+    in_flight: Mutex<HashMap<Signature, InFlight>>,
+}
+
+impl TransactionSender {
+    pub fn new(rpc: Arc<RpcClientAsync>, config: SenderConfig) -> Self {
+        Self {
+            rpc,
+            config,
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn rpc(&self) -> &RpcClientAsync {
+        &self.rpc
+    }
+
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.lock().unwrap().len()
+    }
+
+    /// Broadcasts the transaction until it is confirmed or failed with the configured
+    /// commitment, or until the block height passes last_valid_block_height. Errors are
+    /// only returned for a failed preflight of the first broadcast and rpc failures.
+    pub async fn send(
+        &self,
+        tx: &VersionedTransaction,
+        last_valid_block_height: u64,
+    ) -> anyhow::Result<SendReport> {
+        let signature = tx.signatures[0];
+        let existing = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(&signature) {
+                Some(receiver) => Err(receiver.clone()),
+                None => {
+                    let (sender, receiver) = watch::channel(None);
+                    in_flight.insert(signature, receiver);
+                    Ok(sender)
+                }
+            }
+        };
+        let report_sender = match existing {
+            Ok(sender) => sender,
+            Err(mut receiver) => {
+                let report = receiver
+                    .wait_for(Option::is_some)
+                    .await
+                    .map_err(|_| anyhow::anyhow!("first send of {} failed", signature))?;
+                return Ok(report.clone().unwrap());
+            }
+        };
+
+        let result = self
+            .send_until_final(tx, signature, last_valid_block_height)
+            .await;
+        self.in_flight.lock().unwrap().remove(&signature);
+        if let Ok(report) = &result {
+            report_sender.send_replace(Some(report.clone()));
+        }
+        result
+    }
+
+    async fn send_until_final(
+        &self,
+        tx: &VersionedTransaction,
+        signature: Signature,
+        last_valid_block_height: u64,
+    ) -> anyhow::Result<SendReport> {
+        self.rpc
+            .send_transaction_with_config(tx, self.config.send_config)
+            .await
+            .map_err(prettify_solana_client_error)?;
+        let mut broadcasts = 1;
+        let mut last_broadcast = Instant::now();
+
+        loop {
+            tokio::time::sleep(self.config.status_interval).await;
+
+            if let Some(status) = self.status(tx).await? {
+                return Ok(SendReport {
+                    signature,
+                    status,
+                    broadcasts,
+                });
+            }
+
+            let block_height = self
+                .rpc
+                .get_block_height_with_commitment(self.config.commitment)
+                .await?;
+            if block_height > last_valid_block_height {
+                // it may have landed just before expiring
+                let status = self.status(tx).await?.unwrap_or(TransactionStatus::Expired);
+                return Ok(SendReport {
+                    signature,
+                    status,
+                    broadcasts,
+                });
+            }
+
+            if last_broadcast.elapsed() >= self.config.rebroadcast_interval {
+                let rebroadcast_config = RpcSendTransactionConfig {
+                    skip_preflight: true,
+                    ..self.config.send_config
+                };
+                match self
+                    .rpc
+                    .send_transaction_with_config(tx, rebroadcast_config)
+                    .await
+                {
+                    Ok(_) => broadcasts += 1,
+                    Err(err) => debug!("rebroadcast of {} failed: {:?}", signature, err),
+                }
+                last_broadcast = Instant::now();
+            }
+        }
+    }
+
+    async fn status(&self, tx: &VersionedTransaction) -> anyhow::Result<Option<TransactionStatus>> {
+        let status = self
+            .rpc
+            .get_signature_statuses(&[tx.signatures[0]])
+            .await?
+            .value
+            .remove(0)
+            .filter(|status| status.satisfies_commitment(self.config.commitment));
+        Ok(status.map(|status| match status.err {
+            None => TransactionStatus::Confirmed { slot: status.slot },
+            Some(err) => TransactionStatus::Failed {
+                slot: status.slot,
+                openbook_error: decode_message_error(&err, &tx.message),
+                err,
+            },
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::InstructionError;
+
+    #[test]
+    fn test_report_into_result() {
+        let report = |status| SendReport {
+            signature: Signature::default(),
+            status,
+            broadcasts: 3,
+        };
+
+        assert!(report(TransactionStatus::Confirmed { slot: 1 })
+            .into_result()
+            .is_ok());
+        assert!(report(TransactionStatus::Expired).into_result().is_err());
+
+        let code: u32 = OpenBookError::WouldSelfTrade.into();
+        let err = TransactionError::InstructionError(2, InstructionError::Custom(code));
+        let error = report(TransactionStatus::Failed {
+            slot: 1,
+            openbook_error: Some((2, OpenBookError::WouldSelfTrade)),
+            err: err.clone(),
+        })
+        .into_result()
+        .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<OpenBookClientError>(),
+            Some(OpenBookClientError::Program {
+                instruction_index: 2,
+                error: OpenBookError::WouldSelfTrade,
+                ..
+            })
+        ));
+
+        // the same code from another program's instruction is left undecoded
+        let error = report(TransactionStatus::Failed {
+            slot: 1,
+            openbook_error: None,
+            err,
+        })
+        .into_result()
+        .unwrap_err();
+        assert!(error.downcast_ref::<OpenBookClientError>().is_none());
+    }
+}
@@ -74,7 +74,7 @@ pub struct Config {
 }
 
 #[allow(clippy::type_complexity)]
-async fn feed_snapshots(
+pub(crate) async fn feed_snapshots(
     config: &Config,
     openbook_oracles: Vec<Pubkey>,
     sender: &async_channel::Sender<Message>,
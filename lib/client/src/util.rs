@@ -24,6 +24,35 @@ impl<T: AccountDeserialize + ZeroCopy> ZeroCopyDeserialize for T {
     }
 }
 
+/// Account data copied into an 8-byte aligned buffer, as needed to read zero-copy accounts
+/// of dynamic size like `DynBookSide` and `DynEventHeap`
+#[derive(Clone, Default)]
+pub struct AlignedAccountData {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl AlignedAccountData {
+    pub fn new(data: &[u8]) -> Self {
+        let mut words = vec![0u64; (data.len() + 7) / 8];
+        anchor_lang::__private::bytemuck::cast_slice_mut::<u64, u8>(&mut words)[..data.len()]
+            .copy_from_slice(data);
+        Self {
+            words,
+            len: data.len(),
+        }
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &anchor_lang::__private::bytemuck::cast_slice::<u64, u8>(&self.words)[..self.len]
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut anchor_lang::__private::bytemuck::cast_slice_mut::<u64, u8>(&mut self.words)
+            [..self.len]
+    }
+}
+
 /// Some Result<> types don't convert to anyhow::Result nicely. Force them through stringification.
 pub trait AnyhowWrap {
     type Value;
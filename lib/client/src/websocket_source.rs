@@ -0,0 +1,306 @@
+use jsonrpc_core::futures::StreamExt;
+use jsonrpc_core_client::transports::ws;
+
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_response::{Response, RpcKeyedAccount, RpcResponseContext, SlotInfo, SlotUpdate},
+};
+use solana_rpc::rpc_pubsub::RpcSolPubSubClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, timing::timestamp};
+
+use log::*;
+use std::{sync::Arc, time::Duration};
+use tokio_stream::StreamMap;
+
+use crate::account_update_stream::{AccountUpdate, Message};
+use crate::snapshot_source;
+use crate::AnyhowWrap;
+
+pub struct Config {
+    pub rpc_ws_url: String,
+    /// Snapshot fetched after every (re)connect, to fill in the updates that were missed
+    /// while disconnected. None to only forward live updates.
+    pub snapshot: Option<snapshot_source::Config>,
+    /// Reconnect when no message arrived for this long
+    pub timeout: Duration,
+    pub reconnect_delay: Duration,
+}
+
+/// `slotSubscribe` only tells about new banks and roots, turn them into the slot updates
+/// that `ChainData` understands
+fn slot_messages(info: SlotInfo, newest_root: &mut u64) -> Vec<Message> {
+    let mut messages = vec![Message::Slot(Arc::new(SlotUpdate::CreatedBank {
+        slot: info.slot,
+        parent: info.parent,
+        timestamp: timestamp(),
+    }))];
+    if info.root > *newest_root {
+        *newest_root = info.root;
+        messages.push(Message::Slot(Arc::new(SlotUpdate::Root {
+            slot: info.root,
+            timestamp: timestamp(),
+        })));
+    }
+    messages
+}
+
+async fn send(sender: &async_channel::Sender<Message>, message: Message) -> anyhow::Result<()> {
+    sender
+        .send(message)
+        .await
+        .map_err(|_| anyhow::anyhow!("message receiver was dropped"))
+}
+
+async fn feed_data(
+    config: &Config,
+    openbook_oracles: &[Pubkey],
+    sender: &async_channel::Sender<Message>,
+) -> anyhow::Result<()> {
+    let client = ws::try_connect::<RpcSolPubSubClient>(&config.rpc_ws_url)
+        .map_err_anyhow()?
+        .await
+        .map_err_anyhow()?;
+
+    let account_info_config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(CommitmentConfig::processed()),
+        data_slice: None,
+        min_context_slot: None,
+    };
+    let all_accounts_config = RpcProgramAccountsConfig {
+        filters: None,
+        with_context: Some(true),
+        account_config: account_info_config.clone(),
+    };
+
+    let mut openbook_sub = client
+        .program_subscribe(openbook_v2::id().to_string(), Some(all_accounts_config))
+        .map_err_anyhow()?;
+    let mut oracles_sub_map = StreamMap::new();
+    for oracle in openbook_oracles {
+        oracles_sub_map.insert(
+            *oracle,
+            client
+                .account_subscribe(oracle.to_string(), Some(account_info_config.clone()))
+                .map_err_anyhow()?,
+        );
+    }
+    let mut slot_sub = client.slot_subscribe().map_err_anyhow()?;
+
+    // Subscribe first so that nothing between the snapshot and the live updates is lost
+    if let Some(snapshot_config) = &config.snapshot {
+        snapshot_source::feed_snapshots(snapshot_config, openbook_oracles.to_vec(), sender).await?;
+    }
+
+    let mut newest_root = 0;
+    loop {
+        tokio::select! {
+            message = openbook_sub.next() => {
+                let Some(data) = message else {
+                    anyhow::bail!("openbook stream closed");
+                };
+                let response = data.map_err_anyhow()?;
+                send(sender, Message::Account(AccountUpdate::from_rpc(response)?)).await?;
+            },
+            message = oracles_sub_map.next(), if !oracles_sub_map.is_empty() => {
+                let Some((pubkey, data)) = message else {
+                    anyhow::bail!("oracle stream closed");
+                };
+                let response = data.map_err_anyhow()?;
+                let response = Response {
+                    context: RpcResponseContext::new(response.context.slot),
+                    value: RpcKeyedAccount {
+                        pubkey: pubkey.to_string(),
+                        account: response.value,
+                    },
+                };
+                send(sender, Message::Account(AccountUpdate::from_rpc(response)?)).await?;
+            },
+            message = slot_sub.next() => {
+                let Some(data) = message else {
+                    anyhow::bail!("slot stream closed");
+                };
+                for message in slot_messages(data.map_err_anyhow()?, &mut newest_root) {
+                    send(sender, message).await?;
+                }
+            },
+            _ = tokio::time::sleep(config.timeout) => {
+                anyhow::bail!("no message for {:?}", config.timeout);
+            }
+        }
+    }
+}
+
+/// Streams the accounts of the openbook program, the oracles and the slots into `sender`,
+/// reconnecting and subscribing again whenever the websocket fails. Stops once the
+/// receiver is dropped.
+pub fn start(
+    config: Config,
+    openbook_oracles: Vec<Pubkey>,
+    sender: async_channel::Sender<Message>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            info!("connecting to solana websocket streams");
+            if let Err(err) = feed_data(&config, &openbook_oracles, &sender).await {
+                warn!("websocket error: {:?}", err);
+            }
+            if sender.is_closed() {
+                break;
+            }
+            tokio::time::sleep(config.reconnect_delay).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::SinkExt;
+    use serde_json::{json, Value};
+    use solana_account_decoder::UiAccount;
+    use solana_sdk::account::{Account, AccountSharedData, ReadableAccount};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    fn account(owner: Pubkey, data: &[u8]) -> Account {
+        Account {
+            lamports: 1,
+            data: data.to_vec(),
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn ui_account(pubkey: &Pubkey, account: &Account) -> UiAccount {
+        UiAccount::encode(
+            pubkey,
+            &AccountSharedData::from(account.clone()),
+            UiAccountEncoding::Base64,
+            None,
+            None,
+        )
+    }
+
+    /// Serves a single connection: confirms the subscriptions, then sends one notification
+    /// for each of them and disconnects
+    async fn serve_connection(listener: &TcpListener, market: Pubkey, oracle: Pubkey) -> usize {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+        let mut subscriptions = vec![];
+        while subscriptions.len() < 3 {
+            let Some(Ok(WsMessage::Text(text))) = ws.next().await else {
+                panic!("expected a subscription request");
+            };
+            let request: Value = serde_json::from_str(&text).unwrap();
+            let method = request["method"].as_str().unwrap().to_string();
+            let id = subscriptions.len() + 1;
+            ws.send(WsMessage::Text(
+                json!({"jsonrpc": "2.0", "result": id, "id": request["id"]}).to_string(),
+            ))
+            .await
+            .unwrap();
+            subscriptions.push((method, id));
+        }
+
+        for (method, id) in subscriptions.iter() {
+            let (notification, result) = match method.as_str() {
+                "programSubscribe" => (
+                    "programNotification",
+                    json!(Response {
+                        context: RpcResponseContext::new(10),
+                        value: RpcKeyedAccount {
+                            pubkey: market.to_string(),
+                            account: ui_account(&market, &account(openbook_v2::id(), &[1, 2])),
+                        },
+                    }),
+                ),
+                "accountSubscribe" => (
+                    "accountNotification",
+                    json!(Response {
+                        context: RpcResponseContext::new(11),
+                        value: ui_account(&oracle, &account(Pubkey::new_unique(), &[3])),
+                    }),
+                ),
+                "slotSubscribe" => (
+                    "slotNotification",
+                    json!(SlotInfo {
+                        slot: 12,
+                        parent: 11,
+                        root: 5,
+                    }),
+                ),
+                _ => panic!("unexpected method {method}"),
+            };
+            ws.send(WsMessage::Text(
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": notification,
+                    "params": {"result": result, "subscription": id},
+                })
+                .to_string(),
+            ))
+            .await
+            .unwrap();
+        }
+
+        ws.close(None).await.ok();
+        subscriptions.len()
+    }
+
+    #[tokio::test]
+    async fn test_mock_websocket_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let market = Pubkey::new_unique();
+        let oracle = Pubkey::new_unique();
+
+        let (sender, receiver) = async_channel::unbounded();
+        let source = start(
+            Config {
+                rpc_ws_url: format!("ws://{address}"),
+                snapshot: None,
+                timeout: Duration::from_secs(10),
+                reconnect_delay: Duration::from_millis(10),
+            },
+            vec![oracle],
+            sender,
+        );
+
+        // subscribes again after the server disconnected
+        for _ in 0..2 {
+            assert_eq!(serve_connection(&listener, market, oracle).await, 3);
+
+            let mut accounts = vec![];
+            let mut slots = vec![];
+            while accounts.len() < 2 || slots.len() < 2 {
+                match receiver.recv().await.unwrap() {
+                    Message::Account(update) => accounts.push(update),
+                    Message::Slot(update) => slots.push(update),
+                    Message::Snapshot(_) => panic!("no snapshot was configured"),
+                }
+            }
+
+            accounts.sort_by_key(|update| update.slot);
+            assert_eq!(accounts[0].pubkey, market);
+            assert_eq!(accounts[0].account.data(), &[1, 2]);
+            assert_eq!(accounts[1].pubkey, oracle);
+            assert_eq!(accounts[1].slot, 11);
+            assert!(matches!(
+                *slots[0],
+                SlotUpdate::CreatedBank {
+                    slot: 12,
+                    parent: 11,
+                    ..
+                }
+            ));
+            assert!(matches!(*slots[1], SlotUpdate::Root { slot: 5, .. }));
+        }
+
+        drop(receiver);
+        source.abort();
+    }
+}
@@ -0,0 +1,1670 @@
+//! Builds the instructions of the openbook-v2 program from a `Market` and addresses.
+//!
+//! Nothing here talks to an rpc node or needs an async runtime, so the builders work in
+//! other programs, in wasm and in off-chain signers. The caller fetches the accounts and
+//! signs the transaction.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+
+use openbook_v2::state::{
+    AccessGate, DelegateScope, FeeSplitDestination, Market, MarketStatus, OracleConfigParams,
+    PlaceOrderType, Side,
+};
+use openbook_v2::{
+    PlaceMultipleOrdersArgs, PlaceOrderArgs, PlaceOrderPeggedArgs, PlaceTakeOrderArgs,
+};
+
+pub fn market_authority_address(market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"Market".as_ref(), market.as_ref()], &openbook_v2::id()).0
+}
+
+pub fn event_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority".as_ref()], &openbook_v2::id()).0
+}
+
+pub fn open_orders_indexer_address(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"OpenOrdersIndexer".as_ref(), owner.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
+/// The program creates the account of an owner with the number created_counter + 1 of
+/// the owner's OpenOrdersIndexer
+pub fn open_orders_account_address(owner: &Pubkey, account_num: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"OpenOrders".as_ref(),
+            owner.as_ref(),
+            &account_num.to_le_bytes(),
+        ],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
+pub fn fee_split_address(market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"FeeSplit".as_ref(), market.as_ref()], &openbook_v2::id()).0
+}
+
+pub fn access_gate_address(market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"AccessGate".as_ref(), market.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
+/// The proof of an owner on a market with an allowlist access gate
+pub fn access_pass_address(market: &Pubkey, owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"AccessPass".as_ref(), market.as_ref(), owner.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
+/// The referral of an open orders account bound to a referrer
+pub fn open_orders_referral_address(open_orders_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"Referral".as_ref(), open_orders_account.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
+/// The delegates account of an open orders account with scoped delegates
+pub fn open_orders_delegates_address(open_orders_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"Delegates".as_ref(), open_orders_account.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
+/// The referrer of authority on market, the default pubkey as market is a global referrer
+pub fn referrer_address(authority: &Pubkey, market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"Referrer".as_ref(), authority.as_ref(), market.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
+/// The vault an order of this side locks its funds in: quote for bids, base for asks
+pub fn market_vault(market: &Market, side: Side) -> Pubkey {
+    match side {
+        Side::Bid => market.market_quote_vault,
+        Side::Ask => market.market_base_vault,
+    }
+}
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: openbook_v2::id(),
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn with_remaining_accounts(
+    mut ix: Instruction,
+    accounts: impl IntoIterator<Item = Pubkey>,
+) -> Instruction {
+    ix.accounts.extend(
+        accounts
+            .into_iter()
+            .map(|pubkey| AccountMeta::new(pubkey, false)),
+    );
+    ix
+}
+
+/// The market account and the book sides and event heap must already be allocated and
+/// owned by the program, the market has to sign
+#[allow(clippy::too_many_arguments)]
+pub fn create_market(
+    payer: Pubkey,
+    market: Pubkey,
+    bids: Pubkey,
+    asks: Pubkey,
+    event_heap: Pubkey,
+    base_mint: Pubkey,
+    quote_mint: Pubkey,
+    oracle_a: Option<Pubkey>,
+    oracle_b: Option<Pubkey>,
+    collect_fee_admin: Pubkey,
+    open_orders_admin: Option<Pubkey>,
+    consume_events_admin: Option<Pubkey>,
+    close_market_admin: Option<Pubkey>,
+    market_status_admin: Option<Pubkey>,
+    name: String,
+    oracle_config: OracleConfigParams,
+    base_lot_size: i64,
+    quote_lot_size: i64,
+    maker_fee: i64,
+    taker_fee: i64,
+    time_expiry: i64,
+    auction_duration_secs: u32,
+    auction_interval_secs: u32,
+    min_base_lots_per_order: i64,
+    min_quote_notional: u64,
+    max_base_lots_per_order: i64,
+) -> Instruction {
+    let market_authority = market_authority_address(&market);
+    instruction(
+        openbook_v2::accounts::CreateMarket {
+            market,
+            market_authority,
+            bids,
+            asks,
+            event_heap,
+            payer,
+            market_base_vault: get_associated_token_address(&market_authority, &base_mint),
+            market_quote_vault: get_associated_token_address(&market_authority, &quote_mint),
+            base_mint,
+            quote_mint,
+            system_program: anchor_lang::system_program::ID,
+            oracle_a,
+            oracle_b,
+            collect_fee_admin,
+            open_orders_admin,
+            consume_events_admin,
+            close_market_admin,
+            market_status_admin,
+            event_authority: event_authority_address(),
+            program: openbook_v2::id(),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        openbook_v2::instruction::CreateMarket {
+            name,
+            oracle_config,
+            base_lot_size,
+            quote_lot_size,
+            maker_fee,
+            taker_fee,
+            time_expiry,
+            auction_duration_secs,
+            auction_interval_secs,
+            min_base_lots_per_order,
+            min_quote_notional,
+            max_base_lots_per_order,
+        },
+    )
+}
+
+pub fn close_market(
+    market: &Market,
+    market_address: Pubkey,
+    close_market_admin: Pubkey,
+    sol_destination: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::CloseMarket {
+            close_market_admin,
+            market: market_address,
+            bids: market.bids,
+            asks: market.asks,
+            event_heap: market.event_heap,
+            sol_destination,
+            token_program: anchor_spl::token::ID,
+        },
+        openbook_v2::instruction::CloseMarket {},
+    )
+}
+
+/// Shrinks the book sides and event heap of an expired market once they're empty,
+/// signed by its close_market_admin
+pub fn shrink_market_accounts(
+    market: &Market,
+    market_address: Pubkey,
+    close_market_admin: Pubkey,
+    sol_destination: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::ShrinkMarketAccounts {
+            close_market_admin,
+            market: market_address,
+            bids: market.bids,
+            asks: market.asks,
+            event_heap: market.event_heap,
+            sol_destination,
+        },
+        openbook_v2::instruction::ShrinkMarketAccounts {},
+    )
+}
+
+pub fn set_market_expired(market_address: Pubkey, close_market_admin: Pubkey) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SetMarketExpired {
+            close_market_admin,
+            market: market_address,
+        },
+        openbook_v2::instruction::SetMarketExpired {},
+    )
+}
+
+pub fn set_market_status(
+    market_address: Pubkey,
+    market_status_admin: Pubkey,
+    status: MarketStatus,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SetMarketStatus {
+            market_status_admin,
+            market: market_address,
+        },
+        openbook_v2::instruction::SetMarketStatus { status },
+    )
+}
+
+/// Signed by the market's collect_fee_admin, None clears the market_status_admin
+pub fn set_market_status_admin(
+    market: &Market,
+    market_address: Pubkey,
+    market_status_admin: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SetMarketStatusAdmin {
+            collect_fee_admin: market.collect_fee_admin,
+            market: market_address,
+            market_status_admin,
+        },
+        openbook_v2::instruction::SetMarketStatusAdmin {},
+    )
+}
+
+/// Signed by the market's collect_fee_admin
+pub fn set_crank_rewards(
+    market: &Market,
+    market_address: Pubkey,
+    penalty_event_heap: Option<u32>,
+    crank_reward_per_event: u32,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SetCrankRewards {
+            collect_fee_admin: market.collect_fee_admin,
+            market: market_address,
+        },
+        openbook_v2::instruction::SetCrankRewards {
+            penalty_event_heap,
+            crank_reward_per_event,
+        },
+    )
+}
+
+pub fn create_open_orders_indexer(owner: Pubkey, payer: Pubkey) -> Instruction {
+    instruction(
+        openbook_v2::accounts::CreateOpenOrdersIndexer {
+            owner,
+            open_orders_indexer: open_orders_indexer_address(&owner),
+            payer,
+            system_program: anchor_lang::system_program::ID,
+        },
+        openbook_v2::instruction::CreateOpenOrdersIndexer {},
+    )
+}
+
+pub fn close_open_orders_indexer(owner: Pubkey, sol_destination: Pubkey) -> Instruction {
+    instruction(
+        openbook_v2::accounts::CloseOpenOrdersIndexer {
+            owner,
+            open_orders_indexer: open_orders_indexer_address(&owner),
+            sol_destination,
+            token_program: anchor_spl::token::ID,
+        },
+        openbook_v2::instruction::CloseOpenOrdersIndexer {},
+    )
+}
+
+/// account_num has to be the created_counter of the owner's indexer plus one, the
+/// address of the new account is open_orders_account_address(owner, account_num).
+/// Markets with an access gate also need the gate and the owner's access proof.
+#[allow(clippy::too_many_arguments)]
+pub fn create_open_orders_account(
+    market_address: Pubkey,
+    owner: Pubkey,
+    payer: Pubkey,
+    delegate: Option<Pubkey>,
+    account_num: u32,
+    name: String,
+    access_gate: Option<Pubkey>,
+    access_proof: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::CreateOpenOrdersAccount {
+            owner,
+            open_orders_indexer: open_orders_indexer_address(&owner),
+            open_orders_account: open_orders_account_address(&owner, account_num),
+            payer,
+            delegate_account: delegate,
+            market: market_address,
+            system_program: anchor_lang::system_program::ID,
+            access_gate,
+            access_proof,
+        },
+        openbook_v2::instruction::CreateOpenOrdersAccount { name },
+    )
+}
+
+pub fn close_open_orders_account(
+    owner: Pubkey,
+    open_orders_account: Pubkey,
+    sol_destination: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::CloseOpenOrdersAccount {
+            owner,
+            open_orders_indexer: open_orders_indexer_address(&owner),
+            open_orders_account,
+            sol_destination,
+            system_program: anchor_lang::system_program::ID,
+        },
+        openbook_v2::instruction::CloseOpenOrdersAccount {},
+    )
+}
+
+/// Brings an account of an older version up to date, anyone can sign the transaction
+pub fn migrate_open_orders_account(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::MigrateOpenOrdersAccount {
+            open_orders_account,
+            market: market_address,
+            bids: market.bids,
+        },
+        openbook_v2::instruction::MigrateOpenOrdersAccount {},
+    )
+}
+
+/// Passing None as delegate removes the delegate of the account. Scoped delegates need
+/// the account's `open_orders_delegates`.
+#[allow(clippy::too_many_arguments)]
+pub fn set_delegate(
+    owner: Pubkey,
+    open_orders_account: Pubkey,
+    delegate: Option<Pubkey>,
+    open_orders_delegates: Option<Pubkey>,
+    scope: DelegateScope,
+    allowed_side: Option<Side>,
+    max_quote_native_per_order: u64,
+    expiry_timestamp: u64,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SetDelegate {
+            owner,
+            open_orders_account,
+            delegate_account: delegate,
+            open_orders_delegates,
+        },
+        openbook_v2::instruction::SetDelegate {
+            scope,
+            allowed_side,
+            max_quote_native_per_order,
+            expiry_timestamp,
+        },
+    )
+}
+
+/// Creates the account holding the scoped delegates of open_orders_account, at
+/// open_orders_delegates_address(open_orders_account)
+pub fn create_open_orders_delegates(
+    owner: Pubkey,
+    payer: Pubkey,
+    open_orders_account: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::CreateOpenOrdersDelegates {
+            payer,
+            owner,
+            open_orders_account,
+            open_orders_delegates: open_orders_delegates_address(&open_orders_account),
+            system_program: anchor_lang::system_program::ID,
+        },
+        openbook_v2::instruction::CreateOpenOrdersDelegates {},
+    )
+}
+
+pub fn close_open_orders_delegates(
+    owner: Pubkey,
+    open_orders_account: Pubkey,
+    sol_destination: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::CloseOpenOrdersDelegates {
+            owner,
+            open_orders_account,
+            open_orders_delegates: open_orders_delegates_address(&open_orders_account),
+            sol_destination,
+        },
+        openbook_v2::instruction::CloseOpenOrdersDelegates {},
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn add_delegate(
+    owner: Pubkey,
+    open_orders_account: Pubkey,
+    delegate: Pubkey,
+    scope: DelegateScope,
+    allowed_side: Option<Side>,
+    max_quote_native_per_order: u64,
+    expiry_timestamp: u64,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::AddDelegate {
+            owner,
+            open_orders_account,
+            open_orders_delegates: open_orders_delegates_address(&open_orders_account),
+            delegate_account: delegate,
+        },
+        openbook_v2::instruction::AddDelegate {
+            scope,
+            allowed_side,
+            max_quote_native_per_order,
+            expiry_timestamp,
+        },
+    )
+}
+
+pub fn remove_delegate(
+    owner: Pubkey,
+    open_orders_account: Pubkey,
+    delegate: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::RemoveDelegate {
+            owner,
+            open_orders_account,
+            open_orders_delegates: open_orders_delegates_address(&open_orders_account),
+            delegate_account: delegate,
+        },
+        openbook_v2::instruction::RemoveDelegate {},
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn deposit(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    owner: Pubkey,
+    user_base_account: Pubkey,
+    user_quote_account: Pubkey,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::Deposit {
+            owner,
+            user_base_account,
+            user_quote_account,
+            open_orders_account,
+            market: market_address,
+            market_base_vault: market.market_base_vault,
+            market_quote_vault: market.market_quote_vault,
+            token_program: anchor_spl::token::ID,
+        },
+        openbook_v2::instruction::Deposit {
+            base_amount,
+            quote_amount,
+        },
+    )
+}
+
+/// Like deposit, but the amounts are the totals the position will have
+#[allow(clippy::too_many_arguments)]
+pub fn refill(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    owner: Pubkey,
+    user_base_account: Pubkey,
+    user_quote_account: Pubkey,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::Deposit {
+            owner,
+            user_base_account,
+            user_quote_account,
+            open_orders_account,
+            market: market_address,
+            market_base_vault: market.market_base_vault,
+            market_quote_vault: market.market_quote_vault,
+            token_program: anchor_spl::token::ID,
+        },
+        openbook_v2::instruction::Refill {
+            base_amount,
+            quote_amount,
+        },
+    )
+}
+
+/// The accounts of place_order, place_order_pegged, edit_order and edit_order_pegged.
+/// The market's open_orders_admin, if any, has to sign as well.
+///
+/// The remaining_accounts of orders are the access gate and the owner's access proof on
+/// gated markets, the referral of an account bound to a referrer, the delegates account
+/// when a delegate signs, and the open orders accounts of the makers with market maker
+/// protection the order may match. The orders of such makers that are missing are skipped.
+fn place_order_accounts(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    user_token_account: Pubkey,
+    side: Side,
+) -> openbook_v2::accounts::PlaceOrder {
+    openbook_v2::accounts::PlaceOrder {
+        signer,
+        open_orders_account,
+        open_orders_admin: market.open_orders_admin.into(),
+        user_token_account,
+        market: market_address,
+        bids: market.bids,
+        asks: market.asks,
+        event_heap: market.event_heap,
+        market_vault: market_vault(market, side),
+        oracle_a: market.oracle_a.into(),
+        oracle_b: market.oracle_b.into(),
+        token_program: anchor_spl::token::ID,
+    }
+}
+
+/// user_token_account holds the quote token for bids and the base token for asks
+pub fn place_order(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    user_token_account: Pubkey,
+    args: PlaceOrderArgs,
+    remaining_accounts: impl IntoIterator<Item = Pubkey>,
+) -> Instruction {
+    with_remaining_accounts(
+        instruction(
+            place_order_accounts(
+                market,
+                market_address,
+                open_orders_account,
+                signer,
+                user_token_account,
+                args.side,
+            ),
+            openbook_v2::instruction::PlaceOrder { args },
+        ),
+        remaining_accounts,
+    )
+}
+
+pub fn place_order_pegged(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    user_token_account: Pubkey,
+    args: PlaceOrderPeggedArgs,
+    remaining_accounts: impl IntoIterator<Item = Pubkey>,
+) -> Instruction {
+    with_remaining_accounts(
+        instruction(
+            place_order_accounts(
+                market,
+                market_address,
+                open_orders_account,
+                signer,
+                user_token_account,
+                args.side,
+            ),
+            openbook_v2::instruction::PlaceOrderPegged { args },
+        ),
+        remaining_accounts,
+    )
+}
+
+/// Cancels the order with client_order_id and places place_order, unless less than
+/// expected_cancel_size was left of the cancelled order
+#[allow(clippy::too_many_arguments)]
+pub fn edit_order(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    user_token_account: Pubkey,
+    client_order_id: u64,
+    expected_cancel_size: i64,
+    place_order: PlaceOrderArgs,
+    remaining_accounts: impl IntoIterator<Item = Pubkey>,
+) -> Instruction {
+    with_remaining_accounts(
+        instruction(
+            place_order_accounts(
+                market,
+                market_address,
+                open_orders_account,
+                signer,
+                user_token_account,
+                place_order.side,
+            ),
+            openbook_v2::instruction::EditOrder {
+                client_order_id,
+                expected_cancel_size,
+                place_order,
+            },
+        ),
+        remaining_accounts,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn edit_order_pegged(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    user_token_account: Pubkey,
+    client_order_id: u64,
+    expected_cancel_size: i64,
+    place_order: PlaceOrderPeggedArgs,
+    remaining_accounts: impl IntoIterator<Item = Pubkey>,
+) -> Instruction {
+    with_remaining_accounts(
+        instruction(
+            place_order_accounts(
+                market,
+                market_address,
+                open_orders_account,
+                signer,
+                user_token_account,
+                place_order.side,
+            ),
+            openbook_v2::instruction::EditOrderPegged {
+                client_order_id,
+                expected_cancel_size,
+                place_order,
+            },
+        ),
+        remaining_accounts,
+    )
+}
+
+/// Fills against the book without an open orders account and settles to the user's
+/// token accounts right away. The signer pays the penalty if the order is not filled.
+/// Gated markets need the access gate and the signer's access proof in remaining_accounts.
+pub fn place_take_order(
+    market: &Market,
+    market_address: Pubkey,
+    signer: Pubkey,
+    user_base_account: Pubkey,
+    user_quote_account: Pubkey,
+    args: PlaceTakeOrderArgs,
+    remaining_accounts: impl IntoIterator<Item = Pubkey>,
+) -> Instruction {
+    with_remaining_accounts(
+        instruction(
+            openbook_v2::accounts::PlaceTakeOrder {
+                signer,
+                penalty_payer: signer,
+                market: market_address,
+                market_authority: market.market_authority,
+                bids: market.bids,
+                asks: market.asks,
+                market_base_vault: market.market_base_vault,
+                market_quote_vault: market.market_quote_vault,
+                event_heap: market.event_heap,
+                user_base_account,
+                user_quote_account,
+                oracle_a: market.oracle_a.into(),
+                oracle_b: market.oracle_b.into(),
+                token_program: anchor_spl::token::ID,
+                system_program: anchor_lang::system_program::ID,
+                open_orders_admin: market.open_orders_admin.into(),
+            },
+            openbook_v2::instruction::PlaceTakeOrder { args },
+        ),
+        remaining_accounts,
+    )
+}
+
+fn cancel_all_and_place_orders_accounts(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    user_base_account: Pubkey,
+    user_quote_account: Pubkey,
+) -> openbook_v2::accounts::CancelAllAndPlaceOrders {
+    openbook_v2::accounts::CancelAllAndPlaceOrders {
+        signer,
+        open_orders_account,
+        open_orders_admin: market.open_orders_admin.into(),
+        user_quote_account,
+        user_base_account,
+        market: market_address,
+        bids: market.bids,
+        asks: market.asks,
+        event_heap: market.event_heap,
+        market_quote_vault: market.market_quote_vault,
+        market_base_vault: market.market_base_vault,
+        oracle_a: market.oracle_a.into(),
+        oracle_b: market.oracle_b.into(),
+        token_program: anchor_spl::token::ID,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn place_orders(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    user_base_account: Pubkey,
+    user_quote_account: Pubkey,
+    orders_type: PlaceOrderType,
+    bids: Vec<PlaceMultipleOrdersArgs>,
+    asks: Vec<PlaceMultipleOrdersArgs>,
+    limit: u8,
+    remaining_accounts: impl IntoIterator<Item = Pubkey>,
+) -> Instruction {
+    with_remaining_accounts(
+        instruction(
+            cancel_all_and_place_orders_accounts(
+                market,
+                market_address,
+                open_orders_account,
+                signer,
+                user_base_account,
+                user_quote_account,
+            ),
+            openbook_v2::instruction::PlaceOrders {
+                orders_type,
+                bids,
+                asks,
+                limit,
+            },
+        ),
+        remaining_accounts,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn cancel_all_and_place_orders(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    user_base_account: Pubkey,
+    user_quote_account: Pubkey,
+    orders_type: PlaceOrderType,
+    bids: Vec<PlaceMultipleOrdersArgs>,
+    asks: Vec<PlaceMultipleOrdersArgs>,
+    limit: u8,
+    remaining_accounts: impl IntoIterator<Item = Pubkey>,
+) -> Instruction {
+    with_remaining_accounts(
+        instruction(
+            cancel_all_and_place_orders_accounts(
+                market,
+                market_address,
+                open_orders_account,
+                signer,
+                user_base_account,
+                user_quote_account,
+            ),
+            openbook_v2::instruction::CancelAllAndPlaceOrders {
+                orders_type,
+                bids,
+                asks,
+                limit,
+            },
+        ),
+        remaining_accounts,
+    )
+}
+
+fn cancel_order_accounts(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+) -> openbook_v2::accounts::CancelOrder {
+    openbook_v2::accounts::CancelOrder {
+        signer,
+        open_orders_account,
+        market: market_address,
+        bids: market.bids,
+        asks: market.asks,
+    }
+}
+
+pub fn cancel_order(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    order_id: u128,
+) -> Instruction {
+    instruction(
+        cancel_order_accounts(market, market_address, open_orders_account, signer),
+        openbook_v2::instruction::CancelOrder { order_id },
+    )
+}
+
+pub fn cancel_order_by_client_order_id(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    client_order_id: u64,
+) -> Instruction {
+    instruction(
+        cancel_order_accounts(market, market_address, open_orders_account, signer),
+        openbook_v2::instruction::CancelOrderByClientOrderId { client_order_id },
+    )
+}
+
+pub fn cancel_all_orders(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    side_option: Option<Side>,
+    limit: u8,
+) -> Instruction {
+    instruction(
+        cancel_order_accounts(market, market_address, open_orders_account, signer),
+        openbook_v2::instruction::CancelAllOrders { side_option, limit },
+    )
+}
+
+/// The signer is the owner or a delegate of the account, a timeout of 0 disables it
+pub fn heartbeat(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    timeout: u64,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::Heartbeat {
+            signer,
+            open_orders_account,
+            market: market_address,
+            bids: market.bids,
+            asks: market.asks,
+        },
+        openbook_v2::instruction::Heartbeat { timeout },
+    )
+}
+
+/// Permissionless once the heartbeat deadline of the account passed
+pub fn cancel_orders_heartbeat_expired(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    limit: u8,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::CancelOrdersHeartbeatExpired {
+            open_orders_account,
+            market: market_address,
+            bids: market.bids,
+            asks: market.asks,
+        },
+        openbook_v2::instruction::CancelOrdersHeartbeatExpired { limit },
+    )
+}
+
+/// The signer is the owner or a delegate of the account
+#[allow(clippy::too_many_arguments)]
+pub fn set_mmp(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    signer: Pubkey,
+    window_secs: u16,
+    base_lots_limit: i64,
+    fill_count_limit: u16,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SetMmp {
+            signer,
+            open_orders_account,
+            market: market_address,
+            bids: market.bids,
+            asks: market.asks,
+        },
+        openbook_v2::instruction::SetMmp {
+            window_secs,
+            base_lots_limit,
+            fill_count_limit,
+        },
+    )
+}
+
+pub fn reset_mmp(owner: Pubkey, open_orders_account: Pubkey) -> Instruction {
+    instruction(
+        openbook_v2::accounts::ResetMmp {
+            owner,
+            open_orders_account,
+        },
+        openbook_v2::instruction::ResetMmp {},
+    )
+}
+
+/// The open orders accounts of the makers of the events are passed as remaining
+/// accounts, events of makers that are missing are skipped. The market's
+/// consume_events_admin, if any, has to sign.
+pub fn consume_events(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_accounts: impl IntoIterator<Item = Pubkey>,
+    limit: usize,
+) -> Instruction {
+    with_remaining_accounts(
+        instruction(
+            openbook_v2::accounts::ConsumeEvents {
+                consume_events_admin: market.consume_events_admin.into(),
+                market: market_address,
+                event_heap: market.event_heap,
+            },
+            openbook_v2::instruction::ConsumeEvents { limit },
+        ),
+        open_orders_accounts,
+    )
+}
+
+/// Like consume_events, but only for the events in the given slots of the event heap
+pub fn consume_given_events(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_accounts: impl IntoIterator<Item = Pubkey>,
+    slots: Vec<usize>,
+) -> Instruction {
+    with_remaining_accounts(
+        instruction(
+            openbook_v2::accounts::ConsumeEvents {
+                consume_events_admin: market.consume_events_admin.into(),
+                market: market_address,
+                event_heap: market.event_heap,
+            },
+            openbook_v2::instruction::ConsumeGivenEvents { slots },
+        ),
+        open_orders_accounts,
+    )
+}
+
+/// Like consume_events, or consume_given_events when slots are given, and pays the crank
+/// reward to crank_reward_receiver, which has to sign
+pub fn consume_events_with_reward(
+    market: &Market,
+    market_address: Pubkey,
+    crank_reward_receiver: Pubkey,
+    open_orders_accounts: impl IntoIterator<Item = Pubkey>,
+    limit: usize,
+    slots: Option<Vec<usize>>,
+) -> Instruction {
+    with_remaining_accounts(
+        instruction(
+            openbook_v2::accounts::ConsumeEventsWithReward {
+                consume_events_admin: market.consume_events_admin.into(),
+                market: market_address,
+                event_heap: market.event_heap,
+                crank_reward_receiver,
+            },
+            openbook_v2::instruction::ConsumeEventsWithReward { limit, slots },
+        ),
+        open_orders_accounts,
+    )
+}
+
+/// Permissionless. The open orders accounts of the crossing makers are passed as
+/// remaining accounts, those with market maker protection must be.
+pub fn run_auction(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_accounts: impl IntoIterator<Item = Pubkey>,
+    limit: u8,
+) -> Instruction {
+    with_remaining_accounts(
+        instruction(
+            openbook_v2::accounts::RunAuction {
+                market: market_address,
+                bids: market.bids,
+                asks: market.asks,
+                event_heap: market.event_heap,
+                oracle_a: market.oracle_a.into(),
+                oracle_b: market.oracle_b.into(),
+            },
+            openbook_v2::instruction::RunAuction { limit },
+        ),
+        open_orders_accounts,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn settle_funds(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_account: Pubkey,
+    owner: Pubkey,
+    penalty_payer: Pubkey,
+    user_base_account: Pubkey,
+    user_quote_account: Pubkey,
+    referrer_account: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SettleFunds {
+            owner,
+            penalty_payer,
+            open_orders_account,
+            market: market_address,
+            market_authority: market.market_authority,
+            market_base_vault: market.market_base_vault,
+            market_quote_vault: market.market_quote_vault,
+            user_base_account,
+            user_quote_account,
+            referrer_account,
+            token_program: anchor_spl::token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        openbook_v2::instruction::SettleFunds {},
+    )
+}
+
+/// Settles the funds of an owner on an expired market, signed by its close_market_admin
+#[allow(clippy::too_many_arguments)]
+pub fn settle_funds_expired(
+    market: &Market,
+    market_address: Pubkey,
+    close_market_admin: Pubkey,
+    open_orders_account: Pubkey,
+    owner: Pubkey,
+    penalty_payer: Pubkey,
+    user_base_account: Pubkey,
+    user_quote_account: Pubkey,
+    referrer_account: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SettleFundsExpired {
+            close_market_admin,
+            owner,
+            penalty_payer,
+            open_orders_account,
+            market: market_address,
+            market_authority: market.market_authority,
+            market_base_vault: market.market_base_vault,
+            market_quote_vault: market.market_quote_vault,
+            user_base_account,
+            user_quote_account,
+            referrer_account,
+            token_program: anchor_spl::token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        openbook_v2::instruction::SettleFundsExpired {},
+    )
+}
+
+/// Settles to the owner's token accounts on an expired market without its signature,
+/// signed by the close_market_admin or by anyone after the grace period
+#[allow(clippy::too_many_arguments)]
+pub fn force_settle_funds(
+    market: &Market,
+    market_address: Pubkey,
+    signer: Pubkey,
+    open_orders_account: Pubkey,
+    owner: Pubkey,
+    user_base_account: Pubkey,
+    user_quote_account: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::ForceSettleFunds {
+            signer,
+            owner,
+            open_orders_account,
+            market: market_address,
+            market_authority: market.market_authority,
+            market_base_vault: market.market_base_vault,
+            market_quote_vault: market.market_quote_vault,
+            user_base_account,
+            user_quote_account,
+            token_program: anchor_spl::token::ID,
+        },
+        openbook_v2::instruction::ForceSettleFunds {},
+    )
+}
+
+/// Signed by the market's collect_fee_admin. Markets with a fee split send the fees to
+/// fee_split_destinations, the token accounts of the FeeSplit in order, otherwise they
+/// go to token_receiver_account.
+pub fn sweep_fees(
+    market: &Market,
+    market_address: Pubkey,
+    token_receiver_account: Option<Pubkey>,
+    fee_split_destinations: impl IntoIterator<Item = Pubkey>,
+) -> Instruction {
+    let fee_split = market
+        .has_fee_split()
+        .then(|| fee_split_address(&market_address));
+    with_remaining_accounts(
+        instruction(
+            openbook_v2::accounts::SweepFees {
+                collect_fee_admin: market.collect_fee_admin,
+                market: market_address,
+                market_authority: market.market_authority,
+                market_quote_vault: market.market_quote_vault,
+                token_receiver_account,
+                token_program: anchor_spl::token::ID,
+                fee_split,
+            },
+            openbook_v2::instruction::SweepFees {},
+        ),
+        fee_split_destinations,
+    )
+}
+
+/// Signed by the market's collect_fee_admin, the fee split is at
+/// fee_split_address(market_address)
+pub fn create_fee_split(
+    market: &Market,
+    market_address: Pubkey,
+    payer: Pubkey,
+    destinations: Vec<FeeSplitDestination>,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::CreateFeeSplit {
+            payer,
+            collect_fee_admin: market.collect_fee_admin,
+            market: market_address,
+            fee_split: fee_split_address(&market_address),
+            system_program: anchor_lang::system_program::ID,
+        },
+        openbook_v2::instruction::CreateFeeSplit { destinations },
+    )
+}
+
+pub fn set_fee_split(
+    market: &Market,
+    market_address: Pubkey,
+    destinations: Vec<FeeSplitDestination>,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SetFeeSplit {
+            collect_fee_admin: market.collect_fee_admin,
+            market: market_address,
+            fee_split: fee_split_address(&market_address),
+        },
+        openbook_v2::instruction::SetFeeSplit { destinations },
+    )
+}
+
+/// Signed by the market's collect_fee_admin
+pub fn set_max_referrer_share(
+    market: &Market,
+    market_address: Pubkey,
+    share_bps: u16,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SetMaxReferrerShare {
+            collect_fee_admin: market.collect_fee_admin,
+            market: market_address,
+        },
+        openbook_v2::instruction::SetMaxReferrerShare { share_bps },
+    )
+}
+
+/// The referrer is at referrer_address(authority, market), the default pubkey as market
+/// registers a global referrer
+pub fn register_referrer(
+    payer: Pubkey,
+    authority: Pubkey,
+    market: Pubkey,
+    share_bps: u16,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::RegisterReferrer {
+            payer,
+            authority,
+            referrer: referrer_address(&authority, &market),
+            system_program: anchor_lang::system_program::ID,
+        },
+        openbook_v2::instruction::RegisterReferrer { market, share_bps },
+    )
+}
+
+pub fn set_referrer_share(authority: Pubkey, referrer: Pubkey, share_bps: u16) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SetReferrerShare {
+            authority,
+            referrer,
+        },
+        openbook_v2::instruction::SetReferrerShare { share_bps },
+    )
+}
+
+/// Creates the referral of open_orders_account, at
+/// open_orders_referral_address(open_orders_account)
+pub fn bind_referrer(owner: Pubkey, open_orders_account: Pubkey, referrer: Pubkey) -> Instruction {
+    instruction(
+        openbook_v2::accounts::BindReferrer {
+            owner,
+            open_orders_account,
+            referrer,
+            open_orders_referral: open_orders_referral_address(&open_orders_account),
+            system_program: anchor_lang::system_program::ID,
+        },
+        openbook_v2::instruction::BindReferrer {},
+    )
+}
+
+/// Signed by the referrer's authority, referrer_token_account is one of its quote token
+/// accounts
+pub fn claim_referrer_rebates(
+    market: &Market,
+    market_address: Pubkey,
+    authority: Pubkey,
+    referrer: Pubkey,
+    open_orders_account: Pubkey,
+    referrer_token_account: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::ClaimReferrerRebates {
+            authority,
+            referrer,
+            open_orders_account,
+            open_orders_referral: open_orders_referral_address(&open_orders_account),
+            market: market_address,
+            market_authority: market.market_authority,
+            market_quote_vault: market.market_quote_vault,
+            referrer_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        openbook_v2::instruction::ClaimReferrerRebates {},
+    )
+}
+
+/// Unbinds open_orders_account, paying the referrer's rebates to referrer_token_account
+#[allow(clippy::too_many_arguments)]
+pub fn close_open_orders_referral(
+    market: &Market,
+    market_address: Pubkey,
+    owner: Pubkey,
+    open_orders_account: Pubkey,
+    referrer: Pubkey,
+    referrer_token_account: Pubkey,
+    sol_destination: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::CloseOpenOrdersReferral {
+            owner,
+            open_orders_account,
+            open_orders_referral: open_orders_referral_address(&open_orders_account),
+            referrer,
+            market: market_address,
+            market_authority: market.market_authority,
+            market_quote_vault: market.market_quote_vault,
+            referrer_token_account,
+            sol_destination,
+            token_program: anchor_spl::token::ID,
+        },
+        openbook_v2::instruction::CloseOpenOrdersReferral {},
+    )
+}
+
+/// Removes the orders of an account from the book of an expired market
+pub fn prune_orders(
+    market: &Market,
+    market_address: Pubkey,
+    close_market_admin: Pubkey,
+    open_orders_account: Pubkey,
+    limit: u8,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::PruneOrders {
+            close_market_admin,
+            open_orders_account,
+            market: market_address,
+            bids: market.bids,
+            asks: market.asks,
+        },
+        openbook_v2::instruction::PruneOrders { limit },
+    )
+}
+
+/// Like prune_orders, signed by the close_market_admin or by anyone after the grace
+/// period
+pub fn force_prune_orders(
+    market: &Market,
+    market_address: Pubkey,
+    signer: Pubkey,
+    open_orders_account: Pubkey,
+    limit: u8,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::ForcePruneOrders {
+            signer,
+            open_orders_account,
+            market: market_address,
+            bids: market.bids,
+            asks: market.asks,
+        },
+        openbook_v2::instruction::ForcePruneOrders { limit },
+    )
+}
+
+/// Signed by the market's market_status_admin, the gate is at
+/// access_gate_address(market_address)
+pub fn create_access_gate(
+    market_address: Pubkey,
+    payer: Pubkey,
+    market_status_admin: Pubkey,
+    kind: AccessGate,
+    admin: Pubkey,
+    credential_mint: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::CreateAccessGate {
+            payer,
+            market_status_admin,
+            market: market_address,
+            access_gate: access_gate_address(&market_address),
+            system_program: anchor_lang::system_program::ID,
+        },
+        openbook_v2::instruction::CreateAccessGate {
+            kind,
+            admin,
+            credential_mint,
+        },
+    )
+}
+
+/// Signed by the gate's admin, the pass is at access_pass_address(market_address, owner)
+pub fn add_access_pass(
+    market_address: Pubkey,
+    payer: Pubkey,
+    admin: Pubkey,
+    owner: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::AddAccessPass {
+            payer,
+            admin,
+            access_gate: access_gate_address(&market_address),
+            owner,
+            access_pass: access_pass_address(&market_address, &owner),
+            system_program: anchor_lang::system_program::ID,
+        },
+        openbook_v2::instruction::AddAccessPass {},
+    )
+}
+
+pub fn remove_access_pass(
+    market_address: Pubkey,
+    admin: Pubkey,
+    owner: Pubkey,
+    sol_destination: Pubkey,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::RemoveAccessPass {
+            admin,
+            access_gate: access_gate_address(&market_address),
+            access_pass: access_pass_address(&market_address, &owner),
+            sol_destination,
+        },
+        openbook_v2::instruction::RemoveAccessPass {},
+    )
+}
+
+pub fn set_open_orders_access(
+    market_address: Pubkey,
+    admin: Pubkey,
+    open_orders_account: Pubkey,
+    allowed: bool,
+) -> Instruction {
+    instruction(
+        openbook_v2::accounts::SetOpenOrdersAccess {
+            admin,
+            access_gate: access_gate_address(&market_address),
+            open_orders_account,
+        },
+        openbook_v2::instruction::SetOpenOrdersAccess { allowed },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+    use anchor_lang::__private::bytemuck::Zeroable;
+    use openbook_v2::pubkey_option::NonZeroPubkeyOption;
+    use openbook_v2::state::SelfTradeBehavior;
+
+    fn market() -> Market {
+        let mut market = Market::zeroed();
+        market.bids = Pubkey::new_unique();
+        market.asks = Pubkey::new_unique();
+        market.event_heap = Pubkey::new_unique();
+        market.market_base_vault = Pubkey::new_unique();
+        market.market_quote_vault = Pubkey::new_unique();
+        market
+    }
+
+    #[test]
+    fn test_open_orders_addresses() {
+        let owner = Pubkey::new_unique();
+        assert_ne!(
+            open_orders_account_address(&owner, 1),
+            open_orders_account_address(&owner, 2)
+        );
+
+        let ix = create_open_orders_account(
+            Pubkey::new_unique(),
+            owner,
+            owner,
+            None,
+            1,
+            "name".to_string(),
+            None,
+            None,
+        );
+        assert_eq!(ix.program_id, openbook_v2::id());
+        assert_eq!(ix.accounts[1].pubkey, open_orders_indexer_address(&owner));
+        assert_eq!(
+            ix.accounts[2].pubkey,
+            open_orders_account_address(&owner, 1)
+        );
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(
+            ix.data[..8],
+            openbook_v2::instruction::CreateOpenOrdersAccount::DISCRIMINATOR
+        );
+    }
+
+    #[test]
+    fn test_place_order_accounts() {
+        let mut market = market();
+        let market_address = Pubkey::new_unique();
+        let open_orders_account = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let args = |side| PlaceOrderArgs {
+            side,
+            price_lots: 100,
+            max_base_lots: 10,
+            max_quote_lots_including_fees: 1000,
+            client_order_id: 1,
+            order_type: PlaceOrderType::Limit,
+            expiry_timestamp: 0,
+            self_trade_behavior: SelfTradeBehavior::DecrementTake,
+            limit: 10,
+        };
+
+        let ix = place_order(
+            &market,
+            market_address,
+            open_orders_account,
+            signer,
+            Pubkey::new_unique(),
+            args(Side::Bid),
+            [],
+        );
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert!(keys.contains(&market.market_quote_vault));
+        assert!(!keys.contains(&market.market_base_vault));
+        // absent optional accounts are passed as the program id
+        assert_eq!(keys[2], openbook_v2::id());
+        assert!(ix.accounts[0].is_signer);
+
+        let admin = Pubkey::new_unique();
+        market.open_orders_admin = NonZeroPubkeyOption::from(Some(admin));
+        let ix = place_order(
+            &market,
+            market_address,
+            open_orders_account,
+            signer,
+            Pubkey::new_unique(),
+            args(Side::Ask),
+            [],
+        );
+        assert_eq!(ix.accounts[2].pubkey, admin);
+        assert!(ix.accounts[2].is_signer);
+        assert!(ix
+            .accounts
+            .iter()
+            .any(|meta| meta.pubkey == market.market_base_vault));
+    }
+
+    #[test]
+    fn test_remaining_accounts() {
+        let mut market = market();
+        let market_address = Pubkey::new_unique();
+        let makers = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let ix = consume_events(&market, market_address, makers, 8);
+        assert_eq!(ix.accounts.len(), 3 + makers.len());
+        assert_eq!(ix.accounts[3].pubkey, makers[0]);
+        assert!(ix.accounts[4].is_writable);
+
+        let owner = Pubkey::new_unique();
+        let proofs = [
+            access_gate_address(&market_address),
+            access_pass_address(&market_address, &owner),
+        ];
+        let ix = place_order(
+            &market,
+            market_address,
+            Pubkey::new_unique(),
+            owner,
+            Pubkey::new_unique(),
+            PlaceOrderArgs {
+                side: Side::Bid,
+                price_lots: 100,
+                max_base_lots: 10,
+                max_quote_lots_including_fees: 1000,
+                client_order_id: 1,
+                order_type: PlaceOrderType::Limit,
+                expiry_timestamp: 0,
+                self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                limit: 10,
+            },
+            proofs,
+        );
+        assert_eq!(ix.accounts.len(), 12 + proofs.len());
+        assert_eq!(ix.accounts[12].pubkey, proofs[0]);
+        assert_eq!(ix.accounts[13].pubkey, proofs[1]);
+
+        let destinations = [Pubkey::new_unique()];
+        let ix = sweep_fees(&market, market_address, None, destinations);
+        assert_eq!(ix.accounts[6].pubkey, openbook_v2::id());
+        market.has_fee_split = 1;
+        let ix = sweep_fees(&market, market_address, None, destinations);
+        assert_eq!(ix.accounts[6].pubkey, fee_split_address(&market_address));
+        assert_eq!(ix.accounts[7].pubkey, destinations[0]);
+    }
+
+    #[test]
+    fn test_referral_accounts() {
+        let market = market();
+        let market_address = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let open_orders_account = Pubkey::new_unique();
+        let referrer = referrer_address(&owner, &market_address);
+        assert_ne!(referrer, referrer_address(&owner, &Pubkey::default()));
+
+        let ix = register_referrer(owner, owner, market_address, 100);
+        assert_eq!(ix.accounts[2].pubkey, referrer);
+
+        let ix = bind_referrer(owner, open_orders_account, referrer);
+        assert_eq!(
+            ix.accounts[3].pubkey,
+            open_orders_referral_address(&open_orders_account)
+        );
+
+        let ix = close_open_orders_referral(
+            &market,
+            market_address,
+            owner,
+            open_orders_account,
+            referrer,
+            Pubkey::new_unique(),
+            owner,
+        );
+        assert_eq!(
+            ix.accounts[2].pubkey,
+            open_orders_referral_address(&open_orders_account)
+        );
+        assert_eq!(ix.accounts[6].pubkey, market.market_quote_vault);
+    }
+
+    #[test]
+    fn test_delegates_and_access_accounts() {
+        let market_address = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let open_orders_account = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+
+        let ix = add_delegate(
+            owner,
+            open_orders_account,
+            delegate,
+            DelegateScope::Trade,
+            Some(Side::Bid),
+            1_000,
+            0,
+        );
+        assert_eq!(
+            ix.accounts[2].pubkey,
+            open_orders_delegates_address(&open_orders_account)
+        );
+        assert_eq!(ix.accounts[3].pubkey, delegate);
+        assert!(ix.accounts[0].is_signer);
+
+        let ix = add_access_pass(market_address, owner, owner, delegate);
+        assert_eq!(ix.accounts[2].pubkey, access_gate_address(&market_address));
+        assert_eq!(
+            ix.accounts[4].pubkey,
+            access_pass_address(&market_address, &delegate)
+        );
+    }
+
+    #[test]
+    fn test_run_auction_accounts() {
+        let market = market();
+        let makers = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let ix = run_auction(&market, Pubkey::new_unique(), makers, 10);
+        assert_eq!(ix.accounts.len(), 8);
+        // absent oracles are passed as the program id
+        assert_eq!(ix.accounts[4].pubkey, openbook_v2::id());
+        assert_eq!(ix.accounts[6].pubkey, makers[0]);
+        assert!(ix.accounts[7].is_writable);
+    }
+}
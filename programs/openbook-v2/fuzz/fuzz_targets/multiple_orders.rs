@@ -447,6 +447,8 @@ mod error_parser {
 
     pub fn create_market(err: ProgramError) -> Corpus {
         match err {
+            e if e == OpenBookError::InvalidInputAuction.into() => Corpus::Reject,
+            e if e == OpenBookError::InvalidInputOrderSizeLimits.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidInputLots.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidInputNameLength.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidInputMarketExpired.into() => Corpus::Reject,
@@ -477,7 +479,9 @@ mod error_parser {
             e if e == OpenBookError::InvalidOraclePrice.into() => Corpus::Keep,
             e if e == OpenBookError::InvalidPostAmount.into() => Corpus::Keep,
             e if e == OpenBookError::InvalidPriceLots.into() => Corpus::Keep,
+            e if e == OpenBookError::MarketInAuction.into() => Corpus::Keep,
             e if e == OpenBookError::OpenOrdersFull.into() => Corpus::Keep,
+            e if e == OpenBookError::OrderSizeAboveMaximum.into() => Corpus::Keep,
             e if e == OpenBookError::WouldSelfTrade.into() => Corpus::Keep,
             e if e == OpenBookError::WouldExecutePartially.into() => Corpus::Keep,
             e if e == TokenError::InsufficientFunds.into() => Corpus::Keep,
@@ -496,8 +500,10 @@ mod error_parser {
             e if e == OpenBookError::InvalidOrderPostMarket.into() => Corpus::Keep,
             e if e == OpenBookError::InvalidPostAmount.into() => Corpus::Keep,
             e if e == OpenBookError::InvalidPriceLots.into() => Corpus::Keep,
+            e if e == OpenBookError::MarketInAuction.into() => Corpus::Keep,
             e if e == OpenBookError::OpenOrdersFull.into() => Corpus::Keep,
             e if e == OpenBookError::OraclePegInvalidOracleState.into() => Corpus::Keep,
+            e if e == OpenBookError::OrderSizeAboveMaximum.into() => Corpus::Keep,
             e if e == OpenBookError::WouldSelfTrade.into() => Corpus::Keep,
             e if e == OpenBookError::WouldExecutePartially.into() => Corpus::Keep,
             e if e == TokenError::InsufficientFunds.into() => Corpus::Keep,
@@ -512,6 +518,7 @@ mod error_parser {
             e if e == OpenBookError::InvalidInputOrderType.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidInputPriceLots.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidOraclePrice.into() => Corpus::Keep,
+            e if e == OpenBookError::MarketInAuction.into() => Corpus::Keep,
             e if e == OpenBookError::WouldExecutePartially.into() => Corpus::Keep,
             e if e == TokenError::InsufficientFunds.into() => Corpus::Keep,
             _ => panic!("{}", err),
@@ -545,7 +552,9 @@ mod error_parser {
             e if e == OpenBookError::InvalidOraclePrice.into() => Corpus::Keep,
             e if e == OpenBookError::InvalidPostAmount.into() => Corpus::Keep,
             e if e == OpenBookError::InvalidPriceLots.into() => Corpus::Keep,
+            e if e == OpenBookError::MarketInAuction.into() => Corpus::Keep,
             e if e == OpenBookError::OpenOrdersFull.into() => Corpus::Keep,
+            e if e == OpenBookError::OrderSizeAboveMaximum.into() => Corpus::Keep,
             e if e == OpenBookError::WouldSelfTrade.into() => Corpus::Keep,
             e if e == OpenBookError::WouldExecutePartially.into() => Corpus::Keep,
             e if e == TokenError::InsufficientFunds.into() => Corpus::Keep,
@@ -235,6 +235,8 @@ impl FuzzContext {
                 payer: self.payer,
                 market: self.market,
                 system_program: system_program::ID,
+                access_gate: None,
+                access_proof: None,
             };
             let data = openbook_v2::instruction::CreateOpenOrdersAccount {
                 name: "fuzz test".to_string(),
@@ -309,6 +311,7 @@ impl FuzzContext {
             open_orders_admin: None,
             consume_events_admin: None,
             close_market_admin: None,
+            market_status_admin: None,
             event_authority: self.event_authority,
             program: openbook_v2::ID,
         };
@@ -781,11 +784,12 @@ impl FuzzContext {
     pub fn sweep_fees(&mut self, data: &openbook_v2::instruction::SweepFees) -> ProgramResult {
         let accounts = openbook_v2::accounts::SweepFees {
             collect_fee_admin: self.collect_fee_admin,
-            token_receiver_account: self.collect_fee_admin_quote_vault,
+            token_receiver_account: Some(self.collect_fee_admin_quote_vault),
             market: self.market,
             market_authority: self.market_authority,
             market_quote_vault: self.market_quote_vault,
             token_program: spl_token::ID,
+            fee_split: None,
         };
 
         process_instruction(&mut self.state, data, &accounts, &[])
@@ -0,0 +1,22 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AddAccessPass<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    #[account(has_one = admin)]
+    pub access_gate: Account<'info, MarketAccessGate>,
+    /// CHECK: The owner the access pass is created for
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        init,
+        seeds = [b"AccessPass".as_ref(), access_gate.market.as_ref(), owner.key().as_ref()],
+        bump,
+        payer = payer,
+        space = AccessPass::space(),
+    )]
+    pub access_pass: Account<'info, AccessPass>,
+    pub system_program: Program<'info, System>,
+}
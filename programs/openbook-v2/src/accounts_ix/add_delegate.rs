@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{OpenOrdersAccount, OpenOrdersDelegates};
+
+#[derive(Accounts)]
+pub struct AddDelegate<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        has_one = owner,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    #[account(
+        mut,
+        has_one = open_orders_account,
+    )]
+    pub open_orders_delegates: AccountLoader<'info, OpenOrdersDelegates>,
+    /// CHECK:
+    pub delegate_account: UncheckedAccount<'info>,
+}
@@ -0,0 +1,29 @@
+use crate::error::OpenBookError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct BindReferrer<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = !open_orders_account.load()?.has_referral() @ OpenBookError::ReferrerAlreadyBound,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    #[account(
+        mut,
+        constraint = referrer.can_refer_on(open_orders_account.load()?.market) @ OpenBookError::InvalidReferrer,
+    )]
+    pub referrer: Account<'info, Referrer>,
+    #[account(
+        init,
+        seeds = [b"Referral".as_ref(), open_orders_account.key().as_ref()],
+        bump,
+        payer = owner,
+        space = OpenOrdersReferral::space(),
+    )]
+    pub open_orders_referral: AccountLoader<'info, OpenOrdersReferral>,
+    pub system_program: Program<'info, System>,
+}
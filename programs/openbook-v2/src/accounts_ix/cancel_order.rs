@@ -1,4 +1,3 @@
-use crate::error::OpenBookError;
 use crate::state::{BookSide, Market, OpenOrdersAccount};
 use anchor_lang::prelude::*;
 
@@ -8,7 +7,6 @@ pub struct CancelOrder<'info> {
     #[account(
         mut,
         has_one = market,
-        constraint = open_orders_account.load()?.is_owner_or_delegate(signer.key()) @ OpenBookError::NoOwnerOrDelegate
     )]
     pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
     #[account(
@@ -0,0 +1,41 @@
+use crate::error::OpenBookError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ClaimReferrerRebates<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = authority,
+    )]
+    pub referrer: Account<'info, Referrer>,
+    #[account(
+        has_one = market,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    #[account(
+        mut,
+        has_one = open_orders_account,
+        has_one = referrer @ OpenBookError::InvalidReferrer,
+    )]
+    pub open_orders_referral: AccountLoader<'info, OpenOrdersReferral>,
+    #[account(
+        mut,
+        has_one = market_quote_vault,
+        has_one = market_authority,
+        constraint = referrer.can_refer_on(market.key()) @ OpenBookError::InvalidReferrer,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    /// CHECK: checked on has_one in market
+    pub market_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub market_quote_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = market_quote_vault.mint
+    )]
+    pub referrer_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,21 @@
+use crate::state::{OpenOrdersAccount, OpenOrdersDelegates};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseOpenOrdersDelegates<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    #[account(
+        mut,
+        has_one = open_orders_account,
+        close = sol_destination,
+    )]
+    pub open_orders_delegates: AccountLoader<'info, OpenOrdersDelegates>,
+    #[account(mut)]
+    /// CHECK: target for account rent needs no checks
+    pub sol_destination: UncheckedAccount<'info>,
+}
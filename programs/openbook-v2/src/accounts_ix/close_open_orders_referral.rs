@@ -0,0 +1,43 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CloseOpenOrdersReferral<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = market,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    #[account(
+        mut,
+        has_one = open_orders_account,
+        has_one = referrer,
+        close = sol_destination,
+    )]
+    pub open_orders_referral: AccountLoader<'info, OpenOrdersReferral>,
+    #[account(mut)]
+    pub referrer: Account<'info, Referrer>,
+    #[account(
+        mut,
+        has_one = market_quote_vault,
+        has_one = market_authority,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    /// CHECK: checked on has_one in market
+    pub market_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub market_quote_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = market_quote_vault.mint,
+        token::authority = referrer.authority,
+    )]
+    pub referrer_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    /// CHECK: target for account rent needs no checks
+    pub sol_destination: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
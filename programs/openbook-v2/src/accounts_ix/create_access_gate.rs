@@ -0,0 +1,25 @@
+use crate::error::OpenBookError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateAccessGate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub market_status_admin: Signer<'info>,
+    #[account(
+        mut,
+        constraint = market.load()?.market_status_admin.is_some() @ OpenBookError::NoMarketStatusAdmin,
+        constraint = market.load()?.market_status_admin == market_status_admin.key() @ OpenBookError::InvalidMarketStatusAdmin
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(
+        init,
+        seeds = [b"AccessGate".as_ref(), market.key().as_ref()],
+        bump,
+        payer = payer,
+        space = MarketAccessGate::space(),
+    )]
+    pub access_gate: Account<'info, MarketAccessGate>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,23 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateFeeSplit<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub collect_fee_admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = collect_fee_admin,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(
+        init,
+        seeds = [b"FeeSplit".as_ref(), market.key().as_ref()],
+        bump,
+        payer = payer,
+        space = FeeSplit::space(MAX_FEE_SPLIT_DESTINATIONS),
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+    pub system_program: Program<'info, System>,
+}
@@ -23,6 +23,7 @@ pub struct CreateMarket<'info> {
 
     /// Accounts are initialized by client,
     /// anchor discriminator is set first when ix exits,
+    /// their sizes set the capacities, see `BookSide::space` and `EventHeap::space`
     #[account(zero)]
     pub bids: AccountLoader<'info, BookSide>,
     #[account(zero)]
@@ -68,4 +69,6 @@ pub struct CreateMarket<'info> {
     pub consume_events_admin: Option<UncheckedAccount<'info>>,
     /// CHECK:
     pub close_market_admin: Option<UncheckedAccount<'info>>,
+    /// CHECK:
+    pub market_status_admin: Option<UncheckedAccount<'info>>,
 }
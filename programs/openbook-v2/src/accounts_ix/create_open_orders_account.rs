@@ -1,4 +1,4 @@
-use crate::state::{Market, OpenOrdersAccount, OpenOrdersIndexer};
+use crate::state::{Market, MarketAccessGate, OpenOrdersAccount, OpenOrdersIndexer};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -28,4 +28,10 @@ pub struct CreateOpenOrdersAccount<'info> {
     pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
     pub market: AccountLoader<'info, Market>,
     pub system_program: Program<'info, System>,
+    /// Required when the market is access gated
+    #[account(has_one = market)]
+    pub access_gate: Option<Account<'info, MarketAccessGate>>,
+    /// CHECK: The owner's access pass or credential token account, checked against the
+    /// access gate
+    pub access_proof: Option<UncheckedAccount<'info>>,
 }
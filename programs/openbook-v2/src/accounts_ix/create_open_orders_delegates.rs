@@ -0,0 +1,23 @@
+use crate::state::{OpenOrdersAccount, OpenOrdersDelegates};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateOpenOrdersDelegates<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    #[account(
+        init,
+        seeds = [b"Delegates".as_ref(), open_orders_account.key().as_ref()],
+        bump,
+        payer = payer,
+        space = OpenOrdersDelegates::space(),
+    )]
+    pub open_orders_delegates: AccountLoader<'info, OpenOrdersDelegates>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,42 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ForceSettleFunds<'info> {
+    pub signer: Signer<'info>,
+    /// CHECK: checked on has_one in open_orders_account
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = market,
+        has_one = owner,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    #[account(
+        mut,
+        has_one = market_base_vault,
+        has_one = market_quote_vault,
+        has_one = market_authority,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    /// CHECK: checked on has_one in market
+    pub market_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub market_base_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub market_quote_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = market_base_vault.mint,
+        token::authority = owner,
+    )]
+    pub user_base_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = market_quote_vault.mint,
+        token::authority = owner,
+    )]
+    pub user_quote_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,19 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(market: Pubkey)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        seeds = [b"Referrer".as_ref(), authority.key().as_ref(), market.as_ref()],
+        bump,
+        payer = payer,
+        space = Referrer::space(),
+    )]
+    pub referrer: Account<'info, Referrer>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,19 @@
+use crate::error::OpenBookError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveAccessPass<'info> {
+    pub admin: Signer<'info>,
+    #[account(has_one = admin)]
+    pub access_gate: Account<'info, MarketAccessGate>,
+    #[account(
+        mut,
+        constraint = access_pass.market == access_gate.market @ OpenBookError::InvalidAccessProof,
+        close = sol_destination,
+    )]
+    pub access_pass: Account<'info, AccessPass>,
+    #[account(mut)]
+    /// CHECK: target for account rent needs no checks
+    pub sol_destination: UncheckedAccount<'info>,
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::OpenOrdersAccount;
+use crate::state::{OpenOrdersAccount, OpenOrdersDelegates};
 
 #[derive(Accounts)]
 pub struct SetDelegate<'info> {
@@ -13,4 +13,9 @@ pub struct SetDelegate<'info> {
     pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
     /// CHECK:
     pub delegate_account: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        has_one = open_orders_account,
+    )]
+    pub open_orders_delegates: Option<AccountLoader<'info, OpenOrdersDelegates>>,
 }
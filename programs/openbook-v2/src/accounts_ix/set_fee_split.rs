@@ -0,0 +1,18 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    pub collect_fee_admin: Signer<'info>,
+    #[account(
+        has_one = collect_fee_admin,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(
+        mut,
+        has_one = market,
+        seeds = [b"FeeSplit".as_ref(), market.key().as_ref()],
+        bump = fee_split.bump,
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+}
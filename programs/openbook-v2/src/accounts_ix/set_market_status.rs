@@ -0,0 +1,14 @@
+use crate::error::OpenBookError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMarketStatus<'info> {
+    pub market_status_admin: Signer<'info>,
+    #[account(
+        mut,
+        constraint = market.load()?.market_status_admin.is_some() @ OpenBookError::NoMarketStatusAdmin,
+        constraint = market.load()?.market_status_admin == market_status_admin.key() @ OpenBookError::InvalidMarketStatusAdmin
+    )]
+    pub market: AccountLoader<'info, Market>,
+}
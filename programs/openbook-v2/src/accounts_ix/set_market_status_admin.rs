@@ -0,0 +1,14 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMarketStatusAdmin<'info> {
+    pub collect_fee_admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = collect_fee_admin,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    /// CHECK:
+    pub market_status_admin: Option<UncheckedAccount<'info>>,
+}
@@ -0,0 +1,31 @@
+use crate::state::{BookSide, Market, OpenOrdersAccount};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMmp<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        has_one = market,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    #[account(
+        has_one = bids,
+        has_one = asks,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(mut)]
+    pub bids: AccountLoader<'info, BookSide>,
+    #[account(mut)]
+    pub asks: AccountLoader<'info, BookSide>,
+}
+
+#[derive(Accounts)]
+pub struct ResetMmp<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+}
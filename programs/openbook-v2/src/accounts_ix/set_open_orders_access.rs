@@ -0,0 +1,15 @@
+use crate::error::OpenBookError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetOpenOrdersAccess<'info> {
+    pub admin: Signer<'info>,
+    #[account(has_one = admin)]
+    pub access_gate: Account<'info, MarketAccessGate>,
+    #[account(
+        mut,
+        constraint = open_orders_account.load()?.market == access_gate.market @ OpenBookError::InvalidAccessProof,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+}
@@ -1,4 +1,3 @@
-use crate::error::OpenBookError;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
@@ -12,7 +11,6 @@ pub struct SettleFunds<'info> {
     #[account(
         mut,
         has_one = market,
-        constraint = open_orders_account.load()?.is_owner_or_delegate(owner.key()) @ OpenBookError::NoOwnerOrDelegate
     )]
     pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
     #[account(
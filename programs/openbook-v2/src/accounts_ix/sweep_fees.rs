@@ -17,10 +17,19 @@ pub struct SweepFees<'info> {
     #[account(mut)]
     pub market_quote_vault: Account<'info, TokenAccount>,
 
+    /// Receives all fees when the market has no fee split
     #[account(
         mut,
         token::mint = market_quote_vault.mint
     )]
-    pub token_receiver_account: Account<'info, TokenAccount>,
+    pub token_receiver_account: Option<Account<'info, TokenAccount>>,
     pub token_program: Program<'info, Token>,
+    /// Required when the market has a fee split, the destination token accounts are
+    /// passed as remaining accounts in the same order
+    #[account(
+        has_one = market,
+        seeds = [b"FeeSplit".as_ref(), market.key().as_ref()],
+        bump = fee_split.bump,
+    )]
+    pub fee_split: Option<Account<'info, FeeSplit>>,
 }
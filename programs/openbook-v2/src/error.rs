@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 use core::fmt::Display;
+use num_enum::TryFromPrimitive;
 
+// TryFromPrimitive lets clients turn the code of a failed instruction back into the variant,
+// after subtracting anchor's ERROR_CODE_OFFSET
 #[error_code]
+#[derive(TryFromPrimitive)]
 pub enum OpenBookError {
     #[msg("")]
     SomeError,
@@ -95,6 +99,82 @@ pub enum OpenBookError {
     NonEmptyOpenOrdersPosition,
     #[msg("Fill-Or-Kill order would generate a partial execution")]
     WouldExecutePartially,
+    #[msg("The heartbeat of the open orders account has not expired")]
+    HeartbeatNotExpired,
+    #[msg("Auction duration must be set for recurring auctions")]
+    InvalidInputAuction,
+    #[msg("Only orders that post to the book are allowed during the auction phase")]
+    MarketInAuction,
+    #[msg("The market has no auction phase that ended")]
+    AuctionNotEnded,
+    #[msg("This market does not have a `market_status_admin`.")]
+    NoMarketStatusAdmin,
+    #[msg("The signer of this transaction is not this market's `market_status_admin`.")]
+    InvalidMarketStatusAdmin,
+    #[msg("The market only allows post only orders")]
+    MarketPostOnly,
+    #[msg("The market only allows cancelling orders")]
+    MarketCancelOnly,
+    #[msg("The market is halted")]
+    MarketHalted,
+    #[msg("Order size limits must be positive and the maximum at least the minimum")]
+    InvalidInputOrderSizeLimits,
+    #[msg("The order to post is larger than the maximum order size of the market")]
+    OrderSizeAboveMaximum,
+    #[msg("The delegate is not allowed to place orders on this side")]
+    DelegateSideNotAllowed,
+    #[msg("The order is larger than the delegate is allowed to place")]
+    DelegateOrderTooLarge,
+    #[msg("The owner can't be added as a delegate")]
+    InvalidDelegate,
+    #[msg("The delegate is already in the delegate list")]
+    DelegateAlreadyAdded,
+    #[msg("The delegate list is full")]
+    DelegateListFull,
+    #[msg("The delegate is not in the delegate list")]
+    DelegateNotFound,
+    #[msg("The grace period after the expiry of the market is not over")]
+    MarketGracePeriodNotOver,
+    #[msg("The fee split shares must be positive and add up to 100% over at most 4 destinations")]
+    InvalidInputFeeSplit,
+    #[msg("The fee split account must be passed if and only if the market has a fee split")]
+    InvalidFeeSplit,
+    #[msg("The fee split destination accounts don't match the fee split")]
+    InvalidFeeSplitDestination,
+    #[msg("Missing token receiver account")]
+    MissingTokenReceiverAccount,
+    #[msg("The referrer share can't exceed 100%")]
+    InvalidInputReferrerShare,
+    #[msg("The referrer doesn't refer on this market or isn't bound to the account")]
+    InvalidReferrer,
+    #[msg("The account is already bound to a referrer")]
+    ReferrerAlreadyBound,
+    #[msg("The referral of the open orders account must be closed first")]
+    OpenOrdersReferralNotClosed,
+    #[msg("The access gate needs a kind and, for credential tokens, a mint")]
+    InvalidInputAccessGate,
+    #[msg("The access gate or access proof account is missing")]
+    MissingAccessProof,
+    #[msg("The access proof doesn't match the access gate or the owner")]
+    InvalidAccessProof,
+    #[msg("The open orders account wasn't granted access to the market")]
+    OpenOrdersAccessDenied,
+    #[msg("The account size doesn't fit a supported event heap or book side capacity")]
+    InvalidInputCapacity,
+    #[msg("The open orders account can't be migrated before its fill events are consumed")]
+    OpenOrdersAccountNotMigratable,
+    #[msg("The open orders account of a maker with market maker protection is missing")]
+    MmpMakerAccountMissing,
+    #[msg("The open orders account has delegates, its delegates account must be passed")]
+    MissingOpenOrdersDelegates,
+    #[msg("The delegates account of the open orders account must be closed first")]
+    OpenOrdersDelegatesNotClosed,
+    #[msg("The open orders account is bound to a referrer, its referral must be passed")]
+    MissingOpenOrdersReferral,
+    #[msg("Market maker protection was triggered, the owner must reset it first")]
+    MmpFrozen,
+    #[msg("The current timestamp doesn't fit the u32 timestamp of resting orders")]
+    OrderTimestampOverflow,
 }
 
 impl From<OpenBookError> for ProgramError {
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, AccessPassLog};
+
+pub fn add_access_pass(ctx: Context<AddAccessPass>) -> Result<()> {
+    let access_pass = &mut ctx.accounts.access_pass;
+    access_pass.bump = ctx.bumps.access_pass;
+    access_pass.market = ctx.accounts.access_gate.market;
+    access_pass.owner = ctx.accounts.owner.key();
+
+    emit_stack(AccessPassLog {
+        market: access_pass.market,
+        owner: access_pass.owner,
+        allowed: true,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::logs::{emit_stack, AddDelegateLog};
+use crate::state::{DelegateEntry, DelegatePermissions, DelegateScope, Side};
+
+pub fn add_delegate(
+    ctx: Context<AddDelegate>,
+    scope: DelegateScope,
+    allowed_side: Option<Side>,
+    max_quote_native_per_order: u64,
+    expiry_timestamp: u64,
+) -> Result<()> {
+    let delegate = ctx.accounts.delegate_account.key();
+    require_keys_neq!(
+        delegate,
+        ctx.accounts.owner.key(),
+        OpenBookError::InvalidDelegate
+    );
+
+    let account = ctx.accounts.open_orders_account.load()?;
+    let mut delegates = ctx.accounts.open_orders_delegates.load_mut()?;
+
+    // A key is either the delegate set with `set_delegate` or in the list, never both
+    require!(
+        account.delegate != delegate && delegates.find(delegate).is_none(),
+        OpenBookError::DelegateAlreadyAdded
+    );
+    let permissions = DelegatePermissions::new(
+        scope,
+        allowed_side,
+        max_quote_native_per_order,
+        expiry_timestamp,
+    );
+    *delegates
+        .free_entry_mut()
+        .ok_or(OpenBookError::DelegateListFull)? = DelegateEntry {
+        delegate: Some(delegate).into(),
+        permissions,
+    };
+
+    emit_stack(AddDelegateLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        delegate,
+        scope: scope.into(),
+        allowed_side: allowed_side.map(|side| side.into()),
+        max_quote_native_per_order,
+        expiry_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, BindReferrerLog};
+use crate::state::*;
+
+pub fn bind_referrer(ctx: Context<BindReferrer>) -> Result<()> {
+    let referrer = &mut ctx.accounts.referrer;
+
+    let mut referral = ctx.accounts.open_orders_referral.load_init()?;
+    referral.open_orders_account = ctx.accounts.open_orders_account.key();
+    referral.referrer = referrer.key();
+    referral.share_bps = referrer.share_bps;
+    referral.bump = ctx.bumps.open_orders_referral;
+
+    ctx.accounts
+        .open_orders_account
+        .load_mut()?
+        .set_flag(OPEN_ORDERS_FLAG_REFERRAL, true);
+
+    referrer.referred_accounts += 1;
+
+    emit_stack(BindReferrerLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        referrer: referrer.key(),
+        share_bps: referrer.share_bps,
+    });
+
+    Ok(())
+}
@@ -14,8 +14,18 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
     mut orders: Vec<Order>,
     limit: u8,
 ) -> Result<Vec<Option<u128>>> {
+    let delegate_permissions = ctx.accounts.open_orders_account.check_owner_or_delegate(
+        ctx.accounts.signer.key(),
+        DelegateScope::Trade,
+        ctx.remaining_accounts,
+    )?;
     let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
     let open_orders_account_pk = ctx.accounts.open_orders_account.key();
+    let mut referral = OpenOrdersReferral::find_in(
+        &open_orders_account,
+        open_orders_account_pk,
+        ctx.remaining_accounts,
+    )?;
 
     let clock = Clock::get()?;
 
@@ -24,12 +34,21 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
         !market.is_expired(clock.unix_timestamp),
         OpenBookError::MarketHasExpired
     );
+    if cancel {
+        market.check_can_cancel_order()?;
+    }
+    if !orders.is_empty() {
+        open_orders_account.check_access(&market, ctx.remaining_accounts)?;
+    }
+    for order in orders.iter() {
+        market.check_can_place_order(order.is_post_only())?;
+    }
 
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
     };
-    let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+    let mut event_heap = ctx.accounts.event_heap.load_dyn_mut()?;
     let event_heap_size_before = event_heap.len();
 
     let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
@@ -76,6 +95,10 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
             }
         }
 
+        if let Some(permissions) = delegate_permissions {
+            permissions.check_order(order, now_ts, oracle_price_lots, &book, &market)?;
+        }
+
         let OrderWithAmounts {
             order_id,
             total_base_taken_native,
@@ -84,6 +107,7 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
             posted_quote_native,
             taker_fees,
             maker_fees,
+            referrer_amount,
             ..
         } = book.new_order(
             order,
@@ -98,6 +122,15 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
             ctx.remaining_accounts,
         )?;
 
+        if let Some(referral) = &mut referral {
+            referral.credit_take(
+                &mut open_orders_account.position,
+                &market,
+                referrer_amount,
+                total_quote_taken_native,
+            );
+        }
+
         match order.side {
             Side::Bid => {
                 quote_amount = quote_amount
@@ -132,6 +165,7 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
 
     if event_heap.len() > event_heap_size_before {
         position.penalty_heap_count += 1;
+        position.penalty_lamports_owed += market.penalty_event_heap();
     }
 
     token_transfer(
@@ -6,6 +6,11 @@ use crate::state::*;
 
 pub fn cancel_order(ctx: Context<CancelOrder>, order_id: u128) -> Result<()> {
     require_gt!(order_id, 0, OpenBookError::InvalidInputOrderId);
+    ctx.accounts.open_orders_account.check_owner_or_delegate(
+        ctx.accounts.signer.key(),
+        DelegateScope::Cancel,
+        ctx.remaining_accounts,
+    )?;
 
     let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
     let oo = open_orders_account
@@ -18,9 +23,10 @@ pub fn cancel_order(ctx: Context<CancelOrder>, order_id: u128) -> Result<()> {
     let order_side_and_tree = oo.side_and_tree();
 
     let market = ctx.accounts.market.load()?;
+    market.check_can_cancel_order()?;
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
     };
 
     book.cancel_order(
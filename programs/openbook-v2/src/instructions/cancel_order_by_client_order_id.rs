@@ -7,12 +7,19 @@ pub fn cancel_order_by_client_order_id(
     ctx: Context<CancelOrder>,
     client_order_id: u64,
 ) -> Result<i64> {
+    ctx.accounts.open_orders_account.check_owner_or_delegate(
+        ctx.accounts.signer.key(),
+        DelegateScope::Cancel,
+        ctx.remaining_accounts,
+    )?;
+
     let mut account = ctx.accounts.open_orders_account.load_mut()?;
 
     let market = ctx.accounts.market.load()?;
+    market.check_can_cancel_order()?;
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
     };
 
     book.cancel_all_orders(&mut account, *market, u8::MAX, None, Some(client_order_id))
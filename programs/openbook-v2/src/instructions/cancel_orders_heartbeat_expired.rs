@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::state::*;
+
+pub fn cancel_orders_heartbeat_expired(
+    ctx: Context<CancelOrdersHeartbeatExpired>,
+    limit: u8,
+) -> Result<()> {
+    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+    let market = ctx.accounts.market.load()?;
+    market.check_can_cancel_order()?;
+
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    require!(
+        account.is_heartbeat_expired(now_ts),
+        OpenBookError::HeartbeatNotExpired
+    );
+
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
+    };
+
+    book.cancel_all_orders(&mut account, *market, limit, None, None)?;
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, ClaimReferrerRebatesLog};
+use crate::state::*;
+use crate::token_utils::*;
+
+pub fn claim_referrer_rebates(ctx: Context<ClaimReferrerRebates>) -> Result<()> {
+    let mut referral = ctx.accounts.open_orders_referral.load_mut()?;
+    let mut market = ctx.accounts.market.load_mut()?;
+    let referrer = &mut ctx.accounts.referrer;
+
+    let (amount, volume) = referral.claim(referrer, &mut market);
+
+    let seeds = market_seeds!(market, ctx.accounts.market.key());
+    drop(market);
+
+    token_transfer_signed(
+        amount,
+        &ctx.accounts.token_program,
+        &ctx.accounts.market_quote_vault,
+        &ctx.accounts.referrer_token_account,
+        &ctx.accounts.market_authority,
+        seeds,
+    )?;
+
+    emit_stack(ClaimReferrerRebatesLog {
+        referrer: referrer.key(),
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        amount,
+        referred_taker_volume: volume,
+    });
+
+    Ok(())
+}
@@ -12,12 +12,12 @@ pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
     require!(market.is_empty(), OpenBookError::NonEmptyMarket);
 
     let book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
     };
     require!(book.is_empty(), OpenBookError::BookContainsElements);
 
-    let event_heap = ctx.accounts.event_heap.load()?;
+    let event_heap = ctx.accounts.event_heap.load_dyn()?;
     require!(
         event_heap.is_empty(),
         OpenBookError::EventHeapContainsElements
@@ -11,6 +11,15 @@ pub fn close_open_orders_account(ctx: Context<CloseOpenOrdersAccount>) -> Result
             .is_empty(open_orders_account.version),
         OpenBookError::NonEmptyOpenOrdersPosition
     );
+    require!(
+        !open_orders_account.has_delegates(),
+        OpenBookError::OpenOrdersDelegatesNotClosed
+    );
+
+    require!(
+        !open_orders_account.has_referral(),
+        OpenBookError::OpenOrdersReferralNotClosed
+    );
 
     let indexer = &mut ctx.accounts.open_orders_indexer;
     let index = indexer
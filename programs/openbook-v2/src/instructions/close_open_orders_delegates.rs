@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, RemoveDelegateLog, SetDelegateLog};
+use crate::pubkey_option::NonZeroPubkeyOption;
+use crate::state::{DelegateScope, OPEN_ORDERS_FLAG_DELEGATES};
+
+pub fn close_open_orders_delegates(ctx: Context<CloseOpenOrdersDelegates>) -> Result<()> {
+    let open_orders_account_pk = ctx.accounts.open_orders_account.key();
+    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+    let delegates = ctx.accounts.open_orders_delegates.load()?;
+
+    for entry in delegates.entries.iter() {
+        if let Some(delegate) = Option::<Pubkey>::from(entry.delegate) {
+            emit_stack(RemoveDelegateLog {
+                open_orders_account: open_orders_account_pk,
+                delegate,
+            });
+        }
+    }
+
+    // Without its limits the delegate would become unrestricted, it's removed as well
+    if account.delegate.is_some() {
+        account.delegate = NonZeroPubkeyOption::default();
+        emit_stack(SetDelegateLog {
+            open_orders_account: open_orders_account_pk,
+            delegate: None,
+            scope: DelegateScope::Full.into(),
+            allowed_side: None,
+            max_quote_native_per_order: 0,
+            expiry_timestamp: 0,
+        });
+    }
+    account.set_flag(OPEN_ORDERS_FLAG_DELEGATES, false);
+
+    Ok(())
+}
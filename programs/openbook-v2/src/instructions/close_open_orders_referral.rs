@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, ClaimReferrerRebatesLog};
+use crate::state::*;
+use crate::token_utils::*;
+
+pub fn close_open_orders_referral(ctx: Context<CloseOpenOrdersReferral>) -> Result<()> {
+    let mut referral = ctx.accounts.open_orders_referral.load_mut()?;
+    let mut market = ctx.accounts.market.load_mut()?;
+    let referrer = &mut ctx.accounts.referrer;
+
+    // The referrer is paid what it earned so far, it can't keep the account from closing
+    let (amount, volume) = referral.claim(referrer, &mut market);
+    referrer.referred_accounts -= 1;
+
+    let seeds = market_seeds!(market, ctx.accounts.market.key());
+    drop(market);
+
+    token_transfer_signed(
+        amount,
+        &ctx.accounts.token_program,
+        &ctx.accounts.market_quote_vault,
+        &ctx.accounts.referrer_token_account,
+        &ctx.accounts.market_authority,
+        seeds,
+    )?;
+
+    emit_stack(ClaimReferrerRebatesLog {
+        referrer: referrer.key(),
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        amount,
+        referred_taker_volume: volume,
+    });
+
+    ctx.accounts
+        .open_orders_account
+        .load_mut()?
+        .set_flag(OPEN_ORDERS_FLAG_REFERRAL, false);
+
+    Ok(())
+}
@@ -3,6 +3,7 @@ use bytemuck::cast_ref;
 use itertools::Itertools;
 
 use crate::error::OpenBookError;
+use crate::logs::{emit_stack, CrankRewardLog};
 use crate::state::*;
 
 use crate::accounts_ix::*;
@@ -40,14 +41,73 @@ pub fn consume_events<'c: 'info, 'info>(
     limit: usize,
     slots: Option<Vec<usize>>,
 ) -> Result<()> {
-    let limit = std::cmp::min(limit, MAX_EVENTS_CONSUME);
+    let mut market = ctx.accounts.market.load_mut()?;
+    let mut event_heap = ctx.accounts.event_heap.load_dyn_mut()?;
+    process_events(
+        &mut market,
+        &mut event_heap,
+        ctx.remaining_accounts,
+        limit,
+        slots,
+    )?;
+    Ok(())
+}
 
+pub fn consume_events_with_reward<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ConsumeEventsWithReward>,
+    limit: usize,
+    slots: Option<Vec<usize>>,
+) -> Result<()> {
     let mut market = ctx.accounts.market.load_mut()?;
-    let mut event_heap = ctx.accounts.event_heap.load_mut()?;
-    let remaining_accs = &ctx.remaining_accounts;
+    let mut event_heap = ctx.accounts.event_heap.load_dyn_mut()?;
+    let consumed_events = process_events(
+        &mut market,
+        &mut event_heap,
+        ctx.remaining_accounts,
+        limit,
+        slots,
+    )?;
+
+    let reward = market.crank_reward(consumed_events);
+    if reward > 0 {
+        market.penalty_lamports_accrued -= reward;
+        let penalty_lamports_accrued = market.penalty_lamports_accrued;
+        drop(market);
+
+        let receiver = &ctx.accounts.crank_reward_receiver;
+        **ctx.accounts.market.as_ref().try_borrow_mut_lamports()? -= reward;
+        **receiver.try_borrow_mut_lamports()? += reward;
+
+        emit_stack(CrankRewardLog {
+            market: ctx.accounts.market.key(),
+            receiver: receiver.key(),
+            events: consumed_events as u32,
+            lamports: reward,
+            penalty_lamports_accrued,
+        });
+    }
+
+    Ok(())
+}
+
+/// Consumes up to `limit` events, the ones in `slots` first. Returns the number of
+/// consumed events.
+fn process_events<'c: 'info, 'info>(
+    market: &mut Market,
+    event_heap: &mut DynEventHeap,
+    remaining_accs: &'c [AccountInfo<'info>],
+    limit: usize,
+    slots: Option<Vec<usize>>,
+) -> Result<usize> {
+    let limit = std::cmp::min(limit, MAX_EVENTS_CONSUME);
+
+    let slots = slots.unwrap_or_default();
+    require!(
+        slots.iter().all(|slot| *slot < event_heap.capacity()),
+        OpenBookError::InvalidInputHeapSlots
+    );
 
     let slots_to_consume = slots
-        .unwrap_or_default()
         .into_iter()
         .filter(|slot| !event_heap.nodes[*slot].is_free())
         .chain(event_heap.iter().map(|(_event, slot)| slot))
@@ -55,6 +115,7 @@ pub fn consume_events<'c: 'info, 'info>(
         .take(limit)
         .collect_vec();
 
+    let mut consumed_events = 0;
     for slot in slots_to_consume {
         let event = event_heap.at_slot(slot).unwrap();
 
@@ -62,7 +123,7 @@ pub fn consume_events<'c: 'info, 'info>(
             EventType::Fill => {
                 let fill: &FillEvent = cast_ref(event);
                 load_open_orders_account!(maker, fill.maker, remaining_accs);
-                maker.execute_maker(&mut market, fill);
+                maker.execute_maker(market, fill);
             }
             EventType::Out => {
                 let out: &OutEvent = cast_ref(event);
@@ -73,7 +134,8 @@ pub fn consume_events<'c: 'info, 'info>(
 
         // consume this event
         event_heap.delete_slot(slot)?;
+        consumed_events += 1;
     }
 
-    Ok(())
+    Ok(consumed_events)
 }
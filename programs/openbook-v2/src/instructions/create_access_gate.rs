@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::OpenBookError;
+use crate::logs::{emit_stack, AccessGateLog};
+use crate::state::*;
+
+pub fn create_access_gate(
+    ctx: Context<CreateAccessGate>,
+    kind: AccessGate,
+    admin: Pubkey,
+    credential_mint: Pubkey,
+) -> Result<()> {
+    match kind {
+        AccessGate::None => return Err(OpenBookError::InvalidInputAccessGate.into()),
+        AccessGate::Allowlist => {}
+        AccessGate::CredentialToken => require!(
+            credential_mint != Pubkey::default(),
+            OpenBookError::InvalidInputAccessGate
+        ),
+    }
+
+    let access_gate = &mut ctx.accounts.access_gate;
+    access_gate.bump = ctx.bumps.access_gate;
+    access_gate.market = ctx.accounts.market.key();
+    access_gate.admin = admin;
+    access_gate.kind = kind.into();
+    access_gate.credential_mint = credential_mint;
+
+    ctx.accounts.market.load_mut()?.access_gate = kind.into();
+
+    emit_stack(AccessGateLog {
+        market: ctx.accounts.market.key(),
+        admin,
+        kind: kind.into(),
+        credential_mint,
+    });
+
+    Ok(())
+}
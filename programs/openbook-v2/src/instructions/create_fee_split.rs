@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, FeeSplitLog};
+use crate::state::*;
+
+pub fn create_fee_split(
+    ctx: Context<CreateFeeSplit>,
+    destinations: Vec<FeeSplitDestination>,
+) -> Result<()> {
+    FeeSplit::validate(&destinations)?;
+    FeeSplit::validate_token_accounts(
+        &destinations,
+        ctx.remaining_accounts,
+        ctx.accounts.market.load()?.quote_mint,
+    )?;
+
+    let fee_split = &mut ctx.accounts.fee_split;
+    fee_split.bump = ctx.bumps.fee_split;
+    fee_split.market = ctx.accounts.market.key();
+    fee_split.destinations = destinations.clone();
+
+    ctx.accounts.market.load_mut()?.has_fee_split = 1;
+
+    emit_stack(FeeSplitLog {
+        market: ctx.accounts.market.key(),
+        destinations,
+    });
+
+    Ok(())
+}
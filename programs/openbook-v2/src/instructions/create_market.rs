@@ -18,6 +18,11 @@ pub fn create_market(
     maker_fee: i64,
     taker_fee: i64,
     time_expiry: i64,
+    auction_duration_secs: u32,
+    auction_interval_secs: u32,
+    min_base_lots_per_order: i64,
+    min_quote_notional: u64,
+    max_base_lots_per_order: i64,
 ) -> Result<()> {
     let registration_time = Clock::get()?.unix_timestamp;
 
@@ -39,6 +44,22 @@ pub fn create_market(
         OpenBookError::InvalidInputMarketExpired
     );
 
+    require!(
+        auction_interval_secs == 0 || auction_duration_secs > 0,
+        OpenBookError::InvalidInputAuction
+    );
+
+    require_gte!(
+        min_base_lots_per_order,
+        0,
+        OpenBookError::InvalidInputOrderSizeLimits
+    );
+    require!(
+        max_base_lots_per_order == 0
+            || (max_base_lots_per_order > 0 && max_base_lots_per_order >= min_base_lots_per_order),
+        OpenBookError::InvalidInputOrderSizeLimits
+    );
+
     require_gt!(quote_lot_size, 0, OpenBookError::InvalidInputLots);
     require_gt!(base_lot_size, 0, OpenBookError::InvalidInputLots);
 
@@ -98,17 +119,44 @@ pub fn create_market(
         quote_deposit_total: 0,
         fees_available: 0,
         referrer_rebates_accrued: 0,
+        auction_start_ts: if auction_duration_secs > 0 {
+            registration_time
+        } else {
+            0
+        },
+        auction_end_ts: if auction_duration_secs > 0 {
+            registration_time + auction_duration_secs as i64
+        } else {
+            0
+        },
+        auction_price_lots: 0,
+        auction_duration_secs,
+        auction_interval_secs,
+        market_status_admin: ctx.accounts.market_status_admin.non_zero_key(),
+        status: MarketStatus::Active.into(),
+        has_penalty_event_heap: 0,
+        padding2: Default::default(),
+        penalty_event_heap: 0,
+        min_base_lots_per_order,
+        min_quote_notional,
+        max_base_lots_per_order,
 
-        reserved: [0; 128],
+        time_set_expired: 0,
+        has_fee_split: 0,
+        access_gate: AccessGate::None.into(),
+        max_referrer_share_bps: 0,
+        crank_reward_per_event: 0,
+        penalty_lamports_accrued: 0,
+        reserved: [0; 8],
     };
 
     let mut orderbook = Orderbook {
-        bids: ctx.accounts.bids.load_init()?,
-        asks: ctx.accounts.asks.load_init()?,
+        bids: ctx.accounts.bids.load_dyn_init()?,
+        asks: ctx.accounts.asks.load_dyn_init()?,
     };
     orderbook.init();
 
-    let mut event_heap = ctx.accounts.event_heap.load_init()?;
+    let mut event_heap = ctx.accounts.event_heap.load_dyn_init()?;
     event_heap.init();
 
     emit_cpi!(MarketMetaDataLog {
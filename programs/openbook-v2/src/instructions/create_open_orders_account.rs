@@ -1,4 +1,5 @@
 use crate::accounts_ix::CreateOpenOrdersAccount;
+use crate::error::OpenBookError;
 use crate::pubkey_option::NonZeroKey;
 use crate::state::*;
 use crate::util::fill_from_str;
@@ -21,8 +22,24 @@ pub fn create_open_orders_account(
     account.bump = ctx.bumps.open_orders_account;
     account.owner = ctx.accounts.owner.key();
     account.delegate = ctx.accounts.delegate_account.non_zero_key();
-    account.version = 1;
+    account.version = OPEN_ORDERS_ACCOUNT_VERSION;
     account.open_orders = [OpenOrder::default(); MAX_OPEN_ORDERS];
 
+    if ctx.accounts.market.load()?.is_access_gated() {
+        let access_gate = ctx
+            .accounts
+            .access_gate
+            .as_ref()
+            .ok_or(OpenBookError::MissingAccessProof)?;
+        access_gate.check_proof(
+            account.owner,
+            ctx.accounts
+                .access_proof
+                .as_ref()
+                .map(|proof| proof.as_ref()),
+        )?;
+        account.access = OpenOrdersAccess::Granted.into();
+    }
+
     Ok(())
 }
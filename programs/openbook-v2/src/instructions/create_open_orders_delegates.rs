@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::state::{DelegatePermissions, OPEN_ORDERS_FLAG_DELEGATES};
+
+pub fn create_open_orders_delegates(ctx: Context<CreateOpenOrdersDelegates>) -> Result<()> {
+    let mut delegates = ctx.accounts.open_orders_delegates.load_init()?;
+    delegates.open_orders_account = ctx.accounts.open_orders_account.key();
+    delegates.bump = ctx.bumps.open_orders_delegates;
+    // The delegate keeps the permissions it had without a delegates account
+    delegates.delegate_permissions = DelegatePermissions::default();
+
+    ctx.accounts
+        .open_orders_account
+        .load_mut()?
+        .set_flag(OPEN_ORDERS_FLAG_DELEGATES, true);
+
+    Ok(())
+}
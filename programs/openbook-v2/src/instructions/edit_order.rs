@@ -16,6 +16,12 @@ pub fn edit_order<'c: 'info, 'info>(
         OpenBookError::InvalidInputCancelSize
     );
 
+    // Check before cancelling, the market status may not allow replacing the order
+    ctx.accounts
+        .market
+        .load()?
+        .check_can_place_order(order.is_post_only())?;
+
     let leaf_node_quantity = crate::instructions::cancel_order_by_client_order_id(
         Context::new(
             ctx.program_id,
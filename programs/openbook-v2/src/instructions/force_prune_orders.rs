@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::state::*;
+
+pub fn force_prune_orders(ctx: Context<ForcePruneOrders>, limit: u8) -> Result<()> {
+    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+    let market = ctx.accounts.market.load()?;
+
+    market.check_can_force_close(ctx.accounts.signer.key(), Clock::get()?.unix_timestamp)?;
+
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
+    };
+
+    book.cancel_all_orders(&mut account, *market, limit, None, None)?;
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::instructions::{take_settled_funds, SettledFunds};
+use crate::logs::{emit_stack, SettleFundsLog};
+use crate::state::*;
+use crate::token_utils::*;
+
+pub fn force_settle_funds(ctx: Context<ForceSettleFunds>) -> Result<()> {
+    let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
+    let mut market = ctx.accounts.market.load_mut()?;
+
+    market.check_can_force_close(ctx.accounts.signer.key(), Clock::get()?.unix_timestamp)?;
+
+    // There is no referrer, the rebates go to the market fees
+    let SettledFunds {
+        base_native,
+        quote_native,
+        referrer_rebate,
+    } = take_settled_funds(&mut open_orders_account, &mut market, false);
+
+    let seeds = market_seeds!(market, ctx.accounts.market.key());
+
+    drop(market);
+
+    // Penalties stay owed: nobody but the owner should have to pay them
+
+    token_transfer_signed(
+        base_native,
+        &ctx.accounts.token_program,
+        &ctx.accounts.market_base_vault,
+        &ctx.accounts.user_base_account,
+        &ctx.accounts.market_authority,
+        seeds,
+    )?;
+
+    token_transfer_signed(
+        quote_native,
+        &ctx.accounts.token_program,
+        &ctx.accounts.market_quote_vault,
+        &ctx.accounts.user_quote_account,
+        &ctx.accounts.market_authority,
+        seeds,
+    )?;
+
+    emit_stack(SettleFundsLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        base_native,
+        quote_native,
+        referrer_rebate,
+        referrer: None,
+    });
+
+    Ok(())
+}
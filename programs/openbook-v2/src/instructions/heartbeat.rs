@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, HeartbeatLog};
+use crate::state::*;
+
+pub fn heartbeat(ctx: Context<Heartbeat>, timeout: u64) -> Result<()> {
+    ctx.accounts.open_orders_account.check_owner_or_delegate(
+        ctx.accounts.signer.key(),
+        DelegateScope::Trade,
+        ctx.remaining_accounts,
+    )?;
+
+    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+
+    // A timeout of zero disables the heartbeat. Deadlines are stored on the resting
+    // orders as u32 timestamps.
+    let deadline = if timeout == 0 {
+        0
+    } else {
+        let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+        now_ts.saturating_add(timeout).min(u32::MAX.into())
+    };
+    account.position.heartbeat_deadline = deadline;
+
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
+    };
+    book.update_resting_orders(&account, |leaf| leaf.heartbeat_deadline = deadline as u32);
+
+    emit_stack(HeartbeatLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        deadline,
+    });
+
+    Ok(())
+}
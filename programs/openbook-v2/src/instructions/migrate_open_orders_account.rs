@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, OpenOrdersPositionLog};
+use crate::state::*;
+
+pub fn migrate_open_orders_account(ctx: Context<MigrateOpenOrdersAccount>) -> Result<()> {
+    let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
+    let market = ctx.accounts.market.load()?;
+    let bids = ctx.accounts.bids.load_dyn()?;
+
+    if open_orders_account.migrate(&market, &bids)? {
+        let pa = &open_orders_account.position;
+        emit_stack(OpenOrdersPositionLog {
+            owner: open_orders_account.owner,
+            open_orders_account_num: open_orders_account.account_num,
+            market: open_orders_account.market,
+            bids_base_lots: pa.bids_base_lots,
+            bids_quote_lots: pa.bids_quote_lots,
+            asks_base_lots: pa.asks_base_lots,
+            base_free_native: pa.base_free_native,
+            quote_free_native: pa.quote_free_native,
+            locked_maker_fees: pa.locked_maker_fees,
+            referrer_rebates_available: pa.referrer_rebates_available,
+            maker_volume: pa.maker_volume,
+            taker_volume: pa.taker_volume,
+        });
+    }
+
+    Ok(())
+}
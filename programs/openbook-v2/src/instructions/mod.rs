@@ -1,48 +1,102 @@
+pub use add_access_pass::*;
+pub use add_delegate::*;
+pub use bind_referrer::*;
 pub use cancel_all_and_place_orders::*;
 pub use cancel_all_orders::*;
 pub use cancel_order::*;
 pub use cancel_order_by_client_order_id::*;
+pub use cancel_orders_heartbeat_expired::*;
+pub use claim_referrer_rebates::*;
 pub use close_market::*;
 pub use close_open_orders_account::*;
+pub use close_open_orders_delegates::*;
 pub use close_open_orders_indexer::*;
+pub use close_open_orders_referral::*;
 pub use consume_events::*;
+pub use create_access_gate::*;
+pub use create_fee_split::*;
 pub use create_market::*;
 pub use create_open_orders_account::*;
+pub use create_open_orders_delegates::*;
 pub use create_open_orders_indexer::*;
 pub use deposit::*;
 pub use edit_order::*;
+pub use force_prune_orders::*;
+pub use force_settle_funds::*;
+pub use heartbeat::*;
+pub use migrate_open_orders_account::*;
 pub use place_order::*;
 pub use place_take_order::*;
 pub use prune_orders::*;
+pub use register_referrer::*;
+pub use remove_access_pass::*;
+pub use remove_delegate::*;
+pub use run_auction::*;
+pub use set_crank_rewards::*;
 pub use set_delegate::*;
+pub use set_fee_split::*;
 pub use set_market_expired::*;
+pub use set_market_status::*;
+pub use set_market_status_admin::*;
+pub use set_max_referrer_share::*;
+pub use set_mmp::*;
+pub use set_open_orders_access::*;
+pub use set_referrer_share::*;
 pub use settle_funds::*;
 pub use settle_funds_expired::*;
+pub use shrink_market_accounts::*;
 pub use stub_oracle_close::*;
 pub use stub_oracle_create::*;
 pub use stub_oracle_set::*;
 pub use sweep_fees::*;
 
+mod add_access_pass;
+mod add_delegate;
+mod bind_referrer;
 mod cancel_all_and_place_orders;
 mod cancel_all_orders;
 mod cancel_order;
 mod cancel_order_by_client_order_id;
+mod cancel_orders_heartbeat_expired;
+mod claim_referrer_rebates;
 mod close_market;
 mod close_open_orders_account;
+mod close_open_orders_delegates;
 mod close_open_orders_indexer;
+mod close_open_orders_referral;
 mod consume_events;
+mod create_access_gate;
+mod create_fee_split;
 mod create_market;
 mod create_open_orders_account;
+mod create_open_orders_delegates;
 mod create_open_orders_indexer;
 mod deposit;
 mod edit_order;
+mod force_prune_orders;
+mod force_settle_funds;
+mod heartbeat;
+mod migrate_open_orders_account;
 mod place_order;
 mod place_take_order;
 mod prune_orders;
+mod register_referrer;
+mod remove_access_pass;
+mod remove_delegate;
+mod run_auction;
+mod set_crank_rewards;
 mod set_delegate;
+mod set_fee_split;
 mod set_market_expired;
+mod set_market_status;
+mod set_market_status_admin;
+mod set_max_referrer_share;
+mod set_mmp;
+mod set_open_orders_access;
+mod set_referrer_share;
 mod settle_funds;
 mod settle_funds_expired;
+mod shrink_market_accounts;
 mod stub_oracle_close;
 mod stub_oracle_create;
 mod stub_oracle_set;
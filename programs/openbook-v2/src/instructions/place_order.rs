@@ -20,8 +20,18 @@ pub fn place_order<'c: 'info, 'info>(
         OpenBookError::InvalidInputLots
     );
 
+    let delegate_permissions = ctx.accounts.open_orders_account.check_owner_or_delegate(
+        ctx.accounts.signer.key(),
+        DelegateScope::Trade,
+        ctx.remaining_accounts,
+    )?;
     let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
     let open_orders_account_pk = ctx.accounts.open_orders_account.key();
+    let mut referral = OpenOrdersReferral::find_in(
+        &open_orders_account,
+        open_orders_account_pk,
+        ctx.remaining_accounts,
+    )?;
 
     let clock = Clock::get()?;
 
@@ -35,12 +45,14 @@ pub fn place_order<'c: 'info, 'info>(
         !market.is_expired(clock.unix_timestamp),
         OpenBookError::MarketHasExpired
     );
+    market.check_can_place_order(order.is_post_only())?;
+    open_orders_account.check_access(&market, ctx.remaining_accounts)?;
 
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
     };
-    let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+    let mut event_heap = ctx.accounts.event_heap.load_dyn_mut()?;
     let event_heap_size_before = event_heap.len();
 
     let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
@@ -51,6 +63,10 @@ pub fn place_order<'c: 'info, 'info>(
         clock.slot,
     )?;
 
+    if let Some(permissions) = delegate_permissions {
+        permissions.check_order(&order, now_ts, oracle_price_lots, &book, &market)?;
+    }
+
     let OrderWithAmounts {
         order_id,
         total_base_taken_native,
@@ -59,6 +75,7 @@ pub fn place_order<'c: 'info, 'info>(
         posted_quote_native,
         taker_fees,
         maker_fees,
+        referrer_amount,
         ..
     } = book.new_order(
         &order,
@@ -73,6 +90,15 @@ pub fn place_order<'c: 'info, 'info>(
         ctx.remaining_accounts,
     )?;
 
+    if let Some(referral) = &mut referral {
+        referral.credit_take(
+            &mut open_orders_account.position,
+            &market,
+            referrer_amount,
+            total_quote_taken_native,
+        );
+    }
+
     let position = &mut open_orders_account.position;
     let deposit_amount = match order.side {
         Side::Bid => {
@@ -107,6 +133,7 @@ pub fn place_order<'c: 'info, 'info>(
 
     if event_heap.len() > event_heap_size_before {
         position.penalty_heap_count += 1;
+        position.penalty_lamports_owed += market.penalty_event_heap();
     }
 
     token_transfer(
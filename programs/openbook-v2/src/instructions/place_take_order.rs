@@ -3,6 +3,7 @@ use anchor_lang::prelude::*;
 use crate::accounts_ix::*;
 use crate::accounts_zerocopy::AccountInfoRef;
 use crate::error::*;
+use crate::logs::{emit_stack, PenaltyLog};
 use crate::state::*;
 use crate::token_utils::*;
 
@@ -26,13 +27,23 @@ pub fn place_take_order<'c: 'info, 'info>(
         !market.is_expired(clock.unix_timestamp),
         OpenBookError::MarketHasExpired
     );
+    // Take orders never post, so they can't be allowed by a post only market
+    market.check_can_place_order(false)?;
+    // Take orders have no open orders account, the signer proves its access with each order
+    if market.is_access_gated() {
+        MarketAccessGate::check_proof_in(
+            ctx.accounts.market.key(),
+            ctx.accounts.signer.key(),
+            ctx.remaining_accounts,
+        )?;
+    }
 
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
     };
 
-    let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+    let mut event_heap = ctx.accounts.event_heap.load_dyn_mut()?;
     let event_heap_size_before = event_heap.len();
 
     let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
@@ -82,17 +93,32 @@ pub fn place_take_order<'c: 'info, 'info>(
         }
     };
 
+    let penalty = if event_heap.len() > event_heap_size_before {
+        market.penalty_event_heap()
+    } else {
+        0
+    };
+    market.penalty_lamports_accrued += penalty;
+    let penalty_lamports_accrued = market.penalty_lamports_accrued;
+
     let seeds = market_seeds!(market, ctx.accounts.market.key());
 
     drop(market);
 
-    if event_heap.len() > event_heap_size_before {
+    if penalty > 0 {
         system_program_transfer(
-            PENALTY_EVENT_HEAP,
+            penalty,
             &ctx.accounts.system_program,
             &ctx.accounts.penalty_payer,
             &ctx.accounts.market,
         )?;
+
+        emit_stack(PenaltyLog {
+            market: ctx.accounts.market.key(),
+            penalty_payer: ctx.accounts.penalty_payer.key(),
+            lamports: penalty,
+            penalty_lamports_accrued,
+        });
     }
 
     let (user_deposit_acc, user_withdraw_acc, market_deposit_acc, market_withdraw_acc) = match side
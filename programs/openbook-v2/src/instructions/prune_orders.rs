@@ -14,8 +14,8 @@ pub fn prune_orders(ctx: Context<PruneOrders>, limit: u8) -> Result<()> {
     );
 
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
     };
 
     book.cancel_all_orders(&mut account, *market, limit, None, None)?;
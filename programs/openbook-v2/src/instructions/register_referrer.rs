@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::logs::{emit_stack, ReferrerLog};
+use crate::state::*;
+
+pub fn register_referrer(
+    ctx: Context<RegisterReferrer>,
+    market: Pubkey,
+    share_bps: u16,
+) -> Result<()> {
+    require_gte!(
+        REFERRER_SHARE_TOTAL_BPS,
+        share_bps,
+        OpenBookError::InvalidInputReferrerShare
+    );
+
+    let referrer = &mut ctx.accounts.referrer;
+    referrer.bump = ctx.bumps.referrer;
+    referrer.authority = ctx.accounts.authority.key();
+    referrer.market = market;
+    referrer.share_bps = share_bps;
+
+    emit_stack(ReferrerLog {
+        referrer: referrer.key(),
+        authority: referrer.authority,
+        market,
+        share_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, AccessPassLog};
+
+pub fn remove_access_pass(ctx: Context<RemoveAccessPass>) -> Result<()> {
+    emit_stack(AccessPassLog {
+        market: ctx.accounts.access_pass.market,
+        owner: ctx.accounts.access_pass.owner,
+        allowed: false,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::logs::{emit_stack, RemoveDelegateLog};
+use crate::state::DelegateEntry;
+
+pub fn remove_delegate(ctx: Context<RemoveDelegate>) -> Result<()> {
+    let delegate = ctx.accounts.delegate_account.key();
+
+    let mut delegates = ctx.accounts.open_orders_delegates.load_mut()?;
+    *delegates
+        .find_mut(delegate)
+        .ok_or(OpenBookError::DelegateNotFound)? = DelegateEntry::default();
+
+    emit_stack(RemoveDelegateLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        delegate,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::AccountInfoRef;
+use crate::error::*;
+use crate::logs::{emit_stack, RunAuctionLog};
+use crate::state::*;
+
+pub fn run_auction<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, RunAuction<'info>>,
+    limit: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let mut market = ctx.accounts.market.load_mut()?;
+    market.check_can_match_orders()?;
+    require!(
+        market.is_auction_ended(clock.unix_timestamp),
+        OpenBookError::AuctionNotEnded
+    );
+
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
+    };
+    let mut event_heap = ctx.accounts.event_heap.load_dyn_mut()?;
+
+    let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
+
+    let oracle_price_lots = market.oracle_price_lots(
+        AccountInfoRef::borrow_some(ctx.accounts.oracle_a.as_ref())?.as_ref(),
+        AccountInfoRef::borrow_some(ctx.accounts.oracle_b.as_ref())?.as_ref(),
+        clock.slot,
+    )?;
+
+    // Frozen makers don't take part in the auction, as takers would drop their orders
+    book.drop_mmp_frozen_crossing_orders(
+        &market,
+        now_ts,
+        oracle_price_lots,
+        ctx.remaining_accounts,
+    )?;
+
+    // The clearing price is fixed by the first call, so that an auction that needs
+    // several calls to match all crossing orders still fills them at a uniform price
+    if market.auction_price_lots == 0 {
+        match book.auction_clearing_price(now_ts, oracle_price_lots) {
+            Some((price_lots, _)) => market.auction_price_lots = price_lots,
+            None => {
+                market.end_auction_phase(clock.unix_timestamp);
+                emit_stack(RunAuctionLog {
+                    market: ctx.accounts.market.key(),
+                    price_lots: 0,
+                    base_lots: 0,
+                    completed: true,
+                });
+                return Ok(());
+            }
+        }
+    }
+
+    let price_lots = market.auction_price_lots;
+    let (base_lots, completed) = book.run_auction(
+        &mut market,
+        &mut event_heap,
+        price_lots,
+        now_ts,
+        oracle_price_lots,
+        limit,
+        ctx.remaining_accounts,
+    )?;
+
+    if completed {
+        market.end_auction_phase(clock.unix_timestamp);
+    }
+
+    emit_stack(RunAuctionLog {
+        market: ctx.accounts.market.key(),
+        price_lots,
+        base_lots,
+        completed,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, SetCrankRewardsLog};
+
+pub fn set_crank_rewards(
+    ctx: Context<SetCrankRewards>,
+    penalty_event_heap: Option<u32>,
+    crank_reward_per_event: u32,
+) -> Result<()> {
+    let mut market = ctx.accounts.market.load_mut()?;
+    market.has_penalty_event_heap = u8::from(penalty_event_heap.is_some());
+    market.penalty_event_heap = penalty_event_heap.unwrap_or_default();
+    market.crank_reward_per_event = crank_reward_per_event;
+
+    emit_stack(SetCrankRewardsLog {
+        market: ctx.accounts.market.key(),
+        penalty_event_heap: market.penalty_event_heap(),
+        crank_reward_per_event,
+    });
+
+    Ok(())
+}
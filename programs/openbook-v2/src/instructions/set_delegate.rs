@@ -1,10 +1,18 @@
 use anchor_lang::prelude::*;
 
 use crate::accounts_ix::*;
+use crate::error::*;
 use crate::logs::{emit_stack, SetDelegateLog};
 use crate::pubkey_option::NonZeroPubkeyOption;
+use crate::state::{DelegatePermissions, DelegateScope, Side};
 
-pub fn set_delegate(ctx: Context<SetDelegate>) -> Result<()> {
+pub fn set_delegate(
+    ctx: Context<SetDelegate>,
+    scope: DelegateScope,
+    allowed_side: Option<Side>,
+    max_quote_native_per_order: u64,
+    expiry_timestamp: u64,
+) -> Result<()> {
     let mut account = ctx.accounts.open_orders_account.load_mut()?;
 
     let delegate_account: NonZeroPubkeyOption = ctx
@@ -16,9 +24,50 @@ pub fn set_delegate(ctx: Context<SetDelegate>) -> Result<()> {
 
     account.delegate = delegate_account;
 
+    // Permissions of a previous delegate must not carry over
+    let (scope, allowed_side, max_quote_native_per_order, expiry_timestamp) =
+        if delegate_account.is_some() {
+            (
+                scope,
+                allowed_side,
+                max_quote_native_per_order,
+                expiry_timestamp,
+            )
+        } else {
+            (DelegateScope::Full, None, 0, 0)
+        };
+    let permissions = DelegatePermissions::new(
+        scope,
+        allowed_side,
+        max_quote_native_per_order,
+        expiry_timestamp,
+    );
+
+    match &ctx.accounts.open_orders_delegates {
+        Some(delegates) => {
+            let mut delegates = delegates.load_mut()?;
+            if let Some(delegate) = Option::<Pubkey>::from(delegate_account) {
+                require!(
+                    delegates.find(delegate).is_none(),
+                    OpenBookError::DelegateAlreadyAdded
+                );
+            }
+            delegates.delegate_permissions = permissions;
+        }
+        // Limits are kept in the delegates account, without one the delegate is unrestricted
+        None => require!(
+            !account.has_delegates() && permissions.is_unrestricted(),
+            OpenBookError::MissingOpenOrdersDelegates
+        ),
+    }
+
     emit_stack(SetDelegateLog {
         open_orders_account: ctx.accounts.open_orders_account.key(),
         delegate: delegate_account.into(),
+        scope: scope.into(),
+        allowed_side: allowed_side.map(|side| side.into()),
+        max_quote_native_per_order,
+        expiry_timestamp,
     });
 
     Ok(())
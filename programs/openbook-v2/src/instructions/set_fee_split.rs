@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, FeeSplitLog};
+use crate::state::*;
+
+pub fn set_fee_split(
+    ctx: Context<SetFeeSplit>,
+    destinations: Vec<FeeSplitDestination>,
+) -> Result<()> {
+    FeeSplit::validate(&destinations)?;
+    FeeSplit::validate_token_accounts(
+        &destinations,
+        ctx.remaining_accounts,
+        ctx.accounts.market.load()?.quote_mint,
+    )?;
+
+    ctx.accounts.fee_split.destinations = destinations.clone();
+
+    emit_stack(FeeSplitLog {
+        market: ctx.accounts.market.key(),
+        destinations,
+    });
+
+    Ok(())
+}
@@ -4,12 +4,11 @@ use anchor_lang::prelude::*;
 
 pub fn set_market_expired(ctx: Context<SetMarketExpired>) -> Result<()> {
     let mut market = ctx.accounts.market.load_mut()?;
-    require!(
-        !market.is_expired(Clock::get()?.unix_timestamp),
-        OpenBookError::MarketHasExpired
-    );
+    let now_ts = Clock::get()?.unix_timestamp;
+    require!(!market.is_expired(now_ts), OpenBookError::MarketHasExpired);
 
     market.time_expiry = -1;
+    market.time_set_expired = now_ts;
 
     Ok(())
 }
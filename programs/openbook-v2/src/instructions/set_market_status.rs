@@ -0,0 +1,16 @@
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, SetMarketStatusLog};
+use crate::state::MarketStatus;
+use anchor_lang::prelude::*;
+
+pub fn set_market_status(ctx: Context<SetMarketStatus>, status: MarketStatus) -> Result<()> {
+    let mut market = ctx.accounts.market.load_mut()?;
+    market.status = status.into();
+
+    emit_stack(SetMarketStatusLog {
+        market: ctx.accounts.market.key(),
+        status: status.into(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, SetMarketStatusAdminLog};
+use crate::pubkey_option::NonZeroKey;
+use anchor_lang::prelude::*;
+
+pub fn set_market_status_admin(ctx: Context<SetMarketStatusAdmin>) -> Result<()> {
+    let market_status_admin = ctx.accounts.market_status_admin.non_zero_key();
+
+    let mut market = ctx.accounts.market.load_mut()?;
+    market.market_status_admin = market_status_admin;
+
+    emit_stack(SetMarketStatusAdminLog {
+        market: ctx.accounts.market.key(),
+        market_status_admin: market_status_admin.into(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::state::*;
+
+pub fn set_max_referrer_share(ctx: Context<SetMaxReferrerShare>, share_bps: u16) -> Result<()> {
+    require_gte!(
+        REFERRER_SHARE_TOTAL_BPS,
+        share_bps,
+        OpenBookError::InvalidInputReferrerShare
+    );
+
+    ctx.accounts.market.load_mut()?.max_referrer_share_bps = share_bps;
+
+    Ok(())
+}
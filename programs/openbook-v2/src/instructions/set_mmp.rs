@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::logs::{emit_stack, MmpResetLog, SetMmpLog};
+use crate::state::*;
+
+pub fn set_mmp(
+    ctx: Context<SetMmp>,
+    window_secs: u16,
+    base_lots_limit: i64,
+    fill_count_limit: u16,
+) -> Result<()> {
+    ctx.accounts.open_orders_account.check_owner_or_delegate(
+        ctx.accounts.signer.key(),
+        DelegateScope::Trade,
+        ctx.remaining_accounts,
+    )?;
+
+    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+
+    // A triggered protection stays in place until the owner resets it, disabling it
+    // here would make the frozen orders fillable again
+    require!(!account.is_mmp_frozen(), OpenBookError::MmpFrozen);
+
+    // Start with a fresh window under the new settings
+    let pa = &mut account.position;
+    pa.mmp_window_secs = window_secs;
+    pa.mmp_base_lots_limit = base_lots_limit;
+    pa.mmp_fill_count_limit = fill_count_limit;
+    pa.mmp_window_start = 0;
+    pa.mmp_window_base_lots = 0;
+    pa.mmp_window_fill_count = 0;
+
+    // Takers need to know which resting orders have their fills counted
+    let mmp_enabled = account.is_mmp_enabled().into();
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_dyn_mut()?,
+        asks: ctx.accounts.asks.load_dyn_mut()?,
+    };
+    book.update_resting_orders(&account, |leaf| leaf.mmp_enabled = mmp_enabled);
+
+    emit_stack(SetMmpLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        window_secs,
+        base_lots_limit,
+        fill_count_limit,
+    });
+
+    Ok(())
+}
+
+pub fn reset_mmp(ctx: Context<ResetMmp>) -> Result<()> {
+    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+
+    let pa = &mut account.position;
+    pa.mmp_window_start = 0;
+    pa.mmp_window_base_lots = 0;
+    pa.mmp_window_fill_count = 0;
+    pa.mmp_frozen = 0;
+
+    emit_stack(MmpResetLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, OpenOrdersAccessLog};
+use crate::state::*;
+
+pub fn set_open_orders_access(ctx: Context<SetOpenOrdersAccess>, allowed: bool) -> Result<()> {
+    let access = if allowed {
+        OpenOrdersAccess::Granted
+    } else {
+        OpenOrdersAccess::Revoked
+    };
+    ctx.accounts.open_orders_account.load_mut()?.access = access.into();
+
+    emit_stack(OpenOrdersAccessLog {
+        market: ctx.accounts.access_gate.market,
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        access: access.into(),
+    });
+
+    Ok(())
+}
@@ -2,47 +2,61 @@ use anchor_lang::prelude::*;
 
 use crate::accounts_ix::*;
 use crate::logs::emit_stack;
-use crate::logs::SettleFundsLog;
+use crate::logs::{PenaltyLog, SettleFundsLog};
 use crate::state::*;
 use crate::token_utils::*;
 
 pub fn settle_funds<'info>(ctx: Context<'_, '_, '_, 'info, SettleFunds<'info>>) -> Result<()> {
+    ctx.accounts.open_orders_account.check_owner_or_delegate(
+        ctx.accounts.owner.key(),
+        DelegateScope::Settle,
+        ctx.remaining_accounts,
+    )?;
+    settle_funds_unchecked(ctx)
+}
+
+/// Settles the funds without checking the signer, which is up to the caller
+pub fn settle_funds_unchecked<'info>(
+    ctx: Context<'_, '_, '_, 'info, SettleFunds<'info>>,
+) -> Result<()> {
     let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
     let mut market = ctx.accounts.market.load_mut()?;
 
-    let mut roundoff_maker_fees = 0;
-
-    if market.maker_fee.is_positive() && open_orders_account.position.bids_base_lots == 0 {
-        roundoff_maker_fees = open_orders_account.position.locked_maker_fees;
-        open_orders_account.position.locked_maker_fees = 0;
-    }
+    let SettledFunds {
+        base_native,
+        quote_native,
+        referrer_rebate,
+    } = take_settled_funds(
+        &mut open_orders_account,
+        &mut market,
+        ctx.accounts.referrer_account.is_some(),
+    );
 
     let pa = &mut open_orders_account.position;
-    let referrer_rebate = pa.referrer_rebates_available + roundoff_maker_fees;
-
-    if ctx.accounts.referrer_account.is_some() {
-        market.fees_to_referrers += referrer_rebate as u128;
-        market.quote_deposit_total -= referrer_rebate;
-    } else {
-        market.fees_available += referrer_rebate;
-    }
-
-    market.base_deposit_total -= pa.base_free_native;
-    market.quote_deposit_total -= pa.quote_free_native;
-    market.referrer_rebates_accrued -= pa.referrer_rebates_available;
+    let penalty = pa.penalty_lamports_owed;
+    pa.penalty_heap_count = 0;
+    pa.penalty_lamports_owed = 0;
+    market.penalty_lamports_accrued += penalty;
+    let penalty_lamports_accrued = market.penalty_lamports_accrued;
 
     let seeds = market_seeds!(market, ctx.accounts.market.key());
 
     drop(market);
 
-    if pa.penalty_heap_count > 0 {
+    if penalty > 0 {
         system_program_transfer(
-            pa.penalty_heap_count * PENALTY_EVENT_HEAP,
+            penalty,
             &ctx.accounts.system_program,
             &ctx.accounts.penalty_payer,
             &ctx.accounts.market,
         )?;
-        pa.penalty_heap_count = 0;
+
+        emit_stack(PenaltyLog {
+            market: ctx.accounts.market.key(),
+            penalty_payer: ctx.accounts.penalty_payer.key(),
+            lamports: penalty,
+            penalty_lamports_accrued,
+        });
     }
 
     if let Some(referrer_account) = &ctx.accounts.referrer_account {
@@ -57,7 +71,7 @@ pub fn settle_funds<'info>(ctx: Context<'_, '_, '_, 'info, SettleFunds<'info>>)
     }
 
     token_transfer_signed(
-        pa.base_free_native,
+        base_native,
         &ctx.accounts.token_program,
         &ctx.accounts.market_base_vault,
         &ctx.accounts.user_base_account,
@@ -66,7 +80,7 @@ pub fn settle_funds<'info>(ctx: Context<'_, '_, '_, 'info, SettleFunds<'info>>)
     )?;
 
     token_transfer_signed(
-        pa.quote_free_native,
+        quote_native,
         &ctx.accounts.token_program,
         &ctx.accounts.market_quote_vault,
         &ctx.accounts.user_quote_account,
@@ -76,15 +90,58 @@ pub fn settle_funds<'info>(ctx: Context<'_, '_, '_, 'info, SettleFunds<'info>>)
 
     emit_stack(SettleFundsLog {
         open_orders_account: ctx.accounts.open_orders_account.key(),
-        base_native: pa.base_free_native,
-        quote_native: pa.quote_free_native,
+        base_native,
+        quote_native,
         referrer_rebate,
         referrer: ctx.accounts.referrer_account.as_ref().map(|acc| acc.key()),
     });
 
+    Ok(())
+}
+
+pub struct SettledFunds {
+    pub base_native: u64,
+    pub quote_native: u64,
+    pub referrer_rebate: u64,
+}
+
+/// Takes the free funds and referrer rebates out of the position and the market deposits.
+/// The rebates go to the referrer if there is one, and to the market fees otherwise.
+pub fn take_settled_funds(
+    open_orders_account: &mut OpenOrdersAccount,
+    market: &mut Market,
+    has_referrer: bool,
+) -> SettledFunds {
+    let mut roundoff_maker_fees = 0;
+
+    if market.maker_fee.is_positive() && open_orders_account.position.bids_base_lots == 0 {
+        roundoff_maker_fees = open_orders_account.position.locked_maker_fees;
+        open_orders_account.position.locked_maker_fees = 0;
+    }
+
+    let pa = &mut open_orders_account.position;
+    let referrer_rebate = pa.referrer_rebates_available + roundoff_maker_fees;
+
+    if has_referrer {
+        market.fees_to_referrers += referrer_rebate as u128;
+        market.quote_deposit_total -= referrer_rebate;
+    } else {
+        market.fees_available += referrer_rebate;
+    }
+
+    market.base_deposit_total -= pa.base_free_native;
+    market.quote_deposit_total -= pa.quote_free_native;
+    market.referrer_rebates_accrued -= pa.referrer_rebates_available;
+
+    let settled = SettledFunds {
+        base_native: pa.base_free_native,
+        quote_native: pa.quote_free_native,
+        referrer_rebate,
+    };
+
     pa.base_free_native = 0;
     pa.quote_free_native = 0;
     pa.referrer_rebates_available = 0;
 
-    Ok(())
+    settled
 }
@@ -13,7 +13,7 @@ pub fn settle_funds_expired<'info>(
         );
     }
 
-    crate::instructions::settle_funds(Context::new(
+    crate::instructions::settle_funds_unchecked(Context::new(
         ctx.program_id,
         &mut ctx.accounts.to_settle_funds(),
         ctx.remaining_accounts,
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::OpenBookError;
+use crate::state::*;
+
+pub fn shrink_market_accounts(ctx: Context<ShrinkMarketAccounts>) -> Result<()> {
+    let market = ctx.accounts.market.load()?;
+    require!(
+        market.is_expired(Clock::get()?.unix_timestamp),
+        OpenBookError::MarketHasNotExpired
+    );
+
+    {
+        let book = Orderbook {
+            bids: ctx.accounts.bids.load_dyn_mut()?,
+            asks: ctx.accounts.asks.load_dyn_mut()?,
+        };
+        require!(book.is_empty(), OpenBookError::BookContainsElements);
+
+        let event_heap = ctx.accounts.event_heap.load_dyn()?;
+        require!(
+            event_heap.is_empty(),
+            OpenBookError::EventHeapContainsElements
+        );
+    }
+
+    let sol_destination = ctx.accounts.sol_destination.as_ref();
+    for book_side in [&ctx.accounts.bids, &ctx.accounts.asks] {
+        let space = BookSide::space(MIN_ORDERTREE_NODES);
+        if shrink_account(book_side.as_ref(), space, sol_destination)? {
+            book_side.load_dyn_reinit()?.clear();
+        }
+    }
+
+    let space = EventHeap::space(MIN_NUM_EVENTS.into());
+    if shrink_account(ctx.accounts.event_heap.as_ref(), space, sol_destination)? {
+        let mut event_heap = ctx.accounts.event_heap.load_dyn_reinit()?;
+        let seq_num = event_heap.header.seq_num;
+        event_heap.init();
+        event_heap.header.seq_num = seq_num;
+    }
+
+    Ok(())
+}
+
+/// Shrinks `account` to `space` bytes and sends the rent it doesn't need anymore to
+/// `sol_destination`. Returns false when the account isn't larger than that.
+fn shrink_account(
+    account: &AccountInfo,
+    space: usize,
+    sol_destination: &AccountInfo,
+) -> Result<bool> {
+    if account.data_len() <= space {
+        return Ok(false);
+    }
+    account.realloc(space, false)?;
+
+    let excess_lamports = account
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(space));
+    **account.try_borrow_mut_lamports()? -= excess_lamports;
+    **sol_destination.try_borrow_mut_lamports()? += excess_lamports;
+
+    Ok(true)
+}
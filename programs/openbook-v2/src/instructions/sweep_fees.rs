@@ -2,12 +2,20 @@ use crate::state::market_seeds;
 use anchor_lang::prelude::*;
 
 use crate::accounts_ix::*;
+use crate::error::OpenBookError;
 use crate::logs::{emit_stack, SweepFeesLog};
 use crate::token_utils::*;
 
-pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+pub fn sweep_fees<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SweepFees<'info>>,
+) -> Result<()> {
     let mut market = ctx.accounts.market.load_mut()?;
 
+    require!(
+        market.has_fee_split() == ctx.accounts.fee_split.is_some(),
+        OpenBookError::InvalidFeeSplit
+    );
+
     let amount = market.fees_available;
     market.fees_available = 0;
     market.quote_deposit_total -= amount;
@@ -15,20 +23,65 @@ pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
     let seeds = market_seeds!(market, ctx.accounts.market.key());
     drop(market);
 
-    token_transfer_signed(
-        amount,
-        &ctx.accounts.token_program,
-        &ctx.accounts.market_quote_vault,
-        &ctx.accounts.token_receiver_account,
-        &ctx.accounts.market_authority,
-        seeds,
-    )?;
-
-    emit_stack(SweepFeesLog {
-        market: ctx.accounts.market.key(),
-        amount,
-        receiver: ctx.accounts.token_receiver_account.key(),
-    });
+    let market_quote_vault = ctx.accounts.market_quote_vault.to_account_info();
+
+    if let Some(fee_split) = &ctx.accounts.fee_split {
+        require_eq!(
+            ctx.remaining_accounts.len(),
+            fee_split.destinations.len(),
+            OpenBookError::InvalidFeeSplitDestination
+        );
+
+        let shares = fee_split.shares(amount);
+        for ((destination, share), receiver) in fee_split
+            .destinations
+            .iter()
+            .zip(shares)
+            .zip(ctx.remaining_accounts)
+        {
+            require_keys_eq!(
+                receiver.key(),
+                destination.token_account,
+                OpenBookError::InvalidFeeSplitDestination
+            );
+
+            token_transfer_signed(
+                share,
+                &ctx.accounts.token_program,
+                &market_quote_vault,
+                receiver,
+                &ctx.accounts.market_authority,
+                seeds,
+            )?;
+
+            emit_stack(SweepFeesLog {
+                market: ctx.accounts.market.key(),
+                amount: share,
+                receiver: receiver.key(),
+            });
+        }
+    } else {
+        let token_receiver_account = ctx
+            .accounts
+            .token_receiver_account
+            .as_ref()
+            .ok_or(OpenBookError::MissingTokenReceiverAccount)?;
+
+        token_transfer_signed(
+            amount,
+            &ctx.accounts.token_program,
+            &ctx.accounts.market_quote_vault,
+            token_receiver_account,
+            &ctx.accounts.market_authority,
+            seeds,
+        )?;
+
+        emit_stack(SweepFeesLog {
+            market: ctx.accounts.market.key(),
+            amount,
+            receiver: token_receiver_account.key(),
+        });
+    }
 
     Ok(())
 }
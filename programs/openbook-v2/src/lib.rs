@@ -27,7 +27,10 @@ pub mod instructions;
 use accounts_ix::*;
 use accounts_ix::{StubOracleCreate, StubOracleSet};
 use error::*;
-use state::{OracleConfigParams, Order, OrderParams, PlaceOrderType, SelfTradeBehavior, Side};
+use state::{
+    AccessGate, DelegateScope, FeeSplitDestination, MarketStatus, OracleConfigParams, Order,
+    OrderParams, PlaceOrderType, SelfTradeBehavior, Side,
+};
 use std::cmp;
 
 #[cfg(all(not(feature = "no-entrypoint"), not(feature = "enable-gpl")))]
@@ -38,6 +41,15 @@ pub mod openbook_v2 {
     use super::*;
 
     /// Create a [`Market`](crate::state::Market) for a given token pair.
+    ///
+    /// A non-zero `auction_duration_secs` starts the market with an opening auction
+    /// of that length, see [`run_auction`](crate::openbook_v2::run_auction). With a
+    /// non-zero `auction_interval_secs`, a new auction phase starts every time the
+    /// market traded continuously for that long.
+    ///
+    /// The remainder of an order that would be posted to the book is dropped when it's
+    /// below `min_base_lots_per_order` or worth less than `min_quote_notional` quote
+    /// native, and the order fails when it's above `max_base_lots_per_order` (no limit = 0).
     #[allow(clippy::too_many_arguments)]
     pub fn create_market(
         ctx: Context<CreateMarket>,
@@ -48,6 +60,11 @@ pub mod openbook_v2 {
         maker_fee: i64,
         taker_fee: i64,
         time_expiry: i64,
+        auction_duration_secs: u32,
+        auction_interval_secs: u32,
+        min_base_lots_per_order: i64,
+        min_quote_notional: u64,
+        max_base_lots_per_order: i64,
     ) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::create_market(
@@ -59,6 +76,11 @@ pub mod openbook_v2 {
             maker_fee,
             taker_fee,
             time_expiry,
+            auction_duration_secs,
+            auction_interval_secs,
+            min_base_lots_per_order,
+            min_quote_notional,
+            max_base_lots_per_order,
         )?;
         Ok(())
     }
@@ -71,6 +93,18 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Give back most of the rent of the bids, asks and event heap of an expired
+    /// [`Market`](crate::state::Market) once they're empty, by shrinking them to the minimum
+    /// capacity (only [`close_market_admin`](crate::state::Market::close_market_admin)).
+    ///
+    /// The market stays open so users can still settle their funds, and is closed with
+    /// `close_market` as usual.
+    pub fn shrink_market_accounts(ctx: Context<ShrinkMarketAccounts>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::shrink_market_accounts(ctx)?;
+        Ok(())
+    }
+
     /// Create an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.
     pub fn create_open_orders_indexer(ctx: Context<CreateOpenOrdersIndexer>) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
@@ -102,6 +136,35 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Create the [`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account of an
+    /// open orders account, which holds the delegate permissions and list.
+    ///
+    /// From then on, instructions signed by a delegate need it in their remaining accounts.
+    pub fn create_open_orders_delegates(ctx: Context<CreateOpenOrdersDelegates>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::create_open_orders_delegates(ctx)?;
+        Ok(())
+    }
+
+    /// Close the [`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account of an
+    /// open orders account, removing all its delegates including the
+    /// [`delegate`](crate::state::OpenOrdersAccount::delegate). Needed before closing the
+    /// open orders account.
+    pub fn close_open_orders_delegates(ctx: Context<CloseOpenOrdersDelegates>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::close_open_orders_delegates(ctx)?;
+        Ok(())
+    }
+
+    /// Bring an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) of an older version up
+    /// to date, e.g. recompute the `bids_quote_lots` that version 0 didn't track. Anyone can
+    /// migrate any account, it does nothing for accounts that are up to date already.
+    pub fn migrate_open_orders_account(ctx: Context<MigrateOpenOrdersAccount>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::migrate_open_orders_account(ctx)?;
+        Ok(())
+    }
+
     /// Place an order.
     ///
     /// Different types of orders have different effects on the order book,
@@ -478,17 +541,25 @@ pub mod openbook_v2 {
         ctx: Context<'_, '_, 'c, 'info, ConsumeEvents>,
         slots: Vec<usize>,
     ) -> Result<()> {
-        require!(
-            slots
-                .iter()
-                .all(|slot| *slot < crate::state::MAX_NUM_EVENTS as usize),
-            OpenBookError::InvalidInputHeapSlots
-        );
         #[cfg(feature = "enable-gpl")]
         instructions::consume_events(ctx, slots.len(), Some(slots))?;
         Ok(())
     }
 
+    /// Same as `consume_events`, or `consume_given_events` when `slots` are given, and
+    /// pays the signing `crank_reward_receiver`
+    /// [`crank_reward_per_event`](crate::state::Market::crank_reward_per_event) lamports
+    /// for every event consumed, from the penalties collected by the market.
+    pub fn consume_events_with_reward<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ConsumeEventsWithReward>,
+        limit: usize,
+        slots: Option<Vec<usize>>,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::consume_events_with_reward(ctx, limit, slots)?;
+        Ok(())
+    }
+
     /// Cancel an order by its `order_id`.
     ///
     /// Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a
@@ -572,17 +643,191 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Withdraw the available tokens of an open orders account to token accounts of its
+    /// owner when the market is expired, without the owner's signature.
+    ///
+    /// Only the [`close_market_admin`](crate::state::Market::close_market_admin) can do
+    /// so right after the expiry, anyone once
+    /// [`EXPIRED_MARKET_GRACE_PERIOD`](crate::state::EXPIRED_MARKET_GRACE_PERIOD) has passed.
+    /// For markets set expired before the grace period existed, it is over already.
+    pub fn force_settle_funds(ctx: Context<ForceSettleFunds>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::force_settle_funds(ctx)?;
+        Ok(())
+    }
+
     /// Sweep fees, as a [`Market`](crate::state::Market)'s admin.
-    pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+    ///
+    /// Without a [`FeeSplit`](crate::state::FeeSplit) all fees go to the
+    /// `token_receiver_account`. With one, they are split pro rata between its
+    /// destinations, which are passed as remaining accounts in the same order.
+    pub fn sweep_fees<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SweepFees<'info>>,
+    ) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::sweep_fees(ctx)?;
         Ok(())
     }
 
+    /// Register a [`Referrer`](crate::state::Referrer) with a share of the taker fees of
+    /// the accounts bound to it, in basis points. It's global if `market` is the default
+    /// pubkey, otherwise it can only refer on that market.
+    pub fn register_referrer(
+        ctx: Context<RegisterReferrer>,
+        market: Pubkey,
+        share_bps: u16,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::register_referrer(ctx, market, share_bps)?;
+        Ok(())
+    }
+
+    /// Update the share of a [`Referrer`](crate::state::Referrer). Accounts already bound
+    /// to it keep the share they were bound with.
+    pub fn set_referrer_share(ctx: Context<SetReferrerShare>, share_bps: u16) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_referrer_share(ctx, share_bps)?;
+        Ok(())
+    }
+
+    /// Set the maximum share of the taker fees that referrers get on a market (only
+    /// [`collect_fee_admin`](crate::state::Market::collect_fee_admin)). Markets start at 0,
+    /// without any referrer rebates.
+    pub fn set_max_referrer_share(ctx: Context<SetMaxReferrerShare>, share_bps: u16) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_max_referrer_share(ctx, share_bps)?;
+        Ok(())
+    }
+
+    /// Set the lamports a taker pays for every transaction that adds to the event heap
+    /// (None = [`PENALTY_EVENT_HEAP`](crate::state::PENALTY_EVENT_HEAP)) and the reward paid
+    /// from these penalties for every event consumed (0 = no reward), as the
+    /// [`collect_fee_admin`](crate::state::Market::collect_fee_admin).
+    pub fn set_crank_rewards(
+        ctx: Context<SetCrankRewards>,
+        penalty_event_heap: Option<u32>,
+        crank_reward_per_event: u32,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_crank_rewards(ctx, penalty_event_heap, crank_reward_per_event)?;
+        Ok(())
+    }
+
+    /// Bind an open orders account to a [`Referrer`](crate::state::Referrer), creating its
+    /// [`OpenOrdersReferral`](crate::state::OpenOrdersReferral).
+    ///
+    /// From then on the referrer's share of the taker fees of the account is set aside when
+    /// taking with `place_order`, `edit_order`, `place_order_pegged` or
+    /// `cancel_all_and_place_orders`, which need the referral in their remaining accounts.
+    /// The share is capped by the market's
+    /// [`max_referrer_share_bps`](crate::state::Market::max_referrer_share_bps), 0 unless
+    /// set with `set_max_referrer_share`.
+    pub fn bind_referrer(ctx: Context<BindReferrer>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::bind_referrer(ctx)?;
+        Ok(())
+    }
+
+    /// Withdraw the rebates set aside for a [`Referrer`](crate::state::Referrer) in an
+    /// open orders account bound to it.
+    pub fn claim_referrer_rebates(ctx: Context<ClaimReferrerRebates>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::claim_referrer_rebates(ctx)?;
+        Ok(())
+    }
+
+    /// Unbind an open orders account from its referrer, paying out the rebates set aside
+    /// so far and closing its [`OpenOrdersReferral`](crate::state::OpenOrdersReferral).
+    /// Needed before closing the open orders account.
+    pub fn close_open_orders_referral(ctx: Context<CloseOpenOrdersReferral>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::close_open_orders_referral(ctx)?;
+        Ok(())
+    }
+
+    /// Create the [`FeeSplit`](crate::state::FeeSplit) of a market, as its
+    /// [`collect_fee_admin`](crate::state::Market::collect_fee_admin). From then on the
+    /// fees are always swept according to it.
+    ///
+    /// The destination token accounts are passed as remaining accounts, in order.
+    pub fn create_fee_split(
+        ctx: Context<CreateFeeSplit>,
+        destinations: Vec<FeeSplitDestination>,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::create_fee_split(ctx, destinations)?;
+        Ok(())
+    }
+
+    /// Replace the destinations of the [`FeeSplit`](crate::state::FeeSplit) of a market, as
+    /// its [`collect_fee_admin`](crate::state::Market::collect_fee_admin). The destination
+    /// token accounts are passed as remaining accounts, in order.
+    pub fn set_fee_split(
+        ctx: Context<SetFeeSplit>,
+        destinations: Vec<FeeSplitDestination>,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_fee_split(ctx, destinations)?;
+        Ok(())
+    }
+
     /// Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.
-    pub fn set_delegate(ctx: Context<SetDelegate>) -> Result<()> {
+    ///
+    /// The delegate is limited to the given [`DelegateScope`](crate::state::DelegateScope). Orders
+    /// it places can be restricted to `allowed_side` and to `max_quote_native_per_order`
+    /// (0 = no limit, asks are valued at their limit price, or at the best bid or oracle
+    /// price when higher). After `expiry_timestamp` the delegate is ignored (0 = never).
+    /// Removing the delegate resets the permissions.
+    ///
+    /// The permissions are stored in the
+    /// [`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account, which is required
+    /// for anything but an unrestricted delegate.
+    pub fn set_delegate(
+        ctx: Context<SetDelegate>,
+        scope: DelegateScope,
+        allowed_side: Option<Side>,
+        max_quote_native_per_order: u64,
+        expiry_timestamp: u64,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_delegate(
+            ctx,
+            scope,
+            allowed_side,
+            max_quote_native_per_order,
+            expiry_timestamp,
+        )?;
+        Ok(())
+    }
+
+    /// Add a delegate to the delegate list of an open orders account, in addition to the
+    /// [`delegate`](crate::state::OpenOrdersAccount::delegate). The permissions work as in
+    /// `set_delegate`.
+    ///
+    /// The list holds up to [`MAX_DELEGATES`](crate::state::MAX_DELEGATES) entries in the
+    /// [`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account.
+    pub fn add_delegate(
+        ctx: Context<AddDelegate>,
+        scope: DelegateScope,
+        allowed_side: Option<Side>,
+        max_quote_native_per_order: u64,
+        expiry_timestamp: u64,
+    ) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
-        instructions::set_delegate(ctx)?;
+        instructions::add_delegate(
+            ctx,
+            scope,
+            allowed_side,
+            max_quote_native_per_order,
+            expiry_timestamp,
+        )?;
+        Ok(())
+    }
+
+    /// Remove a delegate from the delegate list of an open orders account.
+    pub fn remove_delegate(ctx: Context<RemoveDelegate>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::remove_delegate(ctx)?;
         Ok(())
     }
 
@@ -594,6 +839,72 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Restrict the order instructions of the market without expiring it (only
+    /// [`market_status_admin`](crate::state::Market::market_status_admin)).
+    ///
+    /// `PostOnly` only accepts orders that never take, `CancelOnly` only accepts
+    /// cancels and `Halted` rejects cancels as well. Setting the status back to
+    /// `Active` lifts all restrictions.
+    pub fn set_market_status(ctx: Context<SetMarketStatus>, status: MarketStatus) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_market_status(ctx, status)?;
+        Ok(())
+    }
+
+    /// Set or clear the [`market_status_admin`](crate::state::Market::market_status_admin)
+    /// of a market (only [`collect_fee_admin`](crate::state::Market::collect_fee_admin)).
+    ///
+    /// Lets markets created without a status admin get one later.
+    pub fn set_market_status_admin(ctx: Context<SetMarketStatusAdmin>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_market_status_admin(ctx)?;
+        Ok(())
+    }
+
+    /// Gate a market behind an allowlist or a credential token, as its
+    /// [`market_status_admin`](crate::state::Market::market_status_admin).
+    ///
+    /// Open orders accounts then need an [`AccessPass`](crate::state::AccessPass) created by
+    /// `admin`, or a token account of `credential_mint` with a balance, when they are created.
+    /// Only accounts that passed the gate can place orders. Every order re-checks the gate, so
+    /// `place_order`, `edit_order` and `cancel_all_and_place_orders` need the access gate and the
+    /// owner's access pass or credential token account in their remaining accounts. Take orders
+    /// have no open orders account, `place_take_order` checks the signer's proof instead.
+    pub fn create_access_gate(
+        ctx: Context<CreateAccessGate>,
+        kind: AccessGate,
+        admin: Pubkey,
+        credential_mint: Pubkey,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::create_access_gate(ctx, kind, admin, credential_mint)?;
+        Ok(())
+    }
+
+    /// Allow an owner to create open orders accounts and place orders on an allowlist gated
+    /// market.
+    pub fn add_access_pass(ctx: Context<AddAccessPass>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::add_access_pass(ctx)?;
+        Ok(())
+    }
+
+    /// Remove the access pass of an owner. Its existing open orders accounts can't place
+    /// orders anymore, but can still cancel orders and settle funds.
+    pub fn remove_access_pass(ctx: Context<RemoveAccessPass>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::remove_access_pass(ctx)?;
+        Ok(())
+    }
+
+    /// Grant or revoke the access of an open orders account to a gated market. Revoked
+    /// accounts can only cancel orders and settle funds.
+    pub fn set_open_orders_access(ctx: Context<SetOpenOrdersAccess>, allowed: bool) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_open_orders_access(ctx, allowed)?;
+        Ok(())
+    }
+
     /// Remove orders from the book when the market is expired (only
     /// [`close_market_admin`](crate::state::Market::close_market_admin)).
     pub fn prune_orders(ctx: Context<PruneOrders>, limit: u8) -> Result<()> {
@@ -602,6 +913,87 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Remove orders from the book when the market is expired, with the same permissions
+    /// as `force_settle_funds`.
+    pub fn force_prune_orders(ctx: Context<ForcePruneOrders>, limit: u8) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::force_prune_orders(ctx, limit)?;
+        Ok(())
+    }
+
+    /// Set the heartbeat deadline of an open orders account and its resting orders
+    /// to now + `timeout` seconds.
+    ///
+    /// Once the deadline passes without another heartbeat, the account's resting
+    /// orders are treated as invalid and dropped by takers, and anyone may cancel
+    /// them with `cancel_orders_heartbeat_expired`. A `timeout` of 0 disables it.
+    pub fn heartbeat(ctx: Context<Heartbeat>, timeout: u64) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::heartbeat(ctx, timeout)?;
+        Ok(())
+    }
+
+    /// Cancel up to `limit` orders of an open orders account whose heartbeat
+    /// deadline has passed (permissionless).
+    pub fn cancel_orders_heartbeat_expired(
+        ctx: Context<CancelOrdersHeartbeatExpired>,
+        limit: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::cancel_orders_heartbeat_expired(ctx, limit)?;
+        Ok(())
+    }
+
+    /// Configure the market maker protection of an open orders account.
+    ///
+    /// When the account's resting orders get filled for `base_lots_limit` base lots or
+    /// `fill_count_limit` times within `window_secs` seconds, the account is frozen: its
+    /// orders are treated as invalid and dropped by takers until the owner calls `reset_mmp`.
+    /// A limit of 0 is ignored, a `window_secs` of 0 disables the protection. Fails while
+    /// the account is frozen.
+    ///
+    /// Fills are counted while matching, so takers have to pass the open orders accounts
+    /// of makers with market maker protection in the remaining accounts. The orders of
+    /// makers whose account is missing are skipped, and the rest of the order isn't posted.
+    pub fn set_mmp(
+        ctx: Context<SetMmp>,
+        window_secs: u16,
+        base_lots_limit: i64,
+        fill_count_limit: u16,
+    ) -> Result<()> {
+        require_gte!(base_lots_limit, 0, OpenBookError::InvalidInputLots);
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_mmp(ctx, window_secs, base_lots_limit, fill_count_limit)?;
+        Ok(())
+    }
+
+    /// Unfreeze an open orders account after its market maker protection was triggered.
+    /// Only the owner can do this.
+    pub fn reset_mmp(ctx: Context<ResetMmp>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::reset_mmp(ctx)?;
+        Ok(())
+    }
+
+    /// Run the auction of a market whose auction phase ended (permissionless).
+    ///
+    /// Computes the uniform price that maximizes the matched volume of the crossing
+    /// bids and asks and fills up to `limit` pairs of orders at that price. Both sides
+    /// are makers and get [`FillEvent`](crate::state::FillEvent)s, processed directly
+    /// for the open orders accounts passed as remaining accounts. Once no crossing
+    /// orders are left, the market switches to continuous trading.
+    ///
+    /// The accounts of crossing makers with market maker protection must be passed, the
+    /// orders of frozen makers are dropped instead of filled.
+    pub fn run_auction<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RunAuction<'info>>,
+        limit: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::run_auction(ctx, limit)?;
+        Ok(())
+    }
+
     pub fn stub_oracle_create(ctx: Context<StubOracleCreate>, price: f64) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::stub_oracle_create(ctx, price)?;
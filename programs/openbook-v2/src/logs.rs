@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use borsh::BorshSerialize;
 
+use crate::state::FeeSplitDestination;
+
 #[inline(never)] // ensure fresh stack frame
 pub fn emit_stack<T: anchor_lang::Event>(e: T) {
     use std::io::{Cursor, Write};
@@ -80,6 +82,62 @@ pub struct TotalOrderFillEvent {
 pub struct SetDelegateLog {
     pub open_orders_account: Pubkey,
     pub delegate: Option<Pubkey>,
+    pub scope: u8,
+    pub allowed_side: Option<u8>,
+    pub max_quote_native_per_order: u64,
+    pub expiry_timestamp: u64,
+}
+
+#[event]
+pub struct AddDelegateLog {
+    pub open_orders_account: Pubkey,
+    pub delegate: Pubkey,
+    pub scope: u8,
+    pub allowed_side: Option<u8>,
+    pub max_quote_native_per_order: u64,
+    pub expiry_timestamp: u64,
+}
+
+#[event]
+pub struct RemoveDelegateLog {
+    pub open_orders_account: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct HeartbeatLog {
+    pub open_orders_account: Pubkey,
+    pub deadline: u64,
+}
+
+#[event]
+pub struct SetMmpLog {
+    pub open_orders_account: Pubkey,
+    pub window_secs: u16,
+    pub base_lots_limit: i64,
+    pub fill_count_limit: u16,
+}
+
+#[event]
+pub struct MmpTriggeredLog {
+    pub open_orders_account: Pubkey,
+    pub market: Pubkey,
+    pub window_start: u64,
+    pub window_base_lots: i64,
+    pub window_fill_count: u16,
+}
+
+#[event]
+pub struct MmpResetLog {
+    pub open_orders_account: Pubkey,
+}
+
+#[event]
+pub struct RunAuctionLog {
+    pub market: Pubkey,
+    pub price_lots: i64,
+    pub base_lots: i64,
+    pub completed: bool,
 }
 
 #[event]
@@ -98,6 +156,57 @@ pub struct SweepFeesLog {
     pub receiver: Pubkey,
 }
 
+#[event]
+pub struct ReferrerLog {
+    pub referrer: Pubkey,
+    pub authority: Pubkey,
+    pub market: Pubkey,
+    pub share_bps: u16,
+}
+
+#[event]
+pub struct AccessGateLog {
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub kind: u8,
+    pub credential_mint: Pubkey,
+}
+
+#[event]
+pub struct AccessPassLog {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub allowed: bool,
+}
+
+#[event]
+pub struct OpenOrdersAccessLog {
+    pub market: Pubkey,
+    pub open_orders_account: Pubkey,
+    pub access: u8,
+}
+
+#[event]
+pub struct BindReferrerLog {
+    pub open_orders_account: Pubkey,
+    pub referrer: Pubkey,
+    pub share_bps: u16,
+}
+
+#[event]
+pub struct ClaimReferrerRebatesLog {
+    pub referrer: Pubkey,
+    pub open_orders_account: Pubkey,
+    pub amount: u64,
+    pub referred_taker_volume: u64,
+}
+
+#[event]
+pub struct FeeSplitLog {
+    pub market: Pubkey,
+    pub destinations: Vec<FeeSplitDestination>,
+}
+
 #[event]
 pub struct OpenOrdersPositionLog {
     pub owner: Pubkey,
@@ -118,3 +227,39 @@ pub struct OpenOrdersPositionLog {
     /// Cumulative taker volume in quote native units (display only)
     pub taker_volume: u128,
 }
+
+#[event]
+pub struct PenaltyLog {
+    pub market: Pubkey,
+    pub penalty_payer: Pubkey,
+    pub lamports: u64,
+    pub penalty_lamports_accrued: u64,
+}
+
+#[event]
+pub struct CrankRewardLog {
+    pub market: Pubkey,
+    pub receiver: Pubkey,
+    pub events: u32,
+    pub lamports: u64,
+    pub penalty_lamports_accrued: u64,
+}
+
+#[event]
+pub struct SetCrankRewardsLog {
+    pub market: Pubkey,
+    pub penalty_event_heap: u64,
+    pub crank_reward_per_event: u32,
+}
+
+#[event]
+pub struct SetMarketStatusLog {
+    pub market: Pubkey,
+    pub status: u8,
+}
+
+#[event]
+pub struct SetMarketStatusAdminLog {
+    pub market: Pubkey,
+    pub market_status_admin: Option<Pubkey>,
+}
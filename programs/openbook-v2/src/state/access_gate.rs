@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use crate::error::OpenBookError;
+
+#[derive(
+    Eq,
+    PartialEq,
+    Copy,
+    Clone,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Debug,
+    AnchorSerialize,
+    AnchorDeserialize,
+)]
+#[repr(u8)]
+pub enum AccessGate {
+    /// Anyone can create open orders accounts and place orders
+    None = 0,
+    /// Owners need an [`AccessPass`] created by the gate admin
+    Allowlist = 1,
+    /// Owners need to hold a token of the gate's credential mint
+    CredentialToken = 2,
+}
+
+/// Whether an open orders account passed the access gate of its market
+#[derive(Eq, PartialEq, Copy, Clone, TryFromPrimitive, IntoPrimitive, Debug)]
+#[repr(u8)]
+pub enum OpenOrdersAccess {
+    /// Created while the market wasn't gated
+    Unchecked = 0,
+    /// Passed the gate when it was created, or granted by the gate admin
+    Granted = 1,
+    /// Revoked by the gate admin, the account can only cancel and settle
+    Revoked = 2,
+}
+
+/// Access gate of a permissioned market, checked when creating open orders accounts and
+/// on each order instead of having the open orders admin co-sign every order
+#[account]
+#[derive(Default)]
+pub struct MarketAccessGate {
+    pub bump: u8,
+    pub market: Pubkey,
+    /// Creates and removes access passes, and grants or revokes open orders accounts
+    pub admin: Pubkey,
+    /// See [`AccessGate`]
+    pub kind: u8,
+    /// Mint of the credential token for [`AccessGate::CredentialToken`]
+    pub credential_mint: Pubkey,
+}
+
+impl MarketAccessGate {
+    pub fn space() -> usize {
+        8 + 1 + 32 + 32 + 1 + 32
+    }
+
+    pub fn kind(&self) -> AccessGate {
+        AccessGate::try_from(self.kind).unwrap()
+    }
+
+    /// Checks the proof that `owner` may trade on the market: its [`AccessPass`] or one
+    /// of its token accounts of the credential mint
+    pub fn check_proof(&self, owner: Pubkey, proof: Option<&AccountInfo>) -> Result<()> {
+        let proof = proof.ok_or(OpenBookError::MissingAccessProof)?;
+        match self.kind() {
+            AccessGate::None => Ok(()),
+            AccessGate::Allowlist => {
+                let pass = Account::<AccessPass>::try_from(proof)?;
+                require!(
+                    pass.market == self.market && pass.owner == owner,
+                    OpenBookError::InvalidAccessProof
+                );
+                Ok(())
+            }
+            AccessGate::CredentialToken => {
+                let token_account = Account::<TokenAccount>::try_from(proof)?;
+                require!(
+                    token_account.mint == self.credential_mint
+                        && token_account.owner == owner
+                        && token_account.amount > 0,
+                    OpenBookError::InvalidAccessProof
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Finds the access gate of `market` and a valid proof of `owner` in `accounts`, the
+    /// remaining accounts of an order. Orders carry the proof so that a removed access
+    /// pass or a credential token that left the owner's wallet stops new orders.
+    pub fn check_proof_in(market: Pubkey, owner: Pubkey, accounts: &[AccountInfo]) -> Result<()> {
+        let access_gate = accounts
+            .iter()
+            .filter_map(|ai| Account::<MarketAccessGate>::try_from(ai).ok())
+            .find(|access_gate| access_gate.market == market)
+            .ok_or(OpenBookError::MissingAccessProof)?;
+        require!(
+            accounts
+                .iter()
+                .any(|ai| access_gate.check_proof(owner, Some(ai)).is_ok()),
+            OpenBookError::InvalidAccessProof
+        );
+        Ok(())
+    }
+}
+
+/// Allows `owner` to create open orders accounts on an allowlist gated market
+#[account]
+#[derive(Default)]
+pub struct AccessPass {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub owner: Pubkey,
+}
+
+impl AccessPass {
+    pub fn space() -> usize {
+        8 + 1 + 32 + 32
+    }
+}
@@ -0,0 +1,275 @@
+use anchor_lang::prelude::*;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use static_assertions::const_assert_eq;
+use std::mem::size_of;
+
+use crate::accounts_zerocopy::LoadZeroCopyRef;
+use crate::error::*;
+use crate::pubkey_option::NonZeroPubkeyOption;
+
+use super::{Market, OpenOrdersAccount, Order, Orderbook, Side};
+
+/// Maximum number of entries in the delegate list of an open orders account
+pub const MAX_DELEGATES: usize = 4;
+
+/// What a delegate is allowed to do. A delegate can never change the delegates or
+/// close the account.
+#[derive(
+    Eq,
+    PartialEq,
+    Copy,
+    Clone,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Debug,
+    AnchorSerialize,
+    AnchorDeserialize,
+)]
+#[repr(u8)]
+pub enum DelegateScope {
+    /// Everything the owner can do otherwise
+    Full = 0,
+    /// Place, edit and cancel orders
+    Trade = 1,
+    /// Cancel orders
+    Cancel = 2,
+    /// Settle funds to the token accounts of the owner
+    Settle = 3,
+}
+
+impl DelegateScope {
+    /// Does a delegate with this scope have the permissions of `scope`?
+    pub fn allows(self, scope: DelegateScope) -> bool {
+        self == DelegateScope::Full
+            || self == scope
+            || (self == DelegateScope::Trade && scope == DelegateScope::Cancel)
+    }
+}
+
+#[zero_copy]
+#[derive(Debug)]
+pub struct DelegatePermissions {
+    /// See [`DelegateScope`]
+    pub scope: u8,
+    /// If set, the delegate can only place orders on `side`
+    pub side_restricted: u8,
+    pub side: u8,
+    pub padding: [u8; 5],
+    /// Max value in quote native of a delegate order, 0 if unlimited
+    pub max_quote_native_per_order: u64,
+    /// Timestamp after which the delegate is ignored, 0 if it never expires
+    pub expiry_timestamp: u64,
+}
+const_assert_eq!(size_of::<DelegatePermissions>(), 1 + 1 + 1 + 5 + 8 + 8);
+const_assert_eq!(size_of::<DelegatePermissions>(), 24);
+const_assert_eq!(size_of::<DelegatePermissions>() % 8, 0);
+
+impl Default for DelegatePermissions {
+    fn default() -> Self {
+        Self::new(DelegateScope::Full, None, 0, 0)
+    }
+}
+
+impl DelegatePermissions {
+    pub fn new(
+        scope: DelegateScope,
+        allowed_side: Option<Side>,
+        max_quote_native_per_order: u64,
+        expiry_timestamp: u64,
+    ) -> Self {
+        Self {
+            scope: scope.into(),
+            side_restricted: allowed_side.is_some().into(),
+            side: allowed_side.map_or(0, |side| side.into()),
+            padding: [0; 5],
+            max_quote_native_per_order,
+            expiry_timestamp,
+        }
+    }
+
+    pub fn scope(&self) -> DelegateScope {
+        DelegateScope::try_from(self.scope).unwrap()
+    }
+
+    pub fn allowed_side(&self) -> Option<Side> {
+        (self.side_restricted != 0).then(|| Side::try_from(self.side).unwrap())
+    }
+
+    /// Are these the permissions of a delegate without a delegates account?
+    pub fn is_unrestricted(&self) -> bool {
+        self.scope() == DelegateScope::Full
+            && self.allowed_side().is_none()
+            && self.max_quote_native_per_order == 0
+            && self.expiry_timestamp == 0
+    }
+
+    pub fn is_expired(&self, now_ts: i64) -> bool {
+        self.expiry_timestamp != 0 && self.expiry_timestamp as i64 <= now_ts
+    }
+
+    /// Is the delegate not expired and allowed to do what `scope` permits?
+    pub fn allows(&self, scope: DelegateScope, now_ts: i64) -> bool {
+        !self.is_expired(now_ts) && self.scope().allows(scope)
+    }
+
+    /// Checks the side and size limits of an order placed by the delegate
+    pub fn check_order(
+        &self,
+        order: &Order,
+        now_ts: u64,
+        oracle_price_lots: Option<i64>,
+        book: &Orderbook,
+        market: &Market,
+    ) -> Result<()> {
+        require!(
+            self.allowed_side()
+                .map_or(true, |allowed| allowed == order.side),
+            OpenBookError::DelegateSideNotAllowed
+        );
+
+        // Bids can't spend more quote than they lock. Asks are valued at the best bid or
+        // oracle price when their limit price is lower, a low limit price would let them
+        // sell any amount of base otherwise
+        let max_quote_lots = match order.side {
+            Side::Bid => order.max_quote_lots_including_fees as i128,
+            Side::Ask => {
+                let (price_lots, _) = order.price(now_ts, oracle_price_lots, book)?;
+                let reference_price_lots = price_lots
+                    .max(book.bids.best_price(now_ts, oracle_price_lots).unwrap_or(0))
+                    .max(oracle_price_lots.unwrap_or(0));
+                order.max_base_lots as i128 * reference_price_lots as i128
+            }
+        };
+        let max_quote_native = max_quote_lots * market.quote_lot_size as i128;
+        require!(
+            self.max_quote_native_per_order == 0
+                || max_quote_native <= self.max_quote_native_per_order as i128,
+            OpenBookError::DelegateOrderTooLarge
+        );
+
+        Ok(())
+    }
+}
+
+/// Entry of the delegate list, free if `delegate` is none
+#[zero_copy]
+#[derive(Debug, Default)]
+pub struct DelegateEntry {
+    pub delegate: NonZeroPubkeyOption,
+    pub permissions: DelegatePermissions,
+}
+const_assert_eq!(size_of::<DelegateEntry>(), 32 + 24);
+const_assert_eq!(size_of::<DelegateEntry>() % 8, 0);
+
+/// Delegate permissions of an open orders account, a PDA of the open orders account
+///
+/// Accounts without one only have an unrestricted [`OpenOrdersAccount::delegate`]. Once
+/// created, it holds the limits of that delegate and a list of further delegates, and
+/// delegates are only recognized when it's passed in the remaining accounts.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct OpenOrdersDelegates {
+    pub open_orders_account: Pubkey,
+    pub bump: u8,
+    pub padding: [u8; 7],
+    /// Limits of [`OpenOrdersAccount::delegate`]
+    pub delegate_permissions: DelegatePermissions,
+    pub entries: [DelegateEntry; MAX_DELEGATES],
+    pub reserved: [u8; 64],
+}
+const_assert_eq!(
+    size_of::<OpenOrdersDelegates>(),
+    32 + 1 + 7 + size_of::<DelegatePermissions>() + MAX_DELEGATES * size_of::<DelegateEntry>() + 64
+);
+const_assert_eq!(size_of::<OpenOrdersDelegates>(), 352);
+const_assert_eq!(size_of::<OpenOrdersDelegates>() % 8, 0);
+
+impl OpenOrdersDelegates {
+    /// Number of bytes needed for the OpenOrdersDelegates, including the discriminator
+    pub fn space() -> usize {
+        8 + size_of::<OpenOrdersDelegates>()
+    }
+
+    /// Finds the delegates of `open_orders_account` among `accounts`
+    pub fn find_in(open_orders_account: Pubkey, accounts: &[AccountInfo]) -> Option<Self> {
+        accounts
+            .iter()
+            .filter_map(|ai| ai.load::<OpenOrdersDelegates>().ok())
+            .find(|delegates| delegates.open_orders_account == open_orders_account)
+            .map(|delegates| *delegates)
+    }
+
+    pub fn find(&self, delegate: Pubkey) -> Option<&DelegateEntry> {
+        self.entries.iter().find(|entry| entry.delegate == delegate)
+    }
+
+    pub fn find_mut(&mut self, delegate: Pubkey) -> Option<&mut DelegateEntry> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.delegate == delegate)
+    }
+
+    pub fn free_entry_mut(&mut self) -> Option<&mut DelegateEntry> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.delegate.is_none())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.iter().all(|entry| entry.delegate.is_none())
+    }
+}
+
+/// Delegate checks on an open orders account, including its [`OpenOrdersDelegates`] if it
+/// has some, which is then looked up in `remaining_accounts`
+pub trait OpenOrdersAccountLoader {
+    /// Permissions of the signer if it's a delegate, None for the owner and anyone else
+    fn delegate_permissions(
+        &self,
+        ix_signer: Pubkey,
+        remaining_accounts: &[AccountInfo],
+    ) -> Result<Option<DelegatePermissions>>;
+
+    /// Checks that the signer is the owner, or a delegate whose permissions allow `scope`.
+    /// Returns the permissions of a delegate.
+    fn check_owner_or_delegate(
+        &self,
+        ix_signer: Pubkey,
+        scope: DelegateScope,
+        remaining_accounts: &[AccountInfo],
+    ) -> Result<Option<DelegatePermissions>>;
+}
+
+impl<'info> OpenOrdersAccountLoader for AccountLoader<'info, OpenOrdersAccount> {
+    fn delegate_permissions(
+        &self,
+        ix_signer: Pubkey,
+        remaining_accounts: &[AccountInfo],
+    ) -> Result<Option<DelegatePermissions>> {
+        let account = self.load()?;
+        if ix_signer == account.owner || !account.has_delegates() {
+            return Ok(account.delegate_permissions(ix_signer, None));
+        }
+        let delegates = OpenOrdersDelegates::find_in(self.key(), remaining_accounts)
+            .ok_or(OpenBookError::MissingOpenOrdersDelegates)?;
+        Ok(account.delegate_permissions(ix_signer, Some(&delegates)))
+    }
+
+    fn check_owner_or_delegate(
+        &self,
+        ix_signer: Pubkey,
+        scope: DelegateScope,
+        remaining_accounts: &[AccountInfo],
+    ) -> Result<Option<DelegatePermissions>> {
+        if self.load()?.owner == ix_signer {
+            return Ok(None);
+        }
+        let now_ts = Clock::get()?.unix_timestamp;
+        let permissions = self.delegate_permissions(ix_signer, remaining_accounts)?;
+        require!(
+            permissions.map_or(false, |permissions| permissions.allows(scope, now_ts)),
+            OpenBookError::NoOwnerOrDelegate
+        );
+        Ok(permissions)
+    }
+}
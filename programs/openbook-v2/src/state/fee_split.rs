@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::error::OpenBookError;
+
+/// Maximum number of destinations of a [`FeeSplit`]
+pub const MAX_FEE_SPLIT_DESTINATIONS: usize = 4;
+/// The shares of all destinations add up to this
+pub const FEE_SPLIT_TOTAL_BPS: u16 = 10_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeSplitDestination {
+    /// Quote token account receiving the share
+    pub token_account: Pubkey,
+    /// Share of the swept fees, in basis points
+    pub share_bps: u16,
+}
+
+/// How `sweep_fees` distributes the fees of a market, created by its `collect_fee_admin`
+#[account]
+#[derive(Default)]
+pub struct FeeSplit {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub destinations: Vec<FeeSplitDestination>,
+}
+
+impl FeeSplit {
+    pub fn space(len: usize) -> usize {
+        8 + 1 + 32 + (4 + (len * (32 + 2)))
+    }
+
+    pub fn validate(destinations: &[FeeSplitDestination]) -> Result<()> {
+        require!(
+            !destinations.is_empty() && destinations.len() <= MAX_FEE_SPLIT_DESTINATIONS,
+            OpenBookError::InvalidInputFeeSplit
+        );
+        require!(
+            destinations.iter().all(|d| d.share_bps > 0),
+            OpenBookError::InvalidInputFeeSplit
+        );
+        let total_bps: u32 = destinations.iter().map(|d| d.share_bps as u32).sum();
+        require_eq!(
+            total_bps,
+            FEE_SPLIT_TOTAL_BPS as u32,
+            OpenBookError::InvalidInputFeeSplit
+        );
+        Ok(())
+    }
+
+    /// Checks that `token_accounts` are the token accounts of the destinations, in the
+    /// same order, and hold `quote_mint`. A bad destination would otherwise only show up
+    /// when `sweep_fees` fails.
+    pub fn validate_token_accounts(
+        destinations: &[FeeSplitDestination],
+        token_accounts: &[AccountInfo],
+        quote_mint: Pubkey,
+    ) -> Result<()> {
+        require_eq!(
+            token_accounts.len(),
+            destinations.len(),
+            OpenBookError::InvalidFeeSplitDestination
+        );
+        for (destination, token_account) in destinations.iter().zip(token_accounts) {
+            require_keys_eq!(
+                token_account.key(),
+                destination.token_account,
+                OpenBookError::InvalidFeeSplitDestination
+            );
+            let token_account = Account::<TokenAccount>::try_from(token_account)?;
+            require_keys_eq!(
+                token_account.mint,
+                quote_mint,
+                OpenBookError::InvalidFeeSplitDestination
+            );
+        }
+        Ok(())
+    }
+
+    /// Splits `amount` pro rata between the destinations. The rounding remainder goes
+    /// to the first destination.
+    pub fn shares(&self, amount: u64) -> Vec<u64> {
+        let mut shares: Vec<u64> = self
+            .destinations
+            .iter()
+            .map(|d| (amount as u128 * d.share_bps as u128 / FEE_SPLIT_TOTAL_BPS as u128) as u64)
+            .collect();
+        let remainder = amount - shares.iter().sum::<u64>();
+        if let Some(first) = shares.first_mut() {
+            *first += remainder;
+        }
+        shares
+    }
+}
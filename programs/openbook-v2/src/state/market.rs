@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use fixed::types::I80F48;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 use static_assertions::const_assert_eq;
 use std::convert::{TryFrom, TryInto};
 use std::mem::size_of;
@@ -9,13 +10,17 @@ use crate::pubkey_option::NonZeroPubkeyOption;
 use crate::state::oracle;
 use crate::{accounts_zerocopy::KeyedAccountReader, state::orderbook::Side};
 
-use super::{orderbook, OracleConfig};
+use super::{orderbook, AccessGate, OracleConfig};
 
 // For a 1bps taker fee, set taker_fee to 100, so taker_fee/FEES_SCALE_FACTOR = 10e-4
 pub const FEES_SCALE_FACTOR: i128 = 1_000_000;
-// taker pays 500 lamports penalty for every transaction that adds to the event heap
+// by default, taker pays 500 lamports penalty for every transaction that adds to the event heap
 pub const PENALTY_EVENT_HEAP: u64 = 500;
 
+/// Seconds after the expiry of a market from which anyone can settle and prune the
+/// open orders accounts of the market
+pub const EXPIRED_MARKET_GRACE_PERIOD: i64 = 30 * 24 * 60 * 60;
+
 #[account(zero_copy)]
 #[derive(Debug)]
 pub struct Market {
@@ -116,7 +121,61 @@ pub struct Market {
     pub market_quote_vault: Pubkey,
     pub quote_deposit_total: u64,
 
-    pub reserved: [u8; 128],
+    /// Start of the current or next auction phase.
+    pub auction_start_ts: i64,
+    /// End of the current or next auction phase. No auction = 0.
+    ///
+    /// During an auction phase orders are posted to the book without matching.
+    /// After it ended, `run_auction` fills all crossing orders at a uniform price
+    /// and switches the market to continuous trading.
+    pub auction_end_ts: i64,
+    /// Clearing price of the auction that is being run, 0 if not computed yet
+    pub auction_price_lots: i64,
+    /// Length of an auction phase in seconds
+    pub auction_duration_secs: u32,
+    /// Seconds of continuous trading between recurring auction phases. Opening auction only = 0
+    pub auction_interval_secs: u32,
+
+    /// Admin who can change the market status
+    pub market_status_admin: NonZeroPubkeyOption,
+    /// See [`MarketStatus`]
+    pub status: u8,
+
+    /// Set once `set_crank_rewards` configured `penalty_event_heap`
+    pub has_penalty_event_heap: u8,
+    pub padding2: [u8; 2],
+    /// Lamports a taker pays for every transaction that adds to the event heap, when
+    /// `has_penalty_event_heap` is set. [`PENALTY_EVENT_HEAP`] otherwise.
+    pub penalty_event_heap: u32,
+
+    /// Minimum base lots of an order posted to the book. Smaller remainders aren't posted.
+    pub min_base_lots_per_order: i64,
+    /// Minimum value in quote native of an order posted to the book. Smaller remainders
+    /// aren't posted.
+    pub min_quote_notional: u64,
+    /// Maximum base lots of an order posted to the book. No limit = 0
+    pub max_base_lots_per_order: i64,
+
+    /// Timestamp at which `set_market_expired` was called, 0 if it wasn't or if it was
+    /// before this field existed
+    pub time_set_expired: i64,
+
+    /// Set once a [`FeeSplit`](crate::state::FeeSplit) was created, `sweep_fees` then
+    /// distributes the fees according to it
+    pub has_fee_split: u8,
+    /// See [`AccessGate`](crate::state::AccessGate), set by `create_access_gate`
+    pub access_gate: u8,
+    /// Maximum share of the taker fees, after maker rebates, that bound referrers get on
+    /// this market, in basis points. No referrer rebates = 0, which is where every market
+    /// starts until `set_max_referrer_share` is called
+    pub max_referrer_share_bps: u16,
+    /// Lamports paid from the penalties to whoever consumes an event. No reward = 0
+    pub crank_reward_per_event: u32,
+    /// Penalty lamports collected into the market account and not yet paid as crank
+    /// rewards
+    pub penalty_lamports_accrued: u64,
+
+    pub reserved: [u8; 8],
 }
 
 const_assert_eq!(
@@ -151,11 +210,55 @@ const_assert_eq!(
     8 +                         // quote_deposit_total
     8 +                         // base_fees_accrued
     8 +                         // referrer_rebates_accrued
-    128 // reserved
+    8 +                         // auction_start_ts
+    8 +                         // auction_end_ts
+    8 +                         // auction_price_lots
+    4 +                         // auction_duration_secs
+    4 +                         // auction_interval_secs
+    32 +                        // market_status_admin
+    1 +                         // status
+    1 +                         // has_penalty_event_heap
+    2 +                         // padding2
+    4 +                         // penalty_event_heap
+    8 +                         // min_base_lots_per_order
+    8 +                         // min_quote_notional
+    8 +                         // max_base_lots_per_order
+    8 +                         // time_set_expired
+    1 +                         // has_fee_split
+    1 +                         // access_gate
+    2 +                         // max_referrer_share_bps
+    4 +                         // crank_reward_per_event
+    8 +                         // penalty_lamports_accrued
+    8 // reserved
 );
 const_assert_eq!(size_of::<Market>(), 840);
 const_assert_eq!(size_of::<Market>() % 8, 0);
 
+/// Reversible restriction of the order instructions, set by the
+/// [`market_status_admin`](Market::market_status_admin)
+#[derive(
+    Eq,
+    PartialEq,
+    Copy,
+    Clone,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Debug,
+    AnchorSerialize,
+    AnchorDeserialize,
+)]
+#[repr(u8)]
+pub enum MarketStatus {
+    /// No restrictions
+    Active = 0,
+    /// Only orders that never take are allowed, cancels are allowed
+    PostOnly = 1,
+    /// No new orders, cancels are allowed
+    CancelOnly = 2,
+    /// Neither new orders nor cancels are allowed
+    Halted = 3,
+}
+
 impl Market {
     pub fn name(&self) -> &str {
         std::str::from_utf8(&self.name)
@@ -167,6 +270,126 @@ impl Market {
         self.time_expiry != 0 && self.time_expiry < timestamp
     }
 
+    /// Checks that the accounts of an expired market can be settled and pruned without
+    /// their owner: right after the expiry by the close market admin, and by anyone once
+    /// the grace period is over. Halted markets can't be force closed.
+    pub fn check_can_force_close(&self, signer: Pubkey, timestamp: i64) -> Result<()> {
+        require!(
+            self.is_expired(timestamp),
+            OpenBookError::MarketHasNotExpired
+        );
+        self.check_can_cancel_order()?;
+        if self.close_market_admin.is_some() && self.close_market_admin == signer {
+            return Ok(());
+        }
+
+        // Markets set expired before `time_set_expired` existed don't know when that was,
+        // their grace period counts from the time_expiry of -1 and is over
+        let expired_at = if self.time_expiry == -1 && self.time_set_expired != 0 {
+            self.time_set_expired
+        } else {
+            self.time_expiry
+        };
+        require!(
+            expired_at.saturating_add(EXPIRED_MARKET_GRACE_PERIOD) < timestamp,
+            OpenBookError::MarketGracePeriodNotOver
+        );
+        Ok(())
+    }
+
+    pub fn has_fee_split(&self) -> bool {
+        self.has_fee_split != 0
+    }
+
+    pub fn access_gate(&self) -> AccessGate {
+        AccessGate::try_from(self.access_gate).unwrap()
+    }
+
+    pub fn is_access_gated(&self) -> bool {
+        self.access_gate() != AccessGate::None
+    }
+
+    /// Lamports charged for a transaction that adds to the event heap
+    pub fn penalty_event_heap(&self) -> u64 {
+        if self.has_penalty_event_heap != 0 {
+            self.penalty_event_heap as u64
+        } else {
+            PENALTY_EVENT_HEAP
+        }
+    }
+
+    /// Crank reward for consuming `events`, capped by the accrued penalties
+    pub fn crank_reward(&self, events: usize) -> u64 {
+        (events as u64)
+            .saturating_mul(self.crank_reward_per_event as u64)
+            .min(self.penalty_lamports_accrued)
+    }
+
+    pub fn status(&self) -> MarketStatus {
+        MarketStatus::try_from(self.status).unwrap()
+    }
+
+    /// Checks that the market status allows placing an order. Post only orders
+    /// are the ones that fail instead of taking.
+    pub fn check_can_place_order(&self, post_only: bool) -> Result<()> {
+        match self.status() {
+            MarketStatus::Active => Ok(()),
+            MarketStatus::PostOnly if post_only => Ok(()),
+            MarketStatus::PostOnly => Err(OpenBookError::MarketPostOnly.into()),
+            MarketStatus::CancelOnly => Err(OpenBookError::MarketCancelOnly.into()),
+            MarketStatus::Halted => Err(OpenBookError::MarketHalted.into()),
+        }
+    }
+
+    /// Checks that the market status allows cancelling orders
+    pub fn check_can_cancel_order(&self) -> Result<()> {
+        require!(
+            self.status() != MarketStatus::Halted,
+            OpenBookError::MarketHalted
+        );
+        Ok(())
+    }
+
+    /// Checks that the market status allows matching resting orders against each other
+    pub fn check_can_match_orders(&self) -> Result<()> {
+        require!(
+            self.status() != MarketStatus::Halted,
+            OpenBookError::MarketHalted
+        );
+        Ok(())
+    }
+
+    /// Is an order of `base_lots` at `price_lots` too small to be posted to the book?
+    pub fn is_below_min_order_size(&self, base_lots: i64, price_lots: i64) -> bool {
+        let quote_native = base_lots as i128 * price_lots as i128 * self.quote_lot_size as i128;
+        base_lots < self.min_base_lots_per_order || quote_native < self.min_quote_notional as i128
+    }
+
+    /// Is an order of `base_lots` too large to be posted to the book?
+    pub fn is_above_max_order_size(&self, base_lots: i64) -> bool {
+        self.max_base_lots_per_order != 0 && base_lots > self.max_base_lots_per_order
+    }
+
+    pub fn is_auction_phase(&self, timestamp: i64) -> bool {
+        self.auction_end_ts != 0 && self.auction_start_ts <= timestamp
+    }
+
+    pub fn is_auction_ended(&self, timestamp: i64) -> bool {
+        self.auction_end_ts != 0 && self.auction_end_ts <= timestamp
+    }
+
+    /// Switch to continuous trading, scheduling the next auction phase for recurring auctions
+    pub fn end_auction_phase(&mut self, timestamp: i64) {
+        self.auction_price_lots = 0;
+        if self.auction_interval_secs == 0 {
+            self.auction_start_ts = 0;
+            self.auction_end_ts = 0;
+        } else {
+            self.auction_start_ts = timestamp + self.auction_interval_secs as i64;
+            self.auction_end_ts = self.auction_start_ts + self.auction_duration_secs as i64;
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.base_deposit_total == 0
             && self.quote_deposit_total == 0
@@ -1,13 +1,21 @@
+pub use access_gate::*;
+pub use delegate::*;
+pub use fee_split::*;
 pub use market::*;
 pub use open_orders_account::*;
 pub use open_orders_indexer::*;
 pub use oracle::*;
 pub use orderbook::*;
+pub use referrer::*;
 
+mod access_gate;
+mod delegate;
+mod fee_split;
 mod market;
 mod open_orders_account;
 mod open_orders_indexer;
 mod orderbook;
+mod referrer;
 
 pub mod oracle;
 mod raydium_internal;
@@ -3,13 +3,26 @@ use derivative::Derivative;
 use static_assertions::const_assert_eq;
 use std::mem::size_of;
 
-use crate::logs::{emit_stack, FillLog};
+use crate::logs::{emit_stack, FillLog, MmpTriggeredLog};
 use crate::pubkey_option::NonZeroPubkeyOption;
 use crate::{error::*, logs::OpenOrdersPositionLog};
 
-use super::{BookSideOrderTree, FillEvent, LeafNode, Market, Side, SideAndOrderTree};
+use super::{
+    BookSideOrderTree, DelegatePermissions, DynBookSide, FillEvent, LeafNode, Market,
+    MarketAccessGate, OpenOrdersAccess, OpenOrdersDelegates, Side, SideAndOrderTree,
+};
 
 pub const MAX_OPEN_ORDERS: usize = 24;
+/// Version of new open orders accounts, older ones are brought up to date with
+/// [`OpenOrdersAccount::migrate`]
+pub const OPEN_ORDERS_ACCOUNT_VERSION: u8 = 1;
+
+/// Bit of [`OpenOrdersAccount::flags`] set while the account has an [`OpenOrdersDelegates`]
+/// account
+pub const OPEN_ORDERS_FLAG_DELEGATES: u8 = 1 << 0;
+/// Bit of [`OpenOrdersAccount::flags`] set while the account has an
+/// [`OpenOrdersReferral`](super::OpenOrdersReferral)
+pub const OPEN_ORDERS_FLAG_REFERRAL: u8 = 1 << 1;
 
 #[account(zero_copy)]
 #[derive(Debug)]
@@ -29,7 +42,10 @@ pub struct OpenOrdersAccount {
     // Introducing a version as we are adding a new field bids_quote_lots
     pub version: u8,
 
-    pub padding: [u8; 2],
+    /// See [`OpenOrdersAccess`]
+    pub access: u8,
+    /// Side accounts of the account, see `OPEN_ORDERS_FLAG_*`
+    pub flags: u8,
 
     pub position: Position,
 
@@ -43,7 +59,9 @@ const_assert_eq!(
         + 32
         + 4
         + 1
-        + 3
+        + 1
+        + 1
+        + 1
         + size_of::<Position>()
         + MAX_OPEN_ORDERS * size_of::<OpenOrder>()
 );
@@ -70,30 +88,177 @@ impl OpenOrdersAccount {
             delegate: NonZeroPubkeyOption::default(),
             account_num: 0,
             bump: 0,
-            version: 1,
-            padding: [0; 2],
+            version: OPEN_ORDERS_ACCOUNT_VERSION,
+            access: OpenOrdersAccess::Unchecked.into(),
+            flags: 0,
             position: Position::default(),
             open_orders: [OpenOrder::default(); MAX_OPEN_ORDERS],
         })
     }
 
-    pub fn is_owner_or_delegate(&self, ix_signer: Pubkey) -> bool {
-        let delegate_option: Option<Pubkey> = Option::from(self.delegate);
-        if let Some(delegate) = delegate_option {
-            return self.owner == ix_signer || delegate == ix_signer;
+    pub fn has_delegates(&self) -> bool {
+        self.flags & OPEN_ORDERS_FLAG_DELEGATES != 0
+    }
+
+    pub fn has_referral(&self) -> bool {
+        self.flags & OPEN_ORDERS_FLAG_REFERRAL != 0
+    }
+
+    pub fn set_flag(&mut self, flag: u8, value: bool) {
+        if value {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
         }
-        self.owner == ix_signer
     }
 
-    pub fn is_settle_destination_allowed(&self, ix_signer: Pubkey, account_owner: Pubkey) -> bool {
-        // delegate can withdraw to owner accounts
-        let delegate_option: Option<Pubkey> = Option::from(self.delegate);
-        if Some(ix_signer) == delegate_option {
-            return self.owner == account_owner;
+    /// Permissions of the signer if it's the delegate or in the delegate list, None for
+    /// the owner and anyone else. `delegates` must be passed if the account has some.
+    pub fn delegate_permissions(
+        &self,
+        ix_signer: Pubkey,
+        delegates: Option<&OpenOrdersDelegates>,
+    ) -> Option<DelegatePermissions> {
+        if ix_signer == self.owner {
+            return None;
+        }
+        if self.delegate == ix_signer {
+            return Some(
+                delegates.map_or_else(DelegatePermissions::default, |delegates| {
+                    delegates.delegate_permissions
+                }),
+            );
         }
+        delegates
+            .and_then(|delegates| delegates.find(ix_signer))
+            .map(|entry| entry.permissions)
+    }
 
-        // owner can withdraw to anywhere
-        ix_signer == self.owner
+    pub fn access(&self) -> OpenOrdersAccess {
+        OpenOrdersAccess::try_from(self.access).unwrap()
+    }
+
+    /// Checks that the account may place orders on an access gated market, and that its
+    /// owner still passes the gate with the proof in `remaining_accounts`
+    pub fn check_access(&self, market: &Market, remaining_accounts: &[AccountInfo]) -> Result<()> {
+        if market.is_access_gated() {
+            require!(
+                self.access() == OpenOrdersAccess::Granted,
+                OpenBookError::OpenOrdersAccessDenied
+            );
+            MarketAccessGate::check_proof_in(self.market, self.owner, remaining_accounts)?;
+        }
+        Ok(())
+    }
+
+    /// Brings an account of an older version up to [`OPEN_ORDERS_ACCOUNT_VERSION`], one
+    /// version at a time. Returns false if it was up to date already.
+    pub fn migrate(&mut self, market: &Market, bids: &DynBookSide) -> Result<bool> {
+        let from_version = self.version;
+        while self.version < OPEN_ORDERS_ACCOUNT_VERSION {
+            match self.version {
+                0 => self.migrate_from_v0(market, bids)?,
+                _ => unreachable!(),
+            }
+            self.version += 1;
+        }
+        Ok(self.version != from_version)
+    }
+
+    /// Version 0 didn't track `bids_quote_lots`: recompute it from the open bids on the book
+    /// and the price their funds were locked at, and recompute `locked_maker_fees` from
+    /// them. What was locked above that is rounding, which is freed.
+    fn migrate_from_v0(&mut self, market: &Market, bids: &DynBookSide) -> Result<()> {
+        let mut bids_base_lots = 0;
+        let mut bids_quote_lots = 0;
+        let mut maker_fees = 0;
+        for oo in self
+            .all_orders_in_use()
+            .filter(|oo| oo.side_and_tree().side() == Side::Bid)
+        {
+            let quantity = bids
+                .leaf_by_key(oo.side_and_tree().order_tree(), oo.id)
+                .map(|leaf| leaf.quantity)
+                .ok_or(OpenBookError::OpenOrdersAccountNotMigratable)?;
+
+            bids_base_lots += quantity;
+            bids_quote_lots += quantity * oo.locked_price;
+            maker_fees +=
+                market.maker_fees_ceil((quantity * oo.locked_price * market.quote_lot_size) as u64);
+        }
+
+        // Fills that aren't processed yet are only reflected on the book
+        require!(
+            bids_base_lots == self.position.bids_base_lots
+                && maker_fees <= self.position.locked_maker_fees,
+            OpenBookError::OpenOrdersAccountNotMigratable
+        );
+
+        let pa = &mut self.position;
+        pa.bids_quote_lots = bids_quote_lots;
+        pa.quote_free_native += pa.locked_maker_fees - maker_fees;
+        pa.locked_maker_fees = maker_fees;
+        Ok(())
+    }
+
+    /// Has the owner failed to send a heartbeat before the configured deadline?
+    pub fn is_heartbeat_expired(&self, now_ts: u64) -> bool {
+        self.position.heartbeat_deadline != 0 && self.position.heartbeat_deadline < now_ts
+    }
+
+    /// Are fills of this account counted by the market maker protection?
+    pub fn is_mmp_enabled(&self) -> bool {
+        self.position.mmp_window_secs != 0
+    }
+
+    /// Was the market maker protection triggered and not reset yet?
+    pub fn is_mmp_frozen(&self) -> bool {
+        self.position.mmp_frozen != 0
+    }
+
+    /// Should new orders of this account be kept off the book?
+    pub fn is_quoting_disabled(&self, now_ts: u64) -> bool {
+        self.is_heartbeat_expired(now_ts) || self.is_mmp_frozen()
+    }
+
+    /// Count a maker fill of `base_lots` towards the market maker protection window and
+    /// freeze the account when one of the limits is reached. Called while matching, since
+    /// fills may only reach the account much later through the event heap.
+    pub fn update_mmp(&mut self, account_pk: Pubkey, now_ts: u64, base_lots: i64) {
+        let market = self.market;
+        let pa = &mut self.position;
+        if pa.mmp_window_secs == 0 || pa.mmp_frozen != 0 {
+            return;
+        }
+
+        if now_ts >= pa.mmp_window_start + pa.mmp_window_secs as u64 {
+            pa.mmp_window_start = now_ts;
+            pa.mmp_window_base_lots = 0;
+            pa.mmp_window_fill_count = 0;
+        }
+        pa.mmp_window_base_lots += base_lots;
+        pa.mmp_window_fill_count = pa.mmp_window_fill_count.saturating_add(1);
+
+        let base_lots_reached =
+            pa.mmp_base_lots_limit != 0 && pa.mmp_window_base_lots >= pa.mmp_base_lots_limit;
+        let fill_count_reached =
+            pa.mmp_fill_count_limit != 0 && pa.mmp_window_fill_count >= pa.mmp_fill_count_limit;
+        if base_lots_reached || fill_count_reached {
+            pa.mmp_frozen = 1;
+            emit_stack(MmpTriggeredLog {
+                open_orders_account: account_pk,
+                market,
+                window_start: pa.mmp_window_start,
+                window_base_lots: pa.mmp_window_base_lots,
+                window_fill_count: pa.mmp_window_fill_count,
+            });
+        }
+    }
+
+    /// The signer must have been checked to be the owner or a delegate
+    pub fn is_settle_destination_allowed(&self, ix_signer: Pubkey, account_owner: Pubkey) -> bool {
+        // owner can withdraw to anywhere, delegates can withdraw to owner accounts
+        ix_signer == self.owner || self.owner == account_owner
     }
 
     pub fn all_orders(&self) -> impl Iterator<Item = &OpenOrder> {
@@ -139,6 +304,9 @@ impl OpenOrdersAccount {
 
         let (maker_fees, maker_rebate) = if is_self_trade {
             (0, 0)
+        } else if fill.is_auction() {
+            // Without a taker there are no taker fees to pay rebates from
+            (market.maker_fees_floor(quote_native), 0)
         } else {
             (
                 market.maker_fees_floor(quote_native),
@@ -198,8 +366,8 @@ impl OpenOrdersAccount {
             }
         }
 
-        // Calculate taker fee, ignoring self trades
-        let taker_fee_ceil = if quote_native > 0 && fill.maker != fill.taker {
+        // Calculate taker fee, ignoring self trades and auction fills which have no taker
+        let taker_fee_ceil = if quote_native > 0 && !is_self_trade && !fill.is_auction() {
             market.taker_fees_ceil(quote_native)
         } else {
             0
@@ -358,7 +526,7 @@ pub struct Position {
 
     pub locked_maker_fees: u64,
     pub referrer_rebates_available: u64,
-    /// Count of ixs when events are added to the heap
+    /// Count of ixs when events are added to the heap since the last settle
     /// To avoid this, send remaining accounts in order to process the events
     pub penalty_heap_count: u64,
 
@@ -370,13 +538,38 @@ pub struct Position {
     /// Quote lots in open bids
     pub bids_quote_lots: i64,
 
+    /// Timestamp after which resting orders of this account are treated as invalid,
+    /// 0 if the heartbeat is disabled
+    pub heartbeat_deadline: u64,
+
+    /// Market maker protection: freeze once this many base lots were filled as maker
+    /// within the window, 0 to ignore
+    pub mmp_base_lots_limit: i64,
+    /// Base lots filled as maker in the current window
+    pub mmp_window_base_lots: i64,
+    /// Start timestamp of the current window
+    pub mmp_window_start: u64,
+    /// Length of the window in seconds, 0 if market maker protection is disabled
+    pub mmp_window_secs: u16,
+    /// Freeze once this many maker fills happened within the window, 0 to ignore
+    pub mmp_fill_count_limit: u16,
+    /// Maker fills in the current window
+    pub mmp_window_fill_count: u16,
+    /// While frozen, the resting orders of this account are treated as invalid
+    pub mmp_frozen: u8,
+    pub mmp_padding: u8,
+
+    /// Penalty lamports owed for the heap additions, priced when the events were added
+    /// and paid on settle
+    pub penalty_lamports_owed: u64,
+
     #[derivative(Debug = "ignore")]
-    pub reserved: [u8; 64],
+    pub reserved: [u8; 16],
 }
 
 const_assert_eq!(
     size_of::<Position>(),
-    8 + 8 + 8 + 8 + 8 + 8 + 8 + 16 + 16 + 8 + 64
+    8 + 8 + 8 + 8 + 8 + 8 + 8 + 16 + 16 + 8 + 8 + 8 + 8 + 8 + 2 + 2 + 2 + 1 + 1 + 8 + 16
 );
 const_assert_eq!(size_of::<Position>(), 160);
 const_assert_eq!(size_of::<Position>() % 8, 0);
@@ -394,7 +587,17 @@ impl Default for Position {
             maker_volume: 0,
             taker_volume: 0,
             bids_quote_lots: 0,
-            reserved: [0; 64],
+            heartbeat_deadline: 0,
+            mmp_base_lots_limit: 0,
+            mmp_window_base_lots: 0,
+            mmp_window_start: 0,
+            mmp_window_secs: 0,
+            mmp_fill_count_limit: 0,
+            mmp_window_fill_count: 0,
+            mmp_frozen: 0,
+            mmp_padding: 0,
+            penalty_lamports_owed: 0,
+            reserved: [0; 16],
         }
     }
 }
@@ -416,6 +619,7 @@ impl Position {
             && self.locked_maker_fees == 0
             && self.referrer_rebates_available == 0
             && self.penalty_heap_count == 0
+            && self.penalty_lamports_owed == 0
             // For version 0, bids_quote_lots was not properly tracked
             && (version == 0 || self.bids_quote_lots == 0)
     }
@@ -0,0 +1,290 @@
+use anchor_lang::prelude::*;
+
+use super::*;
+use crate::state::{DynEventHeap, Market};
+
+/// Collects the book orders that cross `price_lots`, in matching order
+fn crossing_orders(
+    bookside: &DynBookSide,
+    price_lots: i64,
+    now_ts: u64,
+    oracle_price_lots: Option<i64>,
+) -> Vec<(BookSideOrderHandle, LeafNode, i64)> {
+    let side = bookside.side();
+    bookside
+        .iter_valid(now_ts, oracle_price_lots)
+        .take_while(|order| !side.is_price_better(price_lots, order.price_lots))
+        .map(|order| {
+            // Bids lock funds at their peg limit (oracle pegged) or at their price (fixed)
+            let locked_price = match order.handle.order_tree {
+                BookSideOrderTree::OraclePegged => order.node.peg_limit,
+                BookSideOrderTree::Fixed => order.price_lots,
+            };
+            (order.handle, *order.node, locked_price)
+        })
+        .collect()
+}
+
+impl<'a> Orderbook<'a> {
+    /// Removes the crossing orders of makers frozen by their market maker protection, the
+    /// way matching drops them. The accounts of the makers with market maker protection
+    /// among the crossing orders must be passed.
+    pub fn drop_mmp_frozen_crossing_orders<'c: 'info, 'info>(
+        &mut self,
+        market: &Market,
+        now_ts: u64,
+        oracle_price_lots: Option<i64>,
+        remaining_accs: &'c [AccountInfo<'info>],
+    ) -> Result<()> {
+        let (Some(best_bid), Some(best_ask)) = (
+            self.bids.best_price(now_ts, oracle_price_lots),
+            self.asks.best_price(now_ts, oracle_price_lots),
+        ) else {
+            return Ok(());
+        };
+        if best_bid < best_ask {
+            return Ok(());
+        }
+
+        for (bookside, price_lots) in [(&mut *self.bids, best_ask), (&mut *self.asks, best_bid)] {
+            let mut frozen_orders = vec![];
+            for (handle, leaf, _) in
+                crossing_orders(bookside, price_lots, now_ts, oracle_price_lots)
+            {
+                if let Some(maker) = mmp_maker_account(&leaf, remaining_accs)? {
+                    let mut maker_account = maker.load_mut()?;
+                    if maker_account.is_mmp_frozen() {
+                        maker_account.cancel_order(
+                            leaf.owner_slot as usize,
+                            leaf.quantity,
+                            *market,
+                        );
+                        frozen_orders.push((handle.order_tree, leaf.key));
+                    }
+                }
+            }
+            for (order_tree, key) in frozen_orders {
+                let _removed_leaf = bookside.remove_by_key(order_tree, key).unwrap();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Computes the uniform price at which the most base lots would be exchanged when
+    /// matching all crossing bids and asks, together with that amount of base lots.
+    ///
+    /// Ties are broken by the smallest imbalance between the bid and ask volume at the
+    /// price, then by the lowest price. Returns None if the book is not crossed.
+    pub fn auction_clearing_price(
+        &self,
+        now_ts: u64,
+        oracle_price_lots: Option<i64>,
+    ) -> Option<(i64, i64)> {
+        // bids descending and asks ascending by price
+        let bids: Vec<(i64, i64)> = self
+            .bids
+            .iter_valid(now_ts, oracle_price_lots)
+            .map(|order| (order.price_lots, order.node.quantity))
+            .collect();
+        let asks: Vec<(i64, i64)> = self
+            .asks
+            .iter_valid(now_ts, oracle_price_lots)
+            .map(|order| (order.price_lots, order.node.quantity))
+            .collect();
+
+        let best_bid = bids.first()?.0;
+        let best_ask = asks.first()?.0;
+        if best_bid < best_ask {
+            return None;
+        }
+
+        let mut candidates: Vec<i64> = bids
+            .iter()
+            .chain(asks.iter())
+            .map(|(price, _)| *price)
+            .filter(|price| (best_ask..=best_bid).contains(price))
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        // Walk the candidates in ascending order: the bid volume at or above the price
+        // only shrinks while the ask volume at or below the price only grows
+        let mut bid_volume: i64 = bids.iter().map(|(_, quantity)| quantity).sum();
+        let mut ask_volume = 0;
+        let mut bid_index = bids.len();
+        let mut ask_index = 0;
+
+        let mut best: Option<(i64, i64, i64)> = None;
+        for price in candidates {
+            while bid_index > 0 && bids[bid_index - 1].0 < price {
+                bid_index -= 1;
+                bid_volume -= bids[bid_index].1;
+            }
+            while ask_index < asks.len() && asks[ask_index].0 <= price {
+                ask_volume += asks[ask_index].1;
+                ask_index += 1;
+            }
+
+            let volume = bid_volume.min(ask_volume);
+            let imbalance = (bid_volume - ask_volume).abs();
+            let is_better = match best {
+                None => true,
+                Some((_, best_volume, best_imbalance)) => {
+                    volume > best_volume || (volume == best_volume && imbalance < best_imbalance)
+                }
+            };
+            if is_better {
+                best = Some((price, volume, imbalance));
+            }
+        }
+
+        best.map(|(price, volume, _)| (price, volume))
+    }
+
+    /// Matches up to `limit` pairs of crossing bids and asks at `price_lots`, in price-time
+    /// priority. Both sides of a match are makers: each receives a [`FillEvent`] which is
+    /// processed directly if its open orders account is in `remaining_accs`, otherwise it
+    /// is added to the event heap. The accounts of makers with market maker protection
+    /// must be passed. Matching stops after a fill that froze a maker, whose orders are
+    /// then dropped by [`Self::drop_mmp_frozen_crossing_orders`] on the next call.
+    ///
+    /// Returns the matched base lots and whether no crossing orders are left.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_auction<'c: 'info, 'info>(
+        &mut self,
+        market: &mut Market,
+        event_heap: &mut DynEventHeap,
+        price_lots: i64,
+        now_ts: u64,
+        oracle_price_lots: Option<i64>,
+        mut limit: u8,
+        remaining_accs: &'c [AccountInfo<'info>],
+    ) -> Result<(i64, bool)> {
+        let bids = crossing_orders(&self.bids, price_lots, now_ts, oracle_price_lots);
+        let asks = crossing_orders(&self.asks, price_lots, now_ts, oracle_price_lots);
+
+        let mut matched_base_lots = 0;
+        let mut number_of_processed_fill_events = 0;
+        let (mut bid_index, mut ask_index) = (0, 0);
+        let (mut bid_filled, mut ask_filled) = (0, 0);
+
+        while bid_index < bids.len()
+            && ask_index < asks.len()
+            && limit > 0
+            // every match adds up to two events
+            && event_heap.len() + 2 <= event_heap.nodes.len()
+        {
+            let (_, bid, bid_locked_price) = bids[bid_index];
+            let (_, ask, _) = asks[ask_index];
+
+            let quantity = (bid.quantity - bid_filled).min(ask.quantity - ask_filled);
+            bid_filled += quantity;
+            ask_filled += quantity;
+            let bid_out = bid_filled == bid.quantity;
+            let ask_out = ask_filled == ask.quantity;
+
+            // The bid is filled below its locked price, which is handled the same way
+            // as fills of oracle pegged bids below their peg limit
+            let mut bid_fill = FillEvent::new(
+                Side::Ask,
+                bid_out,
+                bid.owner_slot,
+                now_ts,
+                market.seq_num,
+                bid.owner,
+                bid.client_order_id,
+                bid.timestamp.into(),
+                ask.owner,
+                ask.client_order_id,
+                price_lots,
+                bid_locked_price,
+                quantity,
+            );
+            bid_fill.auction = 1;
+
+            let mut ask_fill = FillEvent::new(
+                Side::Bid,
+                ask_out,
+                ask.owner_slot,
+                now_ts,
+                market.seq_num,
+                ask.owner,
+                ask.client_order_id,
+                ask.timestamp.into(),
+                bid.owner,
+                bid.client_order_id,
+                price_lots,
+                ask.peg_limit,
+                quantity,
+            );
+            ask_fill.auction = 1;
+
+            // Both sides count towards the market maker protection of their owners
+            let mut maker_frozen = false;
+            if bid.owner != ask.owner {
+                for leaf in [bid, ask] {
+                    if let Some(maker) = mmp_maker_account(&leaf, remaining_accs)? {
+                        let mut maker_account = maker.load_mut()?;
+                        maker_account.update_mmp(maker.key(), now_ts, quantity);
+                        maker_frozen |= maker_account.is_mmp_frozen();
+                    }
+                }
+            }
+
+            for fill in [bid_fill, ask_fill] {
+                process_fill_event(
+                    fill,
+                    market,
+                    event_heap,
+                    remaining_accs,
+                    &mut number_of_processed_fill_events,
+                )?;
+            }
+
+            if bid_out {
+                bid_index += 1;
+                bid_filled = 0;
+            }
+            if ask_out {
+                ask_index += 1;
+                ask_filled = 0;
+            }
+            matched_base_lots += quantity;
+            limit -= 1;
+
+            if maker_frozen {
+                break;
+            }
+        }
+
+        // Apply changes to partially matched orders (handles invalidate on delete!)
+        if bid_filled > 0 {
+            let (handle, bid, _) = bids[bid_index];
+            self.bids
+                .node_mut(handle.node)
+                .unwrap()
+                .as_leaf_mut()
+                .unwrap()
+                .quantity = bid.quantity - bid_filled;
+        }
+        if ask_filled > 0 {
+            let (handle, ask, _) = asks[ask_index];
+            self.asks
+                .node_mut(handle.node)
+                .unwrap()
+                .as_leaf_mut()
+                .unwrap()
+                .quantity = ask.quantity - ask_filled;
+        }
+        for (handle, bid, _) in &bids[..bid_index] {
+            let _removed_leaf = self.bids.remove_by_key(handle.order_tree, bid.key).unwrap();
+        }
+        for (handle, ask, _) in &asks[..ask_index] {
+            let _removed_leaf = self.asks.remove_by_key(handle.order_tree, ask.key).unwrap();
+        }
+
+        let completed = bid_index == bids.len() || ask_index == asks.len();
+        Ok((matched_base_lots, completed))
+    }
+}
@@ -2,7 +2,7 @@ use crate::logs::*;
 use crate::state::MAX_OPEN_ORDERS;
 use crate::{
     error::*,
-    state::{orderbook::bookside::*, EventHeap, Market, OpenOrdersAccount},
+    state::{orderbook::bookside::*, DynEventHeap, Market, OpenOrdersAccount},
 };
 use anchor_lang::prelude::*;
 use bytemuck::cast;
@@ -18,8 +18,8 @@ pub const DROP_EXPIRED_ORDER_LIMIT: usize = 5;
 pub const FILL_EVENT_REMAINING_LIMIT: usize = 15;
 
 pub struct Orderbook<'a> {
-    pub bids: RefMut<'a, BookSide>,
-    pub asks: RefMut<'a, BookSide>,
+    pub bids: RefMut<'a, DynBookSide>,
+    pub asks: RefMut<'a, DynBookSide>,
 }
 
 pub struct OrderWithAmounts {
@@ -43,14 +43,14 @@ impl<'a> Orderbook<'a> {
         self.bids.is_empty() && self.asks.is_empty()
     }
 
-    pub fn bookside_mut(&mut self, side: Side) -> &mut BookSide {
+    pub fn bookside_mut(&mut self, side: Side) -> &mut DynBookSide {
         match side {
             Side::Bid => &mut self.bids,
             Side::Ask => &mut self.asks,
         }
     }
 
-    pub fn bookside(&self, side: Side) -> &BookSide {
+    pub fn bookside(&self, side: Side) -> &DynBookSide {
         match side {
             Side::Bid => &self.bids,
             Side::Ask => &self.asks,
@@ -63,7 +63,7 @@ impl<'a> Orderbook<'a> {
         order: &Order,
         open_book_market: &mut Market,
         market_pk: &Pubkey,
-        event_heap: &mut EventHeap,
+        event_heap: &mut DynEventHeap,
         oracle_price_lots: Option<i64>,
         mut open_orders_account: Option<&mut OpenOrdersAccount>,
         owner: &Pubkey,
@@ -81,6 +81,13 @@ impl<'a> Orderbook<'a> {
         let mut post_target = order.post_target();
         let (price_lots, price_data) = order.price(now_ts, oracle_price_lots, self)?;
 
+        // During an auction phase orders accumulate on the book without matching
+        let auction_phase = market.is_auction_phase(now_ts as i64);
+        require!(
+            !auction_phase || post_target.is_some(),
+            OpenBookError::MarketInAuction
+        );
+
         // generate new order id
         let order_id = market.gen_order_id(side, price_data);
 
@@ -119,15 +126,42 @@ impl<'a> Orderbook<'a> {
         let mut matched_order_deletes: Vec<(BookSideOrderTree, u128)> = vec![];
         let mut number_of_dropped_expired_orders = 0;
         let mut number_of_processed_fill_events = 0;
+        let mut skipped_mmp_orders = false;
 
         let opposing_bookside = self.bookside_mut(other_side);
         for best_opposing in opposing_bookside.iter_all_including_invalid(now_ts, oracle_price_lots)
         {
-            if remaining_base_lots == 0 || remaining_quote_lots == 0 {
+            if remaining_base_lots == 0 || remaining_quote_lots == 0 || auction_phase {
                 break;
             }
 
-            if !best_opposing.is_valid() {
+            let is_self_trade = open_orders_account.is_some() && owner == &best_opposing.node.owner;
+
+            // Fills against makers with market maker protection are counted while matching,
+            // so a single order can't sweep past their limits. Orders of frozen makers are
+            // dropped like expired ones. Orders of makers whose account is missing are
+            // skipped, their fills couldn't be counted.
+            let mmp_maker = if best_opposing.node.mmp_enabled != 0
+                && best_opposing.is_valid()
+                && !is_self_trade
+                && side.is_price_within_limit(best_opposing.price_lots, price_lots)
+            {
+                match maker_account(best_opposing.node, remaining_accs)? {
+                    Some(maker) => Some(maker),
+                    None => {
+                        skipped_mmp_orders = true;
+                        continue;
+                    }
+                }
+            } else {
+                None
+            };
+            let mmp_frozen = match &mmp_maker {
+                Some(maker) => maker.load()?.is_mmp_frozen(),
+                None => false,
+            };
+
+            if !best_opposing.is_valid() || mmp_frozen {
                 // Remove the order from the book unless we've done that enough
                 if number_of_dropped_expired_orders < DROP_EXPIRED_ORDER_LIMIT {
                     number_of_dropped_expired_orders += 1;
@@ -183,7 +217,7 @@ impl<'a> Orderbook<'a> {
             let match_quote_lots = match_base_lots * best_opposing_price;
 
             // Self-trade behaviour
-            if open_orders_account.is_some() && owner == &best_opposing.node.owner {
+            if is_self_trade {
                 match order.self_trade_behavior {
                     SelfTradeBehavior::DecrementTake => {
                         // remember all decremented quote lots to only charge fees on not-self-trades
@@ -216,6 +250,12 @@ impl<'a> Orderbook<'a> {
             remaining_quote_lots -= match_quote_lots;
             assert!(remaining_quote_lots >= 0);
 
+            if let Some(maker) = &mmp_maker {
+                maker
+                    .load_mut()?
+                    .update_mmp(maker.key(), now_ts, match_base_lots);
+            }
+
             let new_best_opposing_quantity = best_opposing.node.quantity - match_base_lots;
             let maker_out = new_best_opposing_quantity == 0;
             if maker_out {
@@ -233,7 +273,7 @@ impl<'a> Orderbook<'a> {
                 market.seq_num,
                 best_opposing.node.owner,
                 best_opposing.node.client_order_id,
-                best_opposing.node.timestamp,
+                best_opposing.node.timestamp.into(),
                 *owner,
                 order.client_order_id,
                 best_opposing_price,
@@ -257,6 +297,12 @@ impl<'a> Orderbook<'a> {
             limit -= 1;
         }
 
+        // The remainder would cross the skipped orders
+        if skipped_mmp_orders && post_target.is_some() {
+            msg!("Posting on book disallowed due to skipped orders of makers with market maker protection, their accounts are missing");
+            post_target = None;
+        }
+
         let total_quote_lots_taken = order_max_quote_lots - remaining_quote_lots;
         let total_base_lots_taken = order.max_base_lots - remaining_base_lots;
         assert!(total_quote_lots_taken >= 0);
@@ -370,6 +416,23 @@ impl<'a> Orderbook<'a> {
             post_target = None;
         }
 
+        if post_target.is_some()
+            && market.is_below_min_order_size(book_base_quantity_lots, price_lots)
+        {
+            msg!(
+                "Posting on book disallowed due to the minimum order size, base lots {:?}",
+                book_base_quantity_lots,
+            );
+            post_target = None;
+        }
+
+        if let Some(open_orders) = open_orders_account.as_deref() {
+            if post_target.is_some() && open_orders.is_quoting_disabled(now_ts) {
+                msg!("Posting on book disallowed while quoting is disabled");
+                post_target = None;
+            }
+        }
+
         // There is still quantity, but it's a fill or kill order -> kill
         if fill_or_kill && remaining_base_lots > 0 {
             return err!(OpenBookError::WouldExecutePartially);
@@ -385,6 +448,10 @@ impl<'a> Orderbook<'a> {
                 book_base_quantity_lots * price,
                 OpenBookError::InvalidPostAmount
             );
+            require!(
+                !market.is_above_max_order_size(book_base_quantity_lots),
+                OpenBookError::OrderSizeAboveMaximum
+            );
 
             posted_base_native = book_base_quantity_lots * market.base_lot_size;
             posted_quote_native = book_base_quantity_lots * price * market.quote_lot_size;
@@ -451,16 +518,21 @@ impl<'a> Orderbook<'a> {
             }
 
             let owner_slot = open_orders.next_order_slot()?;
-            let new_order = LeafNode::new(
+            let timestamp = now_ts
+                .try_into()
+                .map_err(|_| error!(OpenBookError::OrderTimestampOverflow))?;
+            let mut new_order = LeafNode::new(
                 owner_slot as u8,
                 order_id,
                 *owner,
                 book_base_quantity_lots,
-                now_ts,
+                timestamp,
                 order.time_in_force,
                 order.peg_limit(),
                 order.client_order_id,
             );
+            new_order.heartbeat_deadline = open_orders.position.heartbeat_deadline as u32;
+            new_order.mmp_enabled = open_orders.is_mmp_enabled().into();
             let _result = bookside.insert_leaf(order_tree_target, &new_order)?;
 
             open_orders.add_order(
@@ -490,6 +562,24 @@ impl<'a> Orderbook<'a> {
         })
     }
 
+    /// Applies `update` to all resting orders of the open orders account, used to keep
+    /// the per order copies of account settings in sync.
+    pub fn update_resting_orders(
+        &mut self,
+        open_orders_account: &OpenOrdersAccount,
+        update: impl Fn(&mut LeafNode),
+    ) {
+        for oo in open_orders_account.all_orders_in_use() {
+            let side_and_tree = oo.side_and_tree();
+            if let Some(leaf) = self
+                .bookside_mut(side_and_tree.side())
+                .leaf_by_key_mut(side_and_tree.order_tree(), oo.id)
+            {
+                update(leaf);
+            }
+        }
+    }
+
     /// Cancels up to `limit` orders that are listed on the openorders account for the given market.
     /// Optionally filters by `side_to_cancel_option`.
     /// The orders are removed from the book and from the openorders account open order list.
@@ -577,10 +667,35 @@ impl<'a> Orderbook<'a> {
     }
 }
 
+/// The open orders account of the owner of `leaf` among `remaining_accs`, if passed
+pub fn maker_account<'c: 'info, 'info>(
+    leaf: &LeafNode,
+    remaining_accs: &'c [AccountInfo<'info>],
+) -> Result<Option<AccountLoader<'info, OpenOrdersAccount>>> {
+    match remaining_accs.iter().find(|ai| ai.key == &leaf.owner) {
+        Some(acc) => Ok(Some(AccountLoader::try_from(acc)?)),
+        None => Ok(None),
+    }
+}
+
+/// The open orders account of the owner of `leaf` if it has market maker protection
+/// enabled, which must be passed then
+pub fn mmp_maker_account<'c: 'info, 'info>(
+    leaf: &LeafNode,
+    remaining_accs: &'c [AccountInfo<'info>],
+) -> Result<Option<AccountLoader<'info, OpenOrdersAccount>>> {
+    if leaf.mmp_enabled == 0 {
+        return Ok(None);
+    }
+    let maker = maker_account(leaf, remaining_accs)?
+        .ok_or_else(|| error!(OpenBookError::MmpMakerAccountMissing))?;
+    Ok(Some(maker))
+}
+
 pub fn process_out_event<'c: 'info, 'info>(
     event: OutEvent,
     market: &Market,
-    event_heap: &mut EventHeap,
+    event_heap: &mut DynEventHeap,
     open_orders_account: Option<&mut OpenOrdersAccount>,
     owner: &Pubkey,
     remaining_accs: &'c [AccountInfo<'info>],
@@ -606,7 +721,7 @@ pub fn process_out_event<'c: 'info, 'info>(
 pub fn process_fill_event<'c: 'info, 'info>(
     event: FillEvent,
     market: &mut Market,
-    event_heap: &mut EventHeap,
+    event_heap: &mut DynEventHeap,
     remaining_accs: &'c [AccountInfo<'info>],
     number_of_processed_fill_events: &mut usize,
 ) -> Result<()> {
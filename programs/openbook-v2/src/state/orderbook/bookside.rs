@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use static_assertions::const_assert_eq;
+use std::cell::{Ref, RefMut};
+use std::mem::size_of;
+use std::ops::{Deref, DerefMut};
 
 use super::*;
+use crate::error::OpenBookError;
 
 #[derive(
     Eq,
@@ -27,6 +32,8 @@ pub struct BookSideOrderHandle {
     pub order_tree: BookSideOrderTree,
 }
 
+/// This is the layout of book sides with the default capacity. Book sides of any capacity
+/// are used through [`DynBookSide`], which this derefs to.
 #[account(zero_copy)]
 pub struct BookSide {
     pub roots: [OrderTreeRoot; 2],
@@ -41,7 +48,114 @@ const_assert_eq!(
 const_assert_eq!(std::mem::size_of::<BookSide>(), 90944);
 const_assert_eq!(std::mem::size_of::<BookSide>() % 8, 0);
 
+/// Size of the fields of [`BookSide`] before the order tree nodes
+const BOOK_SIDE_HEADER_SIZE: usize = 6 * size_of::<OrderTreeRoot>() + 256;
+
 impl BookSide {
+    /// Number of bytes needed for a book side of `capacity` nodes, including the discriminator
+    pub fn space(capacity: usize) -> usize {
+        8 + BOOK_SIDE_HEADER_SIZE + ORDER_TREE_NODES_HEADER_SIZE + capacity * size_of::<AnyNode>()
+    }
+
+    /// Capacity of a book side account of `data_len` bytes, if it fits exactly
+    pub fn capacity_for_space(data_len: usize) -> Option<usize> {
+        let nodes_len = data_len.checked_sub(Self::space(0))?;
+        (nodes_len % size_of::<AnyNode>() == 0).then_some(nodes_len / size_of::<AnyNode>())
+    }
+}
+
+impl Deref for BookSide {
+    type Target = DynBookSide;
+
+    fn deref(&self) -> &DynBookSide {
+        DynBookSide::from_bytes_unchecked(bytemuck::bytes_of(self), MAX_ORDERTREE_NODES)
+    }
+}
+
+impl DerefMut for BookSide {
+    fn deref_mut(&mut self) -> &mut DynBookSide {
+        DynBookSide::from_bytes_unchecked_mut(bytemuck::bytes_of_mut(self), MAX_ORDERTREE_NODES)
+    }
+}
+
+/// A [`BookSide`] of any capacity, chosen when the market is created. The capacity is stored
+/// in the order tree header, book sides that predate it have [`MAX_ORDERTREE_NODES`].
+#[repr(C)]
+pub struct DynBookSide {
+    pub roots: [OrderTreeRoot; 2],
+    pub reserved_roots: [OrderTreeRoot; 4],
+    pub reserved: [u8; 256],
+    pub nodes: DynOrderTreeNodes,
+}
+
+impl DynBookSide {
+    fn from_bytes_unchecked(bytes: &[u8], capacity: usize) -> &Self {
+        assert!(bytes.len() >= BookSide::space(capacity) - 8);
+        assert_eq!(bytes.as_ptr().align_offset(8), 0);
+        // SAFETY: Self is repr(C) and made of Pod fields, and the bytes are large and aligned
+        // enough for the header and `capacity` nodes
+        unsafe { &*(std::ptr::slice_from_raw_parts(bytes.as_ptr(), capacity) as *const Self) }
+    }
+
+    fn from_bytes_unchecked_mut(bytes: &mut [u8], capacity: usize) -> &mut Self {
+        assert!(bytes.len() >= BookSide::space(capacity) - 8);
+        assert_eq!(bytes.as_ptr().align_offset(8), 0);
+        // SAFETY: see from_bytes_unchecked
+        unsafe {
+            &mut *(std::ptr::slice_from_raw_parts_mut(bytes.as_mut_ptr(), capacity) as *mut Self)
+        }
+    }
+
+    /// Capacity stored in the order tree header of the account data, after the discriminator
+    fn stored_capacity(data: &[u8]) -> Result<usize> {
+        require_gte!(
+            data.len(),
+            BookSide::space(0) - 8,
+            ErrorCode::AccountDidNotDeserialize
+        );
+        let capacity = match Self::from_bytes_unchecked(data, 0).nodes.capacity {
+            0 => MAX_ORDERTREE_NODES,
+            capacity => capacity as usize,
+        };
+        require_gte!(
+            data.len(),
+            BookSide::space(capacity) - 8,
+            ErrorCode::AccountDidNotDeserialize
+        );
+        Ok(capacity)
+    }
+
+    /// Reads a book side of any capacity from its account data
+    pub fn from_account_data(data: &[u8]) -> Result<&Self> {
+        require!(
+            data.len() >= 8 && data[..8] == BookSide::discriminator(),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let capacity = Self::stored_capacity(&data[8..])?;
+        Ok(Self::from_bytes_unchecked(&data[8..], capacity))
+    }
+
+    pub fn from_account_data_mut(data: &mut [u8]) -> Result<&mut Self> {
+        require!(
+            data.len() >= 8 && data[..8] == BookSide::discriminator(),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let capacity = Self::stored_capacity(&data[8..])?;
+        Ok(Self::from_bytes_unchecked_mut(&mut data[8..], capacity))
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// Removes all nodes, keeping the order tree type
+    pub fn clear(&mut self) {
+        self.roots = bytemuck::Zeroable::zeroed();
+        self.nodes.bump_index = 0;
+        self.nodes.free_list_len = 0;
+        self.nodes.free_list_head = 0;
+    }
+
     /// Iterate over all entries in the book filtering out invalid orders
     ///
     /// smallest to highest for asks
@@ -151,6 +265,24 @@ impl BookSide {
         self.nodes.remove_by_key(root, search_key)
     }
 
+    /// The leaf with `search_key` in the `component` tree, if it exists.
+    pub fn leaf_by_key(&self, component: BookSideOrderTree, search_key: u128) -> Option<&LeafNode> {
+        let root = &self.roots[component as usize];
+        let handle = self.nodes.find_by_key(root, search_key)?;
+        self.nodes.node(handle)?.as_leaf()
+    }
+
+    /// The leaf with `search_key` in the `component` tree, if it exists.
+    pub fn leaf_by_key_mut(
+        &mut self,
+        component: BookSideOrderTree,
+        search_key: u128,
+    ) -> Option<&mut LeafNode> {
+        let root = &self.roots[component as usize];
+        let handle = self.nodes.find_by_key(root, search_key)?;
+        self.nodes.node_mut(handle)?.as_leaf_mut()
+    }
+
     pub fn side(&self) -> Side {
         self.nodes.order_tree_type().side()
     }
@@ -196,6 +328,71 @@ impl BookSide {
     }
 }
 
+/// Loads book sides of any capacity as [`DynBookSide`]
+pub trait BookSideLoader {
+    fn load_dyn(&self) -> Result<Ref<DynBookSide>>;
+    fn load_dyn_mut(&self) -> Result<RefMut<DynBookSide>>;
+    /// Loads a new book side, with the capacity that fits its account size
+    fn load_dyn_init(&self) -> Result<RefMut<DynBookSide>>;
+    /// Loads a book side again after its account was resized, it has to be cleared
+    fn load_dyn_reinit(&self) -> Result<RefMut<DynBookSide>>;
+}
+
+impl<'info> BookSideLoader for AccountLoader<'info, BookSide> {
+    fn load_dyn(&self) -> Result<Ref<DynBookSide>> {
+        let data = self.as_ref().try_borrow_data()?;
+        DynBookSide::from_account_data(&data)?;
+        Ok(Ref::map(data, |data| {
+            DynBookSide::from_account_data(data).unwrap()
+        }))
+    }
+
+    fn load_dyn_mut(&self) -> Result<RefMut<DynBookSide>> {
+        require!(self.as_ref().is_writable, ErrorCode::AccountNotMutable);
+        let mut data = self.as_ref().try_borrow_mut_data()?;
+        DynBookSide::from_account_data_mut(&mut data)?;
+        Ok(RefMut::map(data, |data| {
+            DynBookSide::from_account_data_mut(data).unwrap()
+        }))
+    }
+
+    fn load_dyn_init(&self) -> Result<RefMut<DynBookSide>> {
+        load_fitting_capacity(
+            self.as_ref(),
+            [0; 8],
+            ErrorCode::AccountDiscriminatorAlreadySet,
+        )
+    }
+
+    fn load_dyn_reinit(&self) -> Result<RefMut<DynBookSide>> {
+        load_fitting_capacity(
+            self.as_ref(),
+            BookSide::discriminator(),
+            ErrorCode::AccountDiscriminatorMismatch,
+        )
+    }
+}
+
+/// Borrows a book side with the capacity that fits its account size, and stores that capacity
+/// in its header
+fn load_fitting_capacity<'a>(
+    info: &'a AccountInfo,
+    discriminator: [u8; 8],
+    discriminator_error: ErrorCode,
+) -> Result<RefMut<'a, DynBookSide>> {
+    require!(info.is_writable, ErrorCode::AccountNotMutable);
+    let data = info.try_borrow_mut_data()?;
+    require!(data[..8] == discriminator, discriminator_error);
+    let capacity = BookSide::capacity_for_space(data.len())
+        .filter(|capacity| (MIN_ORDERTREE_NODES..NodeHandle::MAX as usize).contains(capacity))
+        .ok_or(OpenBookError::InvalidInputCapacity)?;
+    Ok(RefMut::map(data, |data| {
+        let book_side = DynBookSide::from_bytes_unchecked_mut(&mut data[8..], capacity);
+        book_side.nodes.capacity = capacity as u32;
+        book_side
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
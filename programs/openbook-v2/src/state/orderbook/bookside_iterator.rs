@@ -19,8 +19,8 @@ impl<'a> BookSideIterItem<'a> {
 /// This will skip over orders that are not currently matchable, but might be valid
 /// in the future.
 ///
-/// This may return invalid orders (tif expired, peg_limit exceeded; see is_valid) which
-/// users are supposed to remove from the orderbook if they can.
+/// This may return invalid orders (tif expired, peg_limit exceeded, heartbeat expired;
+/// see is_valid) which users are supposed to remove from the orderbook if they can.
 pub struct BookSideIter<'a> {
     fixed_iter: OrderTreeIter<'a>,
     oracle_pegged_iter: OrderTreeIter<'a>,
@@ -29,7 +29,7 @@ pub struct BookSideIter<'a> {
 }
 
 impl<'a> BookSideIter<'a> {
-    pub fn new(book_side: &'a BookSide, now_ts: u64, oracle_price_lots: Option<i64>) -> Self {
+    pub fn new(book_side: &'a DynBookSide, now_ts: u64, oracle_price_lots: Option<i64>) -> Self {
         Self {
             fixed_iter: book_side
                 .nodes
@@ -89,7 +89,7 @@ fn key_for_fixed_price(key: u128, price_lots: i64) -> u128 {
 /// Helper for the iterator returning a fixed order
 fn fixed_to_result(fixed: (NodeHandle, &LeafNode), now_ts: u64) -> BookSideIterItem {
     let (handle, node) = fixed;
-    let expired = node.is_expired(now_ts);
+    let expired = node.is_expired(now_ts) || node.is_heartbeat_expired(now_ts);
     BookSideIterItem {
         handle: BookSideOrderHandle {
             order_tree: BookSideOrderTree::Fixed,
@@ -111,7 +111,7 @@ fn oracle_pegged_to_result(
     now_ts: u64,
 ) -> BookSideIterItem {
     let (handle, node, price_lots, state) = pegged;
-    let expired = node.is_expired(now_ts);
+    let expired = node.is_expired(now_ts) || node.is_heartbeat_expired(now_ts);
     BookSideIterItem {
         handle: BookSideOrderHandle {
             order_tree: BookSideOrderTree::OraclePegged,
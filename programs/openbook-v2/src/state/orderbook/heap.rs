@@ -1,24 +1,35 @@
 use crate::error::OpenBookError;
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use static_assertions::const_assert_eq;
+use std::cell::{Ref, RefMut};
 use std::mem::size_of;
+use std::ops::{Deref, DerefMut};
 
 use super::Side;
 
+/// Capacity of the fixed-size [`EventHeap`], and of event heaps that don't store theirs
 pub const MAX_NUM_EVENTS: u16 = 600;
+/// Smallest capacity of an event heap created with `create_market`
+pub const MIN_NUM_EVENTS: u16 = 32;
 pub const NO_NODE: u16 = u16::MAX;
 
+const EVENT_HEAP_RESERVED: usize = 64;
+
 /// Container for the different EventTypes.
 ///
 /// Events are stored in a fixed-array of nodes. Free nodes are connected by a single-linked list
 /// starting at free_head while used nodes form a circular doubly-linked list starting at
 /// used_head.
+///
+/// This is the layout of event heaps with the default capacity. Event heaps of any capacity
+/// are used through [`DynEventHeap`], which this derefs to.
 #[account(zero_copy)]
 pub struct EventHeap {
     pub header: EventHeapHeader,
     pub nodes: [EventNode; MAX_NUM_EVENTS as usize],
-    pub reserved: [u8; 64],
+    pub reserved: [u8; EVENT_HEAP_RESERVED],
 }
 const_assert_eq!(
     std::mem::size_of::<EventHeap>(),
@@ -29,20 +40,119 @@ const_assert_eq!(std::mem::size_of::<EventHeap>(), 91280);
 const_assert_eq!(std::mem::size_of::<EventHeap>() % 8, 0);
 
 impl EventHeap {
+    /// Number of bytes needed for an event heap of `capacity` events, including the
+    /// discriminator
+    pub fn space(capacity: usize) -> usize {
+        8 + size_of::<EventHeapHeader>() + capacity * size_of::<EventNode>() + EVENT_HEAP_RESERVED
+    }
+
+    /// Capacity of an event heap account of `data_len` bytes, if it fits exactly
+    pub fn capacity_for_space(data_len: usize) -> Option<usize> {
+        let nodes_len = data_len.checked_sub(Self::space(0))?;
+        (nodes_len % size_of::<EventNode>() == 0).then_some(nodes_len / size_of::<EventNode>())
+    }
+}
+
+impl Deref for EventHeap {
+    type Target = DynEventHeap;
+
+    fn deref(&self) -> &DynEventHeap {
+        DynEventHeap::from_bytes_unchecked(bytemuck::bytes_of(self), MAX_NUM_EVENTS as usize)
+    }
+}
+
+impl DerefMut for EventHeap {
+    fn deref_mut(&mut self) -> &mut DynEventHeap {
+        DynEventHeap::from_bytes_unchecked_mut(
+            bytemuck::bytes_of_mut(self),
+            MAX_NUM_EVENTS as usize,
+        )
+    }
+}
+
+/// An [`EventHeap`] of any capacity, chosen when the market is created. The capacity is
+/// stored in the header, heaps that predate it have [`MAX_NUM_EVENTS`].
+#[repr(C)]
+pub struct DynEventHeap {
+    pub header: EventHeapHeader,
+    pub nodes: [EventNode],
+}
+
+impl DynEventHeap {
+    fn from_bytes_unchecked(bytes: &[u8], capacity: usize) -> &Self {
+        assert!(bytes.len() >= size_of::<EventHeapHeader>() + capacity * size_of::<EventNode>());
+        assert_eq!(bytes.as_ptr().align_offset(8), 0);
+        // SAFETY: Self is repr(C) and made of Pod fields, and the bytes are large and aligned
+        // enough for the header and `capacity` nodes
+        unsafe { &*(std::ptr::slice_from_raw_parts(bytes.as_ptr(), capacity) as *const Self) }
+    }
+
+    fn from_bytes_unchecked_mut(bytes: &mut [u8], capacity: usize) -> &mut Self {
+        assert!(bytes.len() >= size_of::<EventHeapHeader>() + capacity * size_of::<EventNode>());
+        assert_eq!(bytes.as_ptr().align_offset(8), 0);
+        // SAFETY: see from_bytes_unchecked
+        unsafe {
+            &mut *(std::ptr::slice_from_raw_parts_mut(bytes.as_mut_ptr(), capacity) as *mut Self)
+        }
+    }
+
+    /// Capacity stored in the header of the account data, after the discriminator
+    fn stored_capacity(data: &[u8]) -> Result<usize> {
+        require_gte!(
+            data.len(),
+            size_of::<EventHeapHeader>(),
+            ErrorCode::AccountDidNotDeserialize
+        );
+        let capacity = match Self::from_bytes_unchecked(data, 0).header.capacity {
+            0 => MAX_NUM_EVENTS as usize,
+            capacity => capacity as usize,
+        };
+        require_gte!(
+            data.len(),
+            size_of::<EventHeapHeader>() + capacity * size_of::<EventNode>(),
+            ErrorCode::AccountDidNotDeserialize
+        );
+        Ok(capacity)
+    }
+
+    /// Reads an event heap of any capacity from its account data
+    pub fn from_account_data(data: &[u8]) -> Result<&Self> {
+        require!(
+            data.len() >= 8 && data[..8] == EventHeap::discriminator(),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let capacity = Self::stored_capacity(&data[8..])?;
+        Ok(Self::from_bytes_unchecked(&data[8..], capacity))
+    }
+
+    pub fn from_account_data_mut(data: &mut [u8]) -> Result<&mut Self> {
+        require!(
+            data.len() >= 8 && data[..8] == EventHeap::discriminator(),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let capacity = Self::stored_capacity(&data[8..])?;
+        Ok(Self::from_bytes_unchecked_mut(&mut data[8..], capacity))
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.nodes.len()
+    }
+
     pub fn init(&mut self) {
+        let capacity = self.capacity() as u16;
         self.header = EventHeapHeader {
             free_head: 0,
             used_head: NO_NODE,
             count: 0,
             seq_num: 0,
-            _padd: Default::default(),
+            capacity,
         };
 
-        for i in 0..MAX_NUM_EVENTS {
+        for i in 0..capacity {
             self.nodes[i as usize].next = i + 1;
             self.nodes[i as usize].prev = NO_NODE;
         }
-        self.nodes[MAX_NUM_EVENTS as usize - 1].next = NO_NODE;
+        self.nodes[capacity as usize - 1].next = NO_NODE;
     }
 
     pub fn len(&self) -> usize {
@@ -141,8 +251,71 @@ impl EventHeap {
     }
 }
 
+/// Loads event heaps of any capacity as [`DynEventHeap`]
+pub trait EventHeapLoader {
+    fn load_dyn(&self) -> Result<Ref<DynEventHeap>>;
+    fn load_dyn_mut(&self) -> Result<RefMut<DynEventHeap>>;
+    /// Loads a new event heap, with the capacity that fits its account size
+    fn load_dyn_init(&self) -> Result<RefMut<DynEventHeap>>;
+    /// Loads an event heap again after its account was resized, it has to be initialized again
+    fn load_dyn_reinit(&self) -> Result<RefMut<DynEventHeap>>;
+}
+
+impl<'info> EventHeapLoader for AccountLoader<'info, EventHeap> {
+    fn load_dyn(&self) -> Result<Ref<DynEventHeap>> {
+        let data = self.as_ref().try_borrow_data()?;
+        DynEventHeap::from_account_data(&data)?;
+        Ok(Ref::map(data, |data| {
+            DynEventHeap::from_account_data(data).unwrap()
+        }))
+    }
+
+    fn load_dyn_mut(&self) -> Result<RefMut<DynEventHeap>> {
+        require!(self.as_ref().is_writable, ErrorCode::AccountNotMutable);
+        let mut data = self.as_ref().try_borrow_mut_data()?;
+        DynEventHeap::from_account_data_mut(&mut data)?;
+        Ok(RefMut::map(data, |data| {
+            DynEventHeap::from_account_data_mut(data).unwrap()
+        }))
+    }
+
+    fn load_dyn_init(&self) -> Result<RefMut<DynEventHeap>> {
+        load_fitting_capacity(
+            self.as_ref(),
+            [0; 8],
+            ErrorCode::AccountDiscriminatorAlreadySet,
+        )
+    }
+
+    fn load_dyn_reinit(&self) -> Result<RefMut<DynEventHeap>> {
+        load_fitting_capacity(
+            self.as_ref(),
+            EventHeap::discriminator(),
+            ErrorCode::AccountDiscriminatorMismatch,
+        )
+    }
+}
+
+/// Borrows an event heap with the capacity that fits its account size, ignoring the capacity
+/// in its header
+fn load_fitting_capacity<'a>(
+    info: &'a AccountInfo,
+    discriminator: [u8; 8],
+    discriminator_error: ErrorCode,
+) -> Result<RefMut<'a, DynEventHeap>> {
+    require!(info.is_writable, ErrorCode::AccountNotMutable);
+    let data = info.try_borrow_mut_data()?;
+    require!(data[..8] == discriminator, discriminator_error);
+    let capacity = EventHeap::capacity_for_space(data.len())
+        .filter(|capacity| (MIN_NUM_EVENTS as usize..NO_NODE as usize).contains(capacity))
+        .ok_or(OpenBookError::InvalidInputCapacity)?;
+    Ok(RefMut::map(data, |data| {
+        DynEventHeap::from_bytes_unchecked_mut(&mut data[8..], capacity)
+    }))
+}
+
 struct EventHeapIterator<'a> {
-    heap: &'a EventHeap,
+    heap: &'a DynEventHeap,
     index: usize,
     slot: usize,
 }
@@ -166,7 +339,8 @@ pub struct EventHeapHeader {
     free_head: u16,
     used_head: u16,
     count: u16,
-    _padd: u16,
+    /// Number of nodes, [`MAX_NUM_EVENTS`] = 0
+    capacity: u16,
     pub seq_num: u64,
 }
 const_assert_eq!(std::mem::size_of::<EventHeapHeader>(), 16);
@@ -241,7 +415,8 @@ pub struct FillEvent {
     pub taker_side: u8, // Side, from the taker's POV
     pub maker_out: u8,  // 1 if maker order quantity == 0
     pub maker_slot: u8,
-    pub padding: [u8; 4],
+    pub auction: u8, // 1 if the fill comes from an auction, both sides being makers
+    pub padding: [u8; 3],
     pub timestamp: u64,
     pub market_seq_num: u64,
 
@@ -294,6 +469,7 @@ impl FillEvent {
             price,
             peg_limit,
             quantity,
+            auction: 0,
             padding: Default::default(),
             reserved: [0; 8],
         }
@@ -305,6 +481,9 @@ impl FillEvent {
     pub fn maker_out(&self) -> bool {
         self.maker_out == 1
     }
+    pub fn is_auction(&self) -> bool {
+        self.auction == 1
+    }
 }
 
 #[derive(
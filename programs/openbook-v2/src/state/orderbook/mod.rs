@@ -1,3 +1,4 @@
+pub use auction::*;
 pub use book::*;
 pub use bookside::*;
 pub use bookside_iterator::*;
@@ -8,6 +9,7 @@ pub use order_type::*;
 pub use ordertree::*;
 pub use ordertree_iterator::*;
 
+mod auction;
 mod book;
 mod bookside;
 mod bookside_iterator;
@@ -21,13 +23,14 @@ mod ordertree_iterator;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::{IsAnchorErrorWithCode, OpenBookError};
     use crate::state::{Market, OpenOrdersAccount, FEES_SCALE_FACTOR};
     use bytemuck::Zeroable;
     use fixed::types::I80F48;
     use solana_program::pubkey::Pubkey;
-    use std::cell::RefCell;
+    use std::cell::{RefCell, RefMut};
 
-    fn order_tree_leaf_by_key(bookside: &BookSide, key: u128) -> Option<&LeafNode> {
+    fn order_tree_leaf_by_key(bookside: &DynBookSide, key: u128) -> Option<&LeafNode> {
         for component in [BookSideOrderTree::Fixed, BookSideOrderTree::OraclePegged] {
             for (_, leaf) in bookside.nodes.iter(bookside.root(component)) {
                 if leaf.key == key {
@@ -38,11 +41,11 @@ mod tests {
         None
     }
 
-    fn order_tree_contains_key(bookside: &BookSide, key: u128) -> bool {
+    fn order_tree_contains_key(bookside: &DynBookSide, key: u128) -> bool {
         order_tree_leaf_by_key(bookside, key).is_some()
     }
 
-    fn order_tree_contains_price(bookside: &BookSide, price_data: u64) -> bool {
+    fn order_tree_contains_price(bookside: &DynBookSide, price_data: u64) -> bool {
         for component in [BookSideOrderTree::Fixed, BookSideOrderTree::OraclePegged] {
             for (_, leaf) in bookside.nodes.iter(bookside.root(component)) {
                 if leaf.price_data() == price_data {
@@ -71,8 +74,8 @@ mod tests {
 
         fn orderbook(&self) -> Orderbook {
             Orderbook {
-                bids: self.bids.borrow_mut(),
-                asks: self.asks.borrow_mut(),
+                bids: RefMut::map(self.bids.borrow_mut(), |bids| &mut **bids),
+                asks: RefMut::map(self.asks.borrow_mut(), |asks| &mut **asks),
             }
         }
     }
@@ -450,4 +453,39 @@ mod tests {
         new_order(&mut book, &mut event_heap, Side::Bid, 5005, 30, 1);
         assert_eq!(event_heap.len(), 1);
     }
+
+    #[test]
+    fn book_order_timestamp_overflow() {
+        let (mut market, oracle_price_lots, mut event_heap, book_accs) = test_setup(5000.0);
+        let mut book = book_accs.orderbook();
+        let mut account = OpenOrdersAccount::default_for_tests();
+
+        let result = book.new_order(
+            &Order {
+                side: Side::Bid,
+                max_base_lots: 1,
+                max_quote_lots_including_fees: 5000,
+                client_order_id: 0,
+                time_in_force: 0,
+                params: OrderParams::Fixed {
+                    price_lots: 5000,
+                    order_type: PostOrderType::Limit,
+                },
+                self_trade_behavior: SelfTradeBehavior::DecrementTake,
+            },
+            &mut market,
+            &Pubkey::new_unique(),
+            &mut event_heap,
+            oracle_price_lots,
+            Some(&mut account),
+            &Pubkey::default(),
+            u32::MAX as u64 + 1, // now_ts
+            u8::MAX,
+            &[],
+        );
+        assert!(
+            result.is_anchor_error_with_code(OpenBookError::OrderTimestampOverflow.error_code())
+        );
+        assert!(book.bids.is_empty());
+    }
 }
@@ -154,7 +154,9 @@ pub struct LeafNode {
     /// A value of 0 means no expiry.
     pub time_in_force: u16,
 
-    pub padding: [u8; 4],
+    /// Unix timestamp after which the order is invalid because the owner stopped
+    /// sending heartbeats. 0 if the owner has no heartbeat configured.
+    pub heartbeat_deadline: u32,
 
     /// The binary tree key, see new_node_key()
     pub key: u128,
@@ -166,7 +168,16 @@ pub struct LeafNode {
     pub quantity: i64,
 
     /// The time the order was placed
-    pub timestamp: u64,
+    ///
+    /// A u64 in earlier versions of the layout. Its upper bytes, always zero for past
+    /// timestamps, now hold `mmp_enabled` and padding, so decoders must read a u32.
+    pub timestamp: u32,
+
+    /// 1 if the owner has market maker protection enabled. Fills against such orders
+    /// are counted while matching, so the owner's account must be passed.
+    pub mmp_enabled: u8,
+
+    pub padding: [u8; 3],
 
     /// If the effective price of an oracle pegged order exceeds this limit,
     /// it will be considered invalid and may be removed.
@@ -179,7 +190,7 @@ pub struct LeafNode {
 }
 const_assert_eq!(
     size_of::<LeafNode>(),
-    4 + 1 + 1 + 1 + 1 + 16 + 32 + 8 + 8 + 8 + 8
+    1 + 1 + 2 + 4 + 16 + 32 + 8 + 4 + 1 + 3 + 8 + 8
 );
 const_assert_eq!(size_of::<LeafNode>(), NODE_SIZE);
 const_assert_eq!(size_of::<LeafNode>() % 8, 0);
@@ -191,7 +202,7 @@ impl LeafNode {
         key: u128,
         owner: Pubkey,
         quantity: i64,
-        timestamp: u64,
+        timestamp: u32,
         time_in_force: u16,
         peg_limit: i64,
         client_order_id: u64,
@@ -200,11 +211,13 @@ impl LeafNode {
             tag: NodeTag::LeafNode.into(),
             owner_slot,
             time_in_force,
-            padding: Default::default(),
+            heartbeat_deadline: 0,
             key,
             owner,
             quantity,
             timestamp,
+            mmp_enabled: 0,
+            padding: Default::default(),
             peg_limit,
             client_order_id,
         }
@@ -224,14 +237,20 @@ impl LeafNode {
         if self.time_in_force == 0 {
             u64::MAX
         } else {
-            self.timestamp + self.time_in_force as u64
+            self.timestamp as u64 + self.time_in_force as u64
         }
     }
 
     /// Returns if the order is expired at `now_ts`
     #[inline(always)]
     pub fn is_expired(&self, now_ts: u64) -> bool {
-        self.time_in_force > 0 && now_ts >= self.timestamp + self.time_in_force as u64
+        self.time_in_force > 0 && now_ts >= self.timestamp as u64 + self.time_in_force as u64
+    }
+
+    /// Returns if the owner's heartbeat deadline stored on the order passed at `now_ts`
+    #[inline(always)]
+    pub fn is_heartbeat_expired(&self, now_ts: u64) -> bool {
+        self.heartbeat_deadline != 0 && now_ts > self.heartbeat_deadline as u64
     }
 }
 
@@ -3,11 +3,16 @@ use bytemuck::{cast, cast_mut, cast_ref};
 
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use static_assertions::const_assert_eq;
+use std::mem::size_of;
+use std::ops::{Deref, DerefMut};
 
 use super::*;
 use crate::error::OpenBookError;
 
+/// Capacity of the fixed-size [`OrderTreeNodes`], and of order trees that don't store theirs
 pub const MAX_ORDERTREE_NODES: usize = 1024;
+/// Smallest capacity of an order tree created with `create_market`
+pub const MIN_ORDERTREE_NODES: usize = 32;
 
 #[derive(
     Eq,
@@ -56,6 +61,9 @@ impl OrderTreeRoot {
 /// A binary tree on AnyNode::key()
 ///
 /// The key encodes the price in the top 64 bits.
+///
+/// This is the layout of order trees with the default capacity. Order trees of any capacity
+/// are used through [`DynOrderTreeNodes`], which this derefs to.
 #[zero_copy]
 pub struct OrderTreeNodes {
     pub order_tree_type: u8, // OrderTreeType, but that's not POD
@@ -63,17 +71,74 @@ pub struct OrderTreeNodes {
     pub bump_index: u32,
     pub free_list_len: u32,
     pub free_list_head: NodeHandle,
-    pub reserved: [u8; 512],
+    /// Number of nodes, [`MAX_ORDERTREE_NODES`] = 0
+    pub capacity: u32,
+    pub reserved: [u8; 508],
     pub nodes: [AnyNode; MAX_ORDERTREE_NODES],
 }
 const_assert_eq!(
     std::mem::size_of::<OrderTreeNodes>(),
-    1 + 3 + 4 * 2 + 4 + 512 + 88 * 1024
+    1 + 3 + 4 * 2 + 4 + 4 + 508 + 88 * 1024
 );
 const_assert_eq!(std::mem::size_of::<OrderTreeNodes>(), 90640);
 const_assert_eq!(std::mem::size_of::<OrderTreeNodes>() % 8, 0);
 
-impl OrderTreeNodes {
+/// Size of the fields of [`OrderTreeNodes`] before the nodes
+pub const ORDER_TREE_NODES_HEADER_SIZE: usize =
+    size_of::<OrderTreeNodes>() - MAX_ORDERTREE_NODES * size_of::<AnyNode>();
+
+impl Deref for OrderTreeNodes {
+    type Target = DynOrderTreeNodes;
+
+    fn deref(&self) -> &DynOrderTreeNodes {
+        DynOrderTreeNodes::from_bytes_unchecked(bytemuck::bytes_of(self), MAX_ORDERTREE_NODES)
+    }
+}
+
+impl DerefMut for OrderTreeNodes {
+    fn deref_mut(&mut self) -> &mut DynOrderTreeNodes {
+        DynOrderTreeNodes::from_bytes_unchecked_mut(
+            bytemuck::bytes_of_mut(self),
+            MAX_ORDERTREE_NODES,
+        )
+    }
+}
+
+/// [`OrderTreeNodes`] of any capacity, chosen when the market is created
+#[repr(C)]
+pub struct DynOrderTreeNodes {
+    pub order_tree_type: u8, // OrderTreeType, but that's not POD
+    pub padding: [u8; 3],
+    pub bump_index: u32,
+    pub free_list_len: u32,
+    pub free_list_head: NodeHandle,
+    pub capacity: u32,
+    pub reserved: [u8; 508],
+    pub nodes: [AnyNode],
+}
+
+impl DynOrderTreeNodes {
+    pub(crate) fn from_bytes_unchecked(bytes: &[u8], capacity: usize) -> &Self {
+        assert!(bytes.len() >= ORDER_TREE_NODES_HEADER_SIZE + capacity * size_of::<AnyNode>());
+        assert_eq!(bytes.as_ptr().align_offset(8), 0);
+        // SAFETY: Self is repr(C) and made of Pod fields, and the bytes are large and aligned
+        // enough for the header and `capacity` nodes
+        unsafe { &*(std::ptr::slice_from_raw_parts(bytes.as_ptr(), capacity) as *const Self) }
+    }
+
+    pub(crate) fn from_bytes_unchecked_mut(bytes: &mut [u8], capacity: usize) -> &mut Self {
+        assert!(bytes.len() >= ORDER_TREE_NODES_HEADER_SIZE + capacity * size_of::<AnyNode>());
+        assert_eq!(bytes.as_ptr().align_offset(8), 0);
+        // SAFETY: see from_bytes_unchecked
+        unsafe {
+            &mut *(std::ptr::slice_from_raw_parts_mut(bytes.as_mut_ptr(), capacity) as *mut Self)
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.nodes.len()
+    }
+
     pub fn order_tree_type(&self) -> OrderTreeType {
         OrderTreeType::try_from(self.order_tree_type).unwrap()
     }
@@ -191,6 +256,17 @@ impl OrderTreeNodes {
         }
     }
 
+    /// Returns the handle of the leaf with `search_key`, if it is in the tree.
+    pub fn find_by_key(&self, root: &OrderTreeRoot, search_key: u128) -> Option<NodeHandle> {
+        let mut node_h = root.node()?;
+        loop {
+            match self.node(node_h).unwrap().case().unwrap() {
+                NodeRef::Inner(inner) => node_h = inner.walk_down(search_key).0,
+                NodeRef::Leaf(leaf) => return (leaf.key == search_key).then_some(node_h),
+            }
+        }
+    }
+
     pub fn remove_by_key(
         &mut self,
         root: &mut OrderTreeRoot,
@@ -539,7 +615,16 @@ mod tests {
     fn order_tree_expiry_manual() {
         let mut bids = new_order_tree(OrderTreeType::Bids);
         let new_expiring_leaf = |key: u128, expiry: u64| {
-            LeafNode::new(0, key, Pubkey::default(), 0, expiry - 1, 1, -1, 0)
+            LeafNode::new(
+                0,
+                key,
+                Pubkey::default(),
+                0,
+                (expiry - 1).try_into().unwrap(),
+                1,
+                -1,
+                0,
+            )
         };
 
         let mut root = OrderTreeRoot::zeroed();
@@ -622,7 +707,16 @@ mod tests {
         let mut root = OrderTreeRoot::zeroed();
         let mut bids = new_order_tree(OrderTreeType::Bids);
         let new_expiring_leaf = |key: u128, expiry: u64| {
-            LeafNode::new(0, key, Pubkey::default(), 0, expiry - 1, 1, -1, 0)
+            LeafNode::new(
+                0,
+                key,
+                Pubkey::default(),
+                0,
+                (expiry - 1).try_into().unwrap(),
+                1,
+                -1,
+                0,
+            )
         };
 
         // add 200 random leaves
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+use std::cell::RefMut;
+use std::mem::size_of;
+
+use crate::accounts_zerocopy::LoadMutZeroCopyRef;
+use crate::error::OpenBookError;
+
+use super::{Market, OpenOrdersAccount, Position};
+
+/// Shares of the taker fees are in basis points of this
+pub const REFERRER_SHARE_TOTAL_BPS: u16 = 10_000;
+
+/// A referrer that open orders accounts can bind to. It's global if `market` is the
+/// default pubkey, otherwise it can only be bound on that market.
+#[account]
+#[derive(Default)]
+pub struct Referrer {
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub market: Pubkey,
+    /// Share of the taker fees of referred accounts, after maker rebates, in basis points.
+    /// Capped by [`Market::max_referrer_share_bps`], which is 0 until the market's
+    /// `collect_fee_admin` sets it: referrers earn nothing on a market before that.
+    pub share_bps: u16,
+
+    // Stats
+    /// Open orders accounts currently bound to the referrer
+    pub referred_accounts: u32,
+    /// Taker volume in quote native of the referred accounts whose rebates were claimed
+    pub referred_taker_volume: u128,
+    /// Rebates in quote native claimed so far
+    pub rebates_claimed: u128,
+}
+
+impl Referrer {
+    pub fn space() -> usize {
+        8 + 1 + 32 + 32 + 2 + 4 + 16 + 16
+    }
+
+    pub fn is_global(&self) -> bool {
+        self.market == Pubkey::default()
+    }
+
+    pub fn can_refer_on(&self, market: Pubkey) -> bool {
+        self.is_global() || self.market == market
+    }
+}
+
+/// Referrer an open orders account is bound to, a PDA of the open orders account
+///
+/// The referrer's share of the taker fees is set aside here when the account takes, and
+/// claimed by the referrer with `claim_referrer_rebates`.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct OpenOrdersReferral {
+    pub open_orders_account: Pubkey,
+    pub referrer: Pubkey,
+    /// `Referrer::share_bps` when binding
+    pub share_bps: u16,
+    pub bump: u8,
+    pub padding: [u8; 5],
+    /// Rebates in quote native not claimed by the referrer yet
+    pub rebates_available: u64,
+    /// Taker volume in quote native since binding
+    pub referred_taker_volume: u64,
+    /// Part of `referred_taker_volume` already added to the referrer's stats
+    pub referred_taker_volume_claimed: u64,
+    pub reserved: [u8; 64],
+}
+const_assert_eq!(
+    size_of::<OpenOrdersReferral>(),
+    32 + 32 + 2 + 1 + 5 + 8 + 8 + 8 + 64
+);
+const_assert_eq!(size_of::<OpenOrdersReferral>(), 160);
+const_assert_eq!(size_of::<OpenOrdersReferral>() % 8, 0);
+
+impl OpenOrdersReferral {
+    /// Number of bytes needed for the OpenOrdersReferral, including the discriminator
+    pub fn space() -> usize {
+        8 + size_of::<OpenOrdersReferral>()
+    }
+
+    /// Finds the referral of an open orders account among the writable `accounts`. It
+    /// must be passed once the account is bound, so that takes can't skip the referrer.
+    pub fn find_in<'a>(
+        open_orders_account: &OpenOrdersAccount,
+        open_orders_account_pk: Pubkey,
+        accounts: &'a [AccountInfo],
+    ) -> Result<Option<RefMut<'a, OpenOrdersReferral>>> {
+        if !open_orders_account.has_referral() {
+            return Ok(None);
+        }
+        let referral = accounts
+            .iter()
+            .filter(|ai| ai.is_writable)
+            .filter_map(|ai| ai.load_mut::<OpenOrdersReferral>().ok())
+            .find(|referral| referral.open_orders_account == open_orders_account_pk)
+            .ok_or(OpenBookError::MissingOpenOrdersReferral)?;
+        Ok(Some(referral))
+    }
+
+    /// Moves the referrer's share out of the `referrer_amount` of a take, which was
+    /// credited to the referrer rebates of the position
+    pub fn credit_take(
+        &mut self,
+        position: &mut Position,
+        market: &Market,
+        referrer_amount: u64,
+        quote_native: u64,
+    ) {
+        let share_bps = self.share_bps.min(market.max_referrer_share_bps);
+        let rebate =
+            (referrer_amount as u128 * share_bps as u128 / REFERRER_SHARE_TOTAL_BPS as u128) as u64;
+
+        position.referrer_rebates_available -= rebate;
+        self.rebates_available += rebate;
+        self.referred_taker_volume += quote_native;
+    }
+
+    /// Takes the unclaimed rebates and volume out of the referral, adds them to the stats
+    /// of the `referrer` and the market. The returned rebates are then due to the referrer.
+    pub fn claim(&mut self, referrer: &mut Referrer, market: &mut Market) -> (u64, u64) {
+        let amount = self.rebates_available;
+        let volume = self.referred_taker_volume - self.referred_taker_volume_claimed;
+        self.rebates_available = 0;
+        self.referred_taker_volume_claimed = self.referred_taker_volume;
+
+        referrer.rebates_claimed += amount as u128;
+        referrer.referred_taker_volume += volume as u128;
+
+        market.referrer_rebates_accrued -= amount;
+        market.quote_deposit_total -= amount;
+        market.fees_to_referrers += amount as u128;
+
+        (amount, volume)
+    }
+}
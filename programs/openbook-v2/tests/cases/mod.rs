@@ -13,16 +13,22 @@ pub use super::program_test;
 pub use utils::assert_equal_fixed_f64 as assert_equal;
 
 mod test;
+mod test_auction;
 mod test_crank;
 mod test_create_market;
 mod test_edit_order;
 mod test_fees;
 mod test_fill_or_kill_order;
+mod test_heartbeat;
 mod test_indexer;
+mod test_market_status;
+mod test_mmp;
 mod test_multiple_orders;
 mod test_oracle_peg;
+mod test_order_size_limits;
 mod test_order_types;
 mod test_permissioned;
 mod test_place_order_remaining;
+mod test_referrer;
 mod test_self_trade;
 mod test_take_order;
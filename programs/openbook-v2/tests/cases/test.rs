@@ -781,3 +781,135 @@ async fn test_expired_orders() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_migrate_open_orders_account() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        maker_fee: 200,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 2,
+            max_quote_lots_including_fees: 20000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    // Turn account_1 into a version 0 account, that didn't track bids_quote_lots
+    {
+        let mut open_orders_account = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account.version, OPEN_ORDERS_ACCOUNT_VERSION);
+        assert_eq!(open_orders_account.position.bids_quote_lots, 2 * price_lots);
+
+        open_orders_account.version = 0;
+        open_orders_account.position.bids_quote_lots = 0;
+        // Rounding locked by an older version above the fees of the open bids
+        open_orders_account.position.locked_maker_fees += 5;
+        let mut data = solana.get_account_data(account_1).await.unwrap();
+        data[8..OpenOrdersAccount::space()]
+            .copy_from_slice(bytemuck::bytes_of(&open_orders_account));
+        solana.set_account_data(account_1, &data).await;
+    }
+
+    let migrate_ix = MigrateOpenOrdersAccountInstruction {
+        open_orders_account: account_1,
+        market,
+    };
+
+    // Partially fill the bid
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    // The fill event has to be processed first
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, migrate_ix.clone()).await,
+        Some(OpenBookError::OpenOrdersAccountNotMigratable.into())
+    );
+
+    send_tx(
+        solana,
+        ConsumeEventsInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1, account_2],
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account.position.bids_quote_lots, -price_lots);
+    }
+
+    let before_migrate = solana.get_account::<OpenOrdersAccount>(account_1).await;
+    send_tx(solana, migrate_ix).await.unwrap();
+
+    {
+        let market = solana.get_account::<Market>(market).await;
+        let maker_fees = market.maker_fees_ceil((price_lots * market.quote_lot_size) as u64);
+        let open_orders_account = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account.version, OPEN_ORDERS_ACCOUNT_VERSION);
+        assert_eq!(open_orders_account.position.bids_base_lots, 1);
+        assert_eq!(open_orders_account.position.bids_quote_lots, price_lots);
+        assert_eq!(open_orders_account.position.locked_maker_fees, maker_fees);
+        assert_eq!(
+            open_orders_account.position.quote_free_native,
+            before_migrate.position.quote_free_native + before_migrate.position.locked_maker_fees
+                - maker_fees
+        );
+    }
+
+    Ok(())
+}
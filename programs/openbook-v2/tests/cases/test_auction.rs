@@ -0,0 +1,356 @@
+use super::*;
+
+#[tokio::test]
+async fn test_opening_auction() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        auction_duration_secs: 60,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+    let asks = solana.get_account::<Market>(market).await.asks;
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    let bid_price_lots = price_lots;
+    let ask_price_lots = price_lots - 100;
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots: bid_price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    // Crossing orders rest on the book during the auction phase
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots: ask_price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![account_1],
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+        let asks_data = solana.get_account_boxed::<BookSide>(asks).await;
+        assert_eq!(bids_data.roots[0].leaf_count, 1);
+        assert_eq!(asks_data.roots[0].leaf_count, 1);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 1);
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 1);
+        assert_eq!(open_orders_account_1.position.base_free_native, 0);
+        assert_eq!(open_orders_account_2.position.quote_free_native, 0);
+    }
+
+    // Orders that can't rest on the book are rejected
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            PlaceOrderInstruction {
+                open_orders_account: account_2,
+                open_orders_admin: None,
+                market,
+                signer: owner,
+                user_token_account: owner_token_0,
+                market_vault: market_base_vault,
+                side: Side::Ask,
+                price_lots: ask_price_lots,
+                max_base_lots: 1,
+                max_quote_lots_including_fees: 10000,
+                client_order_id: 0,
+                expiry_timestamp: 0,
+                order_type: PlaceOrderType::ImmediateOrCancel,
+                self_trade_behavior: SelfTradeBehavior::default(),
+                remainings: vec![account_1],
+            },
+        )
+        .await,
+        OpenBookError::MarketInAuction.error_code(),
+        "market is in the auction phase".into(),
+    );
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            RunAuctionInstruction {
+                market,
+                remainings: vec![account_1, account_2],
+                limit: 10,
+            },
+        )
+        .await,
+        OpenBookError::AuctionNotEnded.error_code(),
+        "auction phase has not ended".into(),
+    );
+
+    let quote_free_before = solana
+        .get_account::<OpenOrdersAccount>(account_1)
+        .await
+        .position
+        .quote_free_native;
+
+    solana.advance_clock(61).await;
+
+    send_tx(
+        solana,
+        RunAuctionInstruction {
+            market,
+            remainings: vec![account_1, account_2],
+            limit: 10,
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let log = solana.program_log_events::<openbook_v2::logs::RunAuctionLog>();
+        assert_eq!(log.len(), 1);
+        // Same volume at both prices, the lowest one wins
+        assert_eq!(log[0].price_lots, ask_price_lots);
+        assert_eq!(log[0].base_lots, 1);
+        assert!(log[0].completed);
+    }
+
+    {
+        let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+        let asks_data = solana.get_account_boxed::<BookSide>(asks).await;
+        assert_eq!(bids_data.roots[0].leaf_count, 0);
+        assert_eq!(asks_data.roots[0].leaf_count, 0);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 0);
+        assert_eq!(open_orders_account_1.position.base_free_native, 100);
+        // The bid gets back what was locked above the clearing price
+        assert_eq!(
+            open_orders_account_1.position.quote_free_native - quote_free_before,
+            ((bid_price_lots - ask_price_lots) * 10) as u64
+        );
+        assert_eq!(
+            open_orders_account_2.position.quote_free_native,
+            (ask_price_lots * 10) as u64
+        );
+
+        let market_acc = solana.get_account_boxed::<Market>(market).await;
+        assert_eq!(market_acc.auction_end_ts, 0);
+        assert_eq!(market_acc.auction_price_lots, 0);
+        let event_heap = solana
+            .get_account_boxed::<EventHeap>(market_acc.event_heap)
+            .await;
+        assert_eq!(event_heap.header.count(), 0);
+    }
+
+    // Continuous trading resumes
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots: ask_price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::ImmediateOrCancel,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_auction_skips_mmp_frozen_makers() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        auction_duration_secs: 60,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+    let asks = solana.get_account::<Market>(market).await.asks;
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    send_tx(
+        solana,
+        SetMmpInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_1,
+            window_secs: 60,
+            base_lots_limit: 0,
+            fill_count_limit: 1,
+        },
+    )
+    .await
+    .unwrap();
+
+    let bid = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, bid.clone()).await.unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            price_lots: price_lots - 50,
+            client_order_id: 1,
+            ..bid
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots: price_lots - 100,
+            max_base_lots: 2,
+            max_quote_lots_including_fees: 20000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    solana.advance_clock(61).await;
+
+    // The first fill freezes the maker, which stops the matching
+    let run_auction = RunAuctionInstruction {
+        market,
+        remainings: vec![account_1, account_2],
+        limit: 10,
+    };
+    send_tx(solana, run_auction.clone()).await.unwrap();
+    {
+        let log = solana.program_log_events::<openbook_v2::logs::RunAuctionLog>();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].base_lots, 1);
+        assert!(!log[0].completed);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert!(open_orders_account_1.is_mmp_frozen());
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 1);
+    }
+
+    // The frozen maker's other bid is dropped instead of filled
+    send_tx(solana, run_auction).await.unwrap();
+    {
+        let log = solana.program_log_events::<openbook_v2::logs::RunAuctionLog>();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].base_lots, 0);
+        assert!(log[0].completed);
+
+        let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+        let asks_data = solana.get_account_boxed::<BookSide>(asks).await;
+        assert_eq!(bids_data.roots[0].leaf_count, 0);
+        assert_eq!(asks_data.roots[0].leaf_count, 1);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_1.position.base_free_native, 100);
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 1);
+    }
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
 use super::*;
+use anchor_lang::InstructionData;
 use bytemuck::cast_ref;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::system_instruction;
 
 #[tokio::test]
 async fn test_skip_missing_accounts() -> Result<(), TransportError> {
@@ -397,3 +400,449 @@ fn fill_maker(anyevent: &AnyEvent) -> Pubkey {
     let event: &FillEvent = cast_ref(anyevent);
     event.maker
 }
+
+#[tokio::test]
+async fn test_crank_rewards() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    assert!(send_tx(
+        solana,
+        SetCrankRewardsInstruction {
+            collect_fee_admin: owner,
+            market,
+            penalty_event_heap: Some(1000),
+            crank_reward_per_event: 300,
+        },
+    )
+    .await
+    .is_err());
+    send_tx(
+        solana,
+        SetCrankRewardsInstruction {
+            collect_fee_admin,
+            market,
+            penalty_event_heap: Some(1000),
+            crank_reward_per_event: 300,
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    // The fill event makes the taker pay the configured penalty into the market
+    let market_lamports = solana.get_account_lamports(market).await;
+    send_tx(
+        solana,
+        PlaceTakeOrderInstruction {
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account_lamports(market).await,
+        market_lamports + 1000
+    );
+
+    // Consuming the fill event pays the reward from the penalties
+    let cranker = context.users[2].key;
+    let cranker_lamports = solana.get_account_lamports(cranker.pubkey()).await;
+    send_tx(
+        solana,
+        ConsumeEventsWithRewardInstruction {
+            market,
+            open_orders_accounts: vec![account_1],
+            crank_reward_receiver: cranker,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account_lamports(cranker.pubkey()).await,
+        cranker_lamports + 300
+    );
+    assert_eq!(
+        solana.get_account_lamports(market).await,
+        market_lamports + 700
+    );
+
+    // Nothing left to consume, nothing to pay
+    send_tx(
+        solana,
+        ConsumeEventsWithRewardInstruction {
+            market,
+            open_orders_accounts: vec![account_1],
+            crank_reward_receiver: cranker,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account_lamports(cranker.pubkey()).await,
+        cranker_lamports + 300
+    );
+
+    // A penalty of 0 can be configured
+    send_tx(
+        solana,
+        SetCrankRewardsInstruction {
+            collect_fee_admin,
+            market,
+            penalty_event_heap: Some(0),
+            crank_reward_per_event: 300,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 1,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceTakeOrderInstruction {
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account_lamports(market).await,
+        market_lamports + 700
+    );
+
+    // Crankers built for the original layout pass the makers right after the event heap,
+    // consume_events still works for them and pays no reward
+    let market_account = solana.get_account::<Market>(market).await;
+    assert_eq!(market_account.penalty_lamports_accrued, 700);
+    let legacy_consume_events = Instruction {
+        program_id: openbook_v2::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(openbook_v2::id(), false),
+            AccountMeta::new(market, false),
+            AccountMeta::new(market_account.event_heap, false),
+            AccountMeta::new(account_1, false),
+        ],
+        data: openbook_v2::instruction::ConsumeEvents { limit: 10 }.data(),
+    };
+    solana
+        .process_transaction(&[legacy_consume_events], None)
+        .await
+        .unwrap();
+    let event_heap = solana
+        .get_account_boxed::<EventHeap>(market_account.event_heap)
+        .await;
+    assert_eq!(event_heap.header.count(), 0);
+    assert_eq!(
+        solana.get_account_lamports(market).await,
+        market_lamports + 700
+    );
+
+    // Lamports sent to the market aren't penalties and aren't paid as rewards
+    solana
+        .process_transaction(
+            &[system_instruction::transfer(
+                &cranker.pubkey(),
+                &market,
+                10_000,
+            )],
+            Some(&[cranker]),
+        )
+        .await
+        .unwrap();
+    let cranker_lamports = solana.get_account_lamports(cranker.pubkey()).await;
+    send_tx(
+        solana,
+        SetCrankRewardsInstruction {
+            collect_fee_admin,
+            market,
+            penalty_event_heap: Some(0),
+            crank_reward_per_event: 1000,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 2,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceTakeOrderInstruction {
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        ConsumeEventsWithRewardInstruction {
+            market,
+            open_orders_accounts: vec![account_1],
+            crank_reward_receiver: cranker,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account_lamports(cranker.pubkey()).await,
+        cranker_lamports + 700
+    );
+    let market_account = solana.get_account::<Market>(market).await;
+    assert_eq!(market_account.penalty_lamports_accrued, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_zero_penalty_settle_and_close() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+    send_tx(
+        solana,
+        SetCrankRewardsInstruction {
+            collect_fee_admin,
+            market,
+            penalty_event_heap: Some(0),
+            crank_reward_per_event: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    // The taker adds a fill event to the heap while the penalty is free
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    let position = solana
+        .get_account::<OpenOrdersAccount>(account_2)
+        .await
+        .position;
+    assert_eq!(position.penalty_heap_count, 1);
+    assert_eq!(position.penalty_lamports_owed, 0);
+
+    send_tx(
+        solana,
+        ConsumeEventsInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1, account_2],
+        },
+    )
+    .await
+    .unwrap();
+
+    // Raising the penalty later doesn't charge the additions made while it was free
+    send_tx(
+        solana,
+        SetCrankRewardsInstruction {
+            collect_fee_admin,
+            market,
+            penalty_event_heap: Some(1000),
+            crank_reward_per_event: 0,
+        },
+    )
+    .await
+    .unwrap();
+    let market_lamports = solana.get_account_lamports(market).await;
+    send_tx(
+        solana,
+        SettleFundsInstruction {
+            owner,
+            market,
+            open_orders_account: account_2,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            referrer_account: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(solana.get_account_lamports(market).await, market_lamports);
+    let position = solana
+        .get_account::<OpenOrdersAccount>(account_2)
+        .await
+        .position;
+    assert_eq!(position.penalty_heap_count, 0);
+
+    send_tx(
+        solana,
+        CloseOpenOrdersAccountInstruction {
+            account_num: 2,
+            market,
+            owner,
+            sol_destination: owner.pubkey(),
+        },
+    )
+    .await
+    .unwrap();
+
+    Ok(())
+}
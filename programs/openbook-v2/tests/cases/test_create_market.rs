@@ -145,3 +145,143 @@ async fn test_with_wrong_oracle_types() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_with_invalid_order_size_limits() -> Result<(), TransportError> {
+    let context = TestContextBuilder::new().start_default().await;
+    let solana = &context.solana.clone();
+
+    let payer = context.users[0].key;
+    let mints = &context.mints[0..=2];
+
+    let market = TestKeypair::new();
+
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CreateMarketInstruction {
+                payer,
+                market,
+                quote_lot_size: 100,
+                base_lot_size: 100,
+                base_mint: mints[0].pubkey,
+                quote_mint: mints[1].pubkey,
+                min_base_lots_per_order: 10,
+                max_base_lots_per_order: 5,
+                ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+            },
+        )
+        .await,
+        Some(openbook_v2::error::OpenBookError::InvalidInputOrderSizeLimits.into())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_with_custom_capacity() -> Result<(), TransportError> {
+    let context = TestContextBuilder::new().start_default().await;
+    let solana = &context.solana.clone();
+
+    let payer = context.users[0].key;
+    let mints = &context.mints[0..=2];
+
+    // below the minimum capacity
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CreateMarketInstruction {
+                payer,
+                market: TestKeypair::new(),
+                quote_lot_size: 100,
+                base_lot_size: 100,
+                base_mint: mints[0].pubkey,
+                quote_mint: mints[1].pubkey,
+                ..CreateMarketInstruction::with_book_and_heap_capacity(
+                    solana,
+                    None,
+                    None,
+                    MIN_ORDERTREE_NODES - 1,
+                    MIN_NUM_EVENTS.into(),
+                )
+                .await
+            },
+        )
+        .await,
+        Some(OpenBookError::InvalidInputCapacity.into())
+    );
+
+    // sizes that don't fit a whole number of nodes
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CreateMarketInstruction {
+                payer,
+                market: TestKeypair::new(),
+                quote_lot_size: 100,
+                base_lot_size: 100,
+                base_mint: mints[0].pubkey,
+                quote_mint: mints[1].pubkey,
+                event_heap: solana
+                    .create_account_from_len(&openbook_v2::id(), EventHeap::space(64) + 8)
+                    .await,
+                ..CreateMarketInstruction::with_book_and_heap_capacity(solana, None, None, 64, 64)
+                    .await
+            },
+        )
+        .await,
+        Some(OpenBookError::InvalidInputCapacity.into())
+    );
+
+    let market = TestKeypair::new();
+    let market_pk = market.pubkey();
+    send_tx(
+        solana,
+        CreateMarketInstruction {
+            payer,
+            market,
+            quote_lot_size: 100,
+            base_lot_size: 100,
+            base_mint: mints[0].pubkey,
+            quote_mint: mints[1].pubkey,
+            ..CreateMarketInstruction::with_book_and_heap_capacity(solana, None, None, 64, 64).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let market_acc = solana.get_account::<Market>(market_pk).await;
+    let event_heap_data = solana
+        .get_account_data(market_acc.event_heap)
+        .await
+        .unwrap();
+    assert_eq!(event_heap_data.len(), EventHeap::space(64));
+
+    // slots are checked against the capacity of the event heap
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            ConsumeGivenEventsInstruction {
+                consume_events_admin: None,
+                market: market_pk,
+                slots: vec![64],
+                open_orders_accounts: vec![],
+            },
+        )
+        .await,
+        Some(OpenBookError::InvalidInputHeapSlots.into())
+    );
+    send_tx(
+        solana,
+        ConsumeGivenEventsInstruction {
+            consume_events_admin: None,
+            market: market_pk,
+            slots: vec![63],
+            open_orders_accounts: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    Ok(())
+}
@@ -222,6 +222,7 @@ async fn test_maker_fees() -> Result<(), TransportError> {
             market,
             signer: owner,
             client_order_id: 30,
+            remainings: vec![],
         },
     )
     .await
@@ -486,6 +487,7 @@ async fn test_market_maker_fees() -> Result<(), TransportError> {
             max_base_lots: 1,
             max_quote_lots_including_fees: 10040,
             open_orders_admin: None,
+            remainings: vec![],
         },
     )
     .await
@@ -1394,3 +1396,227 @@ async fn test_locked_maker_fees() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_fee_split() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        maker_fee: -100,
+        taker_fee: 200,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    let place_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, place_ix.clone()).await.unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            ..place_ix
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        ConsumeEventsInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1, account_2],
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        SettleFundsInstruction {
+            owner,
+            market,
+            open_orders_account: account_2,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            referrer_account: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let receivers: Vec<Pubkey> = context.users[1..]
+        .iter()
+        .map(|user| user.token_accounts[1])
+        .collect();
+    let destinations = |shares_bps: &[u16]| -> Vec<FeeSplitDestination> {
+        receivers
+            .iter()
+            .zip(shares_bps)
+            .map(|(token_account, share_bps)| FeeSplitDestination {
+                token_account: *token_account,
+                share_bps: *share_bps,
+            })
+            .collect()
+    };
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            CreateFeeSplitInstruction {
+                payer: owner,
+                collect_fee_admin,
+                market,
+                destinations: destinations(&[5000, 3000, 1000]),
+            },
+        )
+        .await,
+        OpenBookError::InvalidInputFeeSplit.error_code(),
+        "shares don't add up to 100%".into(),
+    );
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            CreateFeeSplitInstruction {
+                payer: owner,
+                collect_fee_admin,
+                market,
+                destinations: vec![FeeSplitDestination {
+                    token_account: context.users[1].token_accounts[0],
+                    share_bps: 10000,
+                }],
+            },
+        )
+        .await,
+        OpenBookError::InvalidFeeSplitDestination.error_code(),
+        "destination doesn't hold the quote mint".into(),
+    );
+    send_tx(
+        solana,
+        CreateFeeSplitInstruction {
+            payer: owner,
+            collect_fee_admin,
+            market,
+            destinations: destinations(&[5000, 3000, 2000]),
+        },
+    )
+    .await
+    .unwrap();
+
+    // The split can't be bypassed
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            SweepFeesInstruction {
+                collect_fee_admin,
+                market,
+                market_quote_vault,
+                token_receiver_account: owner_token_1,
+            },
+        )
+        .await,
+        OpenBookError::InvalidFeeSplit.error_code(),
+        "market has a fee split".into(),
+    );
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            SweepFeesSplitInstruction {
+                collect_fee_admin,
+                market,
+                destinations: receivers.iter().rev().copied().collect(),
+            },
+        )
+        .await,
+        OpenBookError::InvalidFeeSplitDestination.error_code(),
+        "destinations in the wrong order".into(),
+    );
+
+    let mut balances_before = vec![];
+    for receiver in receivers.iter() {
+        balances_before.push(solana.token_account_balance(*receiver).await);
+    }
+
+    send_tx(
+        solana,
+        SweepFeesSplitInstruction {
+            collect_fee_admin,
+            market,
+            destinations: receivers.clone(),
+        },
+    )
+    .await
+    .unwrap();
+
+    for (i, expected) in [5, 3, 2].iter().enumerate() {
+        assert_eq!(
+            solana.token_account_balance(receivers[i]).await,
+            balances_before[i] + expected
+        );
+    }
+    {
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.fees_available, 0);
+        assert_eq!(market.fees_accrued, 10);
+    }
+
+    send_tx(
+        solana,
+        SetFeeSplitInstruction {
+            collect_fee_admin,
+            market,
+            destinations: destinations(&[10000]),
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        SweepFeesSplitInstruction {
+            collect_fee_admin,
+            market,
+            destinations: receivers[..1].to_vec(),
+        },
+    )
+    .await
+    .unwrap();
+
+    Ok(())
+}
@@ -0,0 +1,338 @@
+use super::*;
+
+#[tokio::test]
+async fn test_heartbeat_expired_orders_are_dropped() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        HeartbeatInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_1,
+            timeout: 10,
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let now_ts = solana.get_clock().await.unix_timestamp as u64;
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let deadline = open_orders_account_1.position.heartbeat_deadline;
+        assert!(deadline > now_ts && deadline <= now_ts + 10);
+    }
+
+    // Deadline not reached, nobody can cancel the orders
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            CancelOrdersHeartbeatExpiredInstruction {
+                market,
+                open_orders_account: account_1,
+            },
+        )
+        .await,
+        OpenBookError::HeartbeatNotExpired.error_code(),
+        "heartbeat has not expired".into(),
+    );
+
+    solana.advance_clock(11).await;
+
+    // The bid is dropped instead of being matched, even though the taker doesn't
+    // pass the maker's account
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+        assert_eq!(bids_data.roots[0].leaf_count, 0);
+
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 1);
+        assert_eq!(open_orders_account_2.position.quote_free_native, 0);
+    }
+
+    // Only the out event of the dropped bid is on the event_heap
+    {
+        let market_acc = solana.get_account_boxed::<Market>(market).await;
+        let event_heap = solana
+            .get_account_boxed::<EventHeap>(market_acc.event_heap)
+            .await;
+        assert_eq!(event_heap.header.count(), 1);
+        assert_eq!(event_heap.front().unwrap().event_type, EventType::Out as u8);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cancel_orders_heartbeat_expired() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    // Disabled heartbeat never expires
+    send_tx(
+        solana,
+        HeartbeatInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_1,
+            timeout: 0,
+        },
+    )
+    .await
+    .unwrap();
+    solana.advance_clock(100).await;
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            CancelOrdersHeartbeatExpiredInstruction {
+                market,
+                open_orders_account: account_1,
+            },
+        )
+        .await,
+        OpenBookError::HeartbeatNotExpired.error_code(),
+        "heartbeat is disabled".into(),
+    );
+
+    send_tx(
+        solana,
+        HeartbeatInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_1,
+            timeout: 5,
+        },
+    )
+    .await
+    .unwrap();
+    solana.advance_clock(6).await;
+
+    send_tx(
+        solana,
+        CancelOrdersHeartbeatExpiredInstruction {
+            market,
+            open_orders_account: account_1,
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+        assert_eq!(bids_data.roots[0].leaf_count, 0);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert!(!open_orders_account_1.position.has_open_orders());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_heartbeat_refreshes_resting_orders() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    send_tx(
+        solana,
+        HeartbeatInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_1,
+            timeout: 10,
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    // A later heartbeat moves the deadline of the resting bid as well
+    solana.advance_clock(8).await;
+    send_tx(
+        solana,
+        HeartbeatInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_1,
+            timeout: 10,
+        },
+    )
+    .await
+    .unwrap();
+    solana.advance_clock(8).await;
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![account_1],
+        },
+    )
+    .await
+    .unwrap();
+
+    // The ask was filled against the bid instead of resting on the book
+    {
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 0);
+        assert_eq!(open_orders_account_2.position.quote_free_native, 99960);
+    }
+
+    Ok(())
+}
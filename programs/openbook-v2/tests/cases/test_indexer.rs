@@ -164,6 +164,7 @@ async fn test_size_vector() -> Result<(), TransportError> {
             owner,
             payer: context.users[1].key,
             delegate: None,
+            access_proof: None,
         },
     )
     .await
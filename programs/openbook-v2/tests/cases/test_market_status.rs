@@ -0,0 +1,516 @@
+use super::*;
+
+#[tokio::test]
+async fn test_market_status_admin() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        market_status_admin,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        market_status_admin_bool: true,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 1,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+
+    let place_take_ix = PlaceTakeOrderInstruction {
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        market_base_vault,
+        market_quote_vault,
+        user_base_account: owner_token_0,
+        user_quote_account: owner_token_1,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        remainings: vec![],
+    };
+
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+
+    // Only the market_status_admin can change the status
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            SetMarketStatusInstruction {
+                market_status_admin: owner,
+                market,
+                status: MarketStatus::Halted,
+            },
+        )
+        .await,
+        OpenBookError::InvalidMarketStatusAdmin.error_code(),
+        "owner is not the market_status_admin".into(),
+    );
+
+    send_tx(
+        solana,
+        SetMarketStatusInstruction {
+            market_status_admin,
+            market,
+            status: MarketStatus::PostOnly,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_openbook_error(
+        &send_tx(solana, place_bid_ix.clone()).await,
+        OpenBookError::MarketPostOnly.error_code(),
+        "limit orders may take".into(),
+    );
+    assert_openbook_error(
+        &send_tx(solana, place_take_ix).await,
+        OpenBookError::MarketPostOnly.error_code(),
+        "take orders never post".into(),
+    );
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            client_order_id: 2,
+            order_type: PlaceOrderType::PostOnly,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        SetMarketStatusInstruction {
+            market_status_admin,
+            market,
+            status: MarketStatus::CancelOnly,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            PlaceOrderInstruction {
+                order_type: PlaceOrderType::PostOnly,
+                ..place_bid_ix.clone()
+            },
+        )
+        .await,
+        OpenBookError::MarketCancelOnly.error_code(),
+        "no new orders".into(),
+    );
+    send_tx(
+        solana,
+        CancelOrderByClientOrderIdInstruction {
+            open_orders_account: account_1,
+            market,
+            signer: owner,
+            client_order_id: 1,
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        SetMarketStatusInstruction {
+            market_status_admin,
+            market,
+            status: MarketStatus::Halted,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            CancelAllOrdersInstruction {
+                open_orders_account: account_1,
+                market,
+                signer: owner,
+            },
+        )
+        .await,
+        OpenBookError::MarketHalted.error_code(),
+        "no cancels either".into(),
+    );
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 1);
+    }
+
+    // Back to normal, nothing was permanent
+    send_tx(
+        solana,
+        SetMarketStatusInstruction {
+            market_status_admin,
+            market,
+            status: MarketStatus::Active,
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        CancelAllOrdersInstruction {
+            open_orders_account: account_1,
+            market,
+            signer: owner,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(solana, place_bid_ix).await.unwrap();
+
+    {
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.status(), MarketStatus::Active);
+        assert_eq!(market.time_expiry, 0);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 1);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_market_status_admin() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        market,
+        market_status_admin,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // Markets created without a status admin can't change their status
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            SetMarketStatusInstruction {
+                market_status_admin,
+                market,
+                status: MarketStatus::Halted,
+            },
+        )
+        .await,
+        OpenBookError::NoMarketStatusAdmin.error_code(),
+        "market has no market_status_admin".into(),
+    );
+
+    // Only the collect_fee_admin can appoint one
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            SetMarketStatusAdminInstruction {
+                collect_fee_admin: owner,
+                market,
+                market_status_admin: Some(owner.pubkey()),
+            },
+        )
+        .await,
+        anchor_lang::error::ErrorCode::ConstraintHasOne.into(),
+        "owner is not the collect_fee_admin".into(),
+    );
+
+    send_tx(
+        solana,
+        SetMarketStatusAdminInstruction {
+            collect_fee_admin,
+            market,
+            market_status_admin: Some(market_status_admin.pubkey()),
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        SetMarketStatusInstruction {
+            market_status_admin,
+            market,
+            status: MarketStatus::CancelOnly,
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.market_status_admin, market_status_admin.pubkey());
+        assert_eq!(market.status(), MarketStatus::CancelOnly);
+    }
+
+    // Clearing the admin leaves the status as it is
+    send_tx(
+        solana,
+        SetMarketStatusAdminInstruction {
+            collect_fee_admin,
+            market,
+            market_status_admin: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            SetMarketStatusInstruction {
+                market_status_admin,
+                market,
+                status: MarketStatus::Active,
+            },
+        )
+        .await,
+        OpenBookError::NoMarketStatusAdmin.error_code(),
+        "market_status_admin was cleared".into(),
+    );
+
+    {
+        let market = solana.get_account::<Market>(market).await;
+        assert!(market.market_status_admin.is_none());
+        assert_eq!(market.status(), MarketStatus::CancelOnly);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_market_halted_blocks_cranks() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        close_market_admin,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        market_status_admin,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        auction_duration_secs: 60,
+        close_market_admin_bool: true,
+        market_status_admin_bool: true,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    // Crossing orders rest on the book during the auction phase
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        HeartbeatInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_1,
+            timeout: 10,
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        SetMarketStatusInstruction {
+            market_status_admin,
+            market,
+            status: MarketStatus::Halted,
+        },
+    )
+    .await
+    .unwrap();
+
+    solana.advance_clock(61).await;
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            RunAuctionInstruction {
+                market,
+                remainings: vec![account_1, account_2],
+                limit: 10,
+            },
+        )
+        .await,
+        OpenBookError::MarketHalted.error_code(),
+        "halted markets don't run auctions".into(),
+    );
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            CancelOrdersHeartbeatExpiredInstruction {
+                market,
+                open_orders_account: account_1,
+            },
+        )
+        .await,
+        OpenBookError::MarketHalted.error_code(),
+        "halted markets don't cancel on heartbeat expiry".into(),
+    );
+
+    send_tx(
+        solana,
+        SetMarketExpiredInstruction {
+            close_market_admin,
+            market,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            ForcePruneOrdersInstruction {
+                signer: close_market_admin,
+                market,
+                open_orders_account: account_2,
+            },
+        )
+        .await,
+        OpenBookError::MarketHalted.error_code(),
+        "halted markets can't be force closed".into(),
+    );
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 1);
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 1);
+    }
+
+    send_tx(
+        solana,
+        SetMarketStatusInstruction {
+            market_status_admin,
+            market,
+            status: MarketStatus::Active,
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        CancelOrdersHeartbeatExpiredInstruction {
+            market,
+            open_orders_account: account_1,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        ForcePruneOrdersInstruction {
+            signer: close_market_admin,
+            market,
+            open_orders_account: account_2,
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 0);
+    }
+
+    Ok(())
+}
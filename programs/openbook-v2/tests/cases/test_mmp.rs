@@ -0,0 +1,445 @@
+use super::*;
+use openbook_v2::logs::MmpTriggeredLog;
+
+#[tokio::test]
+async fn test_mmp_freezes_maker_after_fill_burst() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    send_tx(
+        solana,
+        SetMmpInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_1,
+            window_secs: 60,
+            base_lots_limit: 2,
+            fill_count_limit: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    let bid = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 3,
+        max_quote_lots_including_fees: 30_100,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, bid.clone()).await.unwrap();
+
+    let ask = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots,
+        max_base_lots: 2,
+        max_quote_lots_including_fees: 20_000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![account_1],
+    };
+
+    // Filling 2 base lots reaches the limit and freezes the maker
+    send_tx(solana, ask.clone()).await.unwrap();
+    {
+        let logs = solana.program_log_events::<MmpTriggeredLog>();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].open_orders_account, account_1);
+        assert_eq!(logs[0].window_base_lots, 2);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert!(open_orders_account_1.is_mmp_frozen());
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 1);
+        assert_eq!(open_orders_account_1.position.base_free_native, 200);
+    }
+
+    // Disabling the protection doesn't lift a triggered one
+    let disable_mmp = SetMmpInstruction {
+        signer: owner,
+        market,
+        open_orders_account: account_1,
+        window_secs: 0,
+        base_lots_limit: 0,
+        fill_count_limit: 0,
+    };
+    assert_openbook_error(
+        &send_tx(solana, disable_mmp.clone()).await,
+        OpenBookError::MmpFrozen.error_code(),
+        "a triggered protection can only be reset by the owner".into(),
+    );
+
+    // The remaining bid of the frozen maker is dropped instead of being matched
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10_000,
+            ..ask.clone()
+        },
+    )
+    .await
+    .unwrap();
+    {
+        let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+        assert_eq!(bids_data.roots[0].leaf_count, 0);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_1.position.base_free_native, 200);
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 1);
+    }
+
+    send_tx(
+        solana,
+        ResetMmpInstruction {
+            owner,
+            open_orders_account: account_1,
+        },
+    )
+    .await
+    .unwrap();
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert!(!open_orders_account_1.is_mmp_frozen());
+        assert_eq!(open_orders_account_1.position.mmp_window_base_lots, 0);
+    }
+    send_tx(solana, disable_mmp).await.unwrap();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mmp_window_resets() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    send_tx(
+        solana,
+        SetMmpInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_1,
+            window_secs: 10,
+            base_lots_limit: 0,
+            fill_count_limit: 2,
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 3,
+            max_quote_lots_including_fees: 30_100,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    let ask = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10_000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::ImmediateOrCancel,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![account_1],
+    };
+
+    // Two fills in separate windows don't trigger the protection
+    send_tx(solana, ask.clone()).await.unwrap();
+    solana.advance_clock(11).await;
+    send_tx(solana, ask.clone()).await.unwrap();
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert!(!open_orders_account_1.is_mmp_frozen());
+        assert_eq!(open_orders_account_1.position.mmp_window_fill_count, 1);
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 1);
+    }
+
+    // The second fill within the window does
+    send_tx(solana, ask.clone()).await.unwrap();
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert!(open_orders_account_1.is_mmp_frozen());
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mmp_freezes_maker_within_a_sweep() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+    let delegate = context.users[1].key;
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    let bid = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10_100,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, bid.clone()).await.unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            price_lots: price_lots - 1,
+            ..bid.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    // Enabling the protection flags the bids that are already resting
+    send_tx(
+        solana,
+        SetMmpInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_1,
+            window_secs: 60,
+            base_lots_limit: 0,
+            fill_count_limit: 1,
+        },
+    )
+    .await
+    .unwrap();
+
+    let ask = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots: price_lots - 1,
+        max_base_lots: 2,
+        max_quote_lots_including_fees: 20_000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::ImmediateOrCancel,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+
+    // Takers that don't pass the maker's account skip its orders instead of filling them
+    // past the protection, and still match the other makers. Nothing is posted across
+    // the skipped orders.
+    let account_3 =
+        create_open_orders_account(solana, owner, market, 3, &context.users[1], None).await;
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_3,
+            price_lots: price_lots - 1,
+            ..bid.clone()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(solana, ask.clone()).await.unwrap();
+    {
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        // one base lot of the third account's bid, at a quote lot size of 10
+        assert_eq!(
+            open_orders_account_2.position.taker_volume,
+            (price_lots - 1) as u128 * 10
+        );
+    }
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            order_type: PlaceOrderType::Limit,
+            ..ask.clone()
+        },
+    )
+    .await
+    .unwrap();
+    {
+        let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+        assert_eq!(bids_data.roots[0].leaf_count, 2);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert!(!open_orders_account_1.is_mmp_frozen());
+        assert_eq!(open_orders_account_1.position.mmp_window_fill_count, 0);
+
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 0);
+    }
+
+    // The first fill freezes the maker and its second bid is dropped in the same sweep
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            remainings: vec![account_1],
+            ..ask.clone()
+        },
+    )
+    .await
+    .unwrap();
+    {
+        let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+        assert_eq!(bids_data.roots[0].leaf_count, 0);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert!(open_orders_account_1.is_mmp_frozen());
+        assert_eq!(open_orders_account_1.position.mmp_window_fill_count, 1);
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_1.position.base_free_native, 100);
+    }
+
+    // Only the owner can unfreeze the account
+    send_tx(
+        solana,
+        SetDelegateInstruction {
+            owner,
+            open_orders_account: account_1,
+            delegate_account: Some(delegate.pubkey()),
+            scope: DelegateScope::Trade,
+            allowed_side: None,
+            max_quote_native_per_order: 0,
+            expiry_timestamp: 0,
+            open_orders_delegates: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert!(send_tx(
+        solana,
+        ResetMmpInstruction {
+            owner: delegate,
+            open_orders_account: account_1,
+        },
+    )
+    .await
+    .is_err());
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            SetMmpInstruction {
+                signer: delegate,
+                market,
+                open_orders_account: account_1,
+                window_secs: 0,
+                base_lots_limit: 0,
+                fill_count_limit: 0,
+            },
+        )
+        .await,
+        OpenBookError::MmpFrozen.error_code(),
+        "disabling the protection doesn't unfreeze the account".into(),
+    );
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert!(open_orders_account_1.is_mmp_frozen());
+    }
+
+    Ok(())
+}
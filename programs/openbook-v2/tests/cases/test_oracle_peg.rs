@@ -285,6 +285,7 @@ async fn test_oracle_peg() -> Result<(), TransportError> {
             market,
             signer: owner,
             client_order_id: 60,
+            remainings: vec![],
         },
     )
     .await
@@ -413,6 +414,7 @@ async fn test_oracle_peg() -> Result<(), TransportError> {
             market,
             signer: owner,
             client_order_id: 63,
+            remainings: vec![],
         },
     )
     .await
@@ -0,0 +1,131 @@
+use super::*;
+
+#[tokio::test]
+async fn test_order_size_limits() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        min_base_lots_per_order: 2,
+        // 2 base lots at price_lots are worth 200_000 quote native
+        min_quote_notional: 250_000,
+        max_base_lots_per_order: 5,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 100_000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+
+    // Below min_base_lots_per_order and below min_quote_notional, nothing is posted
+    for max_base_lots in [1, 2] {
+        send_tx(
+            solana,
+            PlaceOrderInstruction {
+                max_base_lots,
+                ..place_bid_ix.clone()
+            },
+        )
+        .await
+        .unwrap();
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+    }
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            max_base_lots: 3,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            PlaceOrderInstruction {
+                max_base_lots: 6,
+                ..place_bid_ix.clone()
+            },
+        )
+        .await,
+        OpenBookError::OrderSizeAboveMaximum.error_code(),
+        "above max_base_lots_per_order".into(),
+    );
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 3);
+    }
+
+    // The taken part of an order isn't limited, only its remainder
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 4,
+            max_quote_lots_including_fees: 100_000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![account_1],
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+        assert_eq!(bids_data.roots[0].leaf_count, 0);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_1.position.base_free_native, 300);
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 0);
+    }
+
+    Ok(())
+}
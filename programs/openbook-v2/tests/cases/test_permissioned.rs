@@ -434,8 +434,18 @@ async fn test_close_market_admin() -> Result<(), TransportError> {
     .await
     .unwrap();
 
-    // Can't close, have to prune orders first
+    let shrink_ix = ShrinkMarketAccountsInstruction {
+        close_market_admin,
+        market,
+        sol_destination: owner.pubkey(),
+    };
+
+    // Can't close or shrink the book, have to prune orders first
     assert!(send_tx(solana, close_ix.clone()).await.is_err());
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, shrink_ix.clone()).await,
+        Some(OpenBookError::BookContainsElements.into())
+    );
 
     send_tx(
         solana,
@@ -456,6 +466,28 @@ async fn test_close_market_admin() -> Result<(), TransportError> {
     }
     assert!(send_tx(solana, close_ix.clone()).await.is_err());
 
+    // but the empty book and event heap can give back their rent already
+    let market_acc = solana.get_account::<Market>(market).await;
+    let lamports_before = solana.get_account_lamports(owner.pubkey()).await;
+    send_tx(solana, shrink_ix).await.unwrap();
+    assert!(solana.get_account_lamports(owner.pubkey()).await > lamports_before + 1_000_000_000);
+    assert_eq!(
+        solana
+            .get_account_data(market_acc.bids)
+            .await
+            .unwrap()
+            .len(),
+        BookSide::space(MIN_ORDERTREE_NODES)
+    );
+    assert_eq!(
+        solana
+            .get_account_data(market_acc.event_heap)
+            .await
+            .unwrap()
+            .len(),
+        EventHeap::space(MIN_NUM_EVENTS.into())
+    );
+
     send_tx(
         solana,
         SettleFundsInstruction {
@@ -501,6 +533,181 @@ async fn test_close_market_admin() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_force_settle_after_expiry() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        close_market_admin,
+        collect_fee_admin,
+        owner,
+        mints,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        close_market_admin_bool: true,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+    let anyone = context.users[1].key;
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    for open_orders_account in [account_1, account_2] {
+        send_tx(
+            solana,
+            PlaceOrderInstruction {
+                open_orders_account,
+                open_orders_admin: None,
+                market,
+                signer: owner,
+                user_token_account: owner_token_1,
+                market_vault: market_quote_vault,
+                side: Side::Bid,
+                price_lots,
+                max_base_lots: 1,
+                max_quote_lots_including_fees: 10000,
+                client_order_id: 0,
+                expiry_timestamp: 0,
+                order_type: PlaceOrderType::Limit,
+                self_trade_behavior: SelfTradeBehavior::default(),
+                remainings: vec![],
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    // Any token account of the owner will do, the owner may have closed their ATAs
+    let owner_base_account = solana
+        .create_token_account(&owner.pubkey(), mints[0].pubkey)
+        .await;
+    let owner_quote_account = solana
+        .create_token_account(&owner.pubkey(), mints[1].pubkey)
+        .await;
+
+    let force_prune_ix =
+        |signer: TestKeypair, open_orders_account: Pubkey| ForcePruneOrdersInstruction {
+            signer,
+            market,
+            open_orders_account,
+        };
+    let force_settle_ix =
+        |signer: TestKeypair, open_orders_account: Pubkey| ForceSettleFundsInstruction {
+            signer,
+            owner: owner.pubkey(),
+            open_orders_account,
+            market,
+            user_base_account: owner_base_account,
+            user_quote_account: owner_quote_account,
+        };
+
+    assert_openbook_error(
+        &send_tx(solana, force_prune_ix(close_market_admin, account_1)).await,
+        OpenBookError::MarketHasNotExpired.error_code(),
+        "market not expired".into(),
+    );
+
+    send_tx(
+        solana,
+        SetMarketExpiredInstruction {
+            close_market_admin,
+            market,
+        },
+    )
+    .await
+    .unwrap();
+
+    // Only the close market admin can force the owner out during the grace period
+    assert_openbook_error(
+        &send_tx(solana, force_prune_ix(anyone, account_1)).await,
+        OpenBookError::MarketGracePeriodNotOver.error_code(),
+        "grace period not over".into(),
+    );
+    send_tx(solana, force_prune_ix(close_market_admin, account_1))
+        .await
+        .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert!(open_orders_account_1.position.quote_free_native > 0);
+    }
+
+    assert_openbook_error(
+        &send_tx(solana, force_settle_ix(anyone, account_1)).await,
+        OpenBookError::MarketGracePeriodNotOver.error_code(),
+        "grace period not over".into(),
+    );
+
+    // Markets set expired before the upgrade don't know when that was. Their grace
+    // period counts from the time_expiry of -1 and is over, anyone can force close.
+    let time_set_expired = solana.get_account::<Market>(market).await.time_set_expired;
+    let set_time_set_expired = |time_set_expired: i64| async move {
+        let mut market_state = solana.get_account::<Market>(market).await;
+        market_state.time_set_expired = time_set_expired;
+        let mut data = solana.get_account_data(market).await.unwrap();
+        data[8..8 + std::mem::size_of::<Market>()]
+            .copy_from_slice(bytemuck::bytes_of(&market_state));
+        solana.set_account_data(market, &data).await;
+    };
+    set_time_set_expired(0).await;
+    send_tx(solana, force_prune_ix(anyone, account_2))
+        .await
+        .unwrap();
+    let quote_free_native_2 = solana
+        .get_account::<OpenOrdersAccount>(account_2)
+        .await
+        .position
+        .quote_free_native;
+    send_tx(solana, force_settle_ix(anyone, account_2))
+        .await
+        .unwrap();
+    assert_eq!(
+        solana.token_account_balance(owner_quote_account).await,
+        quote_free_native_2
+    );
+
+    set_time_set_expired(time_set_expired).await;
+    assert_openbook_error(
+        &send_tx(solana, force_settle_ix(anyone, account_1)).await,
+        OpenBookError::MarketGracePeriodNotOver.error_code(),
+        "grace period not over".into(),
+    );
+
+    solana.advance_clock(EXPIRED_MARKET_GRACE_PERIOD + 1).await;
+
+    // Afterwards anyone can push the funds to the owner's token accounts
+    let quote_free_native_1 = solana
+        .get_account::<OpenOrdersAccount>(account_1)
+        .await
+        .position
+        .quote_free_native;
+    send_tx(solana, force_settle_ix(anyone, account_1))
+        .await
+        .unwrap();
+
+    assert_eq!(solana.token_account_balance(owner_base_account).await, 0);
+    assert_eq!(
+        solana.token_account_balance(owner_quote_account).await,
+        quote_free_native_1 + quote_free_native_2
+    );
+    {
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.base_deposit_total, 0);
+        assert_eq!(market.quote_deposit_total, 0);
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_delegate() -> Result<(), TransportError> {
     let TestInitialize {
@@ -564,6 +771,7 @@ async fn test_delegate() -> Result<(), TransportError> {
             market,
             open_orders_account: account_3,
             client_order_id: 23,
+            remainings: vec![],
         },
     )
     .await
@@ -575,6 +783,11 @@ async fn test_delegate() -> Result<(), TransportError> {
             owner,
             open_orders_account: account_3,
             delegate_account: None,
+            scope: DelegateScope::Full,
+            allowed_side: None,
+            max_quote_native_per_order: 0,
+            expiry_timestamp: 0,
+            open_orders_delegates: None,
         },
     )
     .await
@@ -607,3 +820,868 @@ async fn test_delegate() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_delegate_permissions() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let account_3_delegate = context.users[2].key;
+    let account_3 = create_open_orders_account(
+        solana,
+        owner,
+        market,
+        3,
+        &context.users[0],
+        Some(account_3_delegate.pubkey()),
+    )
+    .await;
+    let delegate_token_0 = context.users[2].token_accounts[0];
+    let delegate_token_1 = context.users[2].token_accounts[1];
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    let set_delegate_ix = SetDelegateInstruction {
+        owner,
+        open_orders_account: account_3,
+        delegate_account: Some(account_3_delegate.pubkey()),
+        scope: DelegateScope::Cancel,
+        allowed_side: None,
+        max_quote_native_per_order: 0,
+        expiry_timestamp: 0,
+        open_orders_delegates: None,
+    };
+
+    // Limits are stored in the delegates account
+    assert_openbook_error(
+        &send_tx(solana, set_delegate_ix.clone()).await,
+        OpenBookError::MissingOpenOrdersDelegates.error_code(),
+        "scoped delegate without a delegates account".into(),
+    );
+    send_tx(
+        solana,
+        CreateOpenOrdersDelegatesInstruction {
+            payer: owner,
+            owner,
+            open_orders_account: account_3,
+        },
+    )
+    .await
+    .unwrap();
+    let open_orders_delegates = get_open_orders_delegates_address(account_3);
+    let set_delegate_ix = SetDelegateInstruction {
+        open_orders_delegates: Some(open_orders_delegates),
+        ..set_delegate_ix
+    };
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_3,
+        open_orders_admin: None,
+        market,
+        signer: account_3_delegate,
+        user_token_account: delegate_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 1,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![open_orders_delegates],
+    };
+
+    let cancel_ix = CancelOrderByClientOrderIdInstruction {
+        signer: account_3_delegate,
+        market,
+        open_orders_account: account_3,
+        client_order_id: 1,
+        remainings: vec![open_orders_delegates],
+    };
+
+    send_tx(solana, set_delegate_ix).await.unwrap();
+
+    // Once the account has delegates their limits can't be skipped by leaving them out
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            CancelOrderByClientOrderIdInstruction {
+                remainings: vec![],
+                ..cancel_ix.clone()
+            },
+        )
+        .await,
+        OpenBookError::MissingOpenOrdersDelegates.error_code(),
+        "delegates account not passed".into(),
+    );
+
+    // A cancel only delegate can't trade, but cancel the orders of the owner
+    assert_openbook_error(
+        &send_tx(solana, place_bid_ix.clone()).await,
+        OpenBookError::NoOwnerOrDelegate.error_code(),
+        "delegate can only cancel".into(),
+    );
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            signer: owner,
+            user_token_account: owner_token_1,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(solana, cancel_ix.clone()).await.unwrap();
+
+    send_tx(
+        solana,
+        SetDelegateInstruction {
+            owner,
+            open_orders_account: account_3,
+            delegate_account: Some(account_3_delegate.pubkey()),
+            scope: DelegateScope::Trade,
+            allowed_side: Some(Side::Bid),
+            // 10000 quote lots
+            max_quote_native_per_order: 100_000,
+            expiry_timestamp: solana.get_clock().await.unix_timestamp as u64 + 100,
+            open_orders_delegates: Some(open_orders_delegates),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            PlaceOrderInstruction {
+                user_token_account: delegate_token_0,
+                market_vault: market_base_vault,
+                side: Side::Ask,
+                ..place_bid_ix.clone()
+            },
+        )
+        .await,
+        OpenBookError::DelegateSideNotAllowed.error_code(),
+        "delegate can only bid".into(),
+    );
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            PlaceOrderInstruction {
+                max_quote_lots_including_fees: 10001,
+                ..place_bid_ix.clone()
+            },
+        )
+        .await,
+        OpenBookError::DelegateOrderTooLarge.error_code(),
+        "above max_quote_native_per_order".into(),
+    );
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+
+    // Asks are valued at their limit price, whatever quote they ask for
+    send_tx(
+        solana,
+        SetDelegateInstruction {
+            owner,
+            open_orders_account: account_3,
+            delegate_account: Some(account_3_delegate.pubkey()),
+            scope: DelegateScope::Trade,
+            allowed_side: None,
+            max_quote_native_per_order: 200_000,
+            expiry_timestamp: solana.get_clock().await.unix_timestamp as u64 + 100,
+            open_orders_delegates: Some(open_orders_delegates),
+        },
+    )
+    .await
+    .unwrap();
+
+    let place_ask_ix = PlaceOrderInstruction {
+        user_token_account: delegate_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots: price_lots + 100,
+        client_order_id: 2,
+        ..place_bid_ix.clone()
+    };
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            PlaceOrderInstruction {
+                max_base_lots: 2,
+                ..place_ask_ix.clone()
+            },
+        )
+        .await,
+        OpenBookError::DelegateOrderTooLarge.error_code(),
+        "base lots at the limit price above max_quote_native_per_order".into(),
+    );
+    // A low limit price doesn't let it sell more, the ask is valued at the best bid then
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            PlaceOrderInstruction {
+                price_lots: 1,
+                max_base_lots: 3,
+                ..place_ask_ix.clone()
+            },
+        )
+        .await,
+        OpenBookError::DelegateOrderTooLarge.error_code(),
+        "base lots at the best bid above max_quote_native_per_order".into(),
+    );
+    send_tx(solana, place_ask_ix).await.unwrap();
+
+    {
+        let open_orders_account_3 = solana.get_account::<OpenOrdersAccount>(account_3).await;
+        assert_eq!(open_orders_account_3.position.bids_base_lots, 1);
+        assert_eq!(open_orders_account_3.position.asks_base_lots, 1);
+    }
+
+    // The delegate is ignored once expired
+    solana.advance_clock(101).await;
+    assert_openbook_error(
+        &send_tx(solana, cancel_ix).await,
+        OpenBookError::NoOwnerOrDelegate.error_code(),
+        "delegate expired".into(),
+    );
+
+    // Closing the delegates account removes the delegate
+    send_tx(
+        solana,
+        CloseOpenOrdersDelegatesInstruction {
+            owner,
+            open_orders_account: account_3,
+            sol_destination: owner.pubkey(),
+        },
+    )
+    .await
+    .unwrap();
+    {
+        let open_orders_account_3 = solana.get_account::<OpenOrdersAccount>(account_3).await;
+        assert!(open_orders_account_3.delegate.is_none());
+        assert!(!open_orders_account_3.has_delegates());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delegate_list() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        market,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let account_3 =
+        create_open_orders_account(solana, owner, market, 3, &context.users[0], None).await;
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    send_tx(
+        solana,
+        CreateOpenOrdersDelegatesInstruction {
+            payer: owner,
+            owner,
+            open_orders_account: account_3,
+        },
+    )
+    .await
+    .unwrap();
+    let open_orders_delegates = get_open_orders_delegates_address(account_3);
+
+    // The account can only be closed after its delegates account
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            CloseOpenOrdersAccountInstruction {
+                account_num: 3,
+                market,
+                owner,
+                sol_destination: owner.pubkey(),
+            },
+        )
+        .await,
+        OpenBookError::OpenOrdersDelegatesNotClosed.error_code(),
+        "delegates account still open".into(),
+    );
+
+    let add_delegate_ix = |delegate_account: Pubkey| AddDelegateInstruction {
+        owner,
+        open_orders_account: account_3,
+        delegate_account,
+        scope: DelegateScope::Trade,
+        allowed_side: None,
+        max_quote_native_per_order: 0,
+        expiry_timestamp: 0,
+    };
+    let place_bid_ix = |user: &UserCookie, client_order_id: u64| PlaceOrderInstruction {
+        open_orders_account: account_3,
+        open_orders_admin: None,
+        market,
+        signer: user.key,
+        user_token_account: user.token_accounts[1],
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![open_orders_delegates],
+    };
+
+    let delegate_1 = &context.users[1];
+    let delegate_2 = &context.users[2];
+    let legacy_delegate = &context.users[3];
+
+    assert_openbook_error(
+        &send_tx(solana, add_delegate_ix(owner.pubkey())).await,
+        OpenBookError::InvalidDelegate.error_code(),
+        "owner can't be a delegate".into(),
+    );
+
+    // Every delegate in the list can trade
+    send_tx(solana, add_delegate_ix(delegate_1.key.pubkey()))
+        .await
+        .unwrap();
+    send_tx(solana, add_delegate_ix(delegate_2.key.pubkey()))
+        .await
+        .unwrap();
+    send_tx(solana, place_bid_ix(delegate_1, 1)).await.unwrap();
+    send_tx(solana, place_bid_ix(delegate_2, 2)).await.unwrap();
+
+    assert_openbook_error(
+        &send_tx(solana, add_delegate_ix(delegate_1.key.pubkey())).await,
+        OpenBookError::DelegateAlreadyAdded.error_code(),
+        "delegate already added".into(),
+    );
+
+    // The legacy delegate and the list can't hold the same key
+    let set_delegate_ix = |delegate_account: Pubkey| SetDelegateInstruction {
+        owner,
+        open_orders_account: account_3,
+        delegate_account: Some(delegate_account),
+        scope: DelegateScope::Full,
+        allowed_side: None,
+        max_quote_native_per_order: 0,
+        expiry_timestamp: 0,
+        open_orders_delegates: Some(open_orders_delegates),
+    };
+    assert_openbook_error(
+        &send_tx(solana, set_delegate_ix(delegate_1.key.pubkey())).await,
+        OpenBookError::DelegateAlreadyAdded.error_code(),
+        "delegate is in the list".into(),
+    );
+    send_tx(solana, set_delegate_ix(legacy_delegate.key.pubkey()))
+        .await
+        .unwrap();
+    assert_openbook_error(
+        &send_tx(solana, add_delegate_ix(legacy_delegate.key.pubkey())).await,
+        OpenBookError::DelegateAlreadyAdded.error_code(),
+        "delegate is the legacy delegate".into(),
+    );
+
+    for _ in 2..MAX_DELEGATES {
+        send_tx(solana, add_delegate_ix(TestKeypair::new().pubkey()))
+            .await
+            .unwrap();
+    }
+    assert_openbook_error(
+        &send_tx(solana, add_delegate_ix(TestKeypair::new().pubkey())).await,
+        OpenBookError::DelegateListFull.error_code(),
+        "delegate list is full".into(),
+    );
+
+    let remove_delegate_ix = RemoveDelegateInstruction {
+        owner,
+        open_orders_account: account_3,
+        delegate_account: delegate_1.key.pubkey(),
+    };
+    send_tx(solana, remove_delegate_ix).await.unwrap();
+
+    assert_openbook_error(
+        &send_tx(solana, place_bid_ix(delegate_1, 3)).await,
+        OpenBookError::NoOwnerOrDelegate.error_code(),
+        "delegate was removed".into(),
+    );
+    send_tx(solana, place_bid_ix(delegate_2, 3)).await.unwrap();
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            RemoveDelegateInstruction {
+                owner,
+                open_orders_account: account_3,
+                delegate_account: delegate_1.key.pubkey(),
+            },
+        )
+        .await,
+        OpenBookError::DelegateNotFound.error_code(),
+        "delegate not in the list".into(),
+    );
+
+    // The freed entry can be used again
+    send_tx(solana, add_delegate_ix(delegate_1.key.pubkey()))
+        .await
+        .unwrap();
+    send_tx(solana, place_bid_ix(delegate_1, 4)).await.unwrap();
+
+    send_tx(
+        solana,
+        CloseOpenOrdersDelegatesInstruction {
+            owner,
+            open_orders_account: account_3,
+            sol_destination: owner.pubkey(),
+        },
+    )
+    .await
+    .unwrap();
+    assert!(solana
+        .get_account_data(open_orders_delegates)
+        .await
+        .is_none());
+    assert_openbook_error(
+        &send_tx(solana, place_bid_ix(delegate_2, 5)).await,
+        OpenBookError::NoOwnerOrDelegate.error_code(),
+        "delegates were closed".into(),
+    );
+    {
+        let open_orders_account_3 = solana.get_account::<OpenOrdersAccount>(account_3).await;
+        assert!(open_orders_account_3.delegate.is_none());
+        assert_eq!(open_orders_account_3.position.bids_base_lots, 4);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_access_gate() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        market_status_admin,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        market_status_admin_bool: true,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    let user = &context.users[1];
+    let gate_admin = market_status_admin;
+
+    let access_gate = Pubkey::find_program_address(
+        &[b"AccessGate".as_ref(), market.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0;
+    let access_pass = |owner: Pubkey| {
+        Pubkey::find_program_address(
+            &[b"AccessPass".as_ref(), market.as_ref(), owner.as_ref()],
+            &openbook_v2::id(),
+        )
+        .0
+    };
+
+    let place_bid_ix = |signer: TestKeypair,
+                        open_orders_account: Pubkey,
+                        user_token_account: Pubkey,
+                        client_order_id: u64| PlaceOrderInstruction {
+        open_orders_account,
+        open_orders_admin: None,
+        market,
+        signer,
+        user_token_account,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![access_gate, access_pass(signer.pubkey())],
+    };
+    let create_account_ix = |access_proof: Option<Pubkey>| CreateOpenOrdersAccountInstruction {
+        account_num: 1,
+        market,
+        owner: user.key,
+        payer: user.key,
+        delegate: None,
+        access_proof,
+    };
+
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            CreateAccessGateInstruction {
+                payer: owner,
+                market_status_admin: owner,
+                market,
+                kind: AccessGate::Allowlist,
+                admin: gate_admin.pubkey(),
+                credential_mint: Pubkey::default(),
+            },
+        )
+        .await,
+        OpenBookError::InvalidMarketStatusAdmin.error_code(),
+        "owner is not the market_status_admin".into(),
+    );
+    send_tx(
+        solana,
+        CreateAccessGateInstruction {
+            payer: owner,
+            market_status_admin,
+            market,
+            kind: AccessGate::Allowlist,
+            admin: gate_admin.pubkey(),
+            credential_mint: Pubkey::default(),
+        },
+    )
+    .await
+    .unwrap();
+
+    // Accounts created before the market was gated need to be granted access
+    assert_openbook_error(
+        &send_tx(solana, place_bid_ix(owner, account_1, owner_token_1, 1)).await,
+        OpenBookError::OpenOrdersAccessDenied.error_code(),
+        "account wasn't granted access".into(),
+    );
+    let place_take_ix = PlaceTakeOrderInstruction {
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        market_base_vault,
+        market_quote_vault,
+        user_base_account: owner_token_0,
+        user_quote_account: owner_token_1,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        remainings: vec![access_gate, access_pass(owner.pubkey())],
+    };
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            PlaceTakeOrderInstruction {
+                remainings: vec![],
+                ..place_take_ix.clone()
+            },
+        )
+        .await,
+        OpenBookError::MissingAccessProof.error_code(),
+        "take order without access proof".into(),
+    );
+    assert_openbook_error(
+        &send_tx(solana, place_take_ix.clone()).await,
+        OpenBookError::InvalidAccessProof.error_code(),
+        "take order signer has no access pass".into(),
+    );
+    send_tx(
+        solana,
+        SetOpenOrdersAccessInstruction {
+            admin: gate_admin,
+            market,
+            open_orders_account: account_1,
+            allowed: true,
+        },
+    )
+    .await
+    .unwrap();
+
+    // Every order carries the access gate and the owner's access pass
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            PlaceOrderInstruction {
+                remainings: vec![],
+                ..place_bid_ix(owner, account_1, owner_token_1, 1)
+            },
+        )
+        .await,
+        OpenBookError::MissingAccessProof.error_code(),
+        "no access gate in the remaining accounts".into(),
+    );
+    assert_openbook_error(
+        &send_tx(solana, place_bid_ix(owner, account_1, owner_token_1, 1)).await,
+        OpenBookError::InvalidAccessProof.error_code(),
+        "owner has no access pass".into(),
+    );
+    send_tx(
+        solana,
+        AddAccessPassInstruction {
+            payer: owner,
+            admin: gate_admin,
+            market,
+            owner: owner.pubkey(),
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(solana, place_bid_ix(owner, account_1, owner_token_1, 1))
+        .await
+        .unwrap();
+    // Take orders are checked against the signer's access pass
+    send_tx(solana, place_take_ix).await.unwrap();
+
+    // New accounts need an access pass
+    create_open_orders_indexer(solana, user, user.key, market).await;
+    assert_openbook_error(
+        &send_tx(solana, create_account_ix(None)).await,
+        OpenBookError::MissingAccessProof.error_code(),
+        "no access pass".into(),
+    );
+
+    send_tx(
+        solana,
+        AddAccessPassInstruction {
+            payer: user.key,
+            admin: gate_admin,
+            market,
+            owner: user.key.pubkey(),
+        },
+    )
+    .await
+    .unwrap();
+    let user_account = send_tx(
+        solana,
+        create_account_ix(Some(access_pass(user.key.pubkey()))),
+    )
+    .await
+    .unwrap()
+    .open_orders_account;
+    send_tx(
+        solana,
+        place_bid_ix(user.key, user_account, user.token_accounts[1], 2),
+    )
+    .await
+    .unwrap();
+
+    // Removing the pass stops the orders of existing accounts
+    send_tx(
+        solana,
+        RemoveAccessPassInstruction {
+            admin: gate_admin,
+            market,
+            owner: user.key.pubkey(),
+            sol_destination: user.key.pubkey(),
+        },
+    )
+    .await
+    .unwrap();
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            place_bid_ix(user.key, user_account, user.token_accounts[1], 3),
+        )
+        .await,
+        OpenBookError::InvalidAccessProof.error_code(),
+        "access pass was removed".into(),
+    );
+
+    // Revoking an account makes it cancel only
+    send_tx(
+        solana,
+        SetOpenOrdersAccessInstruction {
+            admin: gate_admin,
+            market,
+            open_orders_account: user_account,
+            allowed: false,
+        },
+    )
+    .await
+    .unwrap();
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            place_bid_ix(user.key, user_account, user.token_accounts[1], 4),
+        )
+        .await,
+        OpenBookError::OpenOrdersAccessDenied.error_code(),
+        "account access was revoked".into(),
+    );
+    send_tx(
+        solana,
+        CancelAllOrdersInstruction {
+            open_orders_account: user_account,
+            market,
+            signer: user.key,
+        },
+    )
+    .await
+    .unwrap();
+
+    let open_orders_account = solana.get_account::<OpenOrdersAccount>(user_account).await;
+    assert_eq!(open_orders_account.access(), OpenOrdersAccess::Revoked);
+    assert_eq!(open_orders_account.position.bids_base_lots, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_access_gate_credential_token() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        market,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        market_status_admin,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        market_status_admin_bool: true,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    let user = &context.users[1];
+    let credential_mint = tokens[2].mint.pubkey;
+    let credential_account = user.token_accounts[2];
+    let access_gate = Pubkey::find_program_address(
+        &[b"AccessGate".as_ref(), market.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0;
+
+    send_tx(
+        solana,
+        CreateAccessGateInstruction {
+            payer: owner,
+            market_status_admin,
+            market,
+            kind: AccessGate::CredentialToken,
+            admin: market_status_admin.pubkey(),
+            credential_mint,
+        },
+    )
+    .await
+    .unwrap();
+
+    create_open_orders_indexer(solana, user, user.key, market).await;
+    let user_account = send_tx(
+        solana,
+        CreateOpenOrdersAccountInstruction {
+            account_num: 1,
+            market,
+            owner: user.key,
+            payer: user.key,
+            delegate: None,
+            access_proof: Some(credential_account),
+        },
+    )
+    .await
+    .unwrap()
+    .open_orders_account;
+
+    let place_bid_ix = |client_order_id: u64, remainings: Vec<Pubkey>| PlaceOrderInstruction {
+        open_orders_account: user_account,
+        open_orders_admin: None,
+        market,
+        signer: user.key,
+        user_token_account: user.token_accounts[1],
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings,
+    };
+
+    // Orders need the credential token account too, not only the account creation
+    assert_openbook_error(
+        &send_tx(solana, place_bid_ix(1, vec![access_gate])).await,
+        OpenBookError::InvalidAccessProof.error_code(),
+        "no credential token account".into(),
+    );
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            place_bid_ix(1, vec![access_gate, user.token_accounts[1]]),
+        )
+        .await,
+        OpenBookError::InvalidAccessProof.error_code(),
+        "token account of another mint".into(),
+    );
+    send_tx(
+        solana,
+        place_bid_ix(1, vec![access_gate, credential_account]),
+    )
+    .await
+    .unwrap();
+
+    // Once the credential token leaves the wallet, the account can't place orders
+    solana.set_account_balance(credential_account, 0).await;
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            place_bid_ix(2, vec![access_gate, credential_account]),
+        )
+        .await,
+        OpenBookError::InvalidAccessProof.error_code(),
+        "credential token was returned".into(),
+    );
+    send_tx(
+        solana,
+        CancelAllOrdersInstruction {
+            open_orders_account: user_account,
+            market,
+            signer: user.key,
+        },
+    )
+    .await
+    .unwrap();
+
+    let open_orders_account = solana.get_account::<OpenOrdersAccount>(user_account).await;
+    assert_eq!(open_orders_account.access(), OpenOrdersAccess::Granted);
+    assert_eq!(open_orders_account.position.bids_base_lots, 0);
+
+    Ok(())
+}
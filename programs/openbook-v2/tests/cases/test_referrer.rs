@@ -0,0 +1,298 @@
+use super::*;
+
+#[tokio::test]
+async fn test_referrer() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let referrer_authority = context.users[2].key;
+    let referrer_token_account = context.users[2].token_accounts[1];
+    let referrer = Pubkey::find_program_address(
+        &[
+            b"Referrer".as_ref(),
+            referrer_authority.pubkey().as_ref(),
+            Pubkey::default().as_ref(),
+        ],
+        &openbook_v2::id(),
+    )
+    .0;
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    // A global referrer asking for all fees only gets what the market allows
+    send_tx(
+        solana,
+        RegisterReferrerInstruction {
+            payer: owner,
+            authority: referrer_authority,
+            market: Pubkey::default(),
+            share_bps: 10_000,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        SetMaxReferrerShareInstruction {
+            collect_fee_admin,
+            market,
+            share_bps: 5_000,
+        },
+    )
+    .await
+    .unwrap();
+
+    let bind_ix = BindReferrerInstruction {
+        owner,
+        open_orders_account: account_1,
+        referrer,
+    };
+    send_tx(solana, bind_ix).await.unwrap();
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            BindReferrerInstruction {
+                owner,
+                open_orders_account: account_1,
+                referrer,
+            },
+        )
+        .await,
+        OpenBookError::ReferrerAlreadyBound.error_code(),
+        "can only bind once".into(),
+    );
+
+    let place_ask_ix = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, place_ask_ix.clone()).await.unwrap();
+
+    // The bound account takes, its referral can't be left out
+    let open_orders_referral = get_open_orders_referral_address(account_1);
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        remainings: vec![open_orders_referral],
+        ..place_ask_ix.clone()
+    };
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            PlaceOrderInstruction {
+                remainings: vec![],
+                ..place_bid_ix.clone()
+            },
+        )
+        .await,
+        OpenBookError::MissingOpenOrdersReferral.error_code(),
+        "referral not passed".into(),
+    );
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+
+    let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+    let rebates_left = open_orders_account_1.position.referrer_rebates_available;
+
+    let claim_ix = ClaimReferrerRebatesInstruction {
+        authority: referrer_authority,
+        referrer,
+        open_orders_account: account_1,
+        market,
+        referrer_token_account,
+    };
+    let balance_before = solana.token_account_balance(referrer_token_account).await;
+    send_tx(solana, claim_ix).await.unwrap();
+    let rebates_claimed =
+        solana.token_account_balance(referrer_token_account).await - balance_before;
+
+    assert!(rebates_claimed > 0);
+    assert_eq!(rebates_claimed, (rebates_claimed + rebates_left) / 2);
+
+    {
+        let referrer = solana.get_account::<Referrer>(referrer).await;
+        assert_eq!(referrer.referred_accounts, 1);
+        assert_eq!(referrer.rebates_claimed, rebates_claimed as u128);
+        assert!(referrer.referred_taker_volume > 0);
+
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.fees_to_referrers, rebates_claimed as u128);
+    }
+
+    // Nothing left to claim, and only for bound accounts
+    let balance_before = solana.token_account_balance(referrer_token_account).await;
+    send_tx(
+        solana,
+        ClaimReferrerRebatesInstruction {
+            authority: referrer_authority,
+            referrer,
+            open_orders_account: account_1,
+            market,
+            referrer_token_account,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.token_account_balance(referrer_token_account).await,
+        balance_before
+    );
+
+    let other_authority = context.users[3].key;
+    send_tx(
+        solana,
+        RegisterReferrerInstruction {
+            payer: owner,
+            authority: other_authority,
+            market,
+            share_bps: 10_000,
+        },
+    )
+    .await
+    .unwrap();
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            ClaimReferrerRebatesInstruction {
+                authority: other_authority,
+                referrer: Pubkey::find_program_address(
+                    &[
+                        b"Referrer".as_ref(),
+                        other_authority.pubkey().as_ref(),
+                        market.as_ref(),
+                    ],
+                    &openbook_v2::id(),
+                )
+                .0,
+                open_orders_account: account_1,
+                market,
+                referrer_token_account: context.users[3].token_accounts[1],
+            },
+        )
+        .await,
+        OpenBookError::InvalidReferrer.error_code(),
+        "account not bound to that referrer".into(),
+    );
+
+    // Unbinding pays out the pending rebates, the account can be closed afterwards
+    send_tx(solana, place_ask_ix).await.unwrap();
+    send_tx(solana, place_bid_ix).await.unwrap();
+    send_tx(
+        solana,
+        SettleFundsInstruction {
+            owner,
+            open_orders_account: account_1,
+            market,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            referrer_account: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let close_account_ix = || CloseOpenOrdersAccountInstruction {
+        account_num: 1,
+        market,
+        owner,
+        sol_destination: owner.pubkey(),
+    };
+    assert_openbook_error(
+        &send_tx(solana, close_account_ix()).await,
+        OpenBookError::OpenOrdersReferralNotClosed.error_code(),
+        "account still bound".into(),
+    );
+
+    let pending_rebates = solana
+        .get_account::<OpenOrdersReferral>(open_orders_referral)
+        .await
+        .rebates_available;
+    assert!(pending_rebates > 0);
+    let close_referral_ix = || CloseOpenOrdersReferralInstruction {
+        owner,
+        open_orders_account: account_1,
+        referrer,
+        market,
+        referrer_token_account,
+        sol_destination: owner.pubkey(),
+    };
+    let balance_before = solana.token_account_balance(referrer_token_account).await;
+    send_tx(solana, close_referral_ix()).await.unwrap();
+    assert_eq!(
+        solana.token_account_balance(referrer_token_account).await,
+        balance_before + pending_rebates
+    );
+    assert!(solana
+        .get_account_data(open_orders_referral)
+        .await
+        .is_none());
+    assert_eq!(
+        solana
+            .get_account::<Referrer>(referrer)
+            .await
+            .referred_accounts,
+        0
+    );
+
+    // Rebinding counts the account once more, until it's unbound again
+    send_tx(
+        solana,
+        BindReferrerInstruction {
+            owner,
+            open_orders_account: account_1,
+            referrer,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana
+            .get_account::<Referrer>(referrer)
+            .await
+            .referred_accounts,
+        1
+    );
+    send_tx(solana, close_referral_ix()).await.unwrap();
+    assert_eq!(
+        solana
+            .get_account::<Referrer>(referrer)
+            .await
+            .referred_accounts,
+        0
+    );
+
+    send_tx(solana, close_account_ix()).await.unwrap();
+
+    Ok(())
+}
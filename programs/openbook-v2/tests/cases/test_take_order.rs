@@ -64,6 +64,7 @@ async fn test_take_ask_order() -> Result<(), TransportError> {
             max_base_lots: 1,
             max_quote_lots_including_fees: 10000,
             open_orders_admin: None,
+            remainings: vec![],
         },
     )
     .await
@@ -208,6 +209,7 @@ async fn test_take_bid_order() -> Result<(), TransportError> {
             max_base_lots: 1,
             max_quote_lots_including_fees: 10040,
             open_orders_admin: None,
+            remainings: vec![],
         },
     )
     .await
@@ -126,6 +126,22 @@ fn make_instruction(
     }
 }
 
+pub fn get_open_orders_delegates_address(open_orders_account: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"Delegates".as_ref(), open_orders_account.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
+pub fn get_open_orders_referral_address(open_orders_account: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"Referral".as_ref(), open_orders_account.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
 pub fn get_market_address(market: TestKeypair) -> Pubkey {
     Pubkey::find_program_address(
         &[b"Market".as_ref(), market.pubkey().to_bytes().as_ref()],
@@ -195,6 +211,8 @@ pub struct CreateOpenOrdersAccountInstruction {
     pub owner: TestKeypair,
     pub payer: TestKeypair,
     pub delegate: Option<Pubkey>,
+    /// Access pass or credential token account, for access gated markets
+    pub access_proof: Option<Pubkey>,
 }
 #[async_trait::async_trait(?Send)]
 impl ClientInstruction for CreateOpenOrdersAccountInstruction {
@@ -233,6 +251,14 @@ impl ClientInstruction for CreateOpenOrdersAccountInstruction {
             payer: self.payer.pubkey(),
             delegate_account: self.delegate,
             system_program: System::id(),
+            access_gate: self.access_proof.map(|_| {
+                Pubkey::find_program_address(
+                    &[b"AccessGate".as_ref(), self.market.as_ref()],
+                    &program_id,
+                )
+                .0
+            }),
+            access_proof: self.access_proof,
         };
 
         let instruction = make_instruction(program_id, &accounts, instruction);
@@ -294,12 +320,45 @@ impl ClientInstruction for CloseOpenOrdersAccountInstruction {
     }
 }
 
+#[derive(Clone)]
+pub struct MigrateOpenOrdersAccountInstruction {
+    pub open_orders_account: Pubkey,
+    pub market: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for MigrateOpenOrdersAccountInstruction {
+    type Accounts = openbook_v2::accounts::MigrateOpenOrdersAccount;
+    type Instruction = openbook_v2::instruction::MigrateOpenOrdersAccount;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            open_orders_account: self.open_orders_account,
+            market: self.market,
+            bids: market.bids,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![]
+    }
+}
+
 #[derive(Default)]
 pub struct CreateMarketInstruction {
     pub collect_fee_admin: Pubkey,
     pub open_orders_admin: Option<Pubkey>,
     pub consume_events_admin: Option<Pubkey>,
     pub close_market_admin: Option<Pubkey>,
+    pub market_status_admin: Option<Pubkey>,
     pub oracle_a: Option<Pubkey>,
     pub oracle_b: Option<Pubkey>,
     pub base_mint: Pubkey,
@@ -318,6 +377,11 @@ pub struct CreateMarketInstruction {
     pub settle_fee_flat: f32,
     pub settle_fee_amount_threshold: f32,
     pub time_expiry: i64,
+    pub auction_duration_secs: u32,
+    pub auction_interval_secs: u32,
+    pub min_base_lots_per_order: i64,
+    pub min_quote_notional: u64,
+    pub max_base_lots_per_order: i64,
 }
 impl CreateMarketInstruction {
     pub async fn with_new_book_and_heap(
@@ -340,6 +404,29 @@ impl CreateMarketInstruction {
             ..CreateMarketInstruction::default()
         }
     }
+
+    pub async fn with_book_and_heap_capacity(
+        solana: &SolanaCookie,
+        oracle_a: Option<Pubkey>,
+        oracle_b: Option<Pubkey>,
+        order_tree_nodes: usize,
+        events: usize,
+    ) -> Self {
+        CreateMarketInstruction {
+            bids: solana
+                .create_account_from_len(&openbook_v2::id(), BookSide::space(order_tree_nodes))
+                .await,
+            asks: solana
+                .create_account_from_len(&openbook_v2::id(), BookSide::space(order_tree_nodes))
+                .await,
+            event_heap: solana
+                .create_account_from_len(&openbook_v2::id(), EventHeap::space(events))
+                .await,
+            oracle_a,
+            oracle_b,
+            ..CreateMarketInstruction::default()
+        }
+    }
 }
 
 #[async_trait::async_trait(?Send)]
@@ -362,6 +449,11 @@ impl ClientInstruction for CreateMarketInstruction {
             maker_fee: self.maker_fee,
             taker_fee: self.taker_fee,
             time_expiry: self.time_expiry,
+            auction_duration_secs: self.auction_duration_secs,
+            auction_interval_secs: self.auction_interval_secs,
+            min_base_lots_per_order: self.min_base_lots_per_order,
+            min_quote_notional: self.min_quote_notional,
+            max_base_lots_per_order: self.max_base_lots_per_order,
         };
 
         let event_authority =
@@ -400,6 +492,7 @@ impl ClientInstruction for CreateMarketInstruction {
             open_orders_admin: self.open_orders_admin,
             consume_events_admin: self.consume_events_admin,
             close_market_admin: self.close_market_admin,
+            market_status_admin: self.market_status_admin,
             oracle_a: self.oracle_a,
             oracle_b: self.oracle_b,
             event_authority,
@@ -560,6 +653,7 @@ impl ClientInstruction for PlaceOrderPeggedInstruction {
     }
 }
 
+#[derive(Clone)]
 pub struct PlaceTakeOrderInstruction {
     pub open_orders_admin: Option<TestKeypair>,
     pub market: Pubkey,
@@ -572,6 +666,7 @@ pub struct PlaceTakeOrderInstruction {
     pub price_lots: i64,
     pub max_base_lots: i64,
     pub max_quote_lots_including_fees: i64,
+    pub remainings: Vec<Pubkey>,
 }
 #[async_trait::async_trait(?Send)]
 impl ClientInstruction for PlaceTakeOrderInstruction {
@@ -614,7 +709,14 @@ impl ClientInstruction for PlaceTakeOrderInstruction {
             system_program: System::id(),
         };
 
-        let instruction = make_instruction(program_id, &accounts, instruction);
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        instruction
+            .accounts
+            .extend(self.remainings.iter().map(|remaining| AccountMeta {
+                pubkey: *remaining,
+                is_signer: false,
+                is_writable: true,
+            }));
         (accounts, instruction)
     }
 
@@ -664,11 +766,13 @@ impl ClientInstruction for CancelOrderInstruction {
     }
 }
 
+#[derive(Clone)]
 pub struct CancelOrderByClientOrderIdInstruction {
     pub open_orders_account: Pubkey,
     pub market: Pubkey,
     pub signer: TestKeypair,
     pub client_order_id: u64,
+    pub remainings: Vec<Pubkey>,
 }
 #[async_trait::async_trait(?Send)]
 impl ClientInstruction for CancelOrderByClientOrderIdInstruction {
@@ -691,7 +795,16 @@ impl ClientInstruction for CancelOrderByClientOrderIdInstruction {
             signer: self.signer.pubkey(),
         };
 
-        let instruction = make_instruction(program_id, &accounts, instruction);
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        let mut vec_remainings: Vec<AccountMeta> = Vec::new();
+        for remaining in &self.remainings {
+            vec_remainings.push(AccountMeta {
+                pubkey: *remaining,
+                is_signer: false,
+                is_writable: false,
+            });
+        }
+        instruction.accounts.append(&mut vec_remainings);
         (accounts, instruction)
     }
 
@@ -780,6 +893,83 @@ impl ClientInstruction for ConsumeEventsInstruction {
     }
 }
 
+pub struct ConsumeEventsWithRewardInstruction {
+    pub market: Pubkey,
+    pub open_orders_accounts: Vec<Pubkey>,
+    pub crank_reward_receiver: TestKeypair,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for ConsumeEventsWithRewardInstruction {
+    type Accounts = openbook_v2::accounts::ConsumeEventsWithReward;
+    type Instruction = openbook_v2::instruction::ConsumeEventsWithReward;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            limit: 10,
+            slots: None,
+        };
+
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+        let accounts = Self::Accounts {
+            consume_events_admin: None,
+            market: self.market,
+            event_heap: market.event_heap,
+            crank_reward_receiver: self.crank_reward_receiver.pubkey(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        instruction
+            .accounts
+            .extend(self.open_orders_accounts.iter().map(|ma| AccountMeta {
+                pubkey: *ma,
+                is_signer: false,
+                is_writable: true,
+            }));
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.crank_reward_receiver]
+    }
+}
+
+pub struct SetCrankRewardsInstruction {
+    pub collect_fee_admin: TestKeypair,
+    pub market: Pubkey,
+    pub penalty_event_heap: Option<u32>,
+    pub crank_reward_per_event: u32,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetCrankRewardsInstruction {
+    type Accounts = openbook_v2::accounts::SetCrankRewards;
+    type Instruction = openbook_v2::instruction::SetCrankRewards;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            penalty_event_heap: self.penalty_event_heap,
+            crank_reward_per_event: self.crank_reward_per_event,
+        };
+
+        let accounts = Self::Accounts {
+            collect_fee_admin: self.collect_fee_admin.pubkey(),
+            market: self.market,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.collect_fee_admin]
+    }
+}
+
 pub struct ConsumeGivenEventsInstruction {
     pub consume_events_admin: Option<TestKeypair>,
     pub market: Pubkey,
@@ -919,6 +1109,47 @@ impl ClientInstruction for SettleFundsExpiredInstruction {
     }
 }
 
+pub struct ForceSettleFundsInstruction {
+    pub signer: TestKeypair,
+    pub owner: Pubkey,
+    pub open_orders_account: Pubkey,
+    pub market: Pubkey,
+    pub user_base_account: Pubkey,
+    pub user_quote_account: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for ForceSettleFundsInstruction {
+    type Accounts = openbook_v2::accounts::ForceSettleFunds;
+    type Instruction = openbook_v2::instruction::ForceSettleFunds;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+        let accounts = Self::Accounts {
+            signer: self.signer.pubkey(),
+            owner: self.owner,
+            open_orders_account: self.open_orders_account,
+            market: self.market,
+            market_authority: market.market_authority,
+            market_base_vault: market.market_base_vault,
+            market_quote_vault: market.market_quote_vault,
+            user_base_account: self.user_base_account,
+            user_quote_account: self.user_quote_account,
+            token_program: Token::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.signer]
+    }
+}
+
 pub struct SweepFeesInstruction {
     pub collect_fee_admin: TestKeypair,
     pub market: Pubkey,
@@ -942,8 +1173,9 @@ impl ClientInstruction for SweepFeesInstruction {
             market: self.market,
             market_authority: market.market_authority,
             market_quote_vault: self.market_quote_vault,
-            token_receiver_account: self.token_receiver_account,
+            token_receiver_account: Some(self.token_receiver_account),
             token_program: Token::id(),
+            fee_split: None,
         };
         let instruction = make_instruction(program_id, &accounts, instruction);
 
@@ -955,108 +1187,404 @@ impl ClientInstruction for SweepFeesInstruction {
     }
 }
 
-pub struct DepositInstruction {
-    pub open_orders_account: Pubkey,
+pub struct SweepFeesSplitInstruction {
+    pub collect_fee_admin: TestKeypair,
     pub market: Pubkey,
-    pub market_base_vault: Pubkey,
-    pub market_quote_vault: Pubkey,
-    pub user_base_account: Pubkey,
-    pub user_quote_account: Pubkey,
-    pub owner: TestKeypair,
-    pub base_amount: u64,
-    pub quote_amount: u64,
+    pub destinations: Vec<Pubkey>,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for DepositInstruction {
-    type Accounts = openbook_v2::accounts::Deposit;
-    type Instruction = openbook_v2::instruction::Deposit;
+impl ClientInstruction for SweepFeesSplitInstruction {
+    type Accounts = openbook_v2::accounts::SweepFees;
+    type Instruction = openbook_v2::instruction::SweepFees;
     async fn to_instruction(
         &self,
-        _account_loader: impl ClientAccountLoader + 'async_trait,
+        account_loader: impl ClientAccountLoader + 'async_trait,
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = openbook_v2::id();
-        let instruction = Self::Instruction {
-            base_amount: self.base_amount,
-            quote_amount: self.quote_amount,
-        };
+        let instruction = Self::Instruction {};
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+        let fee_split = Pubkey::find_program_address(
+            &[b"FeeSplit".as_ref(), self.market.as_ref()],
+            &program_id,
+        )
+        .0;
 
         let accounts = Self::Accounts {
-            owner: self.owner.pubkey(),
-            open_orders_account: self.open_orders_account,
+            collect_fee_admin: self.collect_fee_admin.pubkey(),
             market: self.market,
-            market_base_vault: self.market_base_vault,
-            market_quote_vault: self.market_quote_vault,
-            user_base_account: self.user_base_account,
-            user_quote_account: self.user_quote_account,
+            market_authority: market.market_authority,
+            market_quote_vault: market.market_quote_vault,
+            token_receiver_account: None,
             token_program: Token::id(),
+            fee_split: Some(fee_split),
         };
-        let instruction = make_instruction(program_id, &accounts, instruction);
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        instruction
+            .accounts
+            .extend(self.destinations.iter().map(|destination| AccountMeta {
+                pubkey: *destination,
+                is_signer: false,
+                is_writable: true,
+            }));
 
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.owner]
+        vec![self.collect_fee_admin]
     }
 }
 
-pub struct StubOracleSetInstruction {
-    pub mint: Pubkey,
-    pub owner: TestKeypair,
-    pub price: f64,
+pub struct RegisterReferrerInstruction {
+    pub payer: TestKeypair,
+    pub authority: TestKeypair,
+    pub market: Pubkey,
+    pub share_bps: u16,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for StubOracleSetInstruction {
-    type Accounts = openbook_v2::accounts::StubOracleSet;
-    type Instruction = openbook_v2::instruction::StubOracleSet;
-
+impl ClientInstruction for RegisterReferrerInstruction {
+    type Accounts = openbook_v2::accounts::RegisterReferrer;
+    type Instruction = openbook_v2::instruction::RegisterReferrer;
     async fn to_instruction(
         &self,
-        _loader: impl ClientAccountLoader + 'async_trait,
-    ) -> (Self::Accounts, Instruction) {
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = openbook_v2::id();
-        let instruction = Self::Instruction { price: self.price };
-
-        let oracle = Pubkey::find_program_address(
+        let instruction = Self::Instruction {
+            market: self.market,
+            share_bps: self.share_bps,
+        };
+        let referrer = Pubkey::find_program_address(
             &[
-                b"StubOracle".as_ref(),
-                self.owner.pubkey().as_ref(),
-                self.mint.as_ref(),
+                b"Referrer".as_ref(),
+                self.authority.pubkey().as_ref(),
+                self.market.as_ref(),
             ],
             &program_id,
         )
         .0;
 
         let accounts = Self::Accounts {
-            oracle,
-            owner: self.owner.pubkey(),
+            payer: self.payer.pubkey(),
+            authority: self.authority.pubkey(),
+            referrer,
+            system_program: System::id(),
         };
-
         let instruction = make_instruction(program_id, &accounts, instruction);
+
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.owner]
+        vec![self.payer, self.authority]
     }
 }
 
-pub struct StubOracleCreate {
-    pub mint: Pubkey,
-    pub owner: TestKeypair,
-    pub payer: TestKeypair,
+pub struct SetMaxReferrerShareInstruction {
+    pub collect_fee_admin: TestKeypair,
+    pub market: Pubkey,
+    pub share_bps: u16,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for StubOracleCreate {
-    type Accounts = openbook_v2::accounts::StubOracleCreate;
-    type Instruction = openbook_v2::instruction::StubOracleCreate;
-
+impl ClientInstruction for SetMaxReferrerShareInstruction {
+    type Accounts = openbook_v2::accounts::SetMaxReferrerShare;
+    type Instruction = openbook_v2::instruction::SetMaxReferrerShare;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            share_bps: self.share_bps,
+        };
+
+        let accounts = Self::Accounts {
+            collect_fee_admin: self.collect_fee_admin.pubkey(),
+            market: self.market,
+        };
+        let instruction = make_instruction(program_id, &accounts, instruction);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.collect_fee_admin]
+    }
+}
+
+pub struct BindReferrerInstruction {
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
+    pub referrer: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for BindReferrerInstruction {
+    type Accounts = openbook_v2::accounts::BindReferrer;
+    type Instruction = openbook_v2::instruction::BindReferrer;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+
+        let accounts = Self::Accounts {
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+            referrer: self.referrer,
+            open_orders_referral: get_open_orders_referral_address(self.open_orders_account),
+            system_program: System::id(),
+        };
+        let instruction = make_instruction(program_id, &accounts, instruction);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct ClaimReferrerRebatesInstruction {
+    pub authority: TestKeypair,
+    pub referrer: Pubkey,
+    pub open_orders_account: Pubkey,
+    pub market: Pubkey,
+    pub referrer_token_account: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for ClaimReferrerRebatesInstruction {
+    type Accounts = openbook_v2::accounts::ClaimReferrerRebates;
+    type Instruction = openbook_v2::instruction::ClaimReferrerRebates;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            authority: self.authority.pubkey(),
+            referrer: self.referrer,
+            open_orders_account: self.open_orders_account,
+            open_orders_referral: get_open_orders_referral_address(self.open_orders_account),
+            market: self.market,
+            market_authority: market.market_authority,
+            market_quote_vault: market.market_quote_vault,
+            referrer_token_account: self.referrer_token_account,
+            token_program: Token::id(),
+        };
+        let instruction = make_instruction(program_id, &accounts, instruction);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.authority]
+    }
+}
+
+pub struct CloseOpenOrdersReferralInstruction {
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
+    pub referrer: Pubkey,
+    pub market: Pubkey,
+    pub referrer_token_account: Pubkey,
+    pub sol_destination: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for CloseOpenOrdersReferralInstruction {
+    type Accounts = openbook_v2::accounts::CloseOpenOrdersReferral;
+    type Instruction = openbook_v2::instruction::CloseOpenOrdersReferral;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+            open_orders_referral: get_open_orders_referral_address(self.open_orders_account),
+            referrer: self.referrer,
+            market: self.market,
+            market_authority: market.market_authority,
+            market_quote_vault: market.market_quote_vault,
+            referrer_token_account: self.referrer_token_account,
+            sol_destination: self.sol_destination,
+            token_program: Token::id(),
+        };
+        let instruction = make_instruction(program_id, &accounts, instruction);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct CreateFeeSplitInstruction {
+    pub payer: TestKeypair,
+    pub collect_fee_admin: TestKeypair,
+    pub market: Pubkey,
+    pub destinations: Vec<FeeSplitDestination>,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for CreateFeeSplitInstruction {
+    type Accounts = openbook_v2::accounts::CreateFeeSplit;
+    type Instruction = openbook_v2::instruction::CreateFeeSplit;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            destinations: self.destinations.clone(),
+        };
+        let fee_split = Pubkey::find_program_address(
+            &[b"FeeSplit".as_ref(), self.market.as_ref()],
+            &program_id,
+        )
+        .0;
+
+        let accounts = Self::Accounts {
+            payer: self.payer.pubkey(),
+            collect_fee_admin: self.collect_fee_admin.pubkey(),
+            market: self.market,
+            fee_split,
+            system_program: System::id(),
+        };
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        let mut vec_remainings: Vec<AccountMeta> = Vec::new();
+        for destination in &self.destinations {
+            vec_remainings.push(AccountMeta {
+                pubkey: destination.token_account,
+                is_signer: false,
+                is_writable: false,
+            });
+        }
+        instruction.accounts.append(&mut vec_remainings);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.payer, self.collect_fee_admin]
+    }
+}
+
+pub struct SetFeeSplitInstruction {
+    pub collect_fee_admin: TestKeypair,
+    pub market: Pubkey,
+    pub destinations: Vec<FeeSplitDestination>,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetFeeSplitInstruction {
+    type Accounts = openbook_v2::accounts::SetFeeSplit;
+    type Instruction = openbook_v2::instruction::SetFeeSplit;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            destinations: self.destinations.clone(),
+        };
+        let fee_split = Pubkey::find_program_address(
+            &[b"FeeSplit".as_ref(), self.market.as_ref()],
+            &program_id,
+        )
+        .0;
+
+        let accounts = Self::Accounts {
+            collect_fee_admin: self.collect_fee_admin.pubkey(),
+            market: self.market,
+            fee_split,
+        };
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        let mut vec_remainings: Vec<AccountMeta> = Vec::new();
+        for destination in &self.destinations {
+            vec_remainings.push(AccountMeta {
+                pubkey: destination.token_account,
+                is_signer: false,
+                is_writable: false,
+            });
+        }
+        instruction.accounts.append(&mut vec_remainings);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.collect_fee_admin]
+    }
+}
+
+pub struct DepositInstruction {
+    pub open_orders_account: Pubkey,
+    pub market: Pubkey,
+    pub market_base_vault: Pubkey,
+    pub market_quote_vault: Pubkey,
+    pub user_base_account: Pubkey,
+    pub user_quote_account: Pubkey,
+    pub owner: TestKeypair,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for DepositInstruction {
+    type Accounts = openbook_v2::accounts::Deposit;
+    type Instruction = openbook_v2::instruction::Deposit;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            base_amount: self.base_amount,
+            quote_amount: self.quote_amount,
+        };
+
+        let accounts = Self::Accounts {
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+            market: self.market,
+            market_base_vault: self.market_base_vault,
+            market_quote_vault: self.market_quote_vault,
+            user_base_account: self.user_base_account,
+            user_quote_account: self.user_quote_account,
+            token_program: Token::id(),
+        };
+        let instruction = make_instruction(program_id, &accounts, instruction);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct StubOracleSetInstruction {
+    pub mint: Pubkey,
+    pub owner: TestKeypair,
+    pub price: f64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for StubOracleSetInstruction {
+    type Accounts = openbook_v2::accounts::StubOracleSet;
+    type Instruction = openbook_v2::instruction::StubOracleSet;
+
     async fn to_instruction(
         &self,
         _loader: impl ClientAccountLoader + 'async_trait,
     ) -> (Self::Accounts, Instruction) {
         let program_id = openbook_v2::id();
-        let instruction = Self::Instruction { price: 1.0 };
+        let instruction = Self::Instruction { price: self.price };
 
         let oracle = Pubkey::find_program_address(
             &[
@@ -1070,10 +1598,7 @@ impl ClientInstruction for StubOracleCreate {
 
         let accounts = Self::Accounts {
             oracle,
-            mint: self.mint,
             owner: self.owner.pubkey(),
-            payer: self.payer.pubkey(),
-            system_program: System::id(),
         };
 
         let instruction = make_instruction(program_id, &accounts, instruction);
@@ -1081,42 +1606,532 @@ impl ClientInstruction for StubOracleCreate {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.payer, self.owner]
+        vec![self.owner]
     }
 }
 
-pub struct StubOracleCloseInstruction {
+pub struct StubOracleCreate {
     pub mint: Pubkey,
     pub owner: TestKeypair,
-    pub sol_destination: Pubkey,
+    pub payer: TestKeypair,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for StubOracleCreate {
+    type Accounts = openbook_v2::accounts::StubOracleCreate;
+    type Instruction = openbook_v2::instruction::StubOracleCreate;
+
+    async fn to_instruction(
+        &self,
+        _loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { price: 1.0 };
+
+        let oracle = Pubkey::find_program_address(
+            &[
+                b"StubOracle".as_ref(),
+                self.owner.pubkey().as_ref(),
+                self.mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+
+        let accounts = Self::Accounts {
+            oracle,
+            mint: self.mint,
+            owner: self.owner.pubkey(),
+            payer: self.payer.pubkey(),
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.payer, self.owner]
+    }
+}
+
+pub struct StubOracleCloseInstruction {
+    pub mint: Pubkey,
+    pub owner: TestKeypair,
+    pub sol_destination: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for StubOracleCloseInstruction {
+    type Accounts = openbook_v2::accounts::StubOracleClose;
+    type Instruction = openbook_v2::instruction::StubOracleClose;
+
+    async fn to_instruction(
+        &self,
+        _loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+
+        let oracle = Pubkey::find_program_address(
+            &[
+                b"StubOracle".as_ref(),
+                self.owner.pubkey().as_ref(),
+                self.mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+
+        let accounts = Self::Accounts {
+            owner: self.owner.pubkey(),
+            oracle,
+            sol_destination: self.sol_destination,
+            token_program: Token::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+#[derive(Clone)]
+pub struct CloseMarketInstruction {
+    pub close_market_admin: TestKeypair,
+    pub market: Pubkey,
+    pub sol_destination: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for CloseMarketInstruction {
+    type Accounts = openbook_v2::accounts::CloseMarket;
+    type Instruction = openbook_v2::instruction::CloseMarket;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            close_market_admin: self.close_market_admin.pubkey(),
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+            event_heap: market.event_heap,
+            token_program: Token::id(),
+            sol_destination: self.sol_destination,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.close_market_admin]
+    }
+}
+
+#[derive(Clone)]
+pub struct ShrinkMarketAccountsInstruction {
+    pub close_market_admin: TestKeypair,
+    pub market: Pubkey,
+    pub sol_destination: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for ShrinkMarketAccountsInstruction {
+    type Accounts = openbook_v2::accounts::ShrinkMarketAccounts;
+    type Instruction = openbook_v2::instruction::ShrinkMarketAccounts;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            close_market_admin: self.close_market_admin.pubkey(),
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+            event_heap: market.event_heap,
+            sol_destination: self.sol_destination,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.close_market_admin]
+    }
+}
+
+pub struct SetMarketExpiredInstruction {
+    pub close_market_admin: TestKeypair,
+    pub market: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetMarketExpiredInstruction {
+    type Accounts = openbook_v2::accounts::SetMarketExpired;
+    type Instruction = openbook_v2::instruction::SetMarketExpired;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+
+        let accounts = Self::Accounts {
+            close_market_admin: self.close_market_admin.pubkey(),
+            market: self.market,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.close_market_admin]
+    }
+}
+
+pub struct SetMarketStatusInstruction {
+    pub market_status_admin: TestKeypair,
+    pub market: Pubkey,
+    pub status: MarketStatus,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetMarketStatusInstruction {
+    type Accounts = openbook_v2::accounts::SetMarketStatus;
+    type Instruction = openbook_v2::instruction::SetMarketStatus;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            status: self.status,
+        };
+
+        let accounts = Self::Accounts {
+            market_status_admin: self.market_status_admin.pubkey(),
+            market: self.market,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.market_status_admin]
+    }
+}
+
+pub struct SetMarketStatusAdminInstruction {
+    pub collect_fee_admin: TestKeypair,
+    pub market: Pubkey,
+    pub market_status_admin: Option<Pubkey>,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetMarketStatusAdminInstruction {
+    type Accounts = openbook_v2::accounts::SetMarketStatusAdmin;
+    type Instruction = openbook_v2::instruction::SetMarketStatusAdmin;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+
+        let accounts = Self::Accounts {
+            collect_fee_admin: self.collect_fee_admin.pubkey(),
+            market: self.market,
+            market_status_admin: self.market_status_admin,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.collect_fee_admin]
+    }
+}
+
+pub struct CreateAccessGateInstruction {
+    pub payer: TestKeypair,
+    pub market_status_admin: TestKeypair,
+    pub market: Pubkey,
+    pub kind: AccessGate,
+    pub admin: Pubkey,
+    pub credential_mint: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for CreateAccessGateInstruction {
+    type Accounts = openbook_v2::accounts::CreateAccessGate;
+    type Instruction = openbook_v2::instruction::CreateAccessGate;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            kind: self.kind,
+            admin: self.admin,
+            credential_mint: self.credential_mint,
+        };
+        let access_gate = Pubkey::find_program_address(
+            &[b"AccessGate".as_ref(), self.market.as_ref()],
+            &program_id,
+        )
+        .0;
+
+        let accounts = Self::Accounts {
+            payer: self.payer.pubkey(),
+            market_status_admin: self.market_status_admin.pubkey(),
+            market: self.market,
+            access_gate,
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.payer, self.market_status_admin]
+    }
+}
+
+pub struct AddAccessPassInstruction {
+    pub payer: TestKeypair,
+    pub admin: TestKeypair,
+    pub market: Pubkey,
+    pub owner: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for AddAccessPassInstruction {
+    type Accounts = openbook_v2::accounts::AddAccessPass;
+    type Instruction = openbook_v2::instruction::AddAccessPass;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+        let access_gate = Pubkey::find_program_address(
+            &[b"AccessGate".as_ref(), self.market.as_ref()],
+            &program_id,
+        )
+        .0;
+        let access_pass = Pubkey::find_program_address(
+            &[
+                b"AccessPass".as_ref(),
+                self.market.as_ref(),
+                self.owner.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+
+        let accounts = Self::Accounts {
+            payer: self.payer.pubkey(),
+            admin: self.admin.pubkey(),
+            access_gate,
+            owner: self.owner,
+            access_pass,
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.payer, self.admin]
+    }
+}
+
+pub struct RemoveAccessPassInstruction {
+    pub admin: TestKeypair,
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub sol_destination: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for RemoveAccessPassInstruction {
+    type Accounts = openbook_v2::accounts::RemoveAccessPass;
+    type Instruction = openbook_v2::instruction::RemoveAccessPass;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+        let access_gate = Pubkey::find_program_address(
+            &[b"AccessGate".as_ref(), self.market.as_ref()],
+            &program_id,
+        )
+        .0;
+        let access_pass = Pubkey::find_program_address(
+            &[
+                b"AccessPass".as_ref(),
+                self.market.as_ref(),
+                self.owner.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+
+        let accounts = Self::Accounts {
+            admin: self.admin.pubkey(),
+            access_gate,
+            access_pass,
+            sol_destination: self.sol_destination,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct SetOpenOrdersAccessInstruction {
+    pub admin: TestKeypair,
+    pub market: Pubkey,
+    pub open_orders_account: Pubkey,
+    pub allowed: bool,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetOpenOrdersAccessInstruction {
+    type Accounts = openbook_v2::accounts::SetOpenOrdersAccess;
+    type Instruction = openbook_v2::instruction::SetOpenOrdersAccess;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            allowed: self.allowed,
+        };
+        let access_gate = Pubkey::find_program_address(
+            &[b"AccessGate".as_ref(), self.market.as_ref()],
+            &program_id,
+        )
+        .0;
+
+        let accounts = Self::Accounts {
+            admin: self.admin.pubkey(),
+            access_gate,
+            open_orders_account: self.open_orders_account,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct PruneOrdersInstruction {
+    pub close_market_admin: TestKeypair,
+    pub market: Pubkey,
+    pub open_orders_account: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PruneOrdersInstruction {
+    type Accounts = openbook_v2::accounts::PruneOrders;
+    type Instruction = openbook_v2::instruction::PruneOrders;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { limit: 255 };
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            close_market_admin: self.close_market_admin.pubkey(),
+            market: self.market,
+            open_orders_account: self.open_orders_account,
+            bids: market.bids,
+            asks: market.asks,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.close_market_admin]
+    }
+}
+
+pub struct ForcePruneOrdersInstruction {
+    pub signer: TestKeypair,
+    pub market: Pubkey,
+    pub open_orders_account: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for ForcePruneOrdersInstruction {
+    type Accounts = openbook_v2::accounts::ForcePruneOrders;
+    type Instruction = openbook_v2::instruction::ForcePruneOrders;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { limit: 255 };
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            signer: self.signer.pubkey(),
+            market: self.market,
+            open_orders_account: self.open_orders_account,
+            bids: market.bids,
+            asks: market.asks,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.signer]
+    }
+}
+
+pub struct CreateOpenOrdersDelegatesInstruction {
+    pub payer: TestKeypair,
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for StubOracleCloseInstruction {
-    type Accounts = openbook_v2::accounts::StubOracleClose;
-    type Instruction = openbook_v2::instruction::StubOracleClose;
-
+impl ClientInstruction for CreateOpenOrdersDelegatesInstruction {
+    type Accounts = openbook_v2::accounts::CreateOpenOrdersDelegates;
+    type Instruction = openbook_v2::instruction::CreateOpenOrdersDelegates;
     async fn to_instruction(
         &self,
-        _loader: impl ClientAccountLoader + 'async_trait,
-    ) -> (Self::Accounts, Instruction) {
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = openbook_v2::id();
         let instruction = Self::Instruction {};
 
-        let oracle = Pubkey::find_program_address(
-            &[
-                b"StubOracle".as_ref(),
-                self.owner.pubkey().as_ref(),
-                self.mint.as_ref(),
-            ],
-            &program_id,
-        )
-        .0;
-
         let accounts = Self::Accounts {
+            payer: self.payer.pubkey(),
             owner: self.owner.pubkey(),
-            oracle,
-            sol_destination: self.sol_destination,
-            token_program: Token::id(),
+            open_orders_account: self.open_orders_account,
+            open_orders_delegates: get_open_orders_delegates_address(self.open_orders_account),
+            system_program: System::id(),
         };
 
         let instruction = make_instruction(program_id, &accounts, instruction);
@@ -1124,35 +2139,30 @@ impl ClientInstruction for StubOracleCloseInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.owner]
+        vec![self.payer, self.owner]
     }
 }
 
-#[derive(Clone)]
-pub struct CloseMarketInstruction {
-    pub close_market_admin: TestKeypair,
-    pub market: Pubkey,
+pub struct CloseOpenOrdersDelegatesInstruction {
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
     pub sol_destination: Pubkey,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for CloseMarketInstruction {
-    type Accounts = openbook_v2::accounts::CloseMarket;
-    type Instruction = openbook_v2::instruction::CloseMarket;
+impl ClientInstruction for CloseOpenOrdersDelegatesInstruction {
+    type Accounts = openbook_v2::accounts::CloseOpenOrdersDelegates;
+    type Instruction = openbook_v2::instruction::CloseOpenOrdersDelegates;
     async fn to_instruction(
         &self,
-        account_loader: impl ClientAccountLoader + 'async_trait,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = openbook_v2::id();
         let instruction = Self::Instruction {};
-        let market: Market = account_loader.load(&self.market).await.unwrap();
 
         let accounts = Self::Accounts {
-            close_market_admin: self.close_market_admin.pubkey(),
-            market: self.market,
-            bids: market.bids,
-            asks: market.asks,
-            event_heap: market.event_heap,
-            token_program: Token::id(),
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+            open_orders_delegates: get_open_orders_delegates_address(self.open_orders_account),
             sol_destination: self.sol_destination,
         };
 
@@ -1161,28 +2171,42 @@ impl ClientInstruction for CloseMarketInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.close_market_admin]
+        vec![self.owner]
     }
 }
 
-pub struct SetMarketExpiredInstruction {
-    pub close_market_admin: TestKeypair,
-    pub market: Pubkey,
+#[derive(Clone)]
+pub struct SetDelegateInstruction {
+    pub delegate_account: Option<Pubkey>,
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
+    pub scope: DelegateScope,
+    pub allowed_side: Option<Side>,
+    pub max_quote_native_per_order: u64,
+    pub expiry_timestamp: u64,
+    pub open_orders_delegates: Option<Pubkey>,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for SetMarketExpiredInstruction {
-    type Accounts = openbook_v2::accounts::SetMarketExpired;
-    type Instruction = openbook_v2::instruction::SetMarketExpired;
+impl ClientInstruction for SetDelegateInstruction {
+    type Accounts = openbook_v2::accounts::SetDelegate;
+    type Instruction = openbook_v2::instruction::SetDelegate;
     async fn to_instruction(
         &self,
         _account_loader: impl ClientAccountLoader + 'async_trait,
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = openbook_v2::id();
-        let instruction = Self::Instruction {};
+        let instruction = Self::Instruction {
+            scope: self.scope,
+            allowed_side: self.allowed_side,
+            max_quote_native_per_order: self.max_quote_native_per_order,
+            expiry_timestamp: self.expiry_timestamp,
+        };
 
         let accounts = Self::Accounts {
-            close_market_admin: self.close_market_admin.pubkey(),
-            market: self.market,
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+            delegate_account: self.delegate_account,
+            open_orders_delegates: self.open_orders_delegates,
         };
 
         let instruction = make_instruction(program_id, &accounts, instruction);
@@ -1190,33 +2214,40 @@ impl ClientInstruction for SetMarketExpiredInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.close_market_admin]
+        vec![self.owner]
     }
 }
 
-pub struct PruneOrdersInstruction {
-    pub close_market_admin: TestKeypair,
-    pub market: Pubkey,
+pub struct AddDelegateInstruction {
+    pub delegate_account: Pubkey,
+    pub owner: TestKeypair,
     pub open_orders_account: Pubkey,
+    pub scope: DelegateScope,
+    pub allowed_side: Option<Side>,
+    pub max_quote_native_per_order: u64,
+    pub expiry_timestamp: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PruneOrdersInstruction {
-    type Accounts = openbook_v2::accounts::PruneOrders;
-    type Instruction = openbook_v2::instruction::PruneOrders;
+impl ClientInstruction for AddDelegateInstruction {
+    type Accounts = openbook_v2::accounts::AddDelegate;
+    type Instruction = openbook_v2::instruction::AddDelegate;
     async fn to_instruction(
         &self,
-        account_loader: impl ClientAccountLoader + 'async_trait,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = openbook_v2::id();
-        let instruction = Self::Instruction { limit: 255 };
-        let market: Market = account_loader.load(&self.market).await.unwrap();
+        let instruction = Self::Instruction {
+            scope: self.scope,
+            allowed_side: self.allowed_side,
+            max_quote_native_per_order: self.max_quote_native_per_order,
+            expiry_timestamp: self.expiry_timestamp,
+        };
 
         let accounts = Self::Accounts {
-            close_market_admin: self.close_market_admin.pubkey(),
-            market: self.market,
+            owner: self.owner.pubkey(),
             open_orders_account: self.open_orders_account,
-            bids: market.bids,
-            asks: market.asks,
+            open_orders_delegates: get_open_orders_delegates_address(self.open_orders_account),
+            delegate_account: self.delegate_account,
         };
 
         let instruction = make_instruction(program_id, &accounts, instruction);
@@ -1224,19 +2255,19 @@ impl ClientInstruction for PruneOrdersInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.close_market_admin]
+        vec![self.owner]
     }
 }
 
-pub struct SetDelegateInstruction {
-    pub delegate_account: Option<Pubkey>,
+pub struct RemoveDelegateInstruction {
+    pub delegate_account: Pubkey,
     pub owner: TestKeypair,
     pub open_orders_account: Pubkey,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for SetDelegateInstruction {
-    type Accounts = openbook_v2::accounts::SetDelegate;
-    type Instruction = openbook_v2::instruction::SetDelegate;
+impl ClientInstruction for RemoveDelegateInstruction {
+    type Accounts = openbook_v2::accounts::RemoveDelegate;
+    type Instruction = openbook_v2::instruction::RemoveDelegate;
     async fn to_instruction(
         &self,
         _account_loader: impl ClientAccountLoader + 'async_trait,
@@ -1247,6 +2278,7 @@ impl ClientInstruction for SetDelegateInstruction {
         let accounts = Self::Accounts {
             owner: self.owner.pubkey(),
             open_orders_account: self.open_orders_account,
+            open_orders_delegates: get_open_orders_delegates_address(self.open_orders_account),
             delegate_account: self.delegate_account,
         };
 
@@ -1403,3 +2435,189 @@ impl ClientInstruction for CancelAllAndPlaceOrdersInstruction {
         signers
     }
 }
+
+pub struct HeartbeatInstruction {
+    pub signer: TestKeypair,
+    pub market: Pubkey,
+    pub open_orders_account: Pubkey,
+    pub timeout: u64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for HeartbeatInstruction {
+    type Accounts = openbook_v2::accounts::Heartbeat;
+    type Instruction = openbook_v2::instruction::Heartbeat;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            timeout: self.timeout,
+        };
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            signer: self.signer.pubkey(),
+            open_orders_account: self.open_orders_account,
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.signer]
+    }
+}
+
+pub struct CancelOrdersHeartbeatExpiredInstruction {
+    pub market: Pubkey,
+    pub open_orders_account: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for CancelOrdersHeartbeatExpiredInstruction {
+    type Accounts = openbook_v2::accounts::CancelOrdersHeartbeatExpired;
+    type Instruction = openbook_v2::instruction::CancelOrdersHeartbeatExpired;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { limit: 255 };
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            open_orders_account: self.open_orders_account,
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![]
+    }
+}
+
+#[derive(Clone)]
+pub struct SetMmpInstruction {
+    pub signer: TestKeypair,
+    pub market: Pubkey,
+    pub open_orders_account: Pubkey,
+    pub window_secs: u16,
+    pub base_lots_limit: i64,
+    pub fill_count_limit: u16,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetMmpInstruction {
+    type Accounts = openbook_v2::accounts::SetMmp;
+    type Instruction = openbook_v2::instruction::SetMmp;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            window_secs: self.window_secs,
+            base_lots_limit: self.base_lots_limit,
+            fill_count_limit: self.fill_count_limit,
+        };
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            signer: self.signer.pubkey(),
+            open_orders_account: self.open_orders_account,
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.signer]
+    }
+}
+
+pub struct ResetMmpInstruction {
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for ResetMmpInstruction {
+    type Accounts = openbook_v2::accounts::ResetMmp;
+    type Instruction = openbook_v2::instruction::ResetMmp;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+
+        let accounts = Self::Accounts {
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+#[derive(Clone)]
+pub struct RunAuctionInstruction {
+    pub market: Pubkey,
+    pub remainings: Vec<Pubkey>,
+    pub limit: u8,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for RunAuctionInstruction {
+    type Accounts = openbook_v2::accounts::RunAuction;
+    type Instruction = openbook_v2::instruction::RunAuction;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { limit: self.limit };
+
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+            event_heap: market.event_heap,
+            oracle_a: market.oracle_a.into(),
+            oracle_b: market.oracle_b.into(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        let mut vec_remainings: Vec<AccountMeta> = Vec::new();
+        for remaining in &self.remainings {
+            vec_remainings.push(AccountMeta {
+                pubkey: *remaining,
+                is_signer: false,
+                is_writable: true,
+            })
+        }
+        instruction.accounts.append(&mut vec_remainings);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![]
+    }
+}
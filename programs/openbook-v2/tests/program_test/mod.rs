@@ -31,6 +31,7 @@ pub struct TestInitialize {
     pub open_orders_admin: TestKeypair,
     pub close_market_admin: TestKeypair,
     pub consume_events_admin: TestKeypair,
+    pub market_status_admin: TestKeypair,
     pub owner: TestKeypair,
     pub payer: TestKeypair,
     pub mints: Vec<MintCookie>,
@@ -287,9 +288,15 @@ pub struct TestNewMarketInitialize {
     pub open_orders_admin_bool: bool,
     pub close_market_admin_bool: bool,
     pub consume_events_admin_bool: bool,
+    pub market_status_admin_bool: bool,
     pub time_expiry: i64,
     pub with_oracle: bool,
     pub payer_as_delegate: bool,
+    pub auction_duration_secs: u32,
+    pub auction_interval_secs: u32,
+    pub min_base_lots_per_order: i64,
+    pub min_quote_notional: u64,
+    pub max_base_lots_per_order: i64,
 }
 
 impl Default for TestNewMarketInitialize {
@@ -303,9 +310,15 @@ impl Default for TestNewMarketInitialize {
             open_orders_admin_bool: false,
             close_market_admin_bool: false,
             consume_events_admin_bool: false,
+            market_status_admin_bool: false,
             time_expiry: 0,
             with_oracle: true,
             payer_as_delegate: false,
+            auction_duration_secs: 0,
+            auction_interval_secs: 0,
+            min_base_lots_per_order: 0,
+            min_quote_notional: 0,
+            max_base_lots_per_order: 0,
         }
     }
 }
@@ -339,6 +352,12 @@ impl TestContext {
         } else {
             None
         };
+        let market_status_admin_acc = TestKeypair::new();
+        let market_status_admin = if args.market_status_admin_bool {
+            Some(market_status_admin_acc.pubkey())
+        } else {
+            None
+        };
 
         let owner = context.users[0].key;
         let payer = context.users[1].key;
@@ -372,6 +391,7 @@ impl TestContext {
                 open_orders_admin,
                 close_market_admin,
                 consume_events_admin,
+                market_status_admin,
                 payer,
                 market,
                 quote_lot_size: args.quote_lot_size,
@@ -382,6 +402,11 @@ impl TestContext {
                 quote_mint: mints[1].pubkey,
                 fee_penalty: args.fee_penalty,
                 time_expiry: args.time_expiry,
+                auction_duration_secs: args.auction_duration_secs,
+                auction_interval_secs: args.auction_interval_secs,
+                min_base_lots_per_order: args.min_base_lots_per_order,
+                min_quote_notional: args.min_quote_notional,
+                max_base_lots_per_order: args.max_base_lots_per_order,
                 ..CreateMarketInstruction::with_new_book_and_heap(solana, oracle, None).await
             },
         )
@@ -416,6 +441,7 @@ impl TestContext {
             open_orders_admin: open_orders_admin_acc,
             close_market_admin: close_market_admin_acc,
             consume_events_admin: consume_events_admin_acc,
+            market_status_admin: market_status_admin_acc,
             owner,
             payer,
             mints,
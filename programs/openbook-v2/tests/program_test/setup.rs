@@ -92,6 +92,7 @@ pub async fn create_open_orders_account(
             owner,
             payer: payer.key,
             delegate,
+            access_proof: None,
         },
     )
     .await
@@ -107,9 +107,11 @@ impl SolanaCookie {
         // if this gets too slow for our tests, remove and replace with manual time offset
         // which is configurable
         while clock.unix_timestamp <= target {
+            // slots are ~400ms, so this doesn't overshoot when jumping far ahead
+            let slots = ((target - clock.unix_timestamp) as u64 * 2).max(50);
             self.context
                 .borrow_mut()
-                .warp_to_slot(clock.slot + 50)
+                .warp_to_slot(clock.slot + slots)
                 .unwrap();
             clock = self.get_clock().await;
         }
@@ -242,6 +244,16 @@ impl SolanaCookie {
         )
     }
 
+    pub async fn get_account_lamports(&self, address: Pubkey) -> u64 {
+        self.context
+            .borrow_mut()
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .map_or(0, |account| account.lamports)
+    }
+
     pub async fn get_account_opt<T: AccountDeserialize>(&self, address: Pubkey) -> Option<T> {
         let data = self.get_account_data(address).await?;
         let mut data_slice: &[u8] = &data;
@@ -282,6 +294,23 @@ impl SolanaCookie {
             .set_account(&address, &account.into());
     }
 
+    pub async fn set_account_data(&self, address: Pubkey, data: &[u8]) {
+        let mut account = self
+            .context
+            .borrow_mut()
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+
+        account.data = data.to_vec();
+
+        self.context
+            .borrow_mut()
+            .set_account(&address, &account.into());
+    }
+
     pub fn program_log(&self) -> Vec<String> {
         self.last_transaction_log.borrow().clone()
     }
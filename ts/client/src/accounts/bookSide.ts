@@ -121,7 +121,7 @@ export class BookSide {
       } else if (node.tag === BookSide.LEAF_NODE_TAG) {
         const leafNode = this.toLeafNode(node);
         const expiryTimestamp = leafNode.timeInForce
-          ? leafNode.timestamp.add(new BN(leafNode.timeInForce))
+          ? new BN(leafNode.timestamp).add(new BN(leafNode.timeInForce))
           : U64_MAX_BN;
 
         yield new Order(
@@ -150,7 +150,7 @@ export class BookSide {
       } else if (node.tag === BookSide.LEAF_NODE_TAG) {
         const leafNode = this.toLeafNode(node);
         const expiryTimestamp = leafNode.timeInForce
-          ? leafNode.timestamp.add(new BN(leafNode.timeInForce))
+          ? new BN(leafNode.timestamp).add(new BN(leafNode.timeInForce))
           : U64_MAX_BN;
 
         yield new Order(
@@ -351,6 +351,7 @@ export class OpenOrders {
     debug += ` lockedMakerFees: ${oo.position.lockedMakerFees.toString()}\n`;
     debug += ` referrerRebatesAvailable: ${oo.position.referrerRebatesAvailable.toString()}\n`;
     debug += ` penaltyHeapCount: ${oo.position.penaltyHeapCount.toString()}\n`;
+    debug += ` penaltyLamportsOwed: ${oo.position.penaltyLamportsOwed.toString()}\n`;
     debug += ` makerVolume: ${oo.position.makerVolume.toString()}\n`;
     debug += ` takerVolume: ${oo.position.takerVolume.toString()}\n`;
 
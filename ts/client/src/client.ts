@@ -229,6 +229,12 @@ export class OpenBookV2Client {
     },
     market = Keypair.generate(),
     collectFeeAdmin?: PublicKey,
+    marketStatusAdmin: PublicKey | null = null,
+    auctionDurationSecs = 0,
+    auctionIntervalSecs = 0,
+    minBaseLotsPerOrder: BN = new BN(0),
+    minQuoteNotional: BN = new BN(0),
+    maxBaseLotsPerOrder: BN = new BN(0),
   ): Promise<[TransactionInstruction[], Signer[]]> {
     const [bidIx, bidsKeypair] = await this.createProgramAccountIx(
       payer,
@@ -274,6 +280,11 @@ export class OpenBookV2Client {
         makerFee,
         takerFee,
         timeExpiry,
+        auctionDurationSecs,
+        auctionIntervalSecs,
+        minBaseLotsPerOrder,
+        minQuoteNotional,
+        maxBaseLotsPerOrder,
       )
       .accounts({
         market: market.publicKey,
@@ -295,6 +306,7 @@ export class OpenBookV2Client {
         openOrdersAdmin,
         consumeEventsAdmin,
         closeMarketAdmin,
+        marketStatusAdmin,
         eventAuthority,
         program: this.programId,
       })
@@ -427,6 +439,8 @@ export class OpenBookV2Client {
     owner: PublicKey = this.walletPk,
     delegateAccount: PublicKey | null,
     openOrdersIndexer?: PublicKey | null,
+    accessGate: PublicKey | null = null,
+    accessProof: PublicKey | null = null,
   ): Promise<[TransactionInstruction[], PublicKey]> {
     const ixs: TransactionInstruction[] = [];
     let accountIndex = new BN(1);
@@ -462,6 +476,8 @@ export class OpenBookV2Client {
           delegateAccount,
           payer: this.walletPk,
           // systemProgram: SystemProgram.programId,
+          accessGate,
+          accessProof,
         })
         .instruction(),
     );
@@ -1005,6 +1021,41 @@ export class OpenBookV2Client {
     return ix;
   }
 
+  // Like consumeEventsIx, or consumeGivenEventsIx when slots are given, and pays the
+  // crank reward to crankRewardReceiver, which has to sign
+  public async consumeEventsWithRewardIx(
+    marketPublicKey: PublicKey,
+    market: MarketAccount,
+    limit: BN,
+    remainingAccounts: PublicKey[],
+    crankRewardReceiver: PublicKey,
+    slots: BN[] | null = null,
+  ): Promise<TransactionInstruction> {
+    const accountsMeta: AccountMeta[] = remainingAccounts.map((remaining) => ({
+      pubkey: remaining,
+      isSigner: false,
+      isWritable: true,
+    }));
+
+    const eventAdminBs58 = market.consumeEventsAdmin.key.toBase58();
+    const consumeEventsAdmin =
+      eventAdminBs58 === PublicKey.default.toBase58()
+        ? null
+        : market.consumeEventsAdmin.key;
+
+    const ix = await this.program.methods
+      .consumeEventsWithReward(limit, slots)
+      .accounts({
+        eventHeap: market.eventHeap,
+        market: marketPublicKey,
+        consumeEventsAdmin,
+        crankRewardReceiver,
+      })
+      .remainingAccounts(accountsMeta)
+      .instruction();
+    return ix;
+  }
+
   // Consume events for one specific account. Add other extra accounts as it's "free".
   public async consumeEventsForAccountIx(
     marketPublicKey: PublicKey,
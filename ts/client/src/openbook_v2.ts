@@ -6,6 +6,15 @@ export type OpenbookV2 = {
       name: 'createMarket';
       docs: [
         'Create a [`Market`](crate::state::Market) for a given token pair.',
+        '',
+        'A non-zero `auction_duration_secs` starts the market with an opening auction',
+        'of that length, see [`run_auction`](crate::openbook_v2::run_auction). With a',
+        'non-zero `auction_interval_secs`, a new auction phase starts every time the',
+        'market traded continuously for that long.',
+        '',
+        "The remainder of an order that would be posted to the book is dropped when it's",
+        'below `min_base_lots_per_order` or worth less than `min_quote_notional` quote',
+        "native, and the order fails when it's above `max_base_lots_per_order` (no limit = 0).",
       ];
       accounts: [
         {
@@ -25,6 +34,7 @@ export type OpenbookV2 = {
           docs: [
             'Accounts are initialized by client,',
             'anchor discriminator is set first when ix exits,',
+            'their sizes set the capacities, see `BookSide::space` and `EventHeap::space`',
           ];
         },
         {
@@ -112,6 +122,12 @@ export type OpenbookV2 = {
           isSigner: false;
           isOptional: true;
         },
+        {
+          name: 'marketStatusAdmin';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
         {
           name: 'eventAuthority';
           isMut: false;
@@ -154,6 +170,26 @@ export type OpenbookV2 = {
           name: 'timeExpiry';
           type: 'i64';
         },
+        {
+          name: 'auctionDurationSecs';
+          type: 'u32';
+        },
+        {
+          name: 'auctionIntervalSecs';
+          type: 'u32';
+        },
+        {
+          name: 'minBaseLotsPerOrder';
+          type: 'i64';
+        },
+        {
+          name: 'minQuoteNotional';
+          type: 'u64';
+        },
+        {
+          name: 'maxBaseLotsPerOrder';
+          type: 'i64';
+        },
       ];
     },
     {
@@ -201,6 +237,50 @@ export type OpenbookV2 = {
       ];
       args: [];
     },
+    {
+      name: 'shrinkMarketAccounts';
+      docs: [
+        'Give back most of the rent of the bids, asks and event heap of an expired',
+        "[`Market`](crate::state::Market) once they're empty, by shrinking them to the minimum",
+        'capacity (only [`close_market_admin`](crate::state::Market::close_market_admin)).',
+        '',
+        'The market stays open so users can still settle their funds, and is closed with',
+        '`close_market` as usual.',
+      ];
+      accounts: [
+        {
+          name: 'closeMarketAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'eventHeap';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'solDestination';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
     {
       name: 'createOpenOrdersIndexer';
       docs: [
@@ -301,6 +381,20 @@ export type OpenbookV2 = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: 'accessGate';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Required when the market is access gated'];
+        },
+        {
+          name: 'accessProof';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          docs: ['access gate'];
+        },
       ];
       args: [
         {
@@ -343,6 +437,101 @@ export type OpenbookV2 = {
       ];
       args: [];
     },
+    {
+      name: 'createOpenOrdersDelegates';
+      docs: [
+        'Create the [`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account of an',
+        'open orders account, which holds the delegate permissions and list.',
+        '',
+        'From then on, instructions signed by a delegate need it in their remaining accounts.',
+      ];
+      accounts: [
+        {
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'openOrdersDelegates';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'closeOpenOrdersDelegates';
+      docs: [
+        'Close the [`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account of an',
+        'open orders account, removing all its delegates including the',
+        '[`delegate`](crate::state::OpenOrdersAccount::delegate). Needed before closing the',
+        'open orders account.',
+      ];
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'openOrdersDelegates';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'solDestination';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'migrateOpenOrdersAccount';
+      docs: [
+        'Bring an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) of an older version up',
+        "to date, e.g. recompute the `bids_quote_lots` that version 0 didn't track. Anyone can",
+        'migrate any account, it does nothing for accounts that are up to date already.',
+      ];
+      accounts: [
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
     {
       name: 'placeOrder';
       docs: [
@@ -1106,6 +1295,53 @@ export type OpenbookV2 = {
         },
       ];
     },
+    {
+      name: 'consumeEventsWithReward';
+      docs: [
+        'Same as `consume_events`, or `consume_given_events` when `slots` are given, and',
+        'pays the signing `crank_reward_receiver`',
+        '[`crank_reward_per_event`](crate::state::Market::crank_reward_per_event) lamports',
+        'for every event consumed, from the penalties collected by the market.',
+      ];
+      accounts: [
+        {
+          name: 'consumeEventsAdmin';
+          isMut: false;
+          isSigner: true;
+          isOptional: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'eventHeap';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'crankRewardReceiver';
+          isMut: true;
+          isSigner: true;
+          docs: ['The cranker, receives the crank reward'];
+        },
+      ];
+      args: [
+        {
+          name: 'limit';
+          type: 'u64';
+        },
+        {
+          name: 'slots';
+          type: {
+            option: {
+              vec: 'u64';
+            };
+          };
+        },
+      ];
+    },
     {
       name: 'cancelOrder';
       docs: [
@@ -1366,7 +1602,7 @@ export type OpenbookV2 = {
       accounts: [
         {
           name: 'owner';
-          isMut: true;
+          isMut: false;
           isSigner: true;
         },
         {
@@ -1505,14 +1741,32 @@ export type OpenbookV2 = {
       args: [];
     },
     {
-      name: 'sweepFees';
-      docs: ["Sweep fees, as a [`Market`](crate::state::Market)'s admin."];
+      name: 'forceSettleFunds';
+      docs: [
+        'Withdraw the available tokens of an open orders account to token accounts of its',
+        "owner when the market is expired, without the owner's signature.",
+        '',
+        'Only the [`close_market_admin`](crate::state::Market::close_market_admin) can do',
+        'so right after the expiry, anyone once',
+        '[`EXPIRED_MARKET_GRACE_PERIOD`](crate::state::EXPIRED_MARKET_GRACE_PERIOD) has passed.',
+        'For markets set expired before the grace period existed, it is over already.',
+      ];
       accounts: [
         {
-          name: 'collectFeeAdmin';
+          name: 'signer';
           isMut: false;
           isSigner: true;
         },
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
         {
           name: 'market';
           isMut: true;
@@ -1523,13 +1777,23 @@ export type OpenbookV2 = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: 'marketBaseVault';
+          isMut: true;
+          isSigner: false;
+        },
         {
           name: 'marketQuoteVault';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'tokenReceiverAccount';
+          name: 'userBaseAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'userQuoteAccount';
           isMut: true;
           isSigner: false;
         },
@@ -1542,128 +1806,288 @@ export type OpenbookV2 = {
       args: [];
     },
     {
-      name: 'setDelegate';
+      name: 'sweepFees';
       docs: [
-        'Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.',
+        "Sweep fees, as a [`Market`](crate::state::Market)'s admin.",
+        '',
+        'Without a [`FeeSplit`](crate::state::FeeSplit) all fees go to the',
+        '`token_receiver_account`. With one, they are split pro rata between its',
+        'destinations, which are passed as remaining accounts in the same order.',
       ];
       accounts: [
         {
-          name: 'owner';
-          isMut: true;
+          name: 'collectFeeAdmin';
+          isMut: false;
           isSigner: true;
         },
         {
-          name: 'openOrdersAccount';
+          name: 'market';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'delegateAccount';
+          name: 'marketAuthority';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'marketQuoteVault';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'tokenReceiverAccount';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Receives all fees when the market has no fee split'];
+        },
+        {
+          name: 'tokenProgram';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'feeSplit';
           isMut: false;
           isSigner: false;
           isOptional: true;
+          docs: [
+            'Required when the market has a fee split, the destination token accounts are',
+            'passed as remaining accounts in the same order',
+          ];
         },
       ];
       args: [];
     },
     {
-      name: 'setMarketExpired';
+      name: 'registerReferrer';
       docs: [
-        'Set market to expired before pruning orders and closing the market (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+        'Register a [`Referrer`](crate::state::Referrer) with a share of the taker fees of',
+        "the accounts bound to it, in basis points. It's global if `market` is the default",
+        'pubkey, otherwise it can only refer on that market.',
       ];
       accounts: [
         {
-          name: 'closeMarketAdmin';
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'authority';
           isMut: false;
           isSigner: true;
         },
         {
-          name: 'market';
+          name: 'referrer';
           isMut: true;
           isSigner: false;
         },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'market';
+          type: 'publicKey';
+        },
+        {
+          name: 'shareBps';
+          type: 'u16';
+        },
       ];
-      args: [];
     },
     {
-      name: 'pruneOrders';
+      name: 'setReferrerShare';
       docs: [
-        'Remove orders from the book when the market is expired (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+        'Update the share of a [`Referrer`](crate::state::Referrer). Accounts already bound',
+        'to it keep the share they were bound with.',
       ];
       accounts: [
         {
-          name: 'closeMarketAdmin';
+          name: 'authority';
           isMut: false;
           isSigner: true;
         },
         {
-          name: 'openOrdersAccount';
+          name: 'referrer';
           isMut: true;
           isSigner: false;
         },
+      ];
+      args: [
         {
-          name: 'market';
+          name: 'shareBps';
+          type: 'u16';
+        },
+      ];
+    },
+    {
+      name: 'setMaxReferrerShare';
+      docs: [
+        'Set the maximum share of the taker fees that referrers get on a market (only',
+        '[`collect_fee_admin`](crate::state::Market::collect_fee_admin)). Markets start at 0,',
+        'without any referrer rebates.',
+      ];
+      accounts: [
+        {
+          name: 'collectFeeAdmin';
           isMut: false;
-          isSigner: false;
+          isSigner: true;
         },
         {
-          name: 'bids';
+          name: 'market';
           isMut: true;
           isSigner: false;
         },
+      ];
+      args: [
         {
-          name: 'asks';
+          name: 'shareBps';
+          type: 'u16';
+        },
+      ];
+    },
+    {
+      name: 'setCrankRewards';
+      docs: [
+        'Set the lamports a taker pays for every transaction that adds to the event heap',
+        '(None = [`PENALTY_EVENT_HEAP`](crate::state::PENALTY_EVENT_HEAP)) and the reward paid',
+        'from these penalties for every event consumed (0 = no reward), as the',
+        '[`collect_fee_admin`](crate::state::Market::collect_fee_admin).',
+      ];
+      accounts: [
+        {
+          name: 'collectFeeAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'market';
           isMut: true;
           isSigner: false;
         },
       ];
       args: [
         {
-          name: 'limit';
-          type: 'u8';
+          name: 'penaltyEventHeap';
+          type: {
+            option: 'u32';
+          };
+        },
+        {
+          name: 'crankRewardPerEvent';
+          type: 'u32';
         },
       ];
     },
     {
-      name: 'stubOracleCreate';
+      name: 'bindReferrer';
+      docs: [
+        'Bind an open orders account to a [`Referrer`](crate::state::Referrer), creating its',
+        '[`OpenOrdersReferral`](crate::state::OpenOrdersReferral).',
+        '',
+        "From then on the referrer's share of the taker fees of the account is set aside when",
+        'taking with `place_order`, `edit_order`, `place_order_pegged` or',
+        '`cancel_all_and_place_orders`, which need the referral in their remaining accounts.',
+        "The share is capped by the market's",
+        '[`max_referrer_share_bps`](crate::state::Market::max_referrer_share_bps), 0 unless',
+        'set with `set_max_referrer_share`.',
+      ];
       accounts: [
         {
-          name: 'payer';
+          name: 'owner';
           isMut: true;
           isSigner: true;
         },
         {
-          name: 'owner';
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'referrer';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'openOrdersReferral';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'claimReferrerRebates';
+      docs: [
+        'Withdraw the rebates set aside for a [`Referrer`](crate::state::Referrer) in an',
+        'open orders account bound to it.',
+      ];
+      accounts: [
+        {
+          name: 'authority';
           isMut: false;
           isSigner: true;
         },
         {
-          name: 'oracle';
+          name: 'referrer';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'mint';
+          name: 'openOrdersAccount';
           isMut: false;
           isSigner: false;
         },
         {
-          name: 'systemProgram';
+          name: 'openOrdersReferral';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'marketAuthority';
           isMut: false;
           isSigner: false;
         },
-      ];
-      args: [
         {
-          name: 'price';
-          type: 'f64';
+          name: 'marketQuoteVault';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'referrerTokenAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'tokenProgram';
+          isMut: false;
+          isSigner: false;
         },
       ];
+      args: [];
     },
     {
-      name: 'stubOracleClose';
+      name: 'closeOpenOrdersReferral';
+      docs: [
+        'Unbind an open orders account from its referrer, paying out the rebates set aside',
+        'so far and closing its [`OpenOrdersReferral`](crate::state::OpenOrdersReferral).',
+        'Needed before closing the open orders account.',
+      ];
       accounts: [
         {
           name: 'owner';
@@ -1671,7 +2095,37 @@ export type OpenbookV2 = {
           isSigner: true;
         },
         {
-          name: 'oracle';
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'openOrdersReferral';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'referrer';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'marketAuthority';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'marketQuoteVault';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'referrerTokenAccount';
           isMut: true;
           isSigner: false;
         },
@@ -1689,2032 +2143,5569 @@ export type OpenbookV2 = {
       args: [];
     },
     {
-      name: 'stubOracleSet';
+      name: 'createFeeSplit';
+      docs: [
+        'Create the [`FeeSplit`](crate::state::FeeSplit) of a market, as its',
+        '[`collect_fee_admin`](crate::state::Market::collect_fee_admin). From then on the',
+        'fees are always swept according to it.',
+        '',
+        'The destination token accounts are passed as remaining accounts, in order.',
+      ];
       accounts: [
         {
-          name: 'owner';
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'collectFeeAdmin';
           isMut: false;
           isSigner: true;
         },
         {
-          name: 'oracle';
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'feeSplit';
           isMut: true;
           isSigner: false;
         },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
       ];
       args: [
         {
-          name: 'price';
-          type: 'f64';
+          name: 'destinations';
+          type: {
+            vec: {
+              defined: 'FeeSplitDestination';
+            };
+          };
         },
       ];
     },
-  ];
-  accounts: [
     {
-      name: 'market';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'bump';
-            docs: ['PDA bump'];
-            type: 'u8';
-          },
-          {
-            name: 'baseDecimals';
-            docs: [
-              'Number of decimals used for the base token.',
-              '',
-              "Used to convert the oracle's price into a native/native price.",
-            ];
-            type: 'u8';
-          },
-          {
-            name: 'quoteDecimals';
-            type: 'u8';
-          },
-          {
-            name: 'padding1';
-            type: {
-              array: ['u8', 5];
+      name: 'setFeeSplit';
+      docs: [
+        'Replace the destinations of the [`FeeSplit`](crate::state::FeeSplit) of a market, as',
+        'its [`collect_fee_admin`](crate::state::Market::collect_fee_admin). The destination',
+        'token accounts are passed as remaining accounts, in order.',
+      ];
+      accounts: [
+        {
+          name: 'collectFeeAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'feeSplit';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'destinations';
+          type: {
+            vec: {
+              defined: 'FeeSplitDestination';
             };
-          },
-          {
-            name: 'marketAuthority';
-            type: 'publicKey';
-          },
-          {
-            name: 'timeExpiry';
-            docs: [
-              'No expiry = 0. Market will expire and no trading allowed after time_expiry',
-            ];
-            type: 'i64';
-          },
-          {
-            name: 'collectFeeAdmin';
-            docs: ['Admin who can collect fees from the market'];
-            type: 'publicKey';
-          },
-          {
-            name: 'openOrdersAdmin';
-            docs: ['Admin who must sign off on all order creations'];
-            type: {
-              defined: 'NonZeroPubkeyOption';
-            };
-          },
-          {
-            name: 'consumeEventsAdmin';
-            docs: ['Admin who must sign off on all event consumptions'];
-            type: {
-              defined: 'NonZeroPubkeyOption';
-            };
-          },
-          {
-            name: 'closeMarketAdmin';
-            docs: [
-              'Admin who can set market expired, prune orders and close the market',
-            ];
-            type: {
-              defined: 'NonZeroPubkeyOption';
-            };
-          },
-          {
-            name: 'name';
-            docs: ['Name. Trailing zero bytes are ignored.'];
-            type: {
-              array: ['u8', 16];
-            };
-          },
-          {
-            name: 'bids';
-            docs: ['Address of the BookSide account for bids'];
-            type: 'publicKey';
-          },
-          {
-            name: 'asks';
-            docs: ['Address of the BookSide account for asks'];
-            type: 'publicKey';
-          },
-          {
-            name: 'eventHeap';
-            docs: ['Address of the EventHeap account'];
-            type: 'publicKey';
-          },
-          {
-            name: 'oracleA';
-            docs: ['Oracles account address'];
-            type: {
-              defined: 'NonZeroPubkeyOption';
-            };
-          },
-          {
-            name: 'oracleB';
-            type: {
-              defined: 'NonZeroPubkeyOption';
-            };
-          },
-          {
-            name: 'oracleConfig';
-            docs: ['Oracle configuration'];
-            type: {
-              defined: 'OracleConfig';
-            };
-          },
-          {
-            name: 'quoteLotSize';
-            docs: [
-              'Number of quote native in a quote lot. Must be a power of 10.',
-              '',
-              'Primarily useful for increasing the tick size on the market: A lot price',
-              'of 1 becomes a native price of quote_lot_size/base_lot_size becomes a',
-              'ui price of quote_lot_size*base_decimals/base_lot_size/quote_decimals.',
-            ];
-            type: 'i64';
-          },
-          {
-            name: 'baseLotSize';
-            docs: [
-              'Number of base native in a base lot. Must be a power of 10.',
-              '',
-              'Example: If base decimals for the underlying asset is 6, base lot size',
-              'is 100 and and base position lots is 10_000 then base position native is',
-              '1_000_000 and base position ui is 1.',
-            ];
-            type: 'i64';
-          },
-          {
-            name: 'seqNum';
-            docs: ['Total number of orders seen'];
-            type: 'u64';
-          },
-          {
-            name: 'registrationTime';
-            docs: ['Timestamp in seconds that the market was registered at.'];
-            type: 'i64';
-          },
-          {
-            name: 'makerFee';
-            docs: [
-              'Fees',
-              '',
-              'Fee (in 10^-6) when matching maker orders.',
-              'maker_fee < 0 it means some of the taker_fees goes to the maker',
-              'maker_fee > 0, it means no taker_fee to the maker, and maker fee goes to the referral',
-            ];
-            type: 'i64';
-          },
-          {
-            name: 'takerFee';
-            docs: ['Fee (in 10^-6) for taker orders, always >= 0.'];
-            type: 'i64';
-          },
-          {
-            name: 'feesAccrued';
-            docs: ['Total fees accrued in native quote'];
-            type: 'u128';
-          },
-          {
-            name: 'feesToReferrers';
-            docs: ['Total fees settled in native quote'];
-            type: 'u128';
-          },
-          {
-            name: 'referrerRebatesAccrued';
-            docs: ['Referrer rebates to be distributed'];
-            type: 'u64';
-          },
-          {
-            name: 'feesAvailable';
-            docs: ['Fees generated and available to withdraw via sweep_fees'];
-            type: 'u64';
-          },
-          {
-            name: 'makerVolume';
-            docs: [
-              'Cumulative maker volume (same as taker volume) in quote native units',
-            ];
-            type: 'u128';
-          },
-          {
-            name: 'takerVolumeWoOo';
-            docs: [
-              'Cumulative taker volume in quote native units due to place take orders',
-            ];
-            type: 'u128';
-          },
-          {
-            name: 'baseMint';
-            type: 'publicKey';
-          },
-          {
-            name: 'quoteMint';
-            type: 'publicKey';
-          },
-          {
-            name: 'marketBaseVault';
-            type: 'publicKey';
-          },
-          {
-            name: 'baseDepositTotal';
-            type: 'u64';
-          },
-          {
-            name: 'marketQuoteVault';
-            type: 'publicKey';
-          },
-          {
-            name: 'quoteDepositTotal';
-            type: 'u64';
-          },
-          {
-            name: 'reserved';
-            type: {
-              array: ['u8', 128];
-            };
-          },
-        ];
-      };
+          };
+        },
+      ];
     },
     {
-      name: 'openOrdersAccount';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'owner';
-            type: 'publicKey';
-          },
-          {
-            name: 'market';
-            type: 'publicKey';
-          },
-          {
-            name: 'name';
-            type: {
-              array: ['u8', 32];
-            };
-          },
-          {
-            name: 'delegate';
-            type: {
-              defined: 'NonZeroPubkeyOption';
+      name: 'setDelegate';
+      docs: [
+        'Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.',
+        '',
+        'The delegate is limited to the given [`DelegateScope`](crate::state::DelegateScope). Orders',
+        'it places can be restricted to `allowed_side` and to `max_quote_native_per_order`',
+        '(0 = no limit, asks are valued at their limit price, or at the best bid or oracle',
+        'price when higher). After `expiry_timestamp` the delegate is ignored (0 = never).',
+        'Removing the delegate resets the permissions.',
+        '',
+        'The permissions are stored in the',
+        '[`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account, which is required',
+        'for anything but an unrestricted delegate.',
+      ];
+      accounts: [
+        {
+          name: 'owner';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'delegateAccount';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'openOrdersDelegates';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+      ];
+      args: [
+        {
+          name: 'scope';
+          type: {
+            defined: 'DelegateScope';
+          };
+        },
+        {
+          name: 'allowedSide';
+          type: {
+            option: {
+              defined: 'Side';
             };
-          },
-          {
-            name: 'accountNum';
-            type: 'u32';
-          },
-          {
-            name: 'bump';
-            type: 'u8';
-          },
-          {
-            name: 'version';
-            type: 'u8';
-          },
-          {
-            name: 'padding';
-            type: {
-              array: ['u8', 2];
-            };
-          },
-          {
-            name: 'position';
-            type: {
-              defined: 'Position';
-            };
-          },
-          {
-            name: 'openOrders';
-            type: {
-              array: [
-                {
-                  defined: 'OpenOrder';
-                },
-                24,
-              ];
+          };
+        },
+        {
+          name: 'maxQuoteNativePerOrder';
+          type: 'u64';
+        },
+        {
+          name: 'expiryTimestamp';
+          type: 'u64';
+        },
+      ];
+    },
+    {
+      name: 'addDelegate';
+      docs: [
+        'Add a delegate to the delegate list of an open orders account, in addition to the',
+        '[`delegate`](crate::state::OpenOrdersAccount::delegate). The permissions work as in',
+        '`set_delegate`.',
+        '',
+        'The list holds up to [`MAX_DELEGATES`](crate::state::MAX_DELEGATES) entries in the',
+        '[`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account.',
+      ];
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'openOrdersDelegates';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'delegateAccount';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'scope';
+          type: {
+            defined: 'DelegateScope';
+          };
+        },
+        {
+          name: 'allowedSide';
+          type: {
+            option: {
+              defined: 'Side';
             };
-          },
-        ];
-      };
+          };
+        },
+        {
+          name: 'maxQuoteNativePerOrder';
+          type: 'u64';
+        },
+        {
+          name: 'expiryTimestamp';
+          type: 'u64';
+        },
+      ];
+    },
+    {
+      name: 'removeDelegate';
+      docs: [
+        'Remove a delegate from the delegate list of an open orders account.',
+      ];
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'openOrdersDelegates';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'delegateAccount';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'setMarketExpired';
+      docs: [
+        'Set market to expired before pruning orders and closing the market (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ];
+      accounts: [
+        {
+          name: 'closeMarketAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'setMarketStatus';
+      docs: [
+        'Restrict the order instructions of the market without expiring it (only',
+        '[`market_status_admin`](crate::state::Market::market_status_admin)).',
+        '',
+        '`PostOnly` only accepts orders that never take, `CancelOnly` only accepts',
+        'cancels and `Halted` rejects cancels as well. Setting the status back to',
+        '`Active` lifts all restrictions.',
+      ];
+      accounts: [
+        {
+          name: 'marketStatusAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'status';
+          type: {
+            defined: 'MarketStatus';
+          };
+        },
+      ];
+    },
+    {
+      name: 'setMarketStatusAdmin';
+      docs: [
+        'Set or clear the [`market_status_admin`](crate::state::Market::market_status_admin)',
+        'of a market (only [`collect_fee_admin`](crate::state::Market::collect_fee_admin)).',
+        '',
+        'Lets markets created without a status admin get one later.',
+      ];
+      accounts: [
+        {
+          name: 'collectFeeAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'marketStatusAdmin';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'createAccessGate';
+      docs: [
+        'Gate a market behind an allowlist or a credential token, as its',
+        '[`market_status_admin`](crate::state::Market::market_status_admin).',
+        '',
+        'Open orders accounts then need an [`AccessPass`](crate::state::AccessPass) created by',
+        '`admin`, or a token account of `credential_mint` with a balance, when they are created.',
+        'Only accounts that passed the gate can place orders. Every order re-checks the gate, so',
+        '`place_order`, `edit_order` and `cancel_all_and_place_orders` need the access gate and the',
+        "owner's access pass or credential token account in their remaining accounts. Take orders",
+        "have no open orders account, `place_take_order` checks the signer's proof instead.",
+      ];
+      accounts: [
+        {
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'marketStatusAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'accessGate';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'kind';
+          type: {
+            defined: 'AccessGate';
+          };
+        },
+        {
+          name: 'admin';
+          type: 'publicKey';
+        },
+        {
+          name: 'credentialMint';
+          type: 'publicKey';
+        },
+      ];
+    },
+    {
+      name: 'addAccessPass';
+      docs: [
+        'Allow an owner to create open orders accounts and place orders on an allowlist gated',
+        'market.',
+      ];
+      accounts: [
+        {
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'admin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'accessGate';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'accessPass';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'removeAccessPass';
+      docs: [
+        "Remove the access pass of an owner. Its existing open orders accounts can't place",
+        'orders anymore, but can still cancel orders and settle funds.',
+      ];
+      accounts: [
+        {
+          name: 'admin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'accessGate';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'accessPass';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'solDestination';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'setOpenOrdersAccess';
+      docs: [
+        'Grant or revoke the access of an open orders account to a gated market. Revoked',
+        'accounts can only cancel orders and settle funds.',
+      ];
+      accounts: [
+        {
+          name: 'admin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'accessGate';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'allowed';
+          type: 'bool';
+        },
+      ];
+    },
+    {
+      name: 'pruneOrders';
+      docs: [
+        'Remove orders from the book when the market is expired (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ];
+      accounts: [
+        {
+          name: 'closeMarketAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'limit';
+          type: 'u8';
+        },
+      ];
+    },
+    {
+      name: 'forcePruneOrders';
+      docs: [
+        'Remove orders from the book when the market is expired, with the same permissions',
+        'as `force_settle_funds`.',
+      ];
+      accounts: [
+        {
+          name: 'signer';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'limit';
+          type: 'u8';
+        },
+      ];
+    },
+    {
+      name: 'heartbeat';
+      docs: [
+        'Set the heartbeat deadline of an open orders account and its resting orders',
+        'to now + `timeout` seconds.',
+        '',
+        "Once the deadline passes without another heartbeat, the account's resting",
+        'orders are treated as invalid and dropped by takers, and anyone may cancel',
+        'them with `cancel_orders_heartbeat_expired`. A `timeout` of 0 disables it.',
+      ];
+      accounts: [
+        {
+          name: 'signer';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'timeout';
+          type: 'u64';
+        },
+      ];
+    },
+    {
+      name: 'cancelOrdersHeartbeatExpired';
+      docs: [
+        'Cancel up to `limit` orders of an open orders account whose heartbeat',
+        'deadline has passed (permissionless).',
+      ];
+      accounts: [
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'limit';
+          type: 'u8';
+        },
+      ];
+    },
+    {
+      name: 'setMmp';
+      docs: [
+        'Configure the market maker protection of an open orders account.',
+        '',
+        "When the account's resting orders get filled for `base_lots_limit` base lots or",
+        '`fill_count_limit` times within `window_secs` seconds, the account is frozen: its',
+        'orders are treated as invalid and dropped by takers until the owner calls `reset_mmp`.',
+        'A limit of 0 is ignored, a `window_secs` of 0 disables the protection. Fails while',
+        'the account is frozen.',
+        '',
+        'Fills are counted while matching, so takers have to pass the open orders accounts',
+        'of makers with market maker protection in the remaining accounts. The orders of',
+        "makers whose account is missing are skipped, and the rest of the order isn't posted.",
+      ];
+      accounts: [
+        {
+          name: 'signer';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'windowSecs';
+          type: 'u16';
+        },
+        {
+          name: 'baseLotsLimit';
+          type: 'i64';
+        },
+        {
+          name: 'fillCountLimit';
+          type: 'u16';
+        },
+      ];
+    },
+    {
+      name: 'resetMmp';
+      docs: [
+        'Unfreeze an open orders account after its market maker protection was triggered.',
+        'Only the owner can do this.',
+      ];
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'runAuction';
+      docs: [
+        'Run the auction of a market whose auction phase ended (permissionless).',
+        '',
+        'Computes the uniform price that maximizes the matched volume of the crossing',
+        'bids and asks and fills up to `limit` pairs of orders at that price. Both sides',
+        'are makers and get [`FillEvent`](crate::state::FillEvent)s, processed directly',
+        'for the open orders accounts passed as remaining accounts. Once no crossing',
+        'orders are left, the market switches to continuous trading.',
+        '',
+        'The accounts of crossing makers with market maker protection must be passed, the',
+        'orders of frozen makers are dropped instead of filled.',
+      ];
+      accounts: [
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'eventHeap';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'oracleA';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'oracleB';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+      ];
+      args: [
+        {
+          name: 'limit';
+          type: 'u8';
+        },
+      ];
+    },
+    {
+      name: 'stubOracleCreate';
+      accounts: [
+        {
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'oracle';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'mint';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'price';
+          type: 'f64';
+        },
+      ];
+    },
+    {
+      name: 'stubOracleClose';
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'oracle';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'solDestination';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'tokenProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'stubOracleSet';
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'oracle';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'price';
+          type: 'f64';
+        },
+      ];
+    },
+  ];
+  accounts: [
+    {
+      name: 'marketAccessGate';
+      docs: [
+        'Access gate of a permissioned market, checked when creating open orders accounts and',
+        'on each order instead of having the open orders admin co-sign every order',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'bump';
+            type: 'u8';
+          },
+          {
+            name: 'market';
+            type: 'publicKey';
+          },
+          {
+            name: 'admin';
+            docs: [
+              'Creates and removes access passes, and grants or revokes open orders accounts',
+            ];
+            type: 'publicKey';
+          },
+          {
+            name: 'kind';
+            docs: ['See [`AccessGate`]'];
+            type: 'u8';
+          },
+          {
+            name: 'credentialMint';
+            docs: [
+              'Mint of the credential token for [`AccessGate::CredentialToken`]',
+            ];
+            type: 'publicKey';
+          },
+        ];
+      };
+    },
+    {
+      name: 'accessPass';
+      docs: [
+        'Allows `owner` to create open orders accounts on an allowlist gated market',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'bump';
+            type: 'u8';
+          },
+          {
+            name: 'market';
+            type: 'publicKey';
+          },
+          {
+            name: 'owner';
+            type: 'publicKey';
+          },
+        ];
+      };
+    },
+    {
+      name: 'openOrdersDelegates';
+      docs: [
+        'Delegate permissions of an open orders account, a PDA of the open orders account',
+        '',
+        'Accounts without one only have an unrestricted [`OpenOrdersAccount::delegate`]. Once',
+        'created, it holds the limits of that delegate and a list of further delegates, and',
+        "delegates are only recognized when it's passed in the remaining accounts.",
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'openOrdersAccount';
+            type: 'publicKey';
+          },
+          {
+            name: 'bump';
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 7];
+            };
+          },
+          {
+            name: 'delegatePermissions';
+            docs: ['Limits of [`OpenOrdersAccount::delegate`]'];
+            type: {
+              defined: 'DelegatePermissions';
+            };
+          },
+          {
+            name: 'entries';
+            type: {
+              array: [
+                {
+                  defined: 'DelegateEntry';
+                },
+                4,
+              ];
+            };
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 64];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'feeSplit';
+      docs: [
+        'How `sweep_fees` distributes the fees of a market, created by its `collect_fee_admin`',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'bump';
+            type: 'u8';
+          },
+          {
+            name: 'market';
+            type: 'publicKey';
+          },
+          {
+            name: 'destinations';
+            type: {
+              vec: {
+                defined: 'FeeSplitDestination';
+              };
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'market';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'bump';
+            docs: ['PDA bump'];
+            type: 'u8';
+          },
+          {
+            name: 'baseDecimals';
+            docs: [
+              'Number of decimals used for the base token.',
+              '',
+              "Used to convert the oracle's price into a native/native price.",
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'quoteDecimals';
+            type: 'u8';
+          },
+          {
+            name: 'padding1';
+            type: {
+              array: ['u8', 5];
+            };
+          },
+          {
+            name: 'marketAuthority';
+            type: 'publicKey';
+          },
+          {
+            name: 'timeExpiry';
+            docs: [
+              'No expiry = 0. Market will expire and no trading allowed after time_expiry',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'collectFeeAdmin';
+            docs: ['Admin who can collect fees from the market'];
+            type: 'publicKey';
+          },
+          {
+            name: 'openOrdersAdmin';
+            docs: ['Admin who must sign off on all order creations'];
+            type: {
+              defined: 'NonZeroPubkeyOption';
+            };
+          },
+          {
+            name: 'consumeEventsAdmin';
+            docs: ['Admin who must sign off on all event consumptions'];
+            type: {
+              defined: 'NonZeroPubkeyOption';
+            };
+          },
+          {
+            name: 'closeMarketAdmin';
+            docs: [
+              'Admin who can set market expired, prune orders and close the market',
+            ];
+            type: {
+              defined: 'NonZeroPubkeyOption';
+            };
+          },
+          {
+            name: 'name';
+            docs: ['Name. Trailing zero bytes are ignored.'];
+            type: {
+              array: ['u8', 16];
+            };
+          },
+          {
+            name: 'bids';
+            docs: ['Address of the BookSide account for bids'];
+            type: 'publicKey';
+          },
+          {
+            name: 'asks';
+            docs: ['Address of the BookSide account for asks'];
+            type: 'publicKey';
+          },
+          {
+            name: 'eventHeap';
+            docs: ['Address of the EventHeap account'];
+            type: 'publicKey';
+          },
+          {
+            name: 'oracleA';
+            docs: ['Oracles account address'];
+            type: {
+              defined: 'NonZeroPubkeyOption';
+            };
+          },
+          {
+            name: 'oracleB';
+            type: {
+              defined: 'NonZeroPubkeyOption';
+            };
+          },
+          {
+            name: 'oracleConfig';
+            docs: ['Oracle configuration'];
+            type: {
+              defined: 'OracleConfig';
+            };
+          },
+          {
+            name: 'quoteLotSize';
+            docs: [
+              'Number of quote native in a quote lot. Must be a power of 10.',
+              '',
+              'Primarily useful for increasing the tick size on the market: A lot price',
+              'of 1 becomes a native price of quote_lot_size/base_lot_size becomes a',
+              'ui price of quote_lot_size*base_decimals/base_lot_size/quote_decimals.',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'baseLotSize';
+            docs: [
+              'Number of base native in a base lot. Must be a power of 10.',
+              '',
+              'Example: If base decimals for the underlying asset is 6, base lot size',
+              'is 100 and and base position lots is 10_000 then base position native is',
+              '1_000_000 and base position ui is 1.',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'seqNum';
+            docs: ['Total number of orders seen'];
+            type: 'u64';
+          },
+          {
+            name: 'registrationTime';
+            docs: ['Timestamp in seconds that the market was registered at.'];
+            type: 'i64';
+          },
+          {
+            name: 'makerFee';
+            docs: [
+              'Fees',
+              '',
+              'Fee (in 10^-6) when matching maker orders.',
+              'maker_fee < 0 it means some of the taker_fees goes to the maker',
+              'maker_fee > 0, it means no taker_fee to the maker, and maker fee goes to the referral',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'takerFee';
+            docs: ['Fee (in 10^-6) for taker orders, always >= 0.'];
+            type: 'i64';
+          },
+          {
+            name: 'feesAccrued';
+            docs: ['Total fees accrued in native quote'];
+            type: 'u128';
+          },
+          {
+            name: 'feesToReferrers';
+            docs: ['Total fees settled in native quote'];
+            type: 'u128';
+          },
+          {
+            name: 'referrerRebatesAccrued';
+            docs: ['Referrer rebates to be distributed'];
+            type: 'u64';
+          },
+          {
+            name: 'feesAvailable';
+            docs: ['Fees generated and available to withdraw via sweep_fees'];
+            type: 'u64';
+          },
+          {
+            name: 'makerVolume';
+            docs: [
+              'Cumulative maker volume (same as taker volume) in quote native units',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'takerVolumeWoOo';
+            docs: [
+              'Cumulative taker volume in quote native units due to place take orders',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'baseMint';
+            type: 'publicKey';
+          },
+          {
+            name: 'quoteMint';
+            type: 'publicKey';
+          },
+          {
+            name: 'marketBaseVault';
+            type: 'publicKey';
+          },
+          {
+            name: 'baseDepositTotal';
+            type: 'u64';
+          },
+          {
+            name: 'marketQuoteVault';
+            type: 'publicKey';
+          },
+          {
+            name: 'quoteDepositTotal';
+            type: 'u64';
+          },
+          {
+            name: 'auctionStartTs';
+            docs: ['Start of the current or next auction phase.'];
+            type: 'i64';
+          },
+          {
+            name: 'auctionEndTs';
+            docs: [
+              'End of the current or next auction phase. No auction = 0.',
+              '',
+              'During an auction phase orders are posted to the book without matching.',
+              'After it ended, `run_auction` fills all crossing orders at a uniform price',
+              'and switches the market to continuous trading.',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'auctionPriceLots';
+            docs: [
+              'Clearing price of the auction that is being run, 0 if not computed yet',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'auctionDurationSecs';
+            docs: ['Length of an auction phase in seconds'];
+            type: 'u32';
+          },
+          {
+            name: 'auctionIntervalSecs';
+            docs: [
+              'Seconds of continuous trading between recurring auction phases. Opening auction only = 0',
+            ];
+            type: 'u32';
+          },
+          {
+            name: 'marketStatusAdmin';
+            docs: ['Admin who can change the market status'];
+            type: {
+              defined: 'NonZeroPubkeyOption';
+            };
+          },
+          {
+            name: 'status';
+            docs: ['See [`MarketStatus`]'];
+            type: 'u8';
+          },
+          {
+            name: 'hasPenaltyEventHeap';
+            docs: [
+              'Set once `set_crank_rewards` configured `penalty_event_heap`',
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'padding2';
+            type: {
+              array: ['u8', 2];
+            };
+          },
+          {
+            name: 'penaltyEventHeap';
+            docs: [
+              'Lamports a taker pays for every transaction that adds to the event heap, when',
+              '`has_penalty_event_heap` is set. [`PENALTY_EVENT_HEAP`] otherwise.',
+            ];
+            type: 'u32';
+          },
+          {
+            name: 'minBaseLotsPerOrder';
+            docs: [
+              "Minimum base lots of an order posted to the book. Smaller remainders aren't posted.",
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'minQuoteNotional';
+            docs: [
+              'Minimum value in quote native of an order posted to the book. Smaller remainders',
+              "aren't posted.",
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'maxBaseLotsPerOrder';
+            docs: [
+              'Maximum base lots of an order posted to the book. No limit = 0',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'timeSetExpired';
+            docs: [
+              "Timestamp at which `set_market_expired` was called, 0 if it wasn't or if it was",
+              'before this field existed',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'hasFeeSplit';
+            docs: [
+              'Set once a [`FeeSplit`](crate::state::FeeSplit) was created, `sweep_fees` then',
+              'distributes the fees according to it',
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'accessGate';
+            docs: [
+              'See [`AccessGate`](crate::state::AccessGate), set by `create_access_gate`',
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'maxReferrerShareBps';
+            docs: [
+              'Maximum share of the taker fees, after maker rebates, that bound referrers get on',
+              'this market, in basis points. No referrer rebates = 0, which is where every market',
+              'starts until `set_max_referrer_share` is called',
+            ];
+            type: 'u16';
+          },
+          {
+            name: 'crankRewardPerEvent';
+            docs: [
+              'Lamports paid from the penalties to whoever consumes an event. No reward = 0',
+            ];
+            type: 'u32';
+          },
+          {
+            name: 'penaltyLamportsAccrued';
+            docs: [
+              'Penalty lamports collected into the market account and not yet paid as crank',
+              'rewards',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 8];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'openOrdersAccount';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'owner';
+            type: 'publicKey';
+          },
+          {
+            name: 'market';
+            type: 'publicKey';
+          },
+          {
+            name: 'name';
+            type: {
+              array: ['u8', 32];
+            };
+          },
+          {
+            name: 'delegate';
+            type: {
+              defined: 'NonZeroPubkeyOption';
+            };
+          },
+          {
+            name: 'accountNum';
+            type: 'u32';
+          },
+          {
+            name: 'bump';
+            type: 'u8';
+          },
+          {
+            name: 'version';
+            type: 'u8';
+          },
+          {
+            name: 'access';
+            docs: ['See [`OpenOrdersAccess`]'];
+            type: 'u8';
+          },
+          {
+            name: 'flags';
+            docs: ['Side accounts of the account, see `OPEN_ORDERS_FLAG_*`'];
+            type: 'u8';
+          },
+          {
+            name: 'position';
+            type: {
+              defined: 'Position';
+            };
+          },
+          {
+            name: 'openOrders';
+            type: {
+              array: [
+                {
+                  defined: 'OpenOrder';
+                },
+                24,
+              ];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'openOrdersIndexer';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'bump';
+            type: 'u8';
+          },
+          {
+            name: 'createdCounter';
+            type: 'u32';
+          },
+          {
+            name: 'addresses';
+            type: {
+              vec: 'publicKey';
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'stubOracle';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'owner';
+            type: 'publicKey';
+          },
+          {
+            name: 'mint';
+            type: 'publicKey';
+          },
+          {
+            name: 'price';
+            type: 'f64';
+          },
+          {
+            name: 'lastUpdateTs';
+            type: 'i64';
+          },
+          {
+            name: 'lastUpdateSlot';
+            type: 'u64';
+          },
+          {
+            name: 'deviation';
+            type: 'f64';
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 104];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'bookSide';
+      docs: [
+        'This is the layout of book sides with the default capacity. Book sides of any capacity',
+        'are used through [`DynBookSide`], which this derefs to.',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'roots';
+            type: {
+              array: [
+                {
+                  defined: 'OrderTreeRoot';
+                },
+                2,
+              ];
+            };
+          },
+          {
+            name: 'reservedRoots';
+            type: {
+              array: [
+                {
+                  defined: 'OrderTreeRoot';
+                },
+                4,
+              ];
+            };
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 256];
+            };
+          },
+          {
+            name: 'nodes';
+            type: {
+              defined: 'OrderTreeNodes';
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'eventHeap';
+      docs: [
+        'Container for the different EventTypes.',
+        '',
+        'Events are stored in a fixed-array of nodes. Free nodes are connected by a single-linked list',
+        'starting at free_head while used nodes form a circular doubly-linked list starting at',
+        'used_head.',
+        '',
+        'This is the layout of event heaps with the default capacity. Event heaps of any capacity',
+        'are used through [`DynEventHeap`], which this derefs to.',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'header';
+            type: {
+              defined: 'EventHeapHeader';
+            };
+          },
+          {
+            name: 'nodes';
+            type: {
+              array: [
+                {
+                  defined: 'EventNode';
+                },
+                600,
+              ];
+            };
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 64];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'poolState';
+      docs: [
+        'The pool state',
+        '',
+        'PDA of `[POOL_SEED, config, token_mint_0, token_mint_1]`',
+        '',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'bump';
+            docs: ['Bump to identify PDA'];
+            type: {
+              array: ['u8', 1];
+            };
+          },
+          {
+            name: 'ammConfig';
+            type: 'publicKey';
+          },
+          {
+            name: 'owner';
+            type: 'publicKey';
+          },
+          {
+            name: 'tokenMint0';
+            docs: [
+              'Token pair of the pool, where token_mint_0 address < token_mint_1 address',
+            ];
+            type: 'publicKey';
+          },
+          {
+            name: 'tokenMint1';
+            type: 'publicKey';
+          },
+          {
+            name: 'tokenVault0';
+            docs: ['Token pair vault'];
+            type: 'publicKey';
+          },
+          {
+            name: 'tokenVault1';
+            type: 'publicKey';
+          },
+          {
+            name: 'observationKey';
+            docs: ['observation account key'];
+            type: 'publicKey';
+          },
+          {
+            name: 'mintDecimals0';
+            docs: ['mint0 and mint1 decimals'];
+            type: 'u8';
+          },
+          {
+            name: 'mintDecimals1';
+            type: 'u8';
+          },
+          {
+            name: 'tickSpacing';
+            docs: ['The minimum number of ticks between initialized ticks'];
+            type: 'u16';
+          },
+          {
+            name: 'liquidity';
+            docs: ['The currently in range liquidity available to the pool.'];
+            type: 'u128';
+          },
+          {
+            name: 'sqrtPriceX64';
+            docs: [
+              'The current price of the pool as a sqrt(token_1/token_0) Q64.64 value',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'tickCurrent';
+            docs: [
+              'The current tick of the pool, i.e. according to the last tick transition that was run.',
+            ];
+            type: 'i32';
+          },
+          {
+            name: 'observationIndex';
+            docs: ['the most-recently updated index of the observations array'];
+            type: 'u16';
+          },
+          {
+            name: 'observationUpdateDuration';
+            type: 'u16';
+          },
+          {
+            name: 'feeGrowthGlobal0X64';
+            docs: [
+              'The fee growth as a Q64.64 number, i.e. fees of token_0 and token_1 collected per',
+              'unit of liquidity for the entire life of the pool.',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'feeGrowthGlobal1X64';
+            type: 'u128';
+          },
+          {
+            name: 'protocolFeesToken0';
+            docs: [
+              'The amounts of token_0 and token_1 that are owed to the protocol.',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'protocolFeesToken1';
+            type: 'u64';
+          },
+          {
+            name: 'swapInAmountToken0';
+            docs: ['The amounts in and out of swap token_0 and token_1'];
+            type: 'u128';
+          },
+          {
+            name: 'swapOutAmountToken1';
+            type: 'u128';
+          },
+          {
+            name: 'swapInAmountToken1';
+            type: 'u128';
+          },
+          {
+            name: 'swapOutAmountToken0';
+            type: 'u128';
+          },
+          {
+            name: 'status';
+            docs: [
+              'Bitwise representation of the state of the pool',
+              'bit0, 1: disable open position and increase liquidity, 0: normal',
+              'bit1, 1: disable decrease liquidity, 0: normal',
+              'bit2, 1: disable collect fee, 0: normal',
+              'bit3, 1: disable collect reward, 0: normal',
+              'bit4, 1: disable swap, 0: normal',
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            docs: ['Leave blank for future use'];
+            type: {
+              array: ['u8', 7];
+            };
+          },
+          {
+            name: 'rewardInfos';
+            type: {
+              array: [
+                {
+                  defined: 'RewardInfo';
+                },
+                3,
+              ];
+            };
+          },
+          {
+            name: 'tickArrayBitmap';
+            docs: ['Packed initialized tick array state'];
+            type: {
+              array: ['u64', 16];
+            };
+          },
+          {
+            name: 'totalFeesToken0';
+            docs: ['except protocol_fee and fund_fee'];
+            type: 'u64';
+          },
+          {
+            name: 'totalFeesClaimedToken0';
+            docs: ['except protocol_fee and fund_fee'];
+            type: 'u64';
+          },
+          {
+            name: 'totalFeesToken1';
+            type: 'u64';
+          },
+          {
+            name: 'totalFeesClaimedToken1';
+            type: 'u64';
+          },
+          {
+            name: 'fundFeesToken0';
+            type: 'u64';
+          },
+          {
+            name: 'fundFeesToken1';
+            type: 'u64';
+          },
+          {
+            name: 'openTime';
+            type: 'u64';
+          },
+          {
+            name: 'padding1';
+            type: {
+              array: ['u64', 25];
+            };
+          },
+          {
+            name: 'padding2';
+            type: {
+              array: ['u64', 32];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'referrer';
+      docs: [
+        "A referrer that open orders accounts can bind to. It's global if `market` is the",
+        'default pubkey, otherwise it can only be bound on that market.',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'bump';
+            type: 'u8';
+          },
+          {
+            name: 'authority';
+            type: 'publicKey';
+          },
+          {
+            name: 'market';
+            type: 'publicKey';
+          },
+          {
+            name: 'shareBps';
+            docs: [
+              'Share of the taker fees of referred accounts, after maker rebates, in basis points.',
+              "Capped by [`Market::max_referrer_share_bps`], which is 0 until the market's",
+              '`collect_fee_admin` sets it: referrers earn nothing on a market before that.',
+            ];
+            type: 'u16';
+          },
+          {
+            name: 'referredAccounts';
+            docs: ['Open orders accounts currently bound to the referrer'];
+            type: 'u32';
+          },
+          {
+            name: 'referredTakerVolume';
+            docs: [
+              'Taker volume in quote native of the referred accounts whose rebates were claimed',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'rebatesClaimed';
+            docs: ['Rebates in quote native claimed so far'];
+            type: 'u128';
+          },
+        ];
+      };
+    },
+    {
+      name: 'openOrdersReferral';
+      docs: [
+        'Referrer an open orders account is bound to, a PDA of the open orders account',
+        '',
+        "The referrer's share of the taker fees is set aside here when the account takes, and",
+        'claimed by the referrer with `claim_referrer_rebates`.',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'openOrdersAccount';
+            type: 'publicKey';
+          },
+          {
+            name: 'referrer';
+            type: 'publicKey';
+          },
+          {
+            name: 'shareBps';
+            docs: ['`Referrer::share_bps` when binding'];
+            type: 'u16';
+          },
+          {
+            name: 'bump';
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 5];
+            };
+          },
+          {
+            name: 'rebatesAvailable';
+            docs: ['Rebates in quote native not claimed by the referrer yet'];
+            type: 'u64';
+          },
+          {
+            name: 'referredTakerVolume';
+            docs: ['Taker volume in quote native since binding'];
+            type: 'u64';
+          },
+          {
+            name: 'referredTakerVolumeClaimed';
+            docs: [
+              "Part of `referred_taker_volume` already added to the referrer's stats",
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 64];
+            };
+          },
+        ];
+      };
+    },
+  ];
+  types: [
+    {
+      name: 'NonZeroPubkeyOption';
+      docs: [
+        'Like `Option`, but implemented for `Pubkey` to be used with `zero_copy`',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'key';
+            type: 'publicKey';
+          },
+        ];
+      };
+    },
+    {
+      name: 'DelegatePermissions';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'scope';
+            docs: ['See [`DelegateScope`]'];
+            type: 'u8';
+          },
+          {
+            name: 'sideRestricted';
+            docs: ['If set, the delegate can only place orders on `side`'];
+            type: 'u8';
+          },
+          {
+            name: 'side';
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 5];
+            };
+          },
+          {
+            name: 'maxQuoteNativePerOrder';
+            docs: [
+              'Max value in quote native of a delegate order, 0 if unlimited',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'expiryTimestamp';
+            docs: [
+              'Timestamp after which the delegate is ignored, 0 if it never expires',
+            ];
+            type: 'u64';
+          },
+        ];
+      };
+    },
+    {
+      name: 'DelegateEntry';
+      docs: ['Entry of the delegate list, free if `delegate` is none'];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'delegate';
+            type: {
+              defined: 'NonZeroPubkeyOption';
+            };
+          },
+          {
+            name: 'permissions';
+            type: {
+              defined: 'DelegatePermissions';
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'FeeSplitDestination';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'tokenAccount';
+            docs: ['Quote token account receiving the share'];
+            type: 'publicKey';
+          },
+          {
+            name: 'shareBps';
+            docs: ['Share of the swept fees, in basis points'];
+            type: 'u16';
+          },
+        ];
+      };
+    },
+    {
+      name: 'Position';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'bidsBaseLots';
+            docs: ['Base lots in open bids'];
+            type: 'i64';
+          },
+          {
+            name: 'asksBaseLots';
+            docs: ['Base lots in open asks'];
+            type: 'i64';
+          },
+          {
+            name: 'baseFreeNative';
+            type: 'u64';
+          },
+          {
+            name: 'quoteFreeNative';
+            type: 'u64';
+          },
+          {
+            name: 'lockedMakerFees';
+            type: 'u64';
+          },
+          {
+            name: 'referrerRebatesAvailable';
+            type: 'u64';
+          },
+          {
+            name: 'penaltyHeapCount';
+            docs: [
+              'Count of ixs when events are added to the heap since the last settle',
+              'To avoid this, send remaining accounts in order to process the events',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'makerVolume';
+            docs: [
+              'Cumulative maker volume in quote native units (display only)',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'takerVolume';
+            docs: [
+              'Cumulative taker volume in quote native units (display only)',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'bidsQuoteLots';
+            docs: ['Quote lots in open bids'];
+            type: 'i64';
+          },
+          {
+            name: 'heartbeatDeadline';
+            docs: [
+              'Timestamp after which resting orders of this account are treated as invalid,',
+              '0 if the heartbeat is disabled',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'mmpBaseLotsLimit';
+            docs: [
+              'Market maker protection: freeze once this many base lots were filled as maker',
+              'within the window, 0 to ignore',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'mmpWindowBaseLots';
+            docs: ['Base lots filled as maker in the current window'];
+            type: 'i64';
+          },
+          {
+            name: 'mmpWindowStart';
+            docs: ['Start timestamp of the current window'];
+            type: 'u64';
+          },
+          {
+            name: 'mmpWindowSecs';
+            docs: [
+              'Length of the window in seconds, 0 if market maker protection is disabled',
+            ];
+            type: 'u16';
+          },
+          {
+            name: 'mmpFillCountLimit';
+            docs: [
+              'Freeze once this many maker fills happened within the window, 0 to ignore',
+            ];
+            type: 'u16';
+          },
+          {
+            name: 'mmpWindowFillCount';
+            docs: ['Maker fills in the current window'];
+            type: 'u16';
+          },
+          {
+            name: 'mmpFrozen';
+            docs: [
+              'While frozen, the resting orders of this account are treated as invalid',
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'mmpPadding';
+            type: 'u8';
+          },
+          {
+            name: 'penaltyLamportsOwed';
+            docs: [
+              'Penalty lamports owed for the heap additions, priced when the events were added',
+              'and paid on settle',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 16];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'OpenOrder';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'id';
+            type: 'u128';
+          },
+          {
+            name: 'clientId';
+            type: 'u64';
+          },
+          {
+            name: 'lockedPrice';
+            docs: ["Price at which user's assets were locked"];
+            type: 'i64';
+          },
+          {
+            name: 'isFree';
+            type: 'u8';
+          },
+          {
+            name: 'sideAndTree';
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 6];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'OracleConfig';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'confFilter';
+            type: 'f64';
+          },
+          {
+            name: 'maxStalenessSlots';
+            type: 'i64';
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 72];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'OracleConfigParams';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'confFilter';
+            type: 'f32';
+          },
+          {
+            name: 'maxStalenessSlots';
+            type: {
+              option: 'u32';
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'EventHeapHeader';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'freeHead';
+            type: 'u16';
+          },
+          {
+            name: 'usedHead';
+            type: 'u16';
+          },
+          {
+            name: 'count';
+            type: 'u16';
+          },
+          {
+            name: 'capacity';
+            docs: ['Number of nodes, [`MAX_NUM_EVENTS`] = 0'];
+            type: 'u16';
+          },
+          {
+            name: 'seqNum';
+            type: 'u64';
+          },
+        ];
+      };
+    },
+    {
+      name: 'EventNode';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'next';
+            type: 'u16';
+          },
+          {
+            name: 'prev';
+            type: 'u16';
+          },
+          {
+            name: 'pad';
+            type: {
+              array: ['u8', 4];
+            };
+          },
+          {
+            name: 'event';
+            type: {
+              defined: 'AnyEvent';
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'AnyEvent';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'eventType';
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 143];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'FillEvent';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'eventType';
+            type: 'u8';
+          },
+          {
+            name: 'takerSide';
+            type: 'u8';
+          },
+          {
+            name: 'makerOut';
+            type: 'u8';
+          },
+          {
+            name: 'makerSlot';
+            type: 'u8';
+          },
+          {
+            name: 'auction';
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 3];
+            };
+          },
+          {
+            name: 'timestamp';
+            type: 'u64';
+          },
+          {
+            name: 'marketSeqNum';
+            type: 'u64';
+          },
+          {
+            name: 'maker';
+            type: 'publicKey';
+          },
+          {
+            name: 'makerTimestamp';
+            type: 'u64';
+          },
+          {
+            name: 'taker';
+            type: 'publicKey';
+          },
+          {
+            name: 'takerClientOrderId';
+            type: 'u64';
+          },
+          {
+            name: 'price';
+            type: 'i64';
+          },
+          {
+            name: 'pegLimit';
+            type: 'i64';
+          },
+          {
+            name: 'quantity';
+            type: 'i64';
+          },
+          {
+            name: 'makerClientOrderId';
+            type: 'u64';
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 8];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'OutEvent';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'eventType';
+            type: 'u8';
+          },
+          {
+            name: 'side';
+            type: 'u8';
+          },
+          {
+            name: 'ownerSlot';
+            type: 'u8';
+          },
+          {
+            name: 'padding0';
+            type: {
+              array: ['u8', 5];
+            };
+          },
+          {
+            name: 'timestamp';
+            type: 'u64';
+          },
+          {
+            name: 'seqNum';
+            type: 'u64';
+          },
+          {
+            name: 'owner';
+            type: 'publicKey';
+          },
+          {
+            name: 'quantity';
+            type: 'i64';
+          },
+          {
+            name: 'padding1';
+            type: {
+              array: ['u8', 80];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'InnerNode';
+      docs: [
+        'InnerNodes and LeafNodes compose the binary tree of orders.',
+        '',
+        'Each InnerNode has exactly two children, which are either InnerNodes themselves,',
+        'or LeafNodes. The children share the top `prefix_len` bits of `key`. The left',
+        'child has a 0 in the next bit, and the right a 1.',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'tag';
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 3];
+            };
+          },
+          {
+            name: 'prefixLen';
+            docs: [
+              'number of highest `key` bits that all children share',
+              "e.g. if it's 2, the two highest bits of `key` will be the same on all children",
+            ];
+            type: 'u32';
+          },
+          {
+            name: 'key';
+            docs: ['only the top `prefix_len` bits of `key` are relevant'];
+            type: 'u128';
+          },
+          {
+            name: 'children';
+            docs: ['indexes into `BookSide::nodes`'];
+            type: {
+              array: ['u32', 2];
+            };
+          },
+          {
+            name: 'childEarliestExpiry';
+            docs: [
+              'The earliest expiry timestamp for the left and right subtrees.',
+              '',
+              'Needed to be able to find and remove expired orders without having to',
+              'iterate through the whole bookside.',
+            ];
+            type: {
+              array: ['u64', 2];
+            };
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 40];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'LeafNode';
+      docs: ['LeafNodes represent an order in the binary tree'];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'tag';
+            docs: ['NodeTag'];
+            type: 'u8';
+          },
+          {
+            name: 'ownerSlot';
+            docs: ["Index into the owning OpenOrdersAccount's OpenOrders"];
+            type: 'u8';
+          },
+          {
+            name: 'timeInForce';
+            docs: [
+              'Time in seconds after `timestamp` at which the order expires.',
+              'A value of 0 means no expiry.',
+            ];
+            type: 'u16';
+          },
+          {
+            name: 'heartbeatDeadline';
+            docs: [
+              'Unix timestamp after which the order is invalid because the owner stopped',
+              'sending heartbeats. 0 if the owner has no heartbeat configured.',
+            ];
+            type: 'u32';
+          },
+          {
+            name: 'key';
+            docs: ['The binary tree key, see new_node_key()'];
+            type: 'u128';
+          },
+          {
+            name: 'owner';
+            docs: ['Address of the owning OpenOrdersAccount'];
+            type: 'publicKey';
+          },
+          {
+            name: 'quantity';
+            docs: ['Number of base lots to buy or sell, always >=1'];
+            type: 'i64';
+          },
+          {
+            name: 'timestamp';
+            docs: [
+              'The time the order was placed',
+              '',
+              'A u64 in earlier versions of the layout. Its upper bytes, always zero for past',
+              'timestamps, now hold `mmp_enabled` and padding, so decoders must read a u32.',
+            ];
+            type: 'u32';
+          },
+          {
+            name: 'mmpEnabled';
+            docs: [
+              '1 if the owner has market maker protection enabled. Fills against such orders',
+              "are counted while matching, so the owner's account must be passed.",
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 3];
+            };
+          },
+          {
+            name: 'pegLimit';
+            docs: [
+              'If the effective price of an oracle pegged order exceeds this limit,',
+              'it will be considered invalid and may be removed.',
+              '',
+              'Only applicable in the oracle_pegged OrderTree',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'clientOrderId';
+            docs: ['User defined id for this order, used in FillEvents'];
+            type: 'u64';
+          },
+        ];
+      };
+    },
+    {
+      name: 'AnyNode';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'tag';
+            type: 'u8';
+          },
+          {
+            name: 'data';
+            type: {
+              array: ['u8', 79];
+            };
+          },
+          {
+            name: 'forceAlign';
+            type: 'u64';
+          },
+        ];
+      };
+    },
+    {
+      name: 'OrderTreeRoot';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'maybeNode';
+            type: 'u32';
+          },
+          {
+            name: 'leafCount';
+            type: 'u32';
+          },
+        ];
+      };
+    },
+    {
+      name: 'OrderTreeNodes';
+      docs: [
+        'A binary tree on AnyNode::key()',
+        '',
+        'The key encodes the price in the top 64 bits.',
+        '',
+        'This is the layout of order trees with the default capacity. Order trees of any capacity',
+        'are used through [`DynOrderTreeNodes`], which this derefs to.',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'orderTreeType';
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 3];
+            };
+          },
+          {
+            name: 'bumpIndex';
+            type: 'u32';
+          },
+          {
+            name: 'freeListLen';
+            type: 'u32';
+          },
+          {
+            name: 'freeListHead';
+            type: 'u32';
+          },
+          {
+            name: 'capacity';
+            docs: ['Number of nodes, [`MAX_ORDERTREE_NODES`] = 0'];
+            type: 'u32';
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 508];
+            };
+          },
+          {
+            name: 'nodes';
+            type: {
+              array: [
+                {
+                  defined: 'AnyNode';
+                },
+                1024,
+              ];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'RewardInfo';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'rewardState';
+            docs: ['Reward state'];
+            type: 'u8';
+          },
+          {
+            name: 'openTime';
+            docs: ['Reward open time'];
+            type: 'u64';
+          },
+          {
+            name: 'endTime';
+            docs: ['Reward end time'];
+            type: 'u64';
+          },
+          {
+            name: 'lastUpdateTime';
+            docs: ['Reward last update time'];
+            type: 'u64';
+          },
+          {
+            name: 'emissionsPerSecondX64';
+            docs: [
+              'Q64.64 number indicates how many tokens per second are earned per unit of liquidity.',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'rewardTotalEmissioned';
+            docs: ['The total amount of reward emissioned'];
+            type: 'u64';
+          },
+          {
+            name: 'rewardClaimed';
+            docs: ['The total amount of claimed reward'];
+            type: 'u64';
+          },
+          {
+            name: 'tokenMint';
+            docs: ['Reward token mint.'];
+            type: 'publicKey';
+          },
+          {
+            name: 'tokenVault';
+            docs: ['Reward vault token account.'];
+            type: 'publicKey';
+          },
+          {
+            name: 'authority';
+            docs: ['The owner that has permission to set reward param'];
+            type: 'publicKey';
+          },
+          {
+            name: 'rewardGrowthGlobalX64';
+            docs: [
+              'Q64.64 number that tracks the total tokens earned per unit of liquidity since the reward',
+              'emissions were turned on.',
+            ];
+            type: 'u128';
+          },
+        ];
+      };
+    },
+    {
+      name: 'I80F48';
+      docs: [
+        'Nothing in Rust shall use these types. They only exist so that the Anchor IDL',
+        'knows about them and typescript can deserialize it.',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'val';
+            type: 'i128';
+          },
+        ];
+      };
+    },
+    {
+      name: 'PlaceOrderArgs';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'side';
+            type: {
+              defined: 'Side';
+            };
+          },
+          {
+            name: 'priceLots';
+            type: 'i64';
+          },
+          {
+            name: 'maxBaseLots';
+            type: 'i64';
+          },
+          {
+            name: 'maxQuoteLotsIncludingFees';
+            type: 'i64';
+          },
+          {
+            name: 'clientOrderId';
+            type: 'u64';
+          },
+          {
+            name: 'orderType';
+            type: {
+              defined: 'PlaceOrderType';
+            };
+          },
+          {
+            name: 'expiryTimestamp';
+            type: 'u64';
+          },
+          {
+            name: 'selfTradeBehavior';
+            type: {
+              defined: 'SelfTradeBehavior';
+            };
+          },
+          {
+            name: 'limit';
+            type: 'u8';
+          },
+        ];
+      };
+    },
+    {
+      name: 'PlaceMultipleOrdersArgs';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'priceLots';
+            type: 'i64';
+          },
+          {
+            name: 'maxQuoteLotsIncludingFees';
+            type: 'i64';
+          },
+          {
+            name: 'expiryTimestamp';
+            type: 'u64';
+          },
+        ];
+      };
+    },
+    {
+      name: 'PlaceOrderPeggedArgs';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'side';
+            type: {
+              defined: 'Side';
+            };
+          },
+          {
+            name: 'priceOffsetLots';
+            type: 'i64';
+          },
+          {
+            name: 'pegLimit';
+            type: 'i64';
+          },
+          {
+            name: 'maxBaseLots';
+            type: 'i64';
+          },
+          {
+            name: 'maxQuoteLotsIncludingFees';
+            type: 'i64';
+          },
+          {
+            name: 'clientOrderId';
+            type: 'u64';
+          },
+          {
+            name: 'orderType';
+            type: {
+              defined: 'PlaceOrderType';
+            };
+          },
+          {
+            name: 'expiryTimestamp';
+            type: 'u64';
+          },
+          {
+            name: 'selfTradeBehavior';
+            type: {
+              defined: 'SelfTradeBehavior';
+            };
+          },
+          {
+            name: 'limit';
+            type: 'u8';
+          },
+        ];
+      };
+    },
+    {
+      name: 'PlaceTakeOrderArgs';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'side';
+            type: {
+              defined: 'Side';
+            };
+          },
+          {
+            name: 'priceLots';
+            type: 'i64';
+          },
+          {
+            name: 'maxBaseLots';
+            type: 'i64';
+          },
+          {
+            name: 'maxQuoteLotsIncludingFees';
+            type: 'i64';
+          },
+          {
+            name: 'orderType';
+            type: {
+              defined: 'PlaceOrderType';
+            };
+          },
+          {
+            name: 'limit';
+            type: 'u8';
+          },
+        ];
+      };
+    },
+    {
+      name: 'AccessGate';
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'None';
+          },
+          {
+            name: 'Allowlist';
+          },
+          {
+            name: 'CredentialToken';
+          },
+        ];
+      };
+    },
+    {
+      name: 'OpenOrdersAccess';
+      docs: [
+        'Whether an open orders account passed the access gate of its market',
+      ];
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Unchecked';
+          },
+          {
+            name: 'Granted';
+          },
+          {
+            name: 'Revoked';
+          },
+        ];
+      };
+    },
+    {
+      name: 'DelegateScope';
+      docs: [
+        'What a delegate is allowed to do. A delegate can never change the delegates or',
+        'close the account.',
+      ];
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Full';
+          },
+          {
+            name: 'Trade';
+          },
+          {
+            name: 'Cancel';
+          },
+          {
+            name: 'Settle';
+          },
+        ];
+      };
+    },
+    {
+      name: 'MarketStatus';
+      docs: [
+        'Reversible restriction of the order instructions, set by the',
+        '[`market_status_admin`](Market::market_status_admin)',
+      ];
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Active';
+          },
+          {
+            name: 'PostOnly';
+          },
+          {
+            name: 'CancelOnly';
+          },
+          {
+            name: 'Halted';
+          },
+        ];
+      };
+    },
+    {
+      name: 'OracleType';
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Pyth';
+          },
+          {
+            name: 'Stub';
+          },
+          {
+            name: 'SwitchboardV1';
+          },
+          {
+            name: 'SwitchboardV2';
+          },
+          {
+            name: 'RaydiumCLMM';
+          },
+        ];
+      };
+    },
+    {
+      name: 'OrderState';
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Valid';
+          },
+          {
+            name: 'Invalid';
+          },
+          {
+            name: 'Skipped';
+          },
+        ];
+      };
+    },
+    {
+      name: 'BookSideOrderTree';
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Fixed';
+          },
+          {
+            name: 'OraclePegged';
+          },
+        ];
+      };
+    },
+    {
+      name: 'EventType';
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Fill';
+          },
+          {
+            name: 'Out';
+          },
+        ];
+      };
+    },
+    {
+      name: 'NodeTag';
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Uninitialized';
+          },
+          {
+            name: 'InnerNode';
+          },
+          {
+            name: 'LeafNode';
+          },
+          {
+            name: 'FreeNode';
+          },
+          {
+            name: 'LastFreeNode';
+          },
+        ];
+      };
+    },
+    {
+      name: 'PlaceOrderType';
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Limit';
+          },
+          {
+            name: 'ImmediateOrCancel';
+          },
+          {
+            name: 'PostOnly';
+          },
+          {
+            name: 'Market';
+          },
+          {
+            name: 'PostOnlySlide';
+          },
+          {
+            name: 'FillOrKill';
+          },
+        ];
+      };
+    },
+    {
+      name: 'PostOrderType';
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Limit';
+          },
+          {
+            name: 'PostOnly';
+          },
+          {
+            name: 'PostOnlySlide';
+          },
+        ];
+      };
+    },
+    {
+      name: 'SelfTradeBehavior';
+      docs: [
+        'Self trade behavior controls how taker orders interact with resting limit orders of the same account.',
+        'This setting has no influence on placing a resting or oracle pegged limit order that does not match',
+        "immediately, instead it's the responsibility of the user to correctly configure his taker orders.",
+      ];
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'DecrementTake';
+          },
+          {
+            name: 'CancelProvide';
+          },
+          {
+            name: 'AbortTransaction';
+          },
+        ];
+      };
+    },
+    {
+      name: 'Side';
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Bid';
+          },
+          {
+            name: 'Ask';
+          },
+        ];
+      };
+    },
+    {
+      name: 'SideAndOrderTree';
+      docs: [
+        "SideAndOrderTree is a storage optimization, so we don't need two bytes for the data",
+      ];
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'BidFixed';
+          },
+          {
+            name: 'AskFixed';
+          },
+          {
+            name: 'BidOraclePegged';
+          },
+          {
+            name: 'AskOraclePegged';
+          },
+        ];
+      };
+    },
+    {
+      name: 'OrderParams';
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Market';
+          },
+          {
+            name: 'ImmediateOrCancel';
+            fields: [
+              {
+                name: 'price_lots';
+                type: 'i64';
+              },
+            ];
+          },
+          {
+            name: 'Fixed';
+            fields: [
+              {
+                name: 'price_lots';
+                type: 'i64';
+              },
+              {
+                name: 'order_type';
+                type: {
+                  defined: 'PostOrderType';
+                };
+              },
+            ];
+          },
+          {
+            name: 'OraclePegged';
+            fields: [
+              {
+                name: 'price_offset_lots';
+                type: 'i64';
+              },
+              {
+                name: 'order_type';
+                type: {
+                  defined: 'PostOrderType';
+                };
+              },
+              {
+                name: 'peg_limit';
+                type: 'i64';
+              },
+            ];
+          },
+          {
+            name: 'FillOrKill';
+            fields: [
+              {
+                name: 'price_lots';
+                type: 'i64';
+              },
+            ];
+          },
+        ];
+      };
+    },
+    {
+      name: 'OrderTreeType';
+      type: {
+        kind: 'enum';
+        variants: [
+          {
+            name: 'Bids';
+          },
+          {
+            name: 'Asks';
+          },
+        ];
+      };
+    },
+  ];
+  events: [
+    {
+      name: 'DepositLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'signer';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'baseAmount';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'quoteAmount';
+          type: 'u64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'FillLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'takerSide';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'makerSlot';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'makerOut';
+          type: 'bool';
+          index: false;
+        },
+        {
+          name: 'timestamp';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'seqNum';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'maker';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'makerClientOrderId';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'makerFee';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'makerTimestamp';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'taker';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'takerClientOrderId';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'takerFeeCeil';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'price';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'quantity';
+          type: 'i64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'TakerSignatureLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'seqNum';
+          type: 'u64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'MarketMetaDataLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'name';
+          type: 'string';
+          index: false;
+        },
+        {
+          name: 'baseMint';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'quoteMint';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'baseDecimals';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'quoteDecimals';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'baseLotSize';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'quoteLotSize';
+          type: 'i64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'TotalOrderFillEvent';
+      fields: [
+        {
+          name: 'side';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'taker';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'totalQuantityPaid';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'totalQuantityReceived';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'fees';
+          type: 'u64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SetDelegateLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'delegate';
+          type: {
+            option: 'publicKey';
+          };
+          index: false;
+        },
+        {
+          name: 'scope';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'allowedSide';
+          type: {
+            option: 'u8';
+          };
+          index: false;
+        },
+        {
+          name: 'maxQuoteNativePerOrder';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'expiryTimestamp';
+          type: 'u64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'AddDelegateLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'delegate';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'scope';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'allowedSide';
+          type: {
+            option: 'u8';
+          };
+          index: false;
+        },
+        {
+          name: 'maxQuoteNativePerOrder';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'expiryTimestamp';
+          type: 'u64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'RemoveDelegateLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'delegate';
+          type: 'publicKey';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'HeartbeatLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'deadline';
+          type: 'u64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SetMmpLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'windowSecs';
+          type: 'u16';
+          index: false;
+        },
+        {
+          name: 'baseLotsLimit';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'fillCountLimit';
+          type: 'u16';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'MmpTriggeredLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'windowStart';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'windowBaseLots';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'windowFillCount';
+          type: 'u16';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'MmpResetLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'RunAuctionLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'priceLots';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'baseLots';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'completed';
+          type: 'bool';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SettleFundsLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'baseNative';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'quoteNative';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'referrerRebate';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'referrer';
+          type: {
+            option: 'publicKey';
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SweepFeesLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'amount';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'receiver';
+          type: 'publicKey';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'ReferrerLog';
+      fields: [
+        {
+          name: 'referrer';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'authority';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'shareBps';
+          type: 'u16';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'AccessGateLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'admin';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'kind';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'credentialMint';
+          type: 'publicKey';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'AccessPassLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'owner';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'allowed';
+          type: 'bool';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'OpenOrdersAccessLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'access';
+          type: 'u8';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'BindReferrerLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'referrer';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'shareBps';
+          type: 'u16';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'ClaimReferrerRebatesLog';
+      fields: [
+        {
+          name: 'referrer';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'amount';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'referredTakerVolume';
+          type: 'u64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'FeeSplitLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'destinations';
+          type: {
+            vec: {
+              defined: 'FeeSplitDestination';
+            };
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'OpenOrdersPositionLog';
+      fields: [
+        {
+          name: 'owner';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'openOrdersAccountNum';
+          type: 'u32';
+          index: false;
+        },
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'bidsBaseLots';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'bidsQuoteLots';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'asksBaseLots';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'baseFreeNative';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'quoteFreeNative';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'lockedMakerFees';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'referrerRebatesAvailable';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'makerVolume';
+          type: 'u128';
+          index: false;
+        },
+        {
+          name: 'takerVolume';
+          type: 'u128';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'PenaltyLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'penaltyPayer';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'lamports';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'penaltyLamportsAccrued';
+          type: 'u64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'CrankRewardLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'receiver';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'events';
+          type: 'u32';
+          index: false;
+        },
+        {
+          name: 'lamports';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'penaltyLamportsAccrued';
+          type: 'u64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SetCrankRewardsLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'penaltyEventHeap';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'crankRewardPerEvent';
+          type: 'u32';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SetMarketStatusLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'status';
+          type: 'u8';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SetMarketStatusAdminLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'marketStatusAdmin';
+          type: {
+            option: 'publicKey';
+          };
+          index: false;
+        },
+      ];
+    },
+  ];
+  errors: [
+    {
+      code: 6000;
+      name: 'SomeError';
+      msg: '';
+    },
+    {
+      code: 6001;
+      name: 'InvalidInputNameLength';
+      msg: 'Name lenght above limit';
+    },
+    {
+      code: 6002;
+      name: 'InvalidInputMarketExpired';
+      msg: 'Market cannot be created as expired';
+    },
+    {
+      code: 6003;
+      name: 'InvalidInputMarketFees';
+      msg: 'Taker fees should be positive and if maker fees are negative, greater or equal to their abs value';
+    },
+    {
+      code: 6004;
+      name: 'InvalidInputLots';
+      msg: 'Lots cannot be negative';
+    },
+    {
+      code: 6005;
+      name: 'InvalidInputLotsSize';
+      msg: 'Lots size above market limits';
+    },
+    {
+      code: 6006;
+      name: 'InvalidInputOrdersAmounts';
+      msg: 'Input amounts above limits';
+    },
+    {
+      code: 6007;
+      name: 'InvalidInputCancelSize';
+      msg: 'Price lots should be greater than zero';
+    },
+    {
+      code: 6008;
+      name: 'InvalidInputPriceLots';
+      msg: 'Expected cancel size should be greater than zero';
+    },
+    {
+      code: 6009;
+      name: 'InvalidInputPegLimit';
+      msg: 'Peg limit should be greater than zero';
+    },
+    {
+      code: 6010;
+      name: 'InvalidInputOrderType';
+      msg: 'The order type is invalid. A taker order must be Market or ImmediateOrCancel';
+    },
+    {
+      code: 6011;
+      name: 'InvalidInputOrderId';
+      msg: 'Order id cannot be zero';
+    },
+    {
+      code: 6012;
+      name: 'InvalidInputHeapSlots';
+      msg: 'Slot above heap limit';
+    },
+    {
+      code: 6013;
+      name: 'InvalidOracleTypes';
+      msg: 'Cannot combine two oracles of different providers';
+    },
+    {
+      code: 6014;
+      name: 'InvalidSecondOracle';
+      msg: 'Cannot configure secondary oracle without primary';
+    },
+    {
+      code: 6015;
+      name: 'NoCloseMarketAdmin';
+      msg: 'This market does not have a `close_market_admin` and thus cannot be closed.';
+    },
+    {
+      code: 6016;
+      name: 'InvalidCloseMarketAdmin';
+      msg: "The signer of this transaction is not this market's `close_market_admin`.";
+    },
+    {
+      code: 6017;
+      name: 'InvalidOpenOrdersAdmin';
+      msg: 'The `open_orders_admin` required by this market to sign all instructions that creates orders is missing or is not valid';
+    },
+    {
+      code: 6018;
+      name: 'InvalidConsumeEventsAdmin';
+      msg: 'The `consume_events_admin` required by this market to sign all instructions that consume events is missing or is not valid';
+    },
+    {
+      code: 6019;
+      name: 'InvalidMarketVault';
+      msg: 'Provided `market_vault` is invalid';
+    },
+    {
+      code: 6020;
+      name: 'IndexerActiveOO';
+      msg: 'Cannot be closed due to the existence of open orders accounts';
+    },
+    {
+      code: 6021;
+      name: 'OraclePegInvalidOracleState';
+      msg: 'Cannot place a peg order due to invalid oracle state';
+    },
+    {
+      code: 6022;
+      name: 'UnknownOracleType';
+      msg: 'oracle type cannot be determined';
     },
     {
-      name: 'openOrdersIndexer';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'bump';
-            type: 'u8';
-          },
-          {
-            name: 'createdCounter';
-            type: 'u32';
-          },
-          {
-            name: 'addresses';
-            type: {
-              vec: 'publicKey';
-            };
-          },
-        ];
-      };
+      code: 6023;
+      name: 'OracleConfidence';
+      msg: 'an oracle does not reach the confidence threshold';
     },
     {
-      name: 'stubOracle';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'owner';
-            type: 'publicKey';
-          },
-          {
-            name: 'mint';
-            type: 'publicKey';
-          },
-          {
-            name: 'price';
-            type: 'f64';
-          },
-          {
-            name: 'lastUpdateTs';
-            type: 'i64';
-          },
-          {
-            name: 'lastUpdateSlot';
-            type: 'u64';
-          },
-          {
-            name: 'deviation';
-            type: 'f64';
-          },
-          {
-            name: 'reserved';
-            type: {
-              array: ['u8', 104];
-            };
-          },
-        ];
-      };
+      code: 6024;
+      name: 'OracleStale';
+      msg: 'an oracle is stale';
     },
     {
-      name: 'bookSide';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'roots';
-            type: {
-              array: [
-                {
-                  defined: 'OrderTreeRoot';
-                },
-                2,
-              ];
-            };
-          },
-          {
-            name: 'reservedRoots';
-            type: {
-              array: [
-                {
-                  defined: 'OrderTreeRoot';
-                },
-                4,
-              ];
-            };
-          },
-          {
-            name: 'reserved';
-            type: {
-              array: ['u8', 256];
-            };
-          },
-          {
-            name: 'nodes';
-            type: {
-              defined: 'OrderTreeNodes';
-            };
-          },
-        ];
-      };
+      code: 6025;
+      name: 'OrderIdNotFound';
+      msg: 'Order id not found on the orderbook';
     },
     {
-      name: 'eventHeap';
-      docs: [
-        'Container for the different EventTypes.',
-        '',
-        'Events are stored in a fixed-array of nodes. Free nodes are connected by a single-linked list',
-        'starting at free_head while used nodes form a circular doubly-linked list starting at',
-        'used_head.',
-      ];
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'header';
-            type: {
-              defined: 'EventHeapHeader';
-            };
-          },
-          {
-            name: 'nodes';
-            type: {
-              array: [
-                {
-                  defined: 'EventNode';
-                },
-                600,
-              ];
-            };
-          },
-          {
-            name: 'reserved';
-            type: {
-              array: ['u8', 64];
-            };
-          },
-        ];
-      };
+      code: 6026;
+      name: 'EventHeapContainsElements';
+      msg: "Event heap contains elements and market can't be closed";
     },
-  ];
-  types: [
     {
-      name: 'NonZeroPubkeyOption';
-      docs: [
-        'Like `Option`, but implemented for `Pubkey` to be used with `zero_copy`',
-      ];
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'key';
-            type: 'publicKey';
-          },
-        ];
-      };
+      code: 6027;
+      name: 'InvalidOrderPostIOC';
+      msg: 'ImmediateOrCancel is not a PostOrderType';
     },
     {
-      name: 'Position';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'bidsBaseLots';
-            docs: ['Base lots in open bids'];
-            type: 'i64';
-          },
-          {
-            name: 'asksBaseLots';
-            docs: ['Base lots in open asks'];
-            type: 'i64';
-          },
-          {
-            name: 'baseFreeNative';
-            type: 'u64';
-          },
-          {
-            name: 'quoteFreeNative';
-            type: 'u64';
-          },
-          {
-            name: 'lockedMakerFees';
-            type: 'u64';
-          },
-          {
-            name: 'referrerRebatesAvailable';
-            type: 'u64';
-          },
-          {
-            name: 'penaltyHeapCount';
-            docs: [
-              'Count of ixs when events are added to the heap',
-              'To avoid this, send remaining accounts in order to process the events',
-            ];
-            type: 'u64';
-          },
-          {
-            name: 'makerVolume';
-            docs: [
-              'Cumulative maker volume in quote native units (display only)',
-            ];
-            type: 'u128';
-          },
-          {
-            name: 'takerVolume';
-            docs: [
-              'Cumulative taker volume in quote native units (display only)',
-            ];
-            type: 'u128';
-          },
-          {
-            name: 'bidsQuoteLots';
-            docs: ['Quote lots in open bids'];
-            type: 'i64';
-          },
-          {
-            name: 'reserved';
-            type: {
-              array: ['u8', 64];
-            };
-          },
-        ];
-      };
+      code: 6028;
+      name: 'InvalidOrderPostMarket';
+      msg: 'Market is not a PostOrderType';
+    },
+    {
+      code: 6029;
+      name: 'WouldSelfTrade';
+      msg: 'would self trade';
+    },
+    {
+      code: 6030;
+      name: 'MarketHasExpired';
+      msg: 'The Market has already expired.';
+    },
+    {
+      code: 6031;
+      name: 'InvalidPriceLots';
+      msg: 'Price lots should be greater than zero';
+    },
+    {
+      code: 6032;
+      name: 'InvalidOraclePrice';
+      msg: 'Oracle price above market limits';
+    },
+    {
+      code: 6033;
+      name: 'MarketHasNotExpired';
+      msg: 'The Market has not expired yet.';
     },
     {
-      name: 'OpenOrder';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'id';
-            type: 'u128';
-          },
-          {
-            name: 'clientId';
-            type: 'u64';
-          },
-          {
-            name: 'lockedPrice';
-            docs: ["Price at which user's assets were locked"];
-            type: 'i64';
-          },
-          {
-            name: 'isFree';
-            type: 'u8';
-          },
-          {
-            name: 'sideAndTree';
-            type: 'u8';
-          },
-          {
-            name: 'padding';
-            type: {
-              array: ['u8', 6];
-            };
-          },
-        ];
-      };
+      code: 6034;
+      name: 'NoOwnerOrDelegate';
+      msg: 'No correct owner or delegate.';
     },
     {
-      name: 'OracleConfig';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'confFilter';
-            type: 'f64';
-          },
-          {
-            name: 'maxStalenessSlots';
-            type: 'i64';
-          },
-          {
-            name: 'reserved';
-            type: {
-              array: ['u8', 72];
-            };
-          },
-        ];
-      };
+      code: 6035;
+      name: 'NoOwner';
+      msg: 'No correct owner';
     },
     {
-      name: 'OracleConfigParams';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'confFilter';
-            type: 'f32';
-          },
-          {
-            name: 'maxStalenessSlots';
-            type: {
-              option: 'u32';
-            };
-          },
-        ];
-      };
+      code: 6036;
+      name: 'OpenOrdersFull';
+      msg: 'No free order index in open orders account';
     },
     {
-      name: 'EventHeapHeader';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'freeHead';
-            type: 'u16';
-          },
-          {
-            name: 'usedHead';
-            type: 'u16';
-          },
-          {
-            name: 'count';
-            type: 'u16';
-          },
-          {
-            name: 'padd';
-            type: 'u16';
-          },
-          {
-            name: 'seqNum';
-            type: 'u64';
-          },
-        ];
-      };
+      code: 6037;
+      name: 'BookContainsElements';
+      msg: 'Book contains elements';
     },
     {
-      name: 'EventNode';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'next';
-            type: 'u16';
-          },
-          {
-            name: 'prev';
-            type: 'u16';
-          },
-          {
-            name: 'pad';
-            type: {
-              array: ['u8', 4];
-            };
-          },
-          {
-            name: 'event';
-            type: {
-              defined: 'AnyEvent';
-            };
-          },
-        ];
-      };
+      code: 6038;
+      name: 'OpenOrdersOrderNotFound';
+      msg: 'Could not find order in user account';
     },
     {
-      name: 'AnyEvent';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'eventType';
-            type: 'u8';
-          },
-          {
-            name: 'padding';
-            type: {
-              array: ['u8', 143];
-            };
-          },
-        ];
-      };
+      code: 6039;
+      name: 'InvalidPostAmount';
+      msg: 'Amount to post above book limits';
     },
     {
-      name: 'FillEvent';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'eventType';
-            type: 'u8';
-          },
-          {
-            name: 'takerSide';
-            type: 'u8';
-          },
-          {
-            name: 'makerOut';
-            type: 'u8';
-          },
-          {
-            name: 'makerSlot';
-            type: 'u8';
-          },
-          {
-            name: 'padding';
-            type: {
-              array: ['u8', 4];
-            };
-          },
-          {
-            name: 'timestamp';
-            type: 'u64';
-          },
-          {
-            name: 'seqNum';
-            type: 'u64';
-          },
-          {
-            name: 'maker';
-            type: 'publicKey';
-          },
-          {
-            name: 'makerTimestamp';
-            type: 'u64';
-          },
-          {
-            name: 'taker';
-            type: 'publicKey';
-          },
-          {
-            name: 'takerClientOrderId';
-            type: 'u64';
-          },
-          {
-            name: 'price';
-            type: 'i64';
-          },
-          {
-            name: 'pegLimit';
-            type: 'i64';
-          },
-          {
-            name: 'quantity';
-            type: 'i64';
-          },
-          {
-            name: 'makerClientOrderId';
-            type: 'u64';
-          },
-          {
-            name: 'reserved';
-            type: {
-              array: ['u8', 8];
-            };
-          },
-        ];
-      };
+      code: 6040;
+      name: 'DisabledOraclePeg';
+      msg: 'Oracle peg orders are not enabled for this market';
+    },
+    {
+      code: 6041;
+      name: 'NonEmptyMarket';
+      msg: 'Cannot close a non-empty market';
+    },
+    {
+      code: 6042;
+      name: 'NonEmptyOpenOrdersPosition';
+      msg: 'Cannot close a non-empty open orders account';
+    },
+    {
+      code: 6043;
+      name: 'WouldExecutePartially';
+      msg: 'Fill-Or-Kill order would generate a partial execution';
+    },
+    {
+      code: 6044;
+      name: 'HeartbeatNotExpired';
+      msg: 'The heartbeat of the open orders account has not expired';
+    },
+    {
+      code: 6045;
+      name: 'InvalidInputAuction';
+      msg: 'Auction duration must be set for recurring auctions';
+    },
+    {
+      code: 6046;
+      name: 'MarketInAuction';
+      msg: 'Only orders that post to the book are allowed during the auction phase';
+    },
+    {
+      code: 6047;
+      name: 'AuctionNotEnded';
+      msg: 'The market has no auction phase that ended';
     },
     {
-      name: 'OutEvent';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'eventType';
-            type: 'u8';
-          },
-          {
-            name: 'side';
-            type: 'u8';
-          },
-          {
-            name: 'ownerSlot';
-            type: 'u8';
-          },
-          {
-            name: 'padding0';
-            type: {
-              array: ['u8', 5];
-            };
-          },
-          {
-            name: 'timestamp';
-            type: 'u64';
-          },
-          {
-            name: 'seqNum';
-            type: 'u64';
-          },
-          {
-            name: 'owner';
-            type: 'publicKey';
-          },
-          {
-            name: 'quantity';
-            type: 'i64';
-          },
-          {
-            name: 'padding1';
-            type: {
-              array: ['u8', 80];
-            };
-          },
-        ];
-      };
+      code: 6048;
+      name: 'NoMarketStatusAdmin';
+      msg: 'This market does not have a `market_status_admin`.';
     },
     {
-      name: 'InnerNode';
-      docs: [
-        'InnerNodes and LeafNodes compose the binary tree of orders.',
-        '',
-        'Each InnerNode has exactly two children, which are either InnerNodes themselves,',
-        'or LeafNodes. The children share the top `prefix_len` bits of `key`. The left',
-        'child has a 0 in the next bit, and the right a 1.',
-      ];
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'tag';
-            type: 'u8';
-          },
-          {
-            name: 'padding';
-            type: {
-              array: ['u8', 3];
-            };
-          },
-          {
-            name: 'prefixLen';
-            docs: [
-              'number of highest `key` bits that all children share',
-              "e.g. if it's 2, the two highest bits of `key` will be the same on all children",
-            ];
-            type: 'u32';
-          },
-          {
-            name: 'key';
-            docs: ['only the top `prefix_len` bits of `key` are relevant'];
-            type: 'u128';
-          },
-          {
-            name: 'children';
-            docs: ['indexes into `BookSide::nodes`'];
-            type: {
-              array: ['u32', 2];
-            };
-          },
-          {
-            name: 'childEarliestExpiry';
-            docs: [
-              'The earliest expiry timestamp for the left and right subtrees.',
-              '',
-              'Needed to be able to find and remove expired orders without having to',
-              'iterate through the whole bookside.',
-            ];
-            type: {
-              array: ['u64', 2];
-            };
-          },
-          {
-            name: 'reserved';
-            type: {
-              array: ['u8', 40];
-            };
-          },
-        ];
-      };
+      code: 6049;
+      name: 'InvalidMarketStatusAdmin';
+      msg: "The signer of this transaction is not this market's `market_status_admin`.";
     },
     {
-      name: 'LeafNode';
-      docs: ['LeafNodes represent an order in the binary tree'];
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'tag';
-            docs: ['NodeTag'];
-            type: 'u8';
-          },
-          {
-            name: 'ownerSlot';
-            docs: ["Index into the owning OpenOrdersAccount's OpenOrders"];
-            type: 'u8';
-          },
-          {
-            name: 'timeInForce';
-            docs: [
-              'Time in seconds after `timestamp` at which the order expires.',
-              'A value of 0 means no expiry.',
-            ];
-            type: 'u16';
-          },
-          {
-            name: 'padding';
-            type: {
-              array: ['u8', 4];
-            };
-          },
-          {
-            name: 'key';
-            docs: ['The binary tree key, see new_node_key()'];
-            type: 'u128';
-          },
-          {
-            name: 'owner';
-            docs: ['Address of the owning OpenOrdersAccount'];
-            type: 'publicKey';
-          },
-          {
-            name: 'quantity';
-            docs: ['Number of base lots to buy or sell, always >=1'];
-            type: 'i64';
-          },
-          {
-            name: 'timestamp';
-            docs: ['The time the order was placed'];
-            type: 'u64';
-          },
-          {
-            name: 'pegLimit';
-            docs: [
-              'If the effective price of an oracle pegged order exceeds this limit,',
-              'it will be considered invalid and may be removed.',
-              '',
-              'Only applicable in the oracle_pegged OrderTree',
-            ];
-            type: 'i64';
-          },
-          {
-            name: 'clientOrderId';
-            docs: ['User defined id for this order, used in FillEvents'];
-            type: 'u64';
-          },
-        ];
-      };
+      code: 6050;
+      name: 'MarketPostOnly';
+      msg: 'The market only allows post only orders';
     },
     {
-      name: 'AnyNode';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'tag';
-            type: 'u8';
-          },
-          {
-            name: 'data';
-            type: {
-              array: ['u8', 87];
-            };
-          },
-        ];
-      };
+      code: 6051;
+      name: 'MarketCancelOnly';
+      msg: 'The market only allows cancelling orders';
     },
     {
-      name: 'OrderTreeRoot';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'maybeNode';
-            type: 'u32';
-          },
-          {
-            name: 'leafCount';
-            type: 'u32';
-          },
-        ];
-      };
+      code: 6052;
+      name: 'MarketHalted';
+      msg: 'The market is halted';
     },
     {
-      name: 'OrderTreeNodes';
-      docs: [
-        'A binary tree on AnyNode::key()',
-        '',
-        'The key encodes the price in the top 64 bits.',
-      ];
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'orderTreeType';
-            type: 'u8';
-          },
-          {
-            name: 'padding';
-            type: {
-              array: ['u8', 3];
-            };
-          },
-          {
-            name: 'bumpIndex';
-            type: 'u32';
-          },
-          {
-            name: 'freeListLen';
-            type: 'u32';
-          },
-          {
-            name: 'freeListHead';
-            type: 'u32';
-          },
-          {
-            name: 'reserved';
-            type: {
-              array: ['u8', 512];
-            };
-          },
-          {
-            name: 'nodes';
-            type: {
-              array: [
-                {
-                  defined: 'AnyNode';
-                },
-                1024,
-              ];
-            };
-          },
-        ];
-      };
+      code: 6053;
+      name: 'InvalidInputOrderSizeLimits';
+      msg: 'Order size limits must be positive and the maximum at least the minimum';
+    },
+    {
+      code: 6054;
+      name: 'OrderSizeAboveMaximum';
+      msg: 'The order to post is larger than the maximum order size of the market';
+    },
+    {
+      code: 6055;
+      name: 'DelegateSideNotAllowed';
+      msg: 'The delegate is not allowed to place orders on this side';
+    },
+    {
+      code: 6056;
+      name: 'DelegateOrderTooLarge';
+      msg: 'The order is larger than the delegate is allowed to place';
     },
     {
-      name: 'I80F48';
-      docs: [
-        'Nothing in Rust shall use these types. They only exist so that the Anchor IDL',
-        'knows about them and typescript can deserialize it.',
-      ];
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'val';
-            type: 'i128';
-          },
-        ];
-      };
+      code: 6057;
+      name: 'InvalidDelegate';
+      msg: "The owner can't be added as a delegate";
     },
     {
-      name: 'PlaceOrderArgs';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'side';
-            type: {
-              defined: 'Side';
-            };
-          },
-          {
-            name: 'priceLots';
-            type: 'i64';
-          },
-          {
-            name: 'maxBaseLots';
-            type: 'i64';
-          },
-          {
-            name: 'maxQuoteLotsIncludingFees';
-            type: 'i64';
-          },
-          {
-            name: 'clientOrderId';
-            type: 'u64';
-          },
-          {
-            name: 'orderType';
-            type: {
-              defined: 'PlaceOrderType';
-            };
-          },
-          {
-            name: 'expiryTimestamp';
-            type: 'u64';
-          },
-          {
-            name: 'selfTradeBehavior';
-            type: {
-              defined: 'SelfTradeBehavior';
-            };
-          },
-          {
-            name: 'limit';
-            type: 'u8';
-          },
-        ];
-      };
+      code: 6058;
+      name: 'DelegateAlreadyAdded';
+      msg: 'The delegate is already in the delegate list';
     },
     {
-      name: 'PlaceMultipleOrdersArgs';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'priceLots';
-            type: 'i64';
-          },
-          {
-            name: 'maxQuoteLotsIncludingFees';
-            type: 'i64';
-          },
-          {
-            name: 'expiryTimestamp';
-            type: 'u64';
-          },
-        ];
-      };
+      code: 6059;
+      name: 'DelegateListFull';
+      msg: 'The delegate list is full';
     },
     {
-      name: 'PlaceOrderPeggedArgs';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'side';
-            type: {
-              defined: 'Side';
-            };
-          },
-          {
-            name: 'priceOffsetLots';
-            type: 'i64';
-          },
-          {
-            name: 'pegLimit';
-            type: 'i64';
-          },
-          {
-            name: 'maxBaseLots';
-            type: 'i64';
-          },
-          {
-            name: 'maxQuoteLotsIncludingFees';
-            type: 'i64';
-          },
-          {
-            name: 'clientOrderId';
-            type: 'u64';
-          },
-          {
-            name: 'orderType';
-            type: {
-              defined: 'PlaceOrderType';
-            };
-          },
-          {
-            name: 'expiryTimestamp';
-            type: 'u64';
-          },
-          {
-            name: 'selfTradeBehavior';
-            type: {
-              defined: 'SelfTradeBehavior';
-            };
-          },
-          {
-            name: 'limit';
-            type: 'u8';
-          },
-        ];
-      };
+      code: 6060;
+      name: 'DelegateNotFound';
+      msg: 'The delegate is not in the delegate list';
     },
     {
-      name: 'PlaceTakeOrderArgs';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'side';
-            type: {
-              defined: 'Side';
-            };
-          },
-          {
-            name: 'priceLots';
-            type: 'i64';
-          },
-          {
-            name: 'maxBaseLots';
-            type: 'i64';
-          },
-          {
-            name: 'maxQuoteLotsIncludingFees';
-            type: 'i64';
-          },
-          {
-            name: 'orderType';
-            type: {
-              defined: 'PlaceOrderType';
-            };
-          },
-          {
-            name: 'limit';
-            type: 'u8';
-          },
-        ];
-      };
+      code: 6061;
+      name: 'MarketGracePeriodNotOver';
+      msg: 'The grace period after the expiry of the market is not over';
     },
     {
-      name: 'OracleType';
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'Pyth';
-          },
-          {
-            name: 'Stub';
-          },
-          {
-            name: 'SwitchboardV1';
-          },
-          {
-            name: 'SwitchboardV2';
-          },
-          {
-            name: 'RaydiumCLMM';
+      code: 6062;
+      name: 'InvalidInputFeeSplit';
+      msg: 'The fee split shares must be positive and add up to 100% over at most 4 destinations';
+    },
+    {
+      code: 6063;
+      name: 'InvalidFeeSplit';
+      msg: 'The fee split account must be passed if and only if the market has a fee split';
+    },
+    {
+      code: 6064;
+      name: 'InvalidFeeSplitDestination';
+      msg: "The fee split destination accounts don't match the fee split";
+    },
+    {
+      code: 6065;
+      name: 'MissingTokenReceiverAccount';
+      msg: 'Missing token receiver account';
+    },
+    {
+      code: 6066;
+      name: 'InvalidInputReferrerShare';
+      msg: "The referrer share can't exceed 100%";
+    },
+    {
+      code: 6067;
+      name: 'InvalidReferrer';
+      msg: "The referrer doesn't refer on this market or isn't bound to the account";
+    },
+    {
+      code: 6068;
+      name: 'ReferrerAlreadyBound';
+      msg: 'The account is already bound to a referrer';
+    },
+    {
+      code: 6069;
+      name: 'OpenOrdersReferralNotClosed';
+      msg: 'The referral of the open orders account must be closed first';
+    },
+    {
+      code: 6070;
+      name: 'InvalidInputAccessGate';
+      msg: 'The access gate needs a kind and, for credential tokens, a mint';
+    },
+    {
+      code: 6071;
+      name: 'MissingAccessProof';
+      msg: 'The access gate or access proof account is missing';
+    },
+    {
+      code: 6072;
+      name: 'InvalidAccessProof';
+      msg: "The access proof doesn't match the access gate or the owner";
+    },
+    {
+      code: 6073;
+      name: 'OpenOrdersAccessDenied';
+      msg: "The open orders account wasn't granted access to the market";
+    },
+    {
+      code: 6074;
+      name: 'InvalidInputCapacity';
+      msg: "The account size doesn't fit a supported event heap or book side capacity";
+    },
+    {
+      code: 6075;
+      name: 'OpenOrdersAccountNotMigratable';
+      msg: "The open orders account can't be migrated before its fill events are consumed";
+    },
+    {
+      code: 6076;
+      name: 'MmpMakerAccountMissing';
+      msg: 'The open orders account of a maker with market maker protection is missing';
+    },
+    {
+      code: 6077;
+      name: 'MissingOpenOrdersDelegates';
+      msg: 'The open orders account has delegates, its delegates account must be passed';
+    },
+    {
+      code: 6078;
+      name: 'OpenOrdersDelegatesNotClosed';
+      msg: 'The delegates account of the open orders account must be closed first';
+    },
+    {
+      code: 6079;
+      name: 'MissingOpenOrdersReferral';
+      msg: 'The open orders account is bound to a referrer, its referral must be passed';
+    },
+    {
+      code: 6080;
+      name: 'MmpFrozen';
+      msg: 'Market maker protection was triggered, the owner must reset it first';
+    },
+    {
+      code: 6081;
+      name: 'OrderTimestampOverflow';
+      msg: "The current timestamp doesn't fit the u32 timestamp of resting orders";
+    },
+  ];
+};
+
+export const IDL: OpenbookV2 = {
+  version: '0.1.0',
+  name: 'openbook_v2',
+  instructions: [
+    {
+      name: 'createMarket',
+      docs: [
+        'Create a [`Market`](crate::state::Market) for a given token pair.',
+        '',
+        'A non-zero `auction_duration_secs` starts the market with an opening auction',
+        'of that length, see [`run_auction`](crate::openbook_v2::run_auction). With a',
+        'non-zero `auction_interval_secs`, a new auction phase starts every time the',
+        'market traded continuously for that long.',
+        '',
+        "The remainder of an order that would be posted to the book is dropped when it's",
+        'below `min_base_lots_per_order` or worth less than `min_quote_notional` quote',
+        "native, and the order fails when it's above `max_base_lots_per_order` (no limit = 0).",
+      ],
+      accounts: [
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'marketAuthority',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+          docs: [
+            'Accounts are initialized by client,',
+            'anchor discriminator is set first when ix exits,',
+            'their sizes set the capacities, see `BookSide::space` and `EventHeap::space`',
+          ],
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'marketBaseVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'baseMint',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'quoteMint',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'associatedTokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'collectFeeAdmin',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'consumeEventsAdmin',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'closeMarketAdmin',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'marketStatusAdmin',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'eventAuthority',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'program',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'name',
+          type: 'string',
+        },
+        {
+          name: 'oracleConfig',
+          type: {
+            defined: 'OracleConfigParams',
           },
-        ];
-      };
+        },
+        {
+          name: 'quoteLotSize',
+          type: 'i64',
+        },
+        {
+          name: 'baseLotSize',
+          type: 'i64',
+        },
+        {
+          name: 'makerFee',
+          type: 'i64',
+        },
+        {
+          name: 'takerFee',
+          type: 'i64',
+        },
+        {
+          name: 'timeExpiry',
+          type: 'i64',
+        },
+        {
+          name: 'auctionDurationSecs',
+          type: 'u32',
+        },
+        {
+          name: 'auctionIntervalSecs',
+          type: 'u32',
+        },
+        {
+          name: 'minBaseLotsPerOrder',
+          type: 'i64',
+        },
+        {
+          name: 'minQuoteNotional',
+          type: 'u64',
+        },
+        {
+          name: 'maxBaseLotsPerOrder',
+          type: 'i64',
+        },
+      ],
     },
     {
-      name: 'OrderState';
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'Valid';
-          },
-          {
-            name: 'Invalid';
-          },
-          {
-            name: 'Skipped';
-          },
-        ];
-      };
+      name: 'closeMarket',
+      docs: [
+        'Close a [`Market`](crate::state::Market) (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ],
+      accounts: [
+        {
+          name: 'closeMarketAdmin',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'solDestination',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      name: 'BookSideOrderTree';
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'Fixed';
-          },
-          {
-            name: 'OraclePegged';
-          },
-        ];
-      };
+      name: 'shrinkMarketAccounts',
+      docs: [
+        'Give back most of the rent of the bids, asks and event heap of an expired',
+        "[`Market`](crate::state::Market) once they're empty, by shrinking them to the minimum",
+        'capacity (only [`close_market_admin`](crate::state::Market::close_market_admin)).',
+        '',
+        'The market stays open so users can still settle their funds, and is closed with',
+        '`close_market` as usual.',
+      ],
+      accounts: [
+        {
+          name: 'closeMarketAdmin',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'market',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'solDestination',
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      name: 'EventType';
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'Fill';
-          },
-          {
-            name: 'Out';
-          },
-        ];
-      };
+      name: 'createOpenOrdersIndexer',
+      docs: [
+        'Create an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
+      ],
+      accounts: [
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      name: 'NodeTag';
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'Uninitialized';
-          },
-          {
-            name: 'InnerNode';
-          },
-          {
-            name: 'LeafNode';
-          },
-          {
-            name: 'FreeNode';
-          },
-          {
-            name: 'LastFreeNode';
-          },
-        ];
-      };
+      name: 'closeOpenOrdersIndexer',
+      docs: [
+        'Close an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
+      ],
+      accounts: [
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'solDestination',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      name: 'PlaceOrderType';
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'Limit';
-          },
-          {
-            name: 'ImmediateOrCancel';
-          },
-          {
-            name: 'PostOnly';
-          },
-          {
-            name: 'Market';
-          },
-          {
-            name: 'PostOnlySlide';
-          },
-          {
-            name: 'FillOrKill';
-          },
-        ];
-      };
+      name: 'createOpenOrdersAccount',
+      docs: [
+        'Create an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+      ],
+      accounts: [
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'delegateAccount',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'accessGate',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: ['Required when the market is access gated'],
+        },
+        {
+          name: 'accessProof',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: ['access gate'],
+        },
+      ],
+      args: [
+        {
+          name: 'name',
+          type: 'string',
+        },
+      ],
     },
     {
-      name: 'PostOrderType';
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'Limit';
-          },
-          {
-            name: 'PostOnly';
-          },
-          {
-            name: 'PostOnlySlide';
-          },
-        ];
-      };
+      name: 'closeOpenOrdersAccount',
+      docs: [
+        'Close an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+      ],
+      accounts: [
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'solDestination',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      name: 'SelfTradeBehavior';
+      name: 'createOpenOrdersDelegates',
       docs: [
-        'Self trade behavior controls how taker orders interact with resting limit orders of the same account.',
-        'This setting has no influence on placing a resting or oracle pegged limit order that does not match',
-        "immediately, instead it's the responsibility of the user to correctly configure his taker orders.",
-      ];
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'DecrementTake';
-          },
-          {
-            name: 'CancelProvide';
-          },
-          {
-            name: 'AbortTransaction';
-          },
-        ];
-      };
+        'Create the [`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account of an',
+        'open orders account, which holds the delegate permissions and list.',
+        '',
+        'From then on, instructions signed by a delegate need it in their remaining accounts.',
+      ],
+      accounts: [
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersDelegates',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      name: 'Side';
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'Bid';
-          },
-          {
-            name: 'Ask';
-          },
-        ];
-      };
+      name: 'closeOpenOrdersDelegates',
+      docs: [
+        'Close the [`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account of an',
+        'open orders account, removing all its delegates including the',
+        '[`delegate`](crate::state::OpenOrdersAccount::delegate). Needed before closing the',
+        'open orders account.',
+      ],
+      accounts: [
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersDelegates',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'solDestination',
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      name: 'SideAndOrderTree';
+      name: 'migrateOpenOrdersAccount',
       docs: [
-        "SideAndOrderTree is a storage optimization, so we don't need two bytes for the data",
-      ];
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'BidFixed';
-          },
-          {
-            name: 'AskFixed';
-          },
-          {
-            name: 'BidOraclePegged';
-          },
-          {
-            name: 'AskOraclePegged';
-          },
-        ];
-      };
+        'Bring an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) of an older version up',
+        "to date, e.g. recompute the `bids_quote_lots` that version 0 didn't track. Anyone can",
+        'migrate any account, it does nothing for accounts that are up to date already.',
+      ],
+      accounts: [
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      name: 'OrderParams';
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'Market';
-          },
-          {
-            name: 'ImmediateOrCancel';
-            fields: [
-              {
-                name: 'price_lots';
-                type: 'i64';
-              },
-            ];
-          },
-          {
-            name: 'Fixed';
-            fields: [
-              {
-                name: 'price_lots';
-                type: 'i64';
-              },
-              {
-                name: 'order_type';
-                type: {
-                  defined: 'PostOrderType';
-                };
-              },
-            ];
-          },
-          {
-            name: 'OraclePegged';
-            fields: [
-              {
-                name: 'price_offset_lots';
-                type: 'i64';
-              },
-              {
-                name: 'order_type';
-                type: {
-                  defined: 'PostOrderType';
-                };
-              },
-              {
-                name: 'peg_limit';
-                type: 'i64';
-              },
-            ];
-          },
-          {
-            name: 'FillOrKill';
-            fields: [
-              {
-                name: 'price_lots';
-                type: 'i64';
-              },
-            ];
+      name: 'placeOrder',
+      docs: [
+        'Place an order.',
+        '',
+        'Different types of orders have different effects on the order book,',
+        'as described in [`PlaceOrderType`](crate::state::PlaceOrderType).',
+        '',
+        '`price_lots` refers to the price in lots: the number of quote lots',
+        'per base lot. It is ignored for `PlaceOrderType::Market` orders.',
+        '',
+        '`expiry_timestamp` is a unix timestamp for when this order should',
+        'expire. If 0 is passed in, the order will never expire. If the time',
+        'is in the past, the instruction is skipped. Timestamps in the future',
+        'are reduced to now + 65,535s.',
+        '',
+        '`limit` determines the maximum number of orders from the book to fill,',
+        'and can be used to limit CU spent. When the limit is reached, processing',
+        'stops and the instruction succeeds.',
+      ],
+      accounts: [
+        {
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'userTokenAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'args',
+          type: {
+            defined: 'PlaceOrderArgs',
           },
-        ];
-      };
+        },
+      ],
+      returns: {
+        option: 'u128',
+      },
     },
     {
-      name: 'OrderTreeType';
-      type: {
-        kind: 'enum';
-        variants: [
-          {
-            name: 'Bids';
-          },
-          {
-            name: 'Asks';
+      name: 'editOrder',
+      docs: ['Edit an order.'],
+      accounts: [
+        {
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'userTokenAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'clientOrderId',
+          type: 'u64',
+        },
+        {
+          name: 'expectedCancelSize',
+          type: 'i64',
+        },
+        {
+          name: 'placeOrder',
+          type: {
+            defined: 'PlaceOrderArgs',
           },
-        ];
-      };
+        },
+      ],
+      returns: {
+        option: 'u128',
+      },
     },
-  ];
-  events: [
     {
-      name: 'DepositLog';
-      fields: [
+      name: 'editOrderPegged',
+      docs: ['Edit an order pegged.'],
+      accounts: [
         {
-          name: 'openOrdersAccount';
-          type: 'publicKey';
-          index: false;
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
         },
         {
-          name: 'signer';
-          type: 'publicKey';
-          index: false;
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'baseAmount';
-          type: 'u64';
-          index: false;
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
         },
         {
-          name: 'quoteAmount';
-          type: 'u64';
-          index: false;
+          name: 'userTokenAccount',
+          isMut: true,
+          isSigner: false,
         },
-      ];
-    },
-    {
-      name: 'FillLog';
-      fields: [
         {
-          name: 'market';
-          type: 'publicKey';
-          index: false;
+          name: 'market',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'takerSide';
-          type: 'u8';
-          index: false;
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'makerSlot';
-          type: 'u8';
-          index: false;
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'makerOut';
-          type: 'bool';
-          index: false;
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'timestamp';
-          type: 'u64';
-          index: false;
+          name: 'marketVault',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'seqNum';
-          type: 'u64';
-          index: false;
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'maker';
-          type: 'publicKey';
-          index: false;
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'makerClientOrderId';
-          type: 'u64';
-          index: false;
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'makerFee';
-          type: 'u64';
-          index: false;
+          name: 'clientOrderId',
+          type: 'u64',
         },
         {
-          name: 'makerTimestamp';
-          type: 'u64';
-          index: false;
+          name: 'expectedCancelSize',
+          type: 'i64',
         },
         {
-          name: 'taker';
-          type: 'publicKey';
-          index: false;
+          name: 'placeOrder',
+          type: {
+            defined: 'PlaceOrderPeggedArgs',
+          },
+        },
+      ],
+      returns: {
+        option: 'u128',
+      },
+    },
+    {
+      name: 'placeOrders',
+      docs: ['Place multiple orders'],
+      accounts: [
+        {
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
         },
         {
-          name: 'takerClientOrderId';
-          type: 'u64';
-          index: false;
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'takerFeeCeil';
-          type: 'u64';
-          index: false;
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
         },
         {
-          name: 'price';
-          type: 'i64';
-          index: false;
+          name: 'userQuoteAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'userBaseAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketBaseVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'quantity';
-          type: 'i64';
-          index: false;
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
-      ];
-    },
-    {
-      name: 'MarketMetaDataLog';
-      fields: [
         {
-          name: 'market';
-          type: 'publicKey';
-          index: false;
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'name';
-          type: 'string';
-          index: false;
+          name: 'ordersType',
+          type: {
+            defined: 'PlaceOrderType',
+          },
         },
         {
-          name: 'baseMint';
-          type: 'publicKey';
-          index: false;
+          name: 'bids',
+          type: {
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
+          },
         },
         {
-          name: 'quoteMint';
-          type: 'publicKey';
-          index: false;
+          name: 'asks',
+          type: {
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
+          },
         },
         {
-          name: 'baseDecimals';
-          type: 'u8';
-          index: false;
+          name: 'limit',
+          type: 'u8',
+        },
+      ],
+      returns: {
+        vec: {
+          option: 'u128',
         },
+      },
+    },
+    {
+      name: 'cancelAllAndPlaceOrders',
+      docs: ['Cancel orders and place multiple orders.'],
+      accounts: [
         {
-          name: 'quoteDecimals';
-          type: 'u8';
-          index: false;
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
         },
         {
-          name: 'baseLotSize';
-          type: 'i64';
-          index: false;
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'quoteLotSize';
-          type: 'i64';
-          index: false;
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
         },
-      ];
-    },
-    {
-      name: 'TotalOrderFillEvent';
-      fields: [
         {
-          name: 'side';
-          type: 'u8';
-          index: false;
+          name: 'userQuoteAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'taker';
-          type: 'publicKey';
-          index: false;
+          name: 'userBaseAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'totalQuantityPaid';
-          type: 'u64';
-          index: false;
+          name: 'market',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'totalQuantityReceived';
-          type: 'u64';
-          index: false;
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'fees';
-          type: 'u64';
-          index: false;
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
         },
-      ];
-    },
-    {
-      name: 'SetDelegateLog';
-      fields: [
         {
-          name: 'openOrdersAccount';
-          type: 'publicKey';
-          index: false;
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'delegate';
-          type: {
-            option: 'publicKey';
-          };
-          index: false;
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
         },
-      ];
-    },
-    {
-      name: 'SettleFundsLog';
-      fields: [
         {
-          name: 'openOrdersAccount';
-          type: 'publicKey';
-          index: false;
+          name: 'marketBaseVault',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'baseNative';
-          type: 'u64';
-          index: false;
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'quoteNative';
-          type: 'u64';
-          index: false;
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'referrerRebate';
-          type: 'u64';
-          index: false;
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'referrer';
+          name: 'ordersType',
           type: {
-            option: 'publicKey';
-          };
-          index: false;
+            defined: 'PlaceOrderType',
+          },
         },
-      ];
-    },
-    {
-      name: 'SweepFeesLog';
-      fields: [
         {
-          name: 'market';
-          type: 'publicKey';
-          index: false;
+          name: 'bids',
+          type: {
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
+          },
         },
         {
-          name: 'amount';
-          type: 'u64';
-          index: false;
+          name: 'asks',
+          type: {
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
+          },
         },
         {
-          name: 'receiver';
-          type: 'publicKey';
-          index: false;
+          name: 'limit',
+          type: 'u8',
         },
-      ];
+      ],
+      returns: {
+        vec: {
+          option: 'u128',
+        },
+      },
     },
     {
-      name: 'OpenOrdersPositionLog';
-      fields: [
+      name: 'placeOrderPegged',
+      docs: ['Place an oracle-peg order.'],
+      accounts: [
         {
-          name: 'owner';
-          type: 'publicKey';
-          index: false;
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
         },
         {
-          name: 'openOrdersAccountNum';
-          type: 'u32';
-          index: false;
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'market';
-          type: 'publicKey';
-          index: false;
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
         },
         {
-          name: 'bidsBaseLots';
-          type: 'i64';
-          index: false;
+          name: 'userTokenAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'bidsQuoteLots';
-          type: 'i64';
-          index: false;
+          name: 'market',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'asksBaseLots';
-          type: 'i64';
-          index: false;
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'baseFreeNative';
-          type: 'u64';
-          index: false;
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'quoteFreeNative';
-          type: 'u64';
-          index: false;
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'lockedMakerFees';
-          type: 'u64';
-          index: false;
+          name: 'marketVault',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'referrerRebatesAvailable';
-          type: 'u64';
-          index: false;
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'makerVolume';
-          type: 'u128';
-          index: false;
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'takerVolume';
-          type: 'u128';
-          index: false;
-        },
-      ];
-    },
-  ];
-  errors: [
-    {
-      code: 6000;
-      name: 'SomeError';
-      msg: '';
-    },
-    {
-      code: 6001;
-      name: 'InvalidInputNameLength';
-      msg: 'Name lenght above limit';
-    },
-    {
-      code: 6002;
-      name: 'InvalidInputMarketExpired';
-      msg: 'Market cannot be created as expired';
-    },
-    {
-      code: 6003;
-      name: 'InvalidInputMarketFees';
-      msg: 'Taker fees should be positive and if maker fees are negative, greater or equal to their abs value';
-    },
-    {
-      code: 6004;
-      name: 'InvalidInputLots';
-      msg: 'Lots cannot be negative';
-    },
-    {
-      code: 6005;
-      name: 'InvalidInputLotsSize';
-      msg: 'Lots size above market limits';
-    },
-    {
-      code: 6006;
-      name: 'InvalidInputOrdersAmounts';
-      msg: 'Input amounts above limits';
-    },
-    {
-      code: 6007;
-      name: 'InvalidInputCancelSize';
-      msg: 'Price lots should be greater than zero';
-    },
-    {
-      code: 6008;
-      name: 'InvalidInputPriceLots';
-      msg: 'Expected cancel size should be greater than zero';
-    },
-    {
-      code: 6009;
-      name: 'InvalidInputPegLimit';
-      msg: 'Peg limit should be greater than zero';
-    },
-    {
-      code: 6010;
-      name: 'InvalidInputOrderType';
-      msg: 'The order type is invalid. A taker order must be Market or ImmediateOrCancel';
-    },
-    {
-      code: 6011;
-      name: 'InvalidInputOrderId';
-      msg: 'Order id cannot be zero';
-    },
-    {
-      code: 6012;
-      name: 'InvalidInputHeapSlots';
-      msg: 'Slot above heap limit';
-    },
-    {
-      code: 6013;
-      name: 'InvalidOracleTypes';
-      msg: 'Cannot combine two oracles of different providers';
-    },
-    {
-      code: 6014;
-      name: 'InvalidSecondOracle';
-      msg: 'Cannot configure secondary oracle without primary';
-    },
-    {
-      code: 6015;
-      name: 'NoCloseMarketAdmin';
-      msg: 'This market does not have a `close_market_admin` and thus cannot be closed.';
-    },
-    {
-      code: 6016;
-      name: 'InvalidCloseMarketAdmin';
-      msg: "The signer of this transaction is not this market's `close_market_admin`.";
-    },
-    {
-      code: 6017;
-      name: 'InvalidOpenOrdersAdmin';
-      msg: 'The `open_orders_admin` required by this market to sign all instructions that creates orders is missing or is not valid';
-    },
-    {
-      code: 6018;
-      name: 'InvalidConsumeEventsAdmin';
-      msg: 'The `consume_events_admin` required by this market to sign all instructions that consume events is missing or is not valid';
-    },
-    {
-      code: 6019;
-      name: 'InvalidMarketVault';
-      msg: 'Provided `market_vault` is invalid';
-    },
-    {
-      code: 6020;
-      name: 'IndexerActiveOO';
-      msg: 'Cannot be closed due to the existence of open orders accounts';
-    },
-    {
-      code: 6021;
-      name: 'OraclePegInvalidOracleState';
-      msg: 'Cannot place a peg order due to invalid oracle state';
-    },
-    {
-      code: 6022;
-      name: 'UnknownOracleType';
-      msg: 'oracle type cannot be determined';
-    },
-    {
-      code: 6023;
-      name: 'OracleConfidence';
-      msg: 'an oracle does not reach the confidence threshold';
-    },
-    {
-      code: 6024;
-      name: 'OracleStale';
-      msg: 'an oracle is stale';
-    },
-    {
-      code: 6025;
-      name: 'OrderIdNotFound';
-      msg: 'Order id not found on the orderbook';
-    },
-    {
-      code: 6026;
-      name: 'EventHeapContainsElements';
-      msg: "Event heap contains elements and market can't be closed";
-    },
-    {
-      code: 6027;
-      name: 'InvalidOrderPostIOC';
-      msg: 'ImmediateOrCancel is not a PostOrderType';
-    },
-    {
-      code: 6028;
-      name: 'InvalidOrderPostMarket';
-      msg: 'Market is not a PostOrderType';
-    },
-    {
-      code: 6029;
-      name: 'WouldSelfTrade';
-      msg: 'would self trade';
-    },
-    {
-      code: 6030;
-      name: 'MarketHasExpired';
-      msg: 'The Market has already expired.';
-    },
-    {
-      code: 6031;
-      name: 'InvalidPriceLots';
-      msg: 'Price lots should be greater than zero';
-    },
-    {
-      code: 6032;
-      name: 'InvalidOraclePrice';
-      msg: 'Oracle price above market limits';
-    },
-    {
-      code: 6033;
-      name: 'MarketHasNotExpired';
-      msg: 'The Market has not expired yet.';
-    },
-    {
-      code: 6034;
-      name: 'NoOwnerOrDelegate';
-      msg: 'No correct owner or delegate.';
-    },
-    {
-      code: 6035;
-      name: 'NoOwner';
-      msg: 'No correct owner';
-    },
-    {
-      code: 6036;
-      name: 'OpenOrdersFull';
-      msg: 'No free order index in open orders account';
-    },
-    {
-      code: 6037;
-      name: 'BookContainsElements';
-      msg: 'Book contains elements';
-    },
-    {
-      code: 6038;
-      name: 'OpenOrdersOrderNotFound';
-      msg: 'Could not find order in user account';
-    },
-    {
-      code: 6039;
-      name: 'InvalidPostAmount';
-      msg: 'Amount to post above book limits';
-    },
-    {
-      code: 6040;
-      name: 'DisabledOraclePeg';
-      msg: 'Oracle peg orders are not enabled for this market';
-    },
-    {
-      code: 6041;
-      name: 'NonEmptyMarket';
-      msg: 'Cannot close a non-empty market';
-    },
-    {
-      code: 6042;
-      name: 'NonEmptyOpenOrdersPosition';
-      msg: 'Cannot close a non-empty open orders account';
-    },
-    {
-      code: 6043;
-      name: 'WouldExecutePartially';
-      msg: 'Fill-Or-Kill order would generate a partial execution';
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'args',
+          type: {
+            defined: 'PlaceOrderPeggedArgs',
+          },
+        },
+      ],
+      returns: {
+        option: 'u128',
+      },
     },
-  ];
-};
-
-export const IDL: OpenbookV2 = {
-  version: '0.1.0',
-  name: 'openbook_v2',
-  instructions: [
     {
-      name: 'createMarket',
+      name: 'placeTakeOrder',
       docs: [
-        'Create a [`Market`](crate::state::Market) for a given token pair.',
+        'Place an order that shall take existing liquidity off of the book, not',
+        'add a new order off the book.',
+        '',
+        'This type of order allows for instant token settlement for the taker.',
       ],
       accounts: [
         {
-          name: 'market',
+          name: 'signer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'penaltyPayer',
           isMut: true,
           isSigner: true,
         },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
         {
           name: 'marketAuthority',
           isMut: false,
@@ -3724,10 +7715,6 @@ export const IDL: OpenbookV2 = {
           name: 'bids',
           isMut: true,
           isSigner: false,
-          docs: [
-            'Accounts are initialized by client,',
-            'anchor discriminator is set first when ix exits,',
-          ],
         },
         {
           name: 'asks',
@@ -3735,39 +7722,41 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'marketBaseVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'payer',
+          name: 'marketQuoteVault',
           isMut: true,
-          isSigner: true,
+          isSigner: false,
         },
         {
-          name: 'marketBaseVault',
+          name: 'eventHeap',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'baseMint',
-          isMut: false,
+          name: 'userQuoteAccount',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'quoteMint',
+          name: 'oracleA',
           isMut: false,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'systemProgram',
+          name: 'oracleB',
           isMut: false,
           isSigner: false,
+          isOptional: true,
         },
         {
           name: 'tokenProgram',
@@ -3775,123 +7764,329 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'associatedTokenProgram',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'oracleA',
+          name: 'openOrdersAdmin',
           isMut: false,
-          isSigner: false,
+          isSigner: true,
           isOptional: true,
         },
+      ],
+      args: [
         {
-          name: 'oracleB',
+          name: 'args',
+          type: {
+            defined: 'PlaceTakeOrderArgs',
+          },
+        },
+      ],
+    },
+    {
+      name: 'consumeEvents',
+      docs: [
+        'Process up to `limit` [events](crate::state::AnyEvent).',
+        '',
+        "When a user places a 'take' order, they do not know beforehand which",
+        "market maker will have placed the 'make' order that they get executed",
+        "against. This prevents them from passing in a market maker's",
+        '[`OpenOrdersAccount`](crate::state::OpenOrdersAccount), which is needed',
+        'to credit/debit the relevant tokens to/from the maker. As such, Openbook',
+        "uses a 'crank' system, where `place_order` only emits events, and",
+        '`consume_events` handles token settlement.',
+        '',
+        'Currently, there are two types of events: [`FillEvent`](crate::state::FillEvent)s',
+        'and [`OutEvent`](crate::state::OutEvent)s.',
+        '',
+        'A `FillEvent` is emitted when an order is filled, and it is handled by',
+        'debiting whatever the taker is selling from the taker and crediting',
+        'it to the maker, and debiting whatever the taker is buying from the',
+        'maker and crediting it to the taker. Note that *no tokens are moved*,',
+        "these are just debits and credits to each party's [`Position`](crate::state::Position).",
+        '',
+        'An `OutEvent` is emitted when a limit order needs to be removed from',
+        'the book during a `place_order` invocation, and it is handled by',
+        'crediting whatever the maker would have sold (quote token in a bid,',
+        'base token in an ask) back to the maker.',
+      ],
+      accounts: [
+        {
+          name: 'consumeEventsAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'limit',
+          type: 'u64',
+        },
+      ],
+    },
+    {
+      name: 'consumeGivenEvents',
+      docs: [
+        'Process the [events](crate::state::AnyEvent) at the given positions.',
+      ],
+      accounts: [
+        {
+          name: 'consumeEventsAdmin',
           isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
           isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'slots',
+          type: {
+            vec: 'u64',
+          },
+        },
+      ],
+    },
+    {
+      name: 'consumeEventsWithReward',
+      docs: [
+        'Same as `consume_events`, or `consume_given_events` when `slots` are given, and',
+        'pays the signing `crank_reward_receiver`',
+        '[`crank_reward_per_event`](crate::state::Market::crank_reward_per_event) lamports',
+        'for every event consumed, from the penalties collected by the market.',
+      ],
+      accounts: [
+        {
+          name: 'consumeEventsAdmin',
+          isMut: false,
+          isSigner: true,
           isOptional: true,
         },
         {
-          name: 'collectFeeAdmin',
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'crankRewardReceiver',
+          isMut: true,
+          isSigner: true,
+          docs: ['The cranker, receives the crank reward'],
+        },
+      ],
+      args: [
+        {
+          name: 'limit',
+          type: 'u64',
+        },
+        {
+          name: 'slots',
+          type: {
+            option: {
+              vec: 'u64',
+            },
+          },
+        },
+      ],
+    },
+    {
+      name: 'cancelOrder',
+      docs: [
+        'Cancel an order by its `order_id`.',
+        '',
+        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
+        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+      ],
+      accounts: [
+        {
+          name: 'signer',
           isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'openOrdersAdmin',
+          name: 'market',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'consumeEventsAdmin',
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'orderId',
+          type: 'u128',
+        },
+      ],
+    },
+    {
+      name: 'cancelOrderByClientOrderId',
+      docs: [
+        'Cancel an order by its `client_order_id`.',
+        '',
+        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
+        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+      ],
+      accounts: [
+        {
+          name: 'signer',
           isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'closeMarketAdmin',
+          name: 'market',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'eventAuthority',
-          isMut: false,
+          name: 'bids',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'program',
-          isMut: false,
+          name: 'asks',
+          isMut: true,
           isSigner: false,
         },
       ],
       args: [
         {
-          name: 'name',
-          type: 'string',
+          name: 'clientOrderId',
+          type: 'u64',
         },
+      ],
+      returns: 'i64',
+    },
+    {
+      name: 'cancelAllOrders',
+      docs: ['Cancel up to `limit` orders, optionally filtering by side'],
+      accounts: [
         {
-          name: 'oracleConfig',
-          type: {
-            defined: 'OracleConfigParams',
-          },
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
         },
         {
-          name: 'quoteLotSize',
-          type: 'i64',
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'baseLotSize',
-          type: 'i64',
+          name: 'market',
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: 'makerFee',
-          type: 'i64',
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'takerFee',
-          type: 'i64',
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'sideOption',
+          type: {
+            option: {
+              defined: 'Side',
+            },
+          },
         },
         {
-          name: 'timeExpiry',
-          type: 'i64',
+          name: 'limit',
+          type: 'u8',
         },
       ],
     },
     {
-      name: 'closeMarket',
+      name: 'deposit',
       docs: [
-        'Close a [`Market`](crate::state::Market) (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+        "Deposit a certain amount of `base` and `quote` lamports into one's",
+        '[`Position`](crate::state::Position).',
+        '',
+        'Makers might wish to `deposit`, rather than have actual tokens moved for',
+        'each trade, in order to reduce CUs.',
       ],
       accounts: [
         {
-          name: 'closeMarketAdmin',
+          name: 'owner',
           isMut: false,
           isSigner: true,
         },
         {
-          name: 'market',
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'bids',
+          name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'solDestination',
+          name: 'marketBaseVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketQuoteVault',
           isMut: true,
           isSigner: false,
         },
@@ -3901,55 +8096,59 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
       ],
-      args: [],
+      args: [
+        {
+          name: 'baseAmount',
+          type: 'u64',
+        },
+        {
+          name: 'quoteAmount',
+          type: 'u64',
+        },
+      ],
     },
     {
-      name: 'createOpenOrdersIndexer',
+      name: 'refill',
       docs: [
-        'Create an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
+        'Refill a certain amount of `base` and `quote` lamports. The amount being passed is the',
+        'total lamports that the [`Position`](crate::state::Position) will have.',
+        '',
+        'Makers might wish to `refill`, rather than have actual tokens moved for',
+        'each trade, in order to reduce CUs.',
       ],
       accounts: [
-        {
-          name: 'payer',
-          isMut: true,
-          isSigner: true,
-        },
         {
           name: 'owner',
           isMut: false,
           isSigner: true,
         },
         {
-          name: 'openOrdersIndexer',
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'systemProgram',
-          isMut: false,
+          name: 'userQuoteAccount',
+          isMut: true,
           isSigner: false,
         },
-      ],
-      args: [],
-    },
-    {
-      name: 'closeOpenOrdersIndexer',
-      docs: [
-        'Close an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
-      ],
-      accounts: [
         {
-          name: 'owner',
-          isMut: false,
-          isSigner: true,
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'openOrdersIndexer',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'solDestination',
+          name: 'marketBaseVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketQuoteVault',
           isMut: true,
           isSigner: false,
         },
@@ -3959,34 +8158,30 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
       ],
-      args: [],
+      args: [
+        {
+          name: 'baseAmount',
+          type: 'u64',
+        },
+        {
+          name: 'quoteAmount',
+          type: 'u64',
+        },
+      ],
     },
     {
-      name: 'createOpenOrdersAccount',
-      docs: [
-        'Create an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
-      ],
+      name: 'settleFunds',
+      docs: ['Withdraw any available tokens.'],
       accounts: [
-        {
-          name: 'payer',
-          isMut: true,
-          isSigner: true,
-        },
         {
           name: 'owner',
           isMut: false,
           isSigner: true,
         },
         {
-          name: 'delegateAccount',
-          isMut: false,
-          isSigner: false,
-          isOptional: true,
-        },
-        {
-          name: 'openOrdersIndexer',
+          name: 'penaltyPayer',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
         },
         {
           name: 'openOrdersAccount',
@@ -3995,47 +8190,44 @@ export const IDL: OpenbookV2 = {
         },
         {
           name: 'market',
-          isMut: false,
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'systemProgram',
+          name: 'marketAuthority',
           isMut: false,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'name',
-          type: 'string',
+          name: 'marketBaseVault',
+          isMut: true,
+          isSigner: false,
         },
-      ],
-    },
-    {
-      name: 'closeOpenOrdersAccount',
-      docs: [
-        'Close an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
-      ],
-      accounts: [
         {
-          name: 'owner',
-          isMut: false,
-          isSigner: true,
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'openOrdersIndexer',
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'solDestination',
+          name: 'referrerAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
         },
         {
           name: 'systemProgram',
@@ -4046,44 +8238,29 @@ export const IDL: OpenbookV2 = {
       args: [],
     },
     {
-      name: 'placeOrder',
+      name: 'settleFundsExpired',
       docs: [
-        'Place an order.',
-        '',
-        'Different types of orders have different effects on the order book,',
-        'as described in [`PlaceOrderType`](crate::state::PlaceOrderType).',
-        '',
-        '`price_lots` refers to the price in lots: the number of quote lots',
-        'per base lot. It is ignored for `PlaceOrderType::Market` orders.',
-        '',
-        '`expiry_timestamp` is a unix timestamp for when this order should',
-        'expire. If 0 is passed in, the order will never expire. If the time',
-        'is in the past, the instruction is skipped. Timestamps in the future',
-        'are reduced to now + 65,535s.',
-        '',
-        '`limit` determines the maximum number of orders from the book to fill,',
-        'and can be used to limit CU spent. When the limit is reached, processing',
-        'stops and the instruction succeeds.',
+        'Withdraw any available tokens when the market is expired (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
       ],
       accounts: [
         {
-          name: 'signer',
+          name: 'closeMarketAdmin',
           isMut: false,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'owner',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'openOrdersAdmin',
-          isMut: false,
+          name: 'penaltyPayer',
+          isMut: true,
           isSigner: true,
-          isOptional: true,
         },
         {
-          name: 'userTokenAccount',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
@@ -4093,58 +8270,60 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'bids',
+          name: 'marketAuthority',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'marketBaseVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'marketQuoteVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketVault',
+          name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'oracleA',
-          isMut: false,
+          name: 'referrerAccount',
+          isMut: true,
           isSigner: false,
           isOptional: true,
         },
         {
-          name: 'oracleB',
+          name: 'tokenProgram',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'tokenProgram',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
         },
       ],
-      args: [
-        {
-          name: 'args',
-          type: {
-            defined: 'PlaceOrderArgs',
-          },
-        },
-      ],
-      returns: {
-        option: 'u128',
-      },
+      args: [],
     },
     {
-      name: 'editOrder',
-      docs: ['Edit an order.'],
+      name: 'forceSettleFunds',
+      docs: [
+        'Withdraw the available tokens of an open orders account to token accounts of its',
+        "owner when the market is expired, without the owner's signature.",
+        '',
+        'Only the [`close_market_admin`](crate::state::Market::close_market_admin) can do',
+        'so right after the expiry, anyone once',
+        '[`EXPIRED_MARKET_GRACE_PERIOD`](crate::state::EXPIRED_MARKET_GRACE_PERIOD) has passed.',
+        'For markets set expired before the grace period existed, it is over already.',
+      ],
       accounts: [
         {
           name: 'signer',
@@ -4152,18 +8331,12 @@ export const IDL: OpenbookV2 = {
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'openOrdersAdmin',
+          name: 'owner',
           isMut: false,
-          isSigner: true,
-          isOptional: true,
+          isSigner: false,
         },
         {
-          name: 'userTokenAccount',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
@@ -4173,36 +8346,29 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'bids',
-          isMut: true,
+          name: 'marketAuthority',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'marketBaseVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'marketQuoteVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketVault',
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'oracleA',
-          isMut: false,
-          isSigner: false,
-          isOptional: true,
-        },
-        {
-          name: 'oracleB',
-          isMut: false,
+          name: 'userQuoteAccount',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
           name: 'tokenProgram',
@@ -4210,232 +8376,294 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
       ],
-      args: [
-        {
-          name: 'clientOrderId',
-          type: 'u64',
-        },
-        {
-          name: 'expectedCancelSize',
-          type: 'i64',
-        },
-        {
-          name: 'placeOrder',
-          type: {
-            defined: 'PlaceOrderArgs',
-          },
-        },
-      ],
-      returns: {
-        option: 'u128',
-      },
+      args: [],
     },
     {
-      name: 'editOrderPegged',
-      docs: ['Edit an order pegged.'],
+      name: 'sweepFees',
+      docs: [
+        "Sweep fees, as a [`Market`](crate::state::Market)'s admin.",
+        '',
+        'Without a [`FeeSplit`](crate::state::FeeSplit) all fees go to the',
+        '`token_receiver_account`. With one, they are split pro rata between its',
+        'destinations, which are passed as remaining accounts in the same order.',
+      ],
       accounts: [
         {
-          name: 'signer',
+          name: 'collectFeeAdmin',
           isMut: false,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'openOrdersAdmin',
+          name: 'marketAuthority',
           isMut: false,
-          isSigner: true,
-          isOptional: true,
-        },
-        {
-          name: 'userTokenAccount',
-          isMut: true,
           isSigner: false,
         },
         {
-          name: 'market',
+          name: 'marketQuoteVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'bids',
+          name: 'tokenReceiverAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
+          docs: ['Receives all fees when the market has no fee split'],
         },
         {
-          name: 'asks',
-          isMut: true,
+          name: 'tokenProgram',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'eventHeap',
-          isMut: true,
+          name: 'feeSplit',
+          isMut: false,
           isSigner: false,
+          isOptional: true,
+          docs: [
+            'Required when the market has a fee split, the destination token accounts are',
+            'passed as remaining accounts in the same order',
+          ],
         },
+      ],
+      args: [],
+    },
+    {
+      name: 'registerReferrer',
+      docs: [
+        'Register a [`Referrer`](crate::state::Referrer) with a share of the taker fees of',
+        "the accounts bound to it, in basis points. It's global if `market` is the default",
+        'pubkey, otherwise it can only refer on that market.',
+      ],
+      accounts: [
         {
-          name: 'marketVault',
+          name: 'payer',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'oracleA',
+          name: 'authority',
           isMut: false,
-          isSigner: false,
-          isOptional: true,
+          isSigner: true,
         },
         {
-          name: 'oracleB',
-          isMut: false,
+          name: 'referrer',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'tokenProgram',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
         },
       ],
       args: [
         {
-          name: 'clientOrderId',
-          type: 'u64',
-        },
-        {
-          name: 'expectedCancelSize',
-          type: 'i64',
+          name: 'market',
+          type: 'publicKey',
         },
         {
-          name: 'placeOrder',
-          type: {
-            defined: 'PlaceOrderPeggedArgs',
-          },
+          name: 'shareBps',
+          type: 'u16',
         },
       ],
-      returns: {
-        option: 'u128',
-      },
     },
     {
-      name: 'placeOrders',
-      docs: ['Place multiple orders'],
+      name: 'setReferrerShare',
+      docs: [
+        'Update the share of a [`Referrer`](crate::state::Referrer). Accounts already bound',
+        'to it keep the share they were bound with.',
+      ],
       accounts: [
         {
-          name: 'signer',
+          name: 'authority',
           isMut: false,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'referrer',
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'openOrdersAdmin',
+          name: 'shareBps',
+          type: 'u16',
+        },
+      ],
+    },
+    {
+      name: 'setMaxReferrerShare',
+      docs: [
+        'Set the maximum share of the taker fees that referrers get on a market (only',
+        '[`collect_fee_admin`](crate::state::Market::collect_fee_admin)). Markets start at 0,',
+        'without any referrer rebates.',
+      ],
+      accounts: [
+        {
+          name: 'collectFeeAdmin',
           isMut: false,
           isSigner: true,
-          isOptional: true,
         },
         {
-          name: 'userQuoteAccount',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'userBaseAccount',
-          isMut: true,
-          isSigner: false,
+          name: 'shareBps',
+          type: 'u16',
+        },
+      ],
+    },
+    {
+      name: 'setCrankRewards',
+      docs: [
+        'Set the lamports a taker pays for every transaction that adds to the event heap',
+        '(None = [`PENALTY_EVENT_HEAP`](crate::state::PENALTY_EVENT_HEAP)) and the reward paid',
+        'from these penalties for every event consumed (0 = no reward), as the',
+        '[`collect_fee_admin`](crate::state::Market::collect_fee_admin).',
+      ],
+      accounts: [
+        {
+          name: 'collectFeeAdmin',
+          isMut: false,
+          isSigner: true,
         },
         {
           name: 'market',
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'bids',
-          isMut: true,
-          isSigner: false,
+          name: 'penaltyEventHeap',
+          type: {
+            option: 'u32',
+          },
         },
         {
-          name: 'asks',
+          name: 'crankRewardPerEvent',
+          type: 'u32',
+        },
+      ],
+    },
+    {
+      name: 'bindReferrer',
+      docs: [
+        'Bind an open orders account to a [`Referrer`](crate::state::Referrer), creating its',
+        '[`OpenOrdersReferral`](crate::state::OpenOrdersReferral).',
+        '',
+        "From then on the referrer's share of the taker fees of the account is set aside when",
+        'taking with `place_order`, `edit_order`, `place_order_pegged` or',
+        '`cancel_all_and_place_orders`, which need the referral in their remaining accounts.',
+        "The share is capped by the market's",
+        '[`max_referrer_share_bps`](crate::state::Market::max_referrer_share_bps), 0 unless',
+        'set with `set_max_referrer_share`.',
+      ],
+      accounts: [
+        {
+          name: 'owner',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'eventHeap',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'referrer',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketBaseVault',
+          name: 'openOrdersReferral',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'oracleA',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
+      ],
+      args: [],
+    },
+    {
+      name: 'claimReferrerRebates',
+      docs: [
+        'Withdraw the rebates set aside for a [`Referrer`](crate::state::Referrer) in an',
+        'open orders account bound to it.',
+      ],
+      accounts: [
         {
-          name: 'oracleB',
+          name: 'authority',
           isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'referrer',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'tokenProgram',
+          name: 'openOrdersAccount',
           isMut: false,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'ordersType',
-          type: {
-            defined: 'PlaceOrderType',
-          },
+          name: 'openOrdersReferral',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'bids',
-          type: {
-            vec: {
-              defined: 'PlaceMultipleOrdersArgs',
-            },
-          },
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketAuthority',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'asks',
-          type: {
-            vec: {
-              defined: 'PlaceMultipleOrdersArgs',
-            },
-          },
+          name: 'referrerTokenAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'limit',
-          type: 'u8',
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
         },
       ],
-      returns: {
-        vec: {
-          option: 'u128',
-        },
-      },
+      args: [],
     },
     {
-      name: 'cancelAllAndPlaceOrders',
-      docs: ['Cancel orders and place multiple orders.'],
+      name: 'closeOpenOrdersReferral',
+      docs: [
+        'Unbind an open orders account from its referrer, paying out the rebates set aside',
+        'so far and closing its [`OpenOrdersReferral`](crate::state::OpenOrdersReferral).',
+        'Needed before closing the open orders account.',
+      ],
       accounts: [
         {
-          name: 'signer',
+          name: 'owner',
           isMut: false,
           isSigner: true,
         },
@@ -4445,18 +8673,12 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'openOrdersAdmin',
-          isMut: false,
-          isSigner: true,
-          isOptional: true,
-        },
-        {
-          name: 'userQuoteAccount',
+          name: 'openOrdersReferral',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'userBaseAccount',
+          name: 'referrer',
           isMut: true,
           isSigner: false,
         },
@@ -4466,297 +8688,329 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'bids',
-          isMut: true,
+          name: 'marketAuthority',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'marketQuoteVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'referrerTokenAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'solDestination',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketBaseVault',
-          isMut: true,
+          name: 'tokenProgram',
+          isMut: false,
           isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: 'createFeeSplit',
+      docs: [
+        'Create the [`FeeSplit`](crate::state::FeeSplit) of a market, as its',
+        '[`collect_fee_admin`](crate::state::Market::collect_fee_admin). From then on the',
+        'fees are always swept according to it.',
+        '',
+        'The destination token accounts are passed as remaining accounts, in order.',
+      ],
+      accounts: [
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
         {
-          name: 'oracleA',
+          name: 'collectFeeAdmin',
           isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'market',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'oracleB',
-          isMut: false,
+          name: 'feeSplit',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'tokenProgram',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
         },
       ],
       args: [
         {
-          name: 'ordersType',
-          type: {
-            defined: 'PlaceOrderType',
-          },
-        },
-        {
-          name: 'bids',
-          type: {
-            vec: {
-              defined: 'PlaceMultipleOrdersArgs',
-            },
-          },
-        },
-        {
-          name: 'asks',
+          name: 'destinations',
           type: {
             vec: {
-              defined: 'PlaceMultipleOrdersArgs',
+              defined: 'FeeSplitDestination',
             },
           },
         },
-        {
-          name: 'limit',
-          type: 'u8',
-        },
       ],
-      returns: {
-        vec: {
-          option: 'u128',
-        },
-      },
     },
     {
-      name: 'placeOrderPegged',
-      docs: ['Place an oracle-peg order.'],
+      name: 'setFeeSplit',
+      docs: [
+        'Replace the destinations of the [`FeeSplit`](crate::state::FeeSplit) of a market, as',
+        'its [`collect_fee_admin`](crate::state::Market::collect_fee_admin). The destination',
+        'token accounts are passed as remaining accounts, in order.',
+      ],
       accounts: [
         {
-          name: 'signer',
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: 'openOrdersAccount',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'openOrdersAdmin',
+          name: 'collectFeeAdmin',
           isMut: false,
           isSigner: true,
-          isOptional: true,
-        },
-        {
-          name: 'userTokenAccount',
-          isMut: true,
-          isSigner: false,
         },
         {
           name: 'market',
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'bids',
+          name: 'feeSplit',
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'asks',
-          isMut: true,
-          isSigner: false,
+          name: 'destinations',
+          type: {
+            vec: {
+              defined: 'FeeSplitDestination',
+            },
+          },
         },
+      ],
+    },
+    {
+      name: 'setDelegate',
+      docs: [
+        'Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.',
+        '',
+        'The delegate is limited to the given [`DelegateScope`](crate::state::DelegateScope). Orders',
+        'it places can be restricted to `allowed_side` and to `max_quote_native_per_order`',
+        '(0 = no limit, asks are valued at their limit price, or at the best bid or oracle',
+        'price when higher). After `expiry_timestamp` the delegate is ignored (0 = never).',
+        'Removing the delegate resets the permissions.',
+        '',
+        'The permissions are stored in the',
+        '[`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account, which is required',
+        'for anything but an unrestricted delegate.',
+      ],
+      accounts: [
         {
-          name: 'eventHeap',
+          name: 'owner',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'marketVault',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'oracleA',
+          name: 'delegateAccount',
           isMut: false,
           isSigner: false,
           isOptional: true,
         },
         {
-          name: 'oracleB',
-          isMut: false,
+          name: 'openOrdersDelegates',
+          isMut: true,
           isSigner: false,
           isOptional: true,
         },
-        {
-          name: 'tokenProgram',
-          isMut: false,
-          isSigner: false,
-        },
       ],
       args: [
         {
-          name: 'args',
+          name: 'scope',
           type: {
-            defined: 'PlaceOrderPeggedArgs',
+            defined: 'DelegateScope',
+          },
+        },
+        {
+          name: 'allowedSide',
+          type: {
+            option: {
+              defined: 'Side',
+            },
           },
         },
+        {
+          name: 'maxQuoteNativePerOrder',
+          type: 'u64',
+        },
+        {
+          name: 'expiryTimestamp',
+          type: 'u64',
+        },
       ],
-      returns: {
-        option: 'u128',
-      },
     },
     {
-      name: 'placeTakeOrder',
+      name: 'addDelegate',
       docs: [
-        'Place an order that shall take existing liquidity off of the book, not',
-        'add a new order off the book.',
+        'Add a delegate to the delegate list of an open orders account, in addition to the',
+        '[`delegate`](crate::state::OpenOrdersAccount::delegate). The permissions work as in',
+        '`set_delegate`.',
         '',
-        'This type of order allows for instant token settlement for the taker.',
+        'The list holds up to [`MAX_DELEGATES`](crate::state::MAX_DELEGATES) entries in the',
+        '[`OpenOrdersDelegates`](crate::state::OpenOrdersDelegates) account.',
       ],
       accounts: [
         {
-          name: 'signer',
-          isMut: true,
+          name: 'owner',
+          isMut: false,
           isSigner: true,
         },
         {
-          name: 'penaltyPayer',
-          isMut: true,
-          isSigner: true,
+          name: 'openOrdersAccount',
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: 'market',
+          name: 'openOrdersDelegates',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketAuthority',
+          name: 'delegateAccount',
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'bids',
-          isMut: true,
-          isSigner: false,
+          name: 'scope',
+          type: {
+            defined: 'DelegateScope',
+          },
         },
         {
-          name: 'asks',
-          isMut: true,
-          isSigner: false,
+          name: 'allowedSide',
+          type: {
+            option: {
+              defined: 'Side',
+            },
+          },
         },
         {
-          name: 'marketBaseVault',
-          isMut: true,
-          isSigner: false,
+          name: 'maxQuoteNativePerOrder',
+          type: 'u64',
         },
         {
-          name: 'marketQuoteVault',
-          isMut: true,
-          isSigner: false,
+          name: 'expiryTimestamp',
+          type: 'u64',
         },
+      ],
+    },
+    {
+      name: 'removeDelegate',
+      docs: [
+        'Remove a delegate from the delegate list of an open orders account.',
+      ],
+      accounts: [
         {
-          name: 'eventHeap',
-          isMut: true,
-          isSigner: false,
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
         },
         {
-          name: 'userBaseAccount',
-          isMut: true,
+          name: 'openOrdersAccount',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'userQuoteAccount',
+          name: 'openOrdersDelegates',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'oracleA',
-          isMut: false,
-          isSigner: false,
-          isOptional: true,
-        },
-        {
-          name: 'oracleB',
+          name: 'delegateAccount',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
+      ],
+      args: [],
+    },
+    {
+      name: 'setMarketExpired',
+      docs: [
+        'Set market to expired before pruning orders and closing the market (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ],
+      accounts: [
         {
-          name: 'tokenProgram',
+          name: 'closeMarketAdmin',
           isMut: false,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'systemProgram',
-          isMut: false,
+          name: 'market',
+          isMut: true,
           isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: 'setMarketStatus',
+      docs: [
+        'Restrict the order instructions of the market without expiring it (only',
+        '[`market_status_admin`](crate::state::Market::market_status_admin)).',
+        '',
+        '`PostOnly` only accepts orders that never take, `CancelOnly` only accepts',
+        'cancels and `Halted` rejects cancels as well. Setting the status back to',
+        '`Active` lifts all restrictions.',
+      ],
+      accounts: [
         {
-          name: 'openOrdersAdmin',
+          name: 'marketStatusAdmin',
           isMut: false,
           isSigner: true,
-          isOptional: true,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
         },
       ],
       args: [
         {
-          name: 'args',
+          name: 'status',
           type: {
-            defined: 'PlaceTakeOrderArgs',
+            defined: 'MarketStatus',
           },
         },
       ],
     },
     {
-      name: 'consumeEvents',
+      name: 'setMarketStatusAdmin',
       docs: [
-        'Process up to `limit` [events](crate::state::AnyEvent).',
-        '',
-        "When a user places a 'take' order, they do not know beforehand which",
-        "market maker will have placed the 'make' order that they get executed",
-        "against. This prevents them from passing in a market maker's",
-        '[`OpenOrdersAccount`](crate::state::OpenOrdersAccount), which is needed',
-        'to credit/debit the relevant tokens to/from the maker. As such, Openbook',
-        "uses a 'crank' system, where `place_order` only emits events, and",
-        '`consume_events` handles token settlement.',
-        '',
-        'Currently, there are two types of events: [`FillEvent`](crate::state::FillEvent)s',
-        'and [`OutEvent`](crate::state::OutEvent)s.',
-        '',
-        'A `FillEvent` is emitted when an order is filled, and it is handled by',
-        'debiting whatever the taker is selling from the taker and crediting',
-        'it to the maker, and debiting whatever the taker is buying from the',
-        'maker and crediting it to the taker. Note that *no tokens are moved*,',
-        "these are just debits and credits to each party's [`Position`](crate::state::Position).",
+        'Set or clear the [`market_status_admin`](crate::state::Market::market_status_admin)',
+        'of a market (only [`collect_fee_admin`](crate::state::Market::collect_fee_admin)).',
         '',
-        'An `OutEvent` is emitted when a limit order needs to be removed from',
-        'the book during a `place_order` invocation, and it is handled by',
-        'crediting whatever the maker would have sold (quote token in a bid,',
-        'base token in an ask) back to the maker.',
+        'Lets markets created without a status admin get one later.',
       ],
       accounts: [
         {
-          name: 'consumeEventsAdmin',
+          name: 'collectFeeAdmin',
           isMut: false,
           isSigner: true,
-          isOptional: true,
         },
         {
           name: 'market',
@@ -4764,29 +9018,37 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'eventHeap',
-          isMut: true,
+          name: 'marketStatusAdmin',
+          isMut: false,
           isSigner: false,
+          isOptional: true,
         },
       ],
-      args: [
-        {
-          name: 'limit',
-          type: 'u64',
-        },
-      ],
+      args: [],
     },
     {
-      name: 'consumeGivenEvents',
+      name: 'createAccessGate',
       docs: [
-        'Process the [events](crate::state::AnyEvent) at the given positions.',
+        'Gate a market behind an allowlist or a credential token, as its',
+        '[`market_status_admin`](crate::state::Market::market_status_admin).',
+        '',
+        'Open orders accounts then need an [`AccessPass`](crate::state::AccessPass) created by',
+        '`admin`, or a token account of `credential_mint` with a balance, when they are created.',
+        'Only accounts that passed the gate can place orders. Every order re-checks the gate, so',
+        '`place_order`, `edit_order` and `cancel_all_and_place_orders` need the access gate and the',
+        "owner's access pass or credential token account in their remaining accounts. Take orders",
+        "have no open orders account, `place_take_order` checks the signer's proof instead.",
       ],
       accounts: [
         {
-          name: 'consumeEventsAdmin',
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'marketStatusAdmin',
           isMut: false,
           isSigner: true,
-          isOptional: true,
         },
         {
           name: 'market',
@@ -4794,175 +9056,145 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'accessGate',
           isMut: true,
           isSigner: false,
         },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
       ],
       args: [
         {
-          name: 'slots',
+          name: 'kind',
           type: {
-            vec: 'u64',
+            defined: 'AccessGate',
           },
         },
+        {
+          name: 'admin',
+          type: 'publicKey',
+        },
+        {
+          name: 'credentialMint',
+          type: 'publicKey',
+        },
       ],
     },
     {
-      name: 'cancelOrder',
+      name: 'addAccessPass',
       docs: [
-        'Cancel an order by its `order_id`.',
-        '',
-        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
-        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+        'Allow an owner to create open orders accounts and place orders on an allowlist gated',
+        'market.',
       ],
       accounts: [
         {
-          name: 'signer',
-          isMut: false,
+          name: 'payer',
+          isMut: true,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
-          isMut: true,
-          isSigner: false,
+          name: 'admin',
+          isMut: false,
+          isSigner: true,
         },
         {
-          name: 'market',
+          name: 'accessGate',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'bids',
-          isMut: true,
+          name: 'owner',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'accessPass',
           isMut: true,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'orderId',
-          type: 'u128',
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
         },
       ],
+      args: [],
     },
     {
-      name: 'cancelOrderByClientOrderId',
+      name: 'removeAccessPass',
       docs: [
-        'Cancel an order by its `client_order_id`.',
-        '',
-        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
-        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+        "Remove the access pass of an owner. Its existing open orders accounts can't place",
+        'orders anymore, but can still cancel orders and settle funds.',
       ],
       accounts: [
         {
-          name: 'signer',
+          name: 'admin',
           isMut: false,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'market',
+          name: 'accessGate',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'bids',
+          name: 'accessPass',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'solDestination',
           isMut: true,
           isSigner: false,
         },
       ],
-      args: [
-        {
-          name: 'clientOrderId',
-          type: 'u64',
-        },
-      ],
-      returns: 'i64',
+      args: [],
     },
     {
-      name: 'cancelAllOrders',
-      docs: ['Cancel up to `limit` orders, optionally filtering by side'],
+      name: 'setOpenOrdersAccess',
+      docs: [
+        'Grant or revoke the access of an open orders account to a gated market. Revoked',
+        'accounts can only cancel orders and settle funds.',
+      ],
       accounts: [
         {
-          name: 'signer',
+          name: 'admin',
           isMut: false,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'market',
+          name: 'accessGate',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'bids',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'asks',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
       ],
       args: [
         {
-          name: 'sideOption',
-          type: {
-            option: {
-              defined: 'Side',
-            },
-          },
-        },
-        {
-          name: 'limit',
-          type: 'u8',
+          name: 'allowed',
+          type: 'bool',
         },
       ],
     },
     {
-      name: 'deposit',
+      name: 'pruneOrders',
       docs: [
-        "Deposit a certain amount of `base` and `quote` lamports into one's",
-        '[`Position`](crate::state::Position).',
-        '',
-        'Makers might wish to `deposit`, rather than have actual tokens moved for',
-        'each trade, in order to reduce CUs.',
+        'Remove orders from the book when the market is expired (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
       ],
       accounts: [
         {
-          name: 'owner',
+          name: 'closeMarketAdmin',
           isMut: false,
           isSigner: true,
         },
-        {
-          name: 'userBaseAccount',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'userQuoteAccount',
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: 'openOrdersAccount',
           isMut: true,
@@ -4970,61 +9202,39 @@ export const IDL: OpenbookV2 = {
         },
         {
           name: 'market',
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'marketBaseVault',
+          name: 'bids',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'asks',
           isMut: true,
           isSigner: false,
         },
-        {
-          name: 'tokenProgram',
-          isMut: false,
-          isSigner: false,
-        },
       ],
       args: [
         {
-          name: 'baseAmount',
-          type: 'u64',
-        },
-        {
-          name: 'quoteAmount',
-          type: 'u64',
+          name: 'limit',
+          type: 'u8',
         },
       ],
     },
     {
-      name: 'refill',
+      name: 'forcePruneOrders',
       docs: [
-        'Refill a certain amount of `base` and `quote` lamports. The amount being passed is the',
-        'total lamports that the [`Position`](crate::state::Position) will have.',
-        '',
-        'Makers might wish to `refill`, rather than have actual tokens moved for',
-        'each trade, in order to reduce CUs.',
+        'Remove orders from the book when the market is expired, with the same permissions',
+        'as `force_settle_funds`.',
       ],
       accounts: [
         {
-          name: 'owner',
+          name: 'signer',
           isMut: false,
           isSigner: true,
         },
-        {
-          name: 'userBaseAccount',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'userQuoteAccount',
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: 'openOrdersAccount',
           isMut: true,
@@ -5032,48 +9242,41 @@ export const IDL: OpenbookV2 = {
         },
         {
           name: 'market',
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'marketBaseVault',
+          name: 'bids',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'asks',
           isMut: true,
           isSigner: false,
         },
-        {
-          name: 'tokenProgram',
-          isMut: false,
-          isSigner: false,
-        },
       ],
       args: [
         {
-          name: 'baseAmount',
-          type: 'u64',
-        },
-        {
-          name: 'quoteAmount',
-          type: 'u64',
+          name: 'limit',
+          type: 'u8',
         },
       ],
     },
     {
-      name: 'settleFunds',
-      docs: ['Withdraw any available tokens.'],
+      name: 'heartbeat',
+      docs: [
+        'Set the heartbeat deadline of an open orders account and its resting orders',
+        'to now + `timeout` seconds.',
+        '',
+        "Once the deadline passes without another heartbeat, the account's resting",
+        'orders are treated as invalid and dropped by takers, and anyone may cancel',
+        'them with `cancel_orders_heartbeat_expired`. A `timeout` of 0 disables it.',
+      ],
       accounts: [
         {
-          name: 'owner',
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: 'penaltyPayer',
-          isMut: true,
+          name: 'signer',
+          isMut: false,
           isSigner: true,
         },
         {
@@ -5083,75 +9286,83 @@ export const IDL: OpenbookV2 = {
         },
         {
           name: 'market',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'marketAuthority',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'marketBaseVault',
+          name: 'bids',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'asks',
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'userBaseAccount',
+          name: 'timeout',
+          type: 'u64',
+        },
+      ],
+    },
+    {
+      name: 'cancelOrdersHeartbeatExpired',
+      docs: [
+        'Cancel up to `limit` orders of an open orders account whose heartbeat',
+        'deadline has passed (permissionless).',
+      ],
+      accounts: [
+        {
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'userQuoteAccount',
-          isMut: true,
+          name: 'market',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'referrerAccount',
+          name: 'bids',
           isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'tokenProgram',
-          isMut: false,
+          name: 'asks',
+          isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'systemProgram',
-          isMut: false,
-          isSigner: false,
+          name: 'limit',
+          type: 'u8',
         },
       ],
-      args: [],
     },
     {
-      name: 'settleFundsExpired',
+      name: 'setMmp',
       docs: [
-        'Withdraw any available tokens when the market is expired (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+        'Configure the market maker protection of an open orders account.',
+        '',
+        "When the account's resting orders get filled for `base_lots_limit` base lots or",
+        '`fill_count_limit` times within `window_secs` seconds, the account is frozen: its',
+        'orders are treated as invalid and dropped by takers until the owner calls `reset_mmp`.',
+        'A limit of 0 is ignored, a `window_secs` of 0 disables the protection. Fails while',
+        'the account is frozen.',
+        '',
+        'Fills are counted while matching, so takers have to pass the open orders accounts',
+        'of makers with market maker protection in the remaining accounts. The orders of',
+        "makers whose account is missing are skipped, and the rest of the order isn't posted.",
       ],
       accounts: [
         {
-          name: 'closeMarketAdmin',
+          name: 'signer',
           isMut: false,
           isSigner: true,
         },
-        {
-          name: 'owner',
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: 'penaltyPayer',
-          isMut: true,
-          isSigner: true,
-        },
         {
           name: 'openOrdersAccount',
           isMut: true,
@@ -5159,550 +9370,1134 @@ export const IDL: OpenbookV2 = {
         },
         {
           name: 'market',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'marketAuthority',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'marketBaseVault',
+          name: 'bids',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'asks',
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'userBaseAccount',
-          isMut: true,
-          isSigner: false,
+          name: 'windowSecs',
+          type: 'u16',
         },
         {
-          name: 'userQuoteAccount',
-          isMut: true,
-          isSigner: false,
+          name: 'baseLotsLimit',
+          type: 'i64',
         },
         {
-          name: 'referrerAccount',
-          isMut: true,
-          isSigner: false,
-          isOptional: true,
+          name: 'fillCountLimit',
+          type: 'u16',
         },
+      ],
+    },
+    {
+      name: 'resetMmp',
+      docs: [
+        'Unfreeze an open orders account after its market maker protection was triggered.',
+        'Only the owner can do this.',
+      ],
+      accounts: [
         {
-          name: 'tokenProgram',
+          name: 'owner',
           isMut: false,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'systemProgram',
-          isMut: false,
+          name: 'openOrdersAccount',
+          isMut: true,
           isSigner: false,
         },
       ],
       args: [],
     },
     {
-      name: 'sweepFees',
-      docs: ["Sweep fees, as a [`Market`](crate::state::Market)'s admin."],
+      name: 'runAuction',
+      docs: [
+        'Run the auction of a market whose auction phase ended (permissionless).',
+        '',
+        'Computes the uniform price that maximizes the matched volume of the crossing',
+        'bids and asks and fills up to `limit` pairs of orders at that price. Both sides',
+        'are makers and get [`FillEvent`](crate::state::FillEvent)s, processed directly',
+        'for the open orders accounts passed as remaining accounts. Once no crossing',
+        'orders are left, the market switches to continuous trading.',
+        '',
+        'The accounts of crossing makers with market maker protection must be passed, the',
+        'orders of frozen makers are dropped instead of filled.',
+      ],
       accounts: [
-        {
-          name: 'collectFeeAdmin',
-          isMut: false,
-          isSigner: true,
-        },
         {
           name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketAuthority',
-          isMut: false,
+          name: 'bids',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'asks',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'tokenReceiverAccount',
+          name: 'eventHeap',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'tokenProgram',
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'oracleB',
           isMut: false,
           isSigner: false,
+          isOptional: true,
+        },
+      ],
+      args: [
+        {
+          name: 'limit',
+          type: 'u8',
         },
       ],
-      args: [],
     },
     {
-      name: 'setDelegate',
-      docs: [
-        'Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.',
-      ],
+      name: 'stubOracleCreate',
       accounts: [
         {
-          name: 'owner',
+          name: 'payer',
           isMut: true,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'oracle',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'delegateAccount',
+          name: 'mint',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
-      ],
-      args: [],
-    },
-    {
-      name: 'setMarketExpired',
-      docs: [
-        'Set market to expired before pruning orders and closing the market (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
-      ],
-      accounts: [
         {
-          name: 'closeMarketAdmin',
+          name: 'systemProgram',
           isMut: false,
-          isSigner: true,
+          isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'market',
-          isMut: true,
-          isSigner: false,
+          name: 'price',
+          type: 'f64',
         },
       ],
-      args: [],
     },
     {
-      name: 'pruneOrders',
-      docs: [
-        'Remove orders from the book when the market is expired (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
-      ],
+      name: 'stubOracleClose',
       accounts: [
         {
-          name: 'closeMarketAdmin',
+          name: 'owner',
           isMut: false,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'oracle',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'market',
-          isMut: false,
+          name: 'solDestination',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'bids',
-          isMut: true,
+          name: 'tokenProgram',
+          isMut: false,
           isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: 'stubOracleSet',
+      accounts: [
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
         {
-          name: 'asks',
+          name: 'oracle',
           isMut: true,
           isSigner: false,
         },
       ],
       args: [
         {
-          name: 'limit',
-          type: 'u8',
+          name: 'price',
+          type: 'f64',
         },
       ],
     },
+  ],
+  accounts: [
+    {
+      name: 'marketAccessGate',
+      docs: [
+        'Access gate of a permissioned market, checked when creating open orders accounts and',
+        'on each order instead of having the open orders admin co-sign every order',
+      ],
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'bump',
+            type: 'u8',
+          },
+          {
+            name: 'market',
+            type: 'publicKey',
+          },
+          {
+            name: 'admin',
+            docs: [
+              'Creates and removes access passes, and grants or revokes open orders accounts',
+            ],
+            type: 'publicKey',
+          },
+          {
+            name: 'kind',
+            docs: ['See [`AccessGate`]'],
+            type: 'u8',
+          },
+          {
+            name: 'credentialMint',
+            docs: [
+              'Mint of the credential token for [`AccessGate::CredentialToken`]',
+            ],
+            type: 'publicKey',
+          },
+        ],
+      },
+    },
+    {
+      name: 'accessPass',
+      docs: [
+        'Allows `owner` to create open orders accounts on an allowlist gated market',
+      ],
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'bump',
+            type: 'u8',
+          },
+          {
+            name: 'market',
+            type: 'publicKey',
+          },
+          {
+            name: 'owner',
+            type: 'publicKey',
+          },
+        ],
+      },
+    },
     {
-      name: 'stubOracleCreate',
-      accounts: [
-        {
-          name: 'payer',
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: 'owner',
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: 'oracle',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'mint',
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: 'systemProgram',
-          isMut: false,
-          isSigner: false,
-        },
-      ],
-      args: [
-        {
-          name: 'price',
-          type: 'f64',
-        },
+      name: 'openOrdersDelegates',
+      docs: [
+        'Delegate permissions of an open orders account, a PDA of the open orders account',
+        '',
+        'Accounts without one only have an unrestricted [`OpenOrdersAccount::delegate`]. Once',
+        'created, it holds the limits of that delegate and a list of further delegates, and',
+        "delegates are only recognized when it's passed in the remaining accounts.",
       ],
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'openOrdersAccount',
+            type: 'publicKey',
+          },
+          {
+            name: 'bump',
+            type: 'u8',
+          },
+          {
+            name: 'padding',
+            type: {
+              array: ['u8', 7],
+            },
+          },
+          {
+            name: 'delegatePermissions',
+            docs: ['Limits of [`OpenOrdersAccount::delegate`]'],
+            type: {
+              defined: 'DelegatePermissions',
+            },
+          },
+          {
+            name: 'entries',
+            type: {
+              array: [
+                {
+                  defined: 'DelegateEntry',
+                },
+                4,
+              ],
+            },
+          },
+          {
+            name: 'reserved',
+            type: {
+              array: ['u8', 64],
+            },
+          },
+        ],
+      },
     },
     {
-      name: 'stubOracleClose',
-      accounts: [
-        {
-          name: 'owner',
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: 'oracle',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'solDestination',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'tokenProgram',
-          isMut: false,
-          isSigner: false,
-        },
+      name: 'feeSplit',
+      docs: [
+        'How `sweep_fees` distributes the fees of a market, created by its `collect_fee_admin`',
       ],
-      args: [],
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'bump',
+            type: 'u8',
+          },
+          {
+            name: 'market',
+            type: 'publicKey',
+          },
+          {
+            name: 'destinations',
+            type: {
+              vec: {
+                defined: 'FeeSplitDestination',
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: 'market',
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'bump',
+            docs: ['PDA bump'],
+            type: 'u8',
+          },
+          {
+            name: 'baseDecimals',
+            docs: [
+              'Number of decimals used for the base token.',
+              '',
+              "Used to convert the oracle's price into a native/native price.",
+            ],
+            type: 'u8',
+          },
+          {
+            name: 'quoteDecimals',
+            type: 'u8',
+          },
+          {
+            name: 'padding1',
+            type: {
+              array: ['u8', 5],
+            },
+          },
+          {
+            name: 'marketAuthority',
+            type: 'publicKey',
+          },
+          {
+            name: 'timeExpiry',
+            docs: [
+              'No expiry = 0. Market will expire and no trading allowed after time_expiry',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'collectFeeAdmin',
+            docs: ['Admin who can collect fees from the market'],
+            type: 'publicKey',
+          },
+          {
+            name: 'openOrdersAdmin',
+            docs: ['Admin who must sign off on all order creations'],
+            type: {
+              defined: 'NonZeroPubkeyOption',
+            },
+          },
+          {
+            name: 'consumeEventsAdmin',
+            docs: ['Admin who must sign off on all event consumptions'],
+            type: {
+              defined: 'NonZeroPubkeyOption',
+            },
+          },
+          {
+            name: 'closeMarketAdmin',
+            docs: [
+              'Admin who can set market expired, prune orders and close the market',
+            ],
+            type: {
+              defined: 'NonZeroPubkeyOption',
+            },
+          },
+          {
+            name: 'name',
+            docs: ['Name. Trailing zero bytes are ignored.'],
+            type: {
+              array: ['u8', 16],
+            },
+          },
+          {
+            name: 'bids',
+            docs: ['Address of the BookSide account for bids'],
+            type: 'publicKey',
+          },
+          {
+            name: 'asks',
+            docs: ['Address of the BookSide account for asks'],
+            type: 'publicKey',
+          },
+          {
+            name: 'eventHeap',
+            docs: ['Address of the EventHeap account'],
+            type: 'publicKey',
+          },
+          {
+            name: 'oracleA',
+            docs: ['Oracles account address'],
+            type: {
+              defined: 'NonZeroPubkeyOption',
+            },
+          },
+          {
+            name: 'oracleB',
+            type: {
+              defined: 'NonZeroPubkeyOption',
+            },
+          },
+          {
+            name: 'oracleConfig',
+            docs: ['Oracle configuration'],
+            type: {
+              defined: 'OracleConfig',
+            },
+          },
+          {
+            name: 'quoteLotSize',
+            docs: [
+              'Number of quote native in a quote lot. Must be a power of 10.',
+              '',
+              'Primarily useful for increasing the tick size on the market: A lot price',
+              'of 1 becomes a native price of quote_lot_size/base_lot_size becomes a',
+              'ui price of quote_lot_size*base_decimals/base_lot_size/quote_decimals.',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'baseLotSize',
+            docs: [
+              'Number of base native in a base lot. Must be a power of 10.',
+              '',
+              'Example: If base decimals for the underlying asset is 6, base lot size',
+              'is 100 and and base position lots is 10_000 then base position native is',
+              '1_000_000 and base position ui is 1.',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'seqNum',
+            docs: ['Total number of orders seen'],
+            type: 'u64',
+          },
+          {
+            name: 'registrationTime',
+            docs: ['Timestamp in seconds that the market was registered at.'],
+            type: 'i64',
+          },
+          {
+            name: 'makerFee',
+            docs: [
+              'Fees',
+              '',
+              'Fee (in 10^-6) when matching maker orders.',
+              'maker_fee < 0 it means some of the taker_fees goes to the maker',
+              'maker_fee > 0, it means no taker_fee to the maker, and maker fee goes to the referral',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'takerFee',
+            docs: ['Fee (in 10^-6) for taker orders, always >= 0.'],
+            type: 'i64',
+          },
+          {
+            name: 'feesAccrued',
+            docs: ['Total fees accrued in native quote'],
+            type: 'u128',
+          },
+          {
+            name: 'feesToReferrers',
+            docs: ['Total fees settled in native quote'],
+            type: 'u128',
+          },
+          {
+            name: 'referrerRebatesAccrued',
+            docs: ['Referrer rebates to be distributed'],
+            type: 'u64',
+          },
+          {
+            name: 'feesAvailable',
+            docs: ['Fees generated and available to withdraw via sweep_fees'],
+            type: 'u64',
+          },
+          {
+            name: 'makerVolume',
+            docs: [
+              'Cumulative maker volume (same as taker volume) in quote native units',
+            ],
+            type: 'u128',
+          },
+          {
+            name: 'takerVolumeWoOo',
+            docs: [
+              'Cumulative taker volume in quote native units due to place take orders',
+            ],
+            type: 'u128',
+          },
+          {
+            name: 'baseMint',
+            type: 'publicKey',
+          },
+          {
+            name: 'quoteMint',
+            type: 'publicKey',
+          },
+          {
+            name: 'marketBaseVault',
+            type: 'publicKey',
+          },
+          {
+            name: 'baseDepositTotal',
+            type: 'u64',
+          },
+          {
+            name: 'marketQuoteVault',
+            type: 'publicKey',
+          },
+          {
+            name: 'quoteDepositTotal',
+            type: 'u64',
+          },
+          {
+            name: 'auctionStartTs',
+            docs: ['Start of the current or next auction phase.'],
+            type: 'i64',
+          },
+          {
+            name: 'auctionEndTs',
+            docs: [
+              'End of the current or next auction phase. No auction = 0.',
+              '',
+              'During an auction phase orders are posted to the book without matching.',
+              'After it ended, `run_auction` fills all crossing orders at a uniform price',
+              'and switches the market to continuous trading.',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'auctionPriceLots',
+            docs: [
+              'Clearing price of the auction that is being run, 0 if not computed yet',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'auctionDurationSecs',
+            docs: ['Length of an auction phase in seconds'],
+            type: 'u32',
+          },
+          {
+            name: 'auctionIntervalSecs',
+            docs: [
+              'Seconds of continuous trading between recurring auction phases. Opening auction only = 0',
+            ],
+            type: 'u32',
+          },
+          {
+            name: 'marketStatusAdmin',
+            docs: ['Admin who can change the market status'],
+            type: {
+              defined: 'NonZeroPubkeyOption',
+            },
+          },
+          {
+            name: 'status',
+            docs: ['See [`MarketStatus`]'],
+            type: 'u8',
+          },
+          {
+            name: 'hasPenaltyEventHeap',
+            docs: [
+              'Set once `set_crank_rewards` configured `penalty_event_heap`',
+            ],
+            type: 'u8',
+          },
+          {
+            name: 'padding2',
+            type: {
+              array: ['u8', 2],
+            },
+          },
+          {
+            name: 'penaltyEventHeap',
+            docs: [
+              'Lamports a taker pays for every transaction that adds to the event heap, when',
+              '`has_penalty_event_heap` is set. [`PENALTY_EVENT_HEAP`] otherwise.',
+            ],
+            type: 'u32',
+          },
+          {
+            name: 'minBaseLotsPerOrder',
+            docs: [
+              "Minimum base lots of an order posted to the book. Smaller remainders aren't posted.",
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'minQuoteNotional',
+            docs: [
+              'Minimum value in quote native of an order posted to the book. Smaller remainders',
+              "aren't posted.",
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'maxBaseLotsPerOrder',
+            docs: [
+              'Maximum base lots of an order posted to the book. No limit = 0',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'timeSetExpired',
+            docs: [
+              "Timestamp at which `set_market_expired` was called, 0 if it wasn't or if it was",
+              'before this field existed',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'hasFeeSplit',
+            docs: [
+              'Set once a [`FeeSplit`](crate::state::FeeSplit) was created, `sweep_fees` then',
+              'distributes the fees according to it',
+            ],
+            type: 'u8',
+          },
+          {
+            name: 'accessGate',
+            docs: [
+              'See [`AccessGate`](crate::state::AccessGate), set by `create_access_gate`',
+            ],
+            type: 'u8',
+          },
+          {
+            name: 'maxReferrerShareBps',
+            docs: [
+              'Maximum share of the taker fees, after maker rebates, that bound referrers get on',
+              'this market, in basis points. No referrer rebates = 0, which is where every market',
+              'starts until `set_max_referrer_share` is called',
+            ],
+            type: 'u16',
+          },
+          {
+            name: 'crankRewardPerEvent',
+            docs: [
+              'Lamports paid from the penalties to whoever consumes an event. No reward = 0',
+            ],
+            type: 'u32',
+          },
+          {
+            name: 'penaltyLamportsAccrued',
+            docs: [
+              'Penalty lamports collected into the market account and not yet paid as crank',
+              'rewards',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'reserved',
+            type: {
+              array: ['u8', 8],
+            },
+          },
+        ],
+      },
     },
     {
-      name: 'stubOracleSet',
-      accounts: [
-        {
-          name: 'owner',
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: 'oracle',
-          isMut: true,
-          isSigner: false,
-        },
-      ],
-      args: [
-        {
-          name: 'price',
-          type: 'f64',
-        },
-      ],
+      name: 'openOrdersAccount',
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'owner',
+            type: 'publicKey',
+          },
+          {
+            name: 'market',
+            type: 'publicKey',
+          },
+          {
+            name: 'name',
+            type: {
+              array: ['u8', 32],
+            },
+          },
+          {
+            name: 'delegate',
+            type: {
+              defined: 'NonZeroPubkeyOption',
+            },
+          },
+          {
+            name: 'accountNum',
+            type: 'u32',
+          },
+          {
+            name: 'bump',
+            type: 'u8',
+          },
+          {
+            name: 'version',
+            type: 'u8',
+          },
+          {
+            name: 'access',
+            docs: ['See [`OpenOrdersAccess`]'],
+            type: 'u8',
+          },
+          {
+            name: 'flags',
+            docs: ['Side accounts of the account, see `OPEN_ORDERS_FLAG_*`'],
+            type: 'u8',
+          },
+          {
+            name: 'position',
+            type: {
+              defined: 'Position',
+            },
+          },
+          {
+            name: 'openOrders',
+            type: {
+              array: [
+                {
+                  defined: 'OpenOrder',
+                },
+                24,
+              ],
+            },
+          },
+        ],
+      },
     },
-  ],
-  accounts: [
     {
-      name: 'market',
+      name: 'openOrdersIndexer',
       type: {
         kind: 'struct',
         fields: [
           {
             name: 'bump',
-            docs: ['PDA bump'],
-            type: 'u8',
-          },
-          {
-            name: 'baseDecimals',
-            docs: [
-              'Number of decimals used for the base token.',
-              '',
-              "Used to convert the oracle's price into a native/native price.",
-            ],
             type: 'u8',
           },
           {
-            name: 'quoteDecimals',
-            type: 'u8',
+            name: 'createdCounter',
+            type: 'u32',
           },
           {
-            name: 'padding1',
+            name: 'addresses',
             type: {
-              array: ['u8', 5],
+              vec: 'publicKey',
             },
           },
+        ],
+      },
+    },
+    {
+      name: 'stubOracle',
+      type: {
+        kind: 'struct',
+        fields: [
           {
-            name: 'marketAuthority',
+            name: 'owner',
             type: 'publicKey',
           },
           {
-            name: 'timeExpiry',
-            docs: [
-              'No expiry = 0. Market will expire and no trading allowed after time_expiry',
-            ],
+            name: 'mint',
+            type: 'publicKey',
+          },
+          {
+            name: 'price',
+            type: 'f64',
+          },
+          {
+            name: 'lastUpdateTs',
             type: 'i64',
           },
           {
-            name: 'collectFeeAdmin',
-            docs: ['Admin who can collect fees from the market'],
-            type: 'publicKey',
+            name: 'lastUpdateSlot',
+            type: 'u64',
           },
           {
-            name: 'openOrdersAdmin',
-            docs: ['Admin who must sign off on all order creations'],
-            type: {
-              defined: 'NonZeroPubkeyOption',
-            },
+            name: 'deviation',
+            type: 'f64',
           },
           {
-            name: 'consumeEventsAdmin',
-            docs: ['Admin who must sign off on all event consumptions'],
+            name: 'reserved',
             type: {
-              defined: 'NonZeroPubkeyOption',
+              array: ['u8', 104],
             },
           },
+        ],
+      },
+    },
+    {
+      name: 'bookSide',
+      docs: [
+        'This is the layout of book sides with the default capacity. Book sides of any capacity',
+        'are used through [`DynBookSide`], which this derefs to.',
+      ],
+      type: {
+        kind: 'struct',
+        fields: [
           {
-            name: 'closeMarketAdmin',
-            docs: [
-              'Admin who can set market expired, prune orders and close the market',
-            ],
+            name: 'roots',
             type: {
-              defined: 'NonZeroPubkeyOption',
+              array: [
+                {
+                  defined: 'OrderTreeRoot',
+                },
+                2,
+              ],
             },
           },
           {
-            name: 'name',
-            docs: ['Name. Trailing zero bytes are ignored.'],
+            name: 'reservedRoots',
             type: {
-              array: ['u8', 16],
+              array: [
+                {
+                  defined: 'OrderTreeRoot',
+                },
+                4,
+              ],
             },
           },
           {
-            name: 'bids',
-            docs: ['Address of the BookSide account for bids'],
-            type: 'publicKey',
+            name: 'reserved',
+            type: {
+              array: ['u8', 256],
+            },
           },
           {
-            name: 'asks',
-            docs: ['Address of the BookSide account for asks'],
-            type: 'publicKey',
+            name: 'nodes',
+            type: {
+              defined: 'OrderTreeNodes',
+            },
           },
+        ],
+      },
+    },
+    {
+      name: 'eventHeap',
+      docs: [
+        'Container for the different EventTypes.',
+        '',
+        'Events are stored in a fixed-array of nodes. Free nodes are connected by a single-linked list',
+        'starting at free_head while used nodes form a circular doubly-linked list starting at',
+        'used_head.',
+        '',
+        'This is the layout of event heaps with the default capacity. Event heaps of any capacity',
+        'are used through [`DynEventHeap`], which this derefs to.',
+      ],
+      type: {
+        kind: 'struct',
+        fields: [
           {
-            name: 'eventHeap',
-            docs: ['Address of the EventHeap account'],
-            type: 'publicKey',
+            name: 'header',
+            type: {
+              defined: 'EventHeapHeader',
+            },
           },
           {
-            name: 'oracleA',
-            docs: ['Oracles account address'],
+            name: 'nodes',
             type: {
-              defined: 'NonZeroPubkeyOption',
+              array: [
+                {
+                  defined: 'EventNode',
+                },
+                600,
+              ],
             },
           },
           {
-            name: 'oracleB',
+            name: 'reserved',
             type: {
-              defined: 'NonZeroPubkeyOption',
+              array: ['u8', 64],
             },
           },
+        ],
+      },
+    },
+    {
+      name: 'poolState',
+      docs: [
+        'The pool state',
+        '',
+        'PDA of `[POOL_SEED, config, token_mint_0, token_mint_1]`',
+        '',
+      ],
+      type: {
+        kind: 'struct',
+        fields: [
           {
-            name: 'oracleConfig',
-            docs: ['Oracle configuration'],
+            name: 'bump',
+            docs: ['Bump to identify PDA'],
             type: {
-              defined: 'OracleConfig',
+              array: ['u8', 1],
             },
           },
           {
-            name: 'quoteLotSize',
-            docs: [
-              'Number of quote native in a quote lot. Must be a power of 10.',
-              '',
-              'Primarily useful for increasing the tick size on the market: A lot price',
-              'of 1 becomes a native price of quote_lot_size/base_lot_size becomes a',
-              'ui price of quote_lot_size*base_decimals/base_lot_size/quote_decimals.',
-            ],
-            type: 'i64',
+            name: 'ammConfig',
+            type: 'publicKey',
           },
           {
-            name: 'baseLotSize',
+            name: 'owner',
+            type: 'publicKey',
+          },
+          {
+            name: 'tokenMint0',
             docs: [
-              'Number of base native in a base lot. Must be a power of 10.',
-              '',
-              'Example: If base decimals for the underlying asset is 6, base lot size',
-              'is 100 and and base position lots is 10_000 then base position native is',
-              '1_000_000 and base position ui is 1.',
+              'Token pair of the pool, where token_mint_0 address < token_mint_1 address',
             ],
-            type: 'i64',
+            type: 'publicKey',
           },
           {
-            name: 'seqNum',
-            docs: ['Total number of orders seen'],
-            type: 'u64',
+            name: 'tokenMint1',
+            type: 'publicKey',
           },
           {
-            name: 'registrationTime',
-            docs: ['Timestamp in seconds that the market was registered at.'],
-            type: 'i64',
+            name: 'tokenVault0',
+            docs: ['Token pair vault'],
+            type: 'publicKey',
           },
           {
-            name: 'makerFee',
-            docs: [
-              'Fees',
-              '',
-              'Fee (in 10^-6) when matching maker orders.',
-              'maker_fee < 0 it means some of the taker_fees goes to the maker',
-              'maker_fee > 0, it means no taker_fee to the maker, and maker fee goes to the referral',
-            ],
-            type: 'i64',
+            name: 'tokenVault1',
+            type: 'publicKey',
           },
           {
-            name: 'takerFee',
-            docs: ['Fee (in 10^-6) for taker orders, always >= 0.'],
-            type: 'i64',
+            name: 'observationKey',
+            docs: ['observation account key'],
+            type: 'publicKey',
           },
           {
-            name: 'feesAccrued',
-            docs: ['Total fees accrued in native quote'],
+            name: 'mintDecimals0',
+            docs: ['mint0 and mint1 decimals'],
+            type: 'u8',
+          },
+          {
+            name: 'mintDecimals1',
+            type: 'u8',
+          },
+          {
+            name: 'tickSpacing',
+            docs: ['The minimum number of ticks between initialized ticks'],
+            type: 'u16',
+          },
+          {
+            name: 'liquidity',
+            docs: ['The currently in range liquidity available to the pool.'],
             type: 'u128',
           },
           {
-            name: 'feesToReferrers',
-            docs: ['Total fees settled in native quote'],
+            name: 'sqrtPriceX64',
+            docs: [
+              'The current price of the pool as a sqrt(token_1/token_0) Q64.64 value',
+            ],
             type: 'u128',
           },
           {
-            name: 'referrerRebatesAccrued',
-            docs: ['Referrer rebates to be distributed'],
-            type: 'u64',
+            name: 'tickCurrent',
+            docs: [
+              'The current tick of the pool, i.e. according to the last tick transition that was run.',
+            ],
+            type: 'i32',
           },
           {
-            name: 'feesAvailable',
-            docs: ['Fees generated and available to withdraw via sweep_fees'],
-            type: 'u64',
+            name: 'observationIndex',
+            docs: ['the most-recently updated index of the observations array'],
+            type: 'u16',
           },
           {
-            name: 'makerVolume',
+            name: 'observationUpdateDuration',
+            type: 'u16',
+          },
+          {
+            name: 'feeGrowthGlobal0X64',
             docs: [
-              'Cumulative maker volume (same as taker volume) in quote native units',
+              'The fee growth as a Q64.64 number, i.e. fees of token_0 and token_1 collected per',
+              'unit of liquidity for the entire life of the pool.',
             ],
             type: 'u128',
           },
           {
-            name: 'takerVolumeWoOo',
+            name: 'feeGrowthGlobal1X64',
+            type: 'u128',
+          },
+          {
+            name: 'protocolFeesToken0',
             docs: [
-              'Cumulative taker volume in quote native units due to place take orders',
+              'The amounts of token_0 and token_1 that are owed to the protocol.',
             ],
-            type: 'u128',
+            type: 'u64',
           },
           {
-            name: 'baseMint',
-            type: 'publicKey',
+            name: 'protocolFeesToken1',
+            type: 'u64',
           },
           {
-            name: 'quoteMint',
-            type: 'publicKey',
+            name: 'swapInAmountToken0',
+            docs: ['The amounts in and out of swap token_0 and token_1'],
+            type: 'u128',
           },
           {
-            name: 'marketBaseVault',
-            type: 'publicKey',
+            name: 'swapOutAmountToken1',
+            type: 'u128',
           },
           {
-            name: 'baseDepositTotal',
-            type: 'u64',
+            name: 'swapInAmountToken1',
+            type: 'u128',
           },
           {
-            name: 'marketQuoteVault',
-            type: 'publicKey',
+            name: 'swapOutAmountToken0',
+            type: 'u128',
+          },
+          {
+            name: 'status',
+            docs: [
+              'Bitwise representation of the state of the pool',
+              'bit0, 1: disable open position and increase liquidity, 0: normal',
+              'bit1, 1: disable decrease liquidity, 0: normal',
+              'bit2, 1: disable collect fee, 0: normal',
+              'bit3, 1: disable collect reward, 0: normal',
+              'bit4, 1: disable swap, 0: normal',
+            ],
+            type: 'u8',
           },
           {
-            name: 'quoteDepositTotal',
-            type: 'u64',
+            name: 'padding',
+            docs: ['Leave blank for future use'],
+            type: {
+              array: ['u8', 7],
+            },
           },
           {
-            name: 'reserved',
+            name: 'rewardInfos',
             type: {
-              array: ['u8', 128],
+              array: [
+                {
+                  defined: 'RewardInfo',
+                },
+                3,
+              ],
             },
           },
-        ],
-      },
-    },
-    {
-      name: 'openOrdersAccount',
-      type: {
-        kind: 'struct',
-        fields: [
           {
-            name: 'owner',
-            type: 'publicKey',
+            name: 'tickArrayBitmap',
+            docs: ['Packed initialized tick array state'],
+            type: {
+              array: ['u64', 16],
+            },
           },
           {
-            name: 'market',
-            type: 'publicKey',
+            name: 'totalFeesToken0',
+            docs: ['except protocol_fee and fund_fee'],
+            type: 'u64',
           },
           {
-            name: 'name',
-            type: {
-              array: ['u8', 32],
-            },
+            name: 'totalFeesClaimedToken0',
+            docs: ['except protocol_fee and fund_fee'],
+            type: 'u64',
           },
           {
-            name: 'delegate',
-            type: {
-              defined: 'NonZeroPubkeyOption',
-            },
+            name: 'totalFeesToken1',
+            type: 'u64',
           },
           {
-            name: 'accountNum',
-            type: 'u32',
+            name: 'totalFeesClaimedToken1',
+            type: 'u64',
           },
           {
-            name: 'bump',
-            type: 'u8',
+            name: 'fundFeesToken0',
+            type: 'u64',
           },
           {
-            name: 'version',
-            type: 'u8',
+            name: 'fundFeesToken1',
+            type: 'u64',
           },
           {
-            name: 'padding',
-            type: {
-              array: ['u8', 2],
-            },
+            name: 'openTime',
+            type: 'u64',
           },
           {
-            name: 'position',
+            name: 'padding1',
             type: {
-              defined: 'Position',
+              array: ['u64', 25],
             },
           },
           {
-            name: 'openOrders',
+            name: 'padding2',
             type: {
-              array: [
-                {
-                  defined: 'OpenOrder',
-                },
-                24,
-              ],
+              array: ['u64', 32],
             },
           },
         ],
       },
     },
     {
-      name: 'openOrdersIndexer',
+      name: 'referrer',
+      docs: [
+        "A referrer that open orders accounts can bind to. It's global if `market` is the",
+        'default pubkey, otherwise it can only be bound on that market.',
+      ],
       type: {
         kind: 'struct',
         fields: [
@@ -5711,150 +10506,197 @@ export const IDL: OpenbookV2 = {
             type: 'u8',
           },
           {
-            name: 'createdCounter',
+            name: 'authority',
+            type: 'publicKey',
+          },
+          {
+            name: 'market',
+            type: 'publicKey',
+          },
+          {
+            name: 'shareBps',
+            docs: [
+              'Share of the taker fees of referred accounts, after maker rebates, in basis points.',
+              "Capped by [`Market::max_referrer_share_bps`], which is 0 until the market's",
+              '`collect_fee_admin` sets it: referrers earn nothing on a market before that.',
+            ],
+            type: 'u16',
+          },
+          {
+            name: 'referredAccounts',
+            docs: ['Open orders accounts currently bound to the referrer'],
             type: 'u32',
           },
           {
-            name: 'addresses',
-            type: {
-              vec: 'publicKey',
-            },
+            name: 'referredTakerVolume',
+            docs: [
+              'Taker volume in quote native of the referred accounts whose rebates were claimed',
+            ],
+            type: 'u128',
+          },
+          {
+            name: 'rebatesClaimed',
+            docs: ['Rebates in quote native claimed so far'],
+            type: 'u128',
           },
         ],
       },
     },
     {
-      name: 'stubOracle',
+      name: 'openOrdersReferral',
+      docs: [
+        'Referrer an open orders account is bound to, a PDA of the open orders account',
+        '',
+        "The referrer's share of the taker fees is set aside here when the account takes, and",
+        'claimed by the referrer with `claim_referrer_rebates`.',
+      ],
       type: {
         kind: 'struct',
         fields: [
           {
-            name: 'owner',
+            name: 'openOrdersAccount',
             type: 'publicKey',
           },
           {
-            name: 'mint',
+            name: 'referrer',
             type: 'publicKey',
           },
           {
-            name: 'price',
-            type: 'f64',
+            name: 'shareBps',
+            docs: ['`Referrer::share_bps` when binding'],
+            type: 'u16',
           },
           {
-            name: 'lastUpdateTs',
-            type: 'i64',
+            name: 'bump',
+            type: 'u8',
           },
           {
-            name: 'lastUpdateSlot',
+            name: 'padding',
+            type: {
+              array: ['u8', 5],
+            },
+          },
+          {
+            name: 'rebatesAvailable',
+            docs: ['Rebates in quote native not claimed by the referrer yet'],
             type: 'u64',
           },
           {
-            name: 'deviation',
-            type: 'f64',
+            name: 'referredTakerVolume',
+            docs: ['Taker volume in quote native since binding'],
+            type: 'u64',
+          },
+          {
+            name: 'referredTakerVolumeClaimed',
+            docs: [
+              "Part of `referred_taker_volume` already added to the referrer's stats",
+            ],
+            type: 'u64',
           },
           {
             name: 'reserved',
             type: {
-              array: ['u8', 104],
+              array: ['u8', 64],
             },
           },
         ],
       },
     },
+  ],
+  types: [
     {
-      name: 'bookSide',
+      name: 'NonZeroPubkeyOption',
+      docs: [
+        'Like `Option`, but implemented for `Pubkey` to be used with `zero_copy`',
+      ],
       type: {
         kind: 'struct',
         fields: [
           {
-            name: 'roots',
-            type: {
-              array: [
-                {
-                  defined: 'OrderTreeRoot',
-                },
-                2,
-              ],
-            },
+            name: 'key',
+            type: 'publicKey',
           },
+        ],
+      },
+    },
+    {
+      name: 'DelegatePermissions',
+      type: {
+        kind: 'struct',
+        fields: [
           {
-            name: 'reservedRoots',
-            type: {
-              array: [
-                {
-                  defined: 'OrderTreeRoot',
-                },
-                4,
-              ],
-            },
+            name: 'scope',
+            docs: ['See [`DelegateScope`]'],
+            type: 'u8',
           },
           {
-            name: 'reserved',
-            type: {
-              array: ['u8', 256],
-            },
+            name: 'sideRestricted',
+            docs: ['If set, the delegate can only place orders on `side`'],
+            type: 'u8',
           },
           {
-            name: 'nodes',
+            name: 'side',
+            type: 'u8',
+          },
+          {
+            name: 'padding',
             type: {
-              defined: 'OrderTreeNodes',
+              array: ['u8', 5],
             },
           },
+          {
+            name: 'maxQuoteNativePerOrder',
+            docs: [
+              'Max value in quote native of a delegate order, 0 if unlimited',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'expiryTimestamp',
+            docs: [
+              'Timestamp after which the delegate is ignored, 0 if it never expires',
+            ],
+            type: 'u64',
+          },
         ],
       },
     },
     {
-      name: 'eventHeap',
-      docs: [
-        'Container for the different EventTypes.',
-        '',
-        'Events are stored in a fixed-array of nodes. Free nodes are connected by a single-linked list',
-        'starting at free_head while used nodes form a circular doubly-linked list starting at',
-        'used_head.',
-      ],
+      name: 'DelegateEntry',
+      docs: ['Entry of the delegate list, free if `delegate` is none'],
       type: {
         kind: 'struct',
         fields: [
           {
-            name: 'header',
-            type: {
-              defined: 'EventHeapHeader',
-            },
-          },
-          {
-            name: 'nodes',
+            name: 'delegate',
             type: {
-              array: [
-                {
-                  defined: 'EventNode',
-                },
-                600,
-              ],
+              defined: 'NonZeroPubkeyOption',
             },
           },
           {
-            name: 'reserved',
+            name: 'permissions',
             type: {
-              array: ['u8', 64],
+              defined: 'DelegatePermissions',
             },
           },
         ],
       },
     },
-  ],
-  types: [
     {
-      name: 'NonZeroPubkeyOption',
-      docs: [
-        'Like `Option`, but implemented for `Pubkey` to be used with `zero_copy`',
-      ],
+      name: 'FeeSplitDestination',
       type: {
         kind: 'struct',
         fields: [
           {
-            name: 'key',
+            name: 'tokenAccount',
+            docs: ['Quote token account receiving the share'],
             type: 'publicKey',
           },
+          {
+            name: 'shareBps',
+            docs: ['Share of the swept fees, in basis points'],
+            type: 'u16',
+          },
         ],
       },
     },
@@ -5892,7 +10734,7 @@ export const IDL: OpenbookV2 = {
           {
             name: 'penaltyHeapCount',
             docs: [
-              'Count of ixs when events are added to the heap',
+              'Count of ixs when events are added to the heap since the last settle',
               'To avoid this, send remaining accounts in order to process the events',
             ],
             type: 'u64',
@@ -5916,10 +10758,74 @@ export const IDL: OpenbookV2 = {
             docs: ['Quote lots in open bids'],
             type: 'i64',
           },
+          {
+            name: 'heartbeatDeadline',
+            docs: [
+              'Timestamp after which resting orders of this account are treated as invalid,',
+              '0 if the heartbeat is disabled',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'mmpBaseLotsLimit',
+            docs: [
+              'Market maker protection: freeze once this many base lots were filled as maker',
+              'within the window, 0 to ignore',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'mmpWindowBaseLots',
+            docs: ['Base lots filled as maker in the current window'],
+            type: 'i64',
+          },
+          {
+            name: 'mmpWindowStart',
+            docs: ['Start timestamp of the current window'],
+            type: 'u64',
+          },
+          {
+            name: 'mmpWindowSecs',
+            docs: [
+              'Length of the window in seconds, 0 if market maker protection is disabled',
+            ],
+            type: 'u16',
+          },
+          {
+            name: 'mmpFillCountLimit',
+            docs: [
+              'Freeze once this many maker fills happened within the window, 0 to ignore',
+            ],
+            type: 'u16',
+          },
+          {
+            name: 'mmpWindowFillCount',
+            docs: ['Maker fills in the current window'],
+            type: 'u16',
+          },
+          {
+            name: 'mmpFrozen',
+            docs: [
+              'While frozen, the resting orders of this account are treated as invalid',
+            ],
+            type: 'u8',
+          },
+          {
+            name: 'mmpPadding',
+            type: 'u8',
+          },
+          {
+            name: 'penaltyLamportsOwed',
+            docs: [
+              'Penalty lamports owed for the heap additions, priced when the events were added',
+              'and paid on settle',
+            ],
+            type: 'u64',
+          },
           {
             name: 'reserved',
             type: {
-              array: ['u8', 64],
+              array: ['u8', 16],
             },
           },
         ],
@@ -6018,7 +10924,8 @@ export const IDL: OpenbookV2 = {
             type: 'u16',
           },
           {
-            name: 'padd',
+            name: 'capacity',
+            docs: ['Number of nodes, [`MAX_NUM_EVENTS`] = 0'],
             type: 'u16',
           },
           {
@@ -6092,13 +10999,17 @@ export const IDL: OpenbookV2 = {
             type: 'u8',
           },
           {
-            name: 'makerSlot',
+            name: 'makerSlot',
+            type: 'u8',
+          },
+          {
+            name: 'auction',
             type: 'u8',
           },
           {
             name: 'padding',
             type: {
-              array: ['u8', 4],
+              array: ['u8', 3],
             },
           },
           {
@@ -6106,7 +11017,7 @@ export const IDL: OpenbookV2 = {
             type: 'u64',
           },
           {
-            name: 'seqNum',
+            name: 'marketSeqNum',
             type: 'u64',
           },
           {
@@ -6286,10 +11197,12 @@ export const IDL: OpenbookV2 = {
             type: 'u16',
           },
           {
-            name: 'padding',
-            type: {
-              array: ['u8', 4],
-            },
+            name: 'heartbeatDeadline',
+            docs: [
+              'Unix timestamp after which the order is invalid because the owner stopped',
+              'sending heartbeats. 0 if the owner has no heartbeat configured.',
+            ],
+            type: 'u32',
           },
           {
             name: 'key',
@@ -6308,8 +11221,27 @@ export const IDL: OpenbookV2 = {
           },
           {
             name: 'timestamp',
-            docs: ['The time the order was placed'],
-            type: 'u64',
+            docs: [
+              'The time the order was placed',
+              '',
+              'A u64 in earlier versions of the layout. Its upper bytes, always zero for past',
+              'timestamps, now hold `mmp_enabled` and padding, so decoders must read a u32.',
+            ],
+            type: 'u32',
+          },
+          {
+            name: 'mmpEnabled',
+            docs: [
+              '1 if the owner has market maker protection enabled. Fills against such orders',
+              "are counted while matching, so the owner's account must be passed.",
+            ],
+            type: 'u8',
+          },
+          {
+            name: 'padding',
+            type: {
+              array: ['u8', 3],
+            },
           },
           {
             name: 'pegLimit',
@@ -6341,9 +11273,13 @@ export const IDL: OpenbookV2 = {
           {
             name: 'data',
             type: {
-              array: ['u8', 87],
+              array: ['u8', 79],
             },
           },
+          {
+            name: 'forceAlign',
+            type: 'u64',
+          },
         ],
       },
     },
@@ -6369,6 +11305,9 @@ export const IDL: OpenbookV2 = {
         'A binary tree on AnyNode::key()',
         '',
         'The key encodes the price in the top 64 bits.',
+        '',
+        'This is the layout of order trees with the default capacity. Order trees of any capacity',
+        'are used through [`DynOrderTreeNodes`], which this derefs to.',
       ],
       type: {
         kind: 'struct',
@@ -6395,10 +11334,15 @@ export const IDL: OpenbookV2 = {
             name: 'freeListHead',
             type: 'u32',
           },
+          {
+            name: 'capacity',
+            docs: ['Number of nodes, [`MAX_ORDERTREE_NODES`] = 0'],
+            type: 'u32',
+          },
           {
             name: 'reserved',
             type: {
-              array: ['u8', 512],
+              array: ['u8', 508],
             },
           },
           {
@@ -6415,6 +11359,74 @@ export const IDL: OpenbookV2 = {
         ],
       },
     },
+    {
+      name: 'RewardInfo',
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'rewardState',
+            docs: ['Reward state'],
+            type: 'u8',
+          },
+          {
+            name: 'openTime',
+            docs: ['Reward open time'],
+            type: 'u64',
+          },
+          {
+            name: 'endTime',
+            docs: ['Reward end time'],
+            type: 'u64',
+          },
+          {
+            name: 'lastUpdateTime',
+            docs: ['Reward last update time'],
+            type: 'u64',
+          },
+          {
+            name: 'emissionsPerSecondX64',
+            docs: [
+              'Q64.64 number indicates how many tokens per second are earned per unit of liquidity.',
+            ],
+            type: 'u128',
+          },
+          {
+            name: 'rewardTotalEmissioned',
+            docs: ['The total amount of reward emissioned'],
+            type: 'u64',
+          },
+          {
+            name: 'rewardClaimed',
+            docs: ['The total amount of claimed reward'],
+            type: 'u64',
+          },
+          {
+            name: 'tokenMint',
+            docs: ['Reward token mint.'],
+            type: 'publicKey',
+          },
+          {
+            name: 'tokenVault',
+            docs: ['Reward vault token account.'],
+            type: 'publicKey',
+          },
+          {
+            name: 'authority',
+            docs: ['The owner that has permission to set reward param'],
+            type: 'publicKey',
+          },
+          {
+            name: 'rewardGrowthGlobalX64',
+            docs: [
+              'Q64.64 number that tracks the total tokens earned per unit of liquidity since the reward',
+              'emissions were turned on.',
+            ],
+            type: 'u128',
+          },
+        ],
+      },
+    },
     {
       name: 'I80F48',
       docs: [
@@ -6591,6 +11603,91 @@ export const IDL: OpenbookV2 = {
         ],
       },
     },
+    {
+      name: 'AccessGate',
+      type: {
+        kind: 'enum',
+        variants: [
+          {
+            name: 'None',
+          },
+          {
+            name: 'Allowlist',
+          },
+          {
+            name: 'CredentialToken',
+          },
+        ],
+      },
+    },
+    {
+      name: 'OpenOrdersAccess',
+      docs: [
+        'Whether an open orders account passed the access gate of its market',
+      ],
+      type: {
+        kind: 'enum',
+        variants: [
+          {
+            name: 'Unchecked',
+          },
+          {
+            name: 'Granted',
+          },
+          {
+            name: 'Revoked',
+          },
+        ],
+      },
+    },
+    {
+      name: 'DelegateScope',
+      docs: [
+        'What a delegate is allowed to do. A delegate can never change the delegates or',
+        'close the account.',
+      ],
+      type: {
+        kind: 'enum',
+        variants: [
+          {
+            name: 'Full',
+          },
+          {
+            name: 'Trade',
+          },
+          {
+            name: 'Cancel',
+          },
+          {
+            name: 'Settle',
+          },
+        ],
+      },
+    },
+    {
+      name: 'MarketStatus',
+      docs: [
+        'Reversible restriction of the order instructions, set by the',
+        '[`market_status_admin`](Market::market_status_admin)',
+      ],
+      type: {
+        kind: 'enum',
+        variants: [
+          {
+            name: 'Active',
+          },
+          {
+            name: 'PostOnly',
+          },
+          {
+            name: 'CancelOnly',
+          },
+          {
+            name: 'Halted',
+          },
+        ],
+      },
+    },
     {
       name: 'OracleType',
       type: {
@@ -6720,151 +11817,477 @@ export const IDL: OpenbookV2 = {
             name: 'PostOnly',
           },
           {
-            name: 'PostOnlySlide',
+            name: 'PostOnlySlide',
+          },
+        ],
+      },
+    },
+    {
+      name: 'SelfTradeBehavior',
+      docs: [
+        'Self trade behavior controls how taker orders interact with resting limit orders of the same account.',
+        'This setting has no influence on placing a resting or oracle pegged limit order that does not match',
+        "immediately, instead it's the responsibility of the user to correctly configure his taker orders.",
+      ],
+      type: {
+        kind: 'enum',
+        variants: [
+          {
+            name: 'DecrementTake',
+          },
+          {
+            name: 'CancelProvide',
+          },
+          {
+            name: 'AbortTransaction',
+          },
+        ],
+      },
+    },
+    {
+      name: 'Side',
+      type: {
+        kind: 'enum',
+        variants: [
+          {
+            name: 'Bid',
+          },
+          {
+            name: 'Ask',
+          },
+        ],
+      },
+    },
+    {
+      name: 'SideAndOrderTree',
+      docs: [
+        "SideAndOrderTree is a storage optimization, so we don't need two bytes for the data",
+      ],
+      type: {
+        kind: 'enum',
+        variants: [
+          {
+            name: 'BidFixed',
+          },
+          {
+            name: 'AskFixed',
+          },
+          {
+            name: 'BidOraclePegged',
+          },
+          {
+            name: 'AskOraclePegged',
+          },
+        ],
+      },
+    },
+    {
+      name: 'OrderParams',
+      type: {
+        kind: 'enum',
+        variants: [
+          {
+            name: 'Market',
+          },
+          {
+            name: 'ImmediateOrCancel',
+            fields: [
+              {
+                name: 'price_lots',
+                type: 'i64',
+              },
+            ],
+          },
+          {
+            name: 'Fixed',
+            fields: [
+              {
+                name: 'price_lots',
+                type: 'i64',
+              },
+              {
+                name: 'order_type',
+                type: {
+                  defined: 'PostOrderType',
+                },
+              },
+            ],
+          },
+          {
+            name: 'OraclePegged',
+            fields: [
+              {
+                name: 'price_offset_lots',
+                type: 'i64',
+              },
+              {
+                name: 'order_type',
+                type: {
+                  defined: 'PostOrderType',
+                },
+              },
+              {
+                name: 'peg_limit',
+                type: 'i64',
+              },
+            ],
+          },
+          {
+            name: 'FillOrKill',
+            fields: [
+              {
+                name: 'price_lots',
+                type: 'i64',
+              },
+            ],
           },
         ],
       },
     },
     {
-      name: 'SelfTradeBehavior',
-      docs: [
-        'Self trade behavior controls how taker orders interact with resting limit orders of the same account.',
-        'This setting has no influence on placing a resting or oracle pegged limit order that does not match',
-        "immediately, instead it's the responsibility of the user to correctly configure his taker orders.",
-      ],
+      name: 'OrderTreeType',
       type: {
         kind: 'enum',
         variants: [
           {
-            name: 'DecrementTake',
-          },
-          {
-            name: 'CancelProvide',
+            name: 'Bids',
           },
           {
-            name: 'AbortTransaction',
+            name: 'Asks',
           },
         ],
       },
     },
+  ],
+  events: [
     {
-      name: 'Side',
-      type: {
-        kind: 'enum',
-        variants: [
-          {
-            name: 'Bid',
+      name: 'DepositLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'signer',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'baseAmount',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'quoteAmount',
+          type: 'u64',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'FillLog',
+      fields: [
+        {
+          name: 'market',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'takerSide',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'makerSlot',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'makerOut',
+          type: 'bool',
+          index: false,
+        },
+        {
+          name: 'timestamp',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'seqNum',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'maker',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'makerClientOrderId',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'makerFee',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'makerTimestamp',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'taker',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'takerClientOrderId',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'takerFeeCeil',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'price',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'quantity',
+          type: 'i64',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'TakerSignatureLog',
+      fields: [
+        {
+          name: 'market',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'seqNum',
+          type: 'u64',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'MarketMetaDataLog',
+      fields: [
+        {
+          name: 'market',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'name',
+          type: 'string',
+          index: false,
+        },
+        {
+          name: 'baseMint',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'quoteMint',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'baseDecimals',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'quoteDecimals',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'baseLotSize',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'quoteLotSize',
+          type: 'i64',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'TotalOrderFillEvent',
+      fields: [
+        {
+          name: 'side',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'taker',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'totalQuantityPaid',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'totalQuantityReceived',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'fees',
+          type: 'u64',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'SetDelegateLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'delegate',
+          type: {
+            option: 'publicKey',
           },
-          {
-            name: 'Ask',
+          index: false,
+        },
+        {
+          name: 'scope',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'allowedSide',
+          type: {
+            option: 'u8',
+          },
+          index: false,
+        },
+        {
+          name: 'maxQuoteNativePerOrder',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'expiryTimestamp',
+          type: 'u64',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'AddDelegateLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'delegate',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'scope',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'allowedSide',
+          type: {
+            option: 'u8',
           },
-        ],
-      },
+          index: false,
+        },
+        {
+          name: 'maxQuoteNativePerOrder',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'expiryTimestamp',
+          type: 'u64',
+          index: false,
+        },
+      ],
     },
     {
-      name: 'SideAndOrderTree',
-      docs: [
-        "SideAndOrderTree is a storage optimization, so we don't need two bytes for the data",
+      name: 'RemoveDelegateLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'delegate',
+          type: 'publicKey',
+          index: false,
+        },
       ],
-      type: {
-        kind: 'enum',
-        variants: [
-          {
-            name: 'BidFixed',
-          },
-          {
-            name: 'AskFixed',
-          },
-          {
-            name: 'BidOraclePegged',
-          },
-          {
-            name: 'AskOraclePegged',
-          },
-        ],
-      },
     },
     {
-      name: 'OrderParams',
-      type: {
-        kind: 'enum',
-        variants: [
-          {
-            name: 'Market',
-          },
-          {
-            name: 'ImmediateOrCancel',
-            fields: [
-              {
-                name: 'price_lots',
-                type: 'i64',
-              },
-            ],
-          },
-          {
-            name: 'Fixed',
-            fields: [
-              {
-                name: 'price_lots',
-                type: 'i64',
-              },
-              {
-                name: 'order_type',
-                type: {
-                  defined: 'PostOrderType',
-                },
-              },
-            ],
-          },
-          {
-            name: 'OraclePegged',
-            fields: [
-              {
-                name: 'price_offset_lots',
-                type: 'i64',
-              },
-              {
-                name: 'order_type',
-                type: {
-                  defined: 'PostOrderType',
-                },
-              },
-              {
-                name: 'peg_limit',
-                type: 'i64',
-              },
-            ],
-          },
-          {
-            name: 'FillOrKill',
-            fields: [
-              {
-                name: 'price_lots',
-                type: 'i64',
-              },
-            ],
-          },
-        ],
-      },
+      name: 'HeartbeatLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'deadline',
+          type: 'u64',
+          index: false,
+        },
+      ],
     },
     {
-      name: 'OrderTreeType',
-      type: {
-        kind: 'enum',
-        variants: [
-          {
-            name: 'Bids',
-          },
-          {
-            name: 'Asks',
-          },
-        ],
-      },
+      name: 'SetMmpLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'windowSecs',
+          type: 'u16',
+          index: false,
+        },
+        {
+          name: 'baseLotsLimit',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'fillCountLimit',
+          type: 'u16',
+          index: false,
+        },
+      ],
     },
-  ],
-  events: [
     {
-      name: 'DepositLog',
+      name: 'MmpTriggeredLog',
       fields: [
         {
           name: 'openOrdersAccount',
@@ -6872,24 +12295,39 @@ export const IDL: OpenbookV2 = {
           index: false,
         },
         {
-          name: 'signer',
+          name: 'market',
           type: 'publicKey',
           index: false,
         },
         {
-          name: 'baseAmount',
+          name: 'windowStart',
           type: 'u64',
           index: false,
         },
         {
-          name: 'quoteAmount',
-          type: 'u64',
+          name: 'windowBaseLots',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'windowFillCount',
+          type: 'u16',
           index: false,
         },
       ],
     },
     {
-      name: 'FillLog',
+      name: 'MmpResetLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'RunAuctionLog',
       fields: [
         {
           name: 'market',
@@ -6897,79 +12335,126 @@ export const IDL: OpenbookV2 = {
           index: false,
         },
         {
-          name: 'takerSide',
-          type: 'u8',
+          name: 'priceLots',
+          type: 'i64',
           index: false,
         },
         {
-          name: 'makerSlot',
-          type: 'u8',
+          name: 'baseLots',
+          type: 'i64',
           index: false,
         },
         {
-          name: 'makerOut',
+          name: 'completed',
           type: 'bool',
           index: false,
         },
+      ],
+    },
+    {
+      name: 'SettleFundsLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
         {
-          name: 'timestamp',
+          name: 'baseNative',
           type: 'u64',
           index: false,
         },
         {
-          name: 'seqNum',
+          name: 'quoteNative',
           type: 'u64',
           index: false,
         },
         {
-          name: 'maker',
+          name: 'referrerRebate',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'referrer',
+          type: {
+            option: 'publicKey',
+          },
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'SweepFeesLog',
+      fields: [
+        {
+          name: 'market',
           type: 'publicKey',
           index: false,
         },
         {
-          name: 'makerClientOrderId',
+          name: 'amount',
           type: 'u64',
           index: false,
         },
         {
-          name: 'makerFee',
-          type: 'u64',
+          name: 'receiver',
+          type: 'publicKey',
           index: false,
         },
+      ],
+    },
+    {
+      name: 'ReferrerLog',
+      fields: [
         {
-          name: 'makerTimestamp',
-          type: 'u64',
+          name: 'referrer',
+          type: 'publicKey',
           index: false,
         },
         {
-          name: 'taker',
+          name: 'authority',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'market',
           type: 'publicKey',
           index: false,
         },
         {
-          name: 'takerClientOrderId',
-          type: 'u64',
+          name: 'shareBps',
+          type: 'u16',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'AccessGateLog',
+      fields: [
+        {
+          name: 'market',
+          type: 'publicKey',
           index: false,
         },
         {
-          name: 'takerFeeCeil',
-          type: 'u64',
+          name: 'admin',
+          type: 'publicKey',
           index: false,
         },
         {
-          name: 'price',
-          type: 'i64',
+          name: 'kind',
+          type: 'u8',
           index: false,
         },
         {
-          name: 'quantity',
-          type: 'i64',
+          name: 'credentialMint',
+          type: 'publicKey',
           index: false,
         },
       ],
     },
     {
-      name: 'MarketMetaDataLog',
+      name: 'AccessPassLog',
       fields: [
         {
           name: 'market',
@@ -6977,123 +12462,168 @@ export const IDL: OpenbookV2 = {
           index: false,
         },
         {
-          name: 'name',
-          type: 'string',
+          name: 'owner',
+          type: 'publicKey',
           index: false,
         },
         {
-          name: 'baseMint',
+          name: 'allowed',
+          type: 'bool',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'OpenOrdersAccessLog',
+      fields: [
+        {
+          name: 'market',
           type: 'publicKey',
           index: false,
         },
         {
-          name: 'quoteMint',
+          name: 'openOrdersAccount',
           type: 'publicKey',
           index: false,
         },
         {
-          name: 'baseDecimals',
+          name: 'access',
           type: 'u8',
           index: false,
         },
+      ],
+    },
+    {
+      name: 'BindReferrerLog',
+      fields: [
         {
-          name: 'quoteDecimals',
-          type: 'u8',
+          name: 'openOrdersAccount',
+          type: 'publicKey',
           index: false,
         },
         {
-          name: 'baseLotSize',
-          type: 'i64',
+          name: 'referrer',
+          type: 'publicKey',
           index: false,
         },
         {
-          name: 'quoteLotSize',
-          type: 'i64',
+          name: 'shareBps',
+          type: 'u16',
           index: false,
         },
       ],
     },
     {
-      name: 'TotalOrderFillEvent',
+      name: 'ClaimReferrerRebatesLog',
       fields: [
         {
-          name: 'side',
-          type: 'u8',
-          index: false,
-        },
-        {
-          name: 'taker',
+          name: 'referrer',
           type: 'publicKey',
           index: false,
         },
         {
-          name: 'totalQuantityPaid',
-          type: 'u64',
+          name: 'openOrdersAccount',
+          type: 'publicKey',
           index: false,
         },
         {
-          name: 'totalQuantityReceived',
+          name: 'amount',
           type: 'u64',
           index: false,
         },
         {
-          name: 'fees',
+          name: 'referredTakerVolume',
           type: 'u64',
           index: false,
         },
       ],
     },
     {
-      name: 'SetDelegateLog',
+      name: 'FeeSplitLog',
       fields: [
         {
-          name: 'openOrdersAccount',
+          name: 'market',
           type: 'publicKey',
           index: false,
         },
         {
-          name: 'delegate',
+          name: 'destinations',
           type: {
-            option: 'publicKey',
+            vec: {
+              defined: 'FeeSplitDestination',
+            },
           },
           index: false,
         },
       ],
     },
     {
-      name: 'SettleFundsLog',
+      name: 'OpenOrdersPositionLog',
       fields: [
         {
-          name: 'openOrdersAccount',
+          name: 'owner',
           type: 'publicKey',
           index: false,
         },
         {
-          name: 'baseNative',
+          name: 'openOrdersAccountNum',
+          type: 'u32',
+          index: false,
+        },
+        {
+          name: 'market',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'bidsBaseLots',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'bidsQuoteLots',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'asksBaseLots',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'baseFreeNative',
           type: 'u64',
           index: false,
         },
         {
-          name: 'quoteNative',
+          name: 'quoteFreeNative',
           type: 'u64',
           index: false,
         },
         {
-          name: 'referrerRebate',
+          name: 'lockedMakerFees',
           type: 'u64',
           index: false,
         },
         {
-          name: 'referrer',
-          type: {
-            option: 'publicKey',
-          },
+          name: 'referrerRebatesAvailable',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'makerVolume',
+          type: 'u128',
+          index: false,
+        },
+        {
+          name: 'takerVolume',
+          type: 'u128',
           index: false,
         },
       ],
     },
     {
-      name: 'SweepFeesLog',
+      name: 'PenaltyLog',
       fields: [
         {
           name: 'market',
@@ -7101,78 +12631,100 @@ export const IDL: OpenbookV2 = {
           index: false,
         },
         {
-          name: 'amount',
+          name: 'penaltyPayer',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'lamports',
           type: 'u64',
           index: false,
         },
         {
-          name: 'receiver',
-          type: 'publicKey',
+          name: 'penaltyLamportsAccrued',
+          type: 'u64',
           index: false,
         },
       ],
     },
     {
-      name: 'OpenOrdersPositionLog',
+      name: 'CrankRewardLog',
       fields: [
         {
-          name: 'owner',
+          name: 'market',
           type: 'publicKey',
           index: false,
         },
         {
-          name: 'openOrdersAccountNum',
-          type: 'u32',
+          name: 'receiver',
+          type: 'publicKey',
           index: false,
         },
         {
-          name: 'market',
-          type: 'publicKey',
+          name: 'events',
+          type: 'u32',
           index: false,
         },
         {
-          name: 'bidsBaseLots',
-          type: 'i64',
+          name: 'lamports',
+          type: 'u64',
           index: false,
         },
         {
-          name: 'bidsQuoteLots',
-          type: 'i64',
+          name: 'penaltyLamportsAccrued',
+          type: 'u64',
           index: false,
         },
+      ],
+    },
+    {
+      name: 'SetCrankRewardsLog',
+      fields: [
         {
-          name: 'asksBaseLots',
-          type: 'i64',
+          name: 'market',
+          type: 'publicKey',
           index: false,
         },
         {
-          name: 'baseFreeNative',
+          name: 'penaltyEventHeap',
           type: 'u64',
           index: false,
         },
         {
-          name: 'quoteFreeNative',
-          type: 'u64',
+          name: 'crankRewardPerEvent',
+          type: 'u32',
           index: false,
         },
+      ],
+    },
+    {
+      name: 'SetMarketStatusLog',
+      fields: [
         {
-          name: 'lockedMakerFees',
-          type: 'u64',
+          name: 'market',
+          type: 'publicKey',
           index: false,
         },
         {
-          name: 'referrerRebatesAvailable',
-          type: 'u64',
+          name: 'status',
+          type: 'u8',
           index: false,
         },
+      ],
+    },
+    {
+      name: 'SetMarketStatusAdminLog',
+      fields: [
         {
-          name: 'makerVolume',
-          type: 'u128',
+          name: 'market',
+          type: 'publicKey',
           index: false,
         },
         {
-          name: 'takerVolume',
-          type: 'u128',
+          name: 'marketStatusAdmin',
+          type: {
+            option: 'publicKey',
+          },
           index: false,
         },
       ],
@@ -7399,5 +12951,195 @@ export const IDL: OpenbookV2 = {
       name: 'WouldExecutePartially',
       msg: 'Fill-Or-Kill order would generate a partial execution',
     },
+    {
+      code: 6044,
+      name: 'HeartbeatNotExpired',
+      msg: 'The heartbeat of the open orders account has not expired',
+    },
+    {
+      code: 6045,
+      name: 'InvalidInputAuction',
+      msg: 'Auction duration must be set for recurring auctions',
+    },
+    {
+      code: 6046,
+      name: 'MarketInAuction',
+      msg: 'Only orders that post to the book are allowed during the auction phase',
+    },
+    {
+      code: 6047,
+      name: 'AuctionNotEnded',
+      msg: 'The market has no auction phase that ended',
+    },
+    {
+      code: 6048,
+      name: 'NoMarketStatusAdmin',
+      msg: 'This market does not have a `market_status_admin`.',
+    },
+    {
+      code: 6049,
+      name: 'InvalidMarketStatusAdmin',
+      msg: "The signer of this transaction is not this market's `market_status_admin`.",
+    },
+    {
+      code: 6050,
+      name: 'MarketPostOnly',
+      msg: 'The market only allows post only orders',
+    },
+    {
+      code: 6051,
+      name: 'MarketCancelOnly',
+      msg: 'The market only allows cancelling orders',
+    },
+    {
+      code: 6052,
+      name: 'MarketHalted',
+      msg: 'The market is halted',
+    },
+    {
+      code: 6053,
+      name: 'InvalidInputOrderSizeLimits',
+      msg: 'Order size limits must be positive and the maximum at least the minimum',
+    },
+    {
+      code: 6054,
+      name: 'OrderSizeAboveMaximum',
+      msg: 'The order to post is larger than the maximum order size of the market',
+    },
+    {
+      code: 6055,
+      name: 'DelegateSideNotAllowed',
+      msg: 'The delegate is not allowed to place orders on this side',
+    },
+    {
+      code: 6056,
+      name: 'DelegateOrderTooLarge',
+      msg: 'The order is larger than the delegate is allowed to place',
+    },
+    {
+      code: 6057,
+      name: 'InvalidDelegate',
+      msg: "The owner can't be added as a delegate",
+    },
+    {
+      code: 6058,
+      name: 'DelegateAlreadyAdded',
+      msg: 'The delegate is already in the delegate list',
+    },
+    {
+      code: 6059,
+      name: 'DelegateListFull',
+      msg: 'The delegate list is full',
+    },
+    {
+      code: 6060,
+      name: 'DelegateNotFound',
+      msg: 'The delegate is not in the delegate list',
+    },
+    {
+      code: 6061,
+      name: 'MarketGracePeriodNotOver',
+      msg: 'The grace period after the expiry of the market is not over',
+    },
+    {
+      code: 6062,
+      name: 'InvalidInputFeeSplit',
+      msg: 'The fee split shares must be positive and add up to 100% over at most 4 destinations',
+    },
+    {
+      code: 6063,
+      name: 'InvalidFeeSplit',
+      msg: 'The fee split account must be passed if and only if the market has a fee split',
+    },
+    {
+      code: 6064,
+      name: 'InvalidFeeSplitDestination',
+      msg: "The fee split destination accounts don't match the fee split",
+    },
+    {
+      code: 6065,
+      name: 'MissingTokenReceiverAccount',
+      msg: 'Missing token receiver account',
+    },
+    {
+      code: 6066,
+      name: 'InvalidInputReferrerShare',
+      msg: "The referrer share can't exceed 100%",
+    },
+    {
+      code: 6067,
+      name: 'InvalidReferrer',
+      msg: "The referrer doesn't refer on this market or isn't bound to the account",
+    },
+    {
+      code: 6068,
+      name: 'ReferrerAlreadyBound',
+      msg: 'The account is already bound to a referrer',
+    },
+    {
+      code: 6069,
+      name: 'OpenOrdersReferralNotClosed',
+      msg: 'The referral of the open orders account must be closed first',
+    },
+    {
+      code: 6070,
+      name: 'InvalidInputAccessGate',
+      msg: 'The access gate needs a kind and, for credential tokens, a mint',
+    },
+    {
+      code: 6071,
+      name: 'MissingAccessProof',
+      msg: 'The access gate or access proof account is missing',
+    },
+    {
+      code: 6072,
+      name: 'InvalidAccessProof',
+      msg: "The access proof doesn't match the access gate or the owner",
+    },
+    {
+      code: 6073,
+      name: 'OpenOrdersAccessDenied',
+      msg: "The open orders account wasn't granted access to the market",
+    },
+    {
+      code: 6074,
+      name: 'InvalidInputCapacity',
+      msg: "The account size doesn't fit a supported event heap or book side capacity",
+    },
+    {
+      code: 6075,
+      name: 'OpenOrdersAccountNotMigratable',
+      msg: "The open orders account can't be migrated before its fill events are consumed",
+    },
+    {
+      code: 6076,
+      name: 'MmpMakerAccountMissing',
+      msg: 'The open orders account of a maker with market maker protection is missing',
+    },
+    {
+      code: 6077,
+      name: 'MissingOpenOrdersDelegates',
+      msg: 'The open orders account has delegates, its delegates account must be passed',
+    },
+    {
+      code: 6078,
+      name: 'OpenOrdersDelegatesNotClosed',
+      msg: 'The delegates account of the open orders account must be closed first',
+    },
+    {
+      code: 6079,
+      name: 'MissingOpenOrdersReferral',
+      msg: 'The open orders account is bound to a referrer, its referral must be passed',
+    },
+    {
+      code: 6080,
+      name: 'MmpFrozen',
+      msg: 'Market maker protection was triggered, the owner must reset it first',
+    },
+    {
+      code: 6081,
+      name: 'OrderTimestampOverflow',
+      msg: "The current timestamp doesn't fit the u32 timestamp of resting orders",
+    },
   ],
 };